@@ -36,7 +36,7 @@ export type AnchorErrorsError =
 let anchorErrorsErrorMessages: Record<AnchorErrorsError, string> | undefined;
 if (process.env.NODE_ENV !== "production") {
 	anchorErrorsErrorMessages = {
-		[ANCHOR_ERRORS_ERROR__HELLO]: ``,
+		[ANCHOR_ERRORS_ERROR__HELLO]: `This is an error message clients will automatically display`,
 		[ANCHOR_ERRORS_ERROR__HELLO_CUSTOM]: ``,
 		[ANCHOR_ERRORS_ERROR__HELLO_NEXT]: ``,
 		[ANCHOR_ERRORS_ERROR__HELLO_NO_MSG]: ``,
@@ -23,12 +23,12 @@ import {
 	type InstructionWithAccounts,
 	type InstructionWithData,
 	type ReadonlyAccount,
-	type ReadonlySignerAccount,
 	type ReadonlyUint8Array,
 	SOLANA_ERROR__PROGRAM_CLIENTS__INSUFFICIENT_ACCOUNT_METAS,
 	SolanaError,
 	type TransactionSigner,
 	type WritableAccount,
+	type WritableSignerAccount,
 } from "@solana/kit";
 import {
 	getAccountMetaFactory,
@@ -55,7 +55,7 @@ export type ReallocInstruction<
 	& InstructionWithAccounts<
 		[
 			TAccountAuthority extends string ?
-					& ReadonlySignerAccount<TAccountAuthority>
+					& WritableSignerAccount<TAccountAuthority>
 					& AccountSignerMeta<TAccountAuthority>
 				: TAccountAuthority,
 			TAccountSample extends string ? WritableAccount<TAccountSample>
@@ -124,7 +124,7 @@ export function getReallocInstruction<
 
 	// Original accounts.
 	const originalAccounts = {
-		authority: { value: input.authority ?? null, isWritable: false },
+		authority: { value: input.authority ?? null, isWritable: true },
 		sample: { value: input.sample ?? null, isWritable: true },
 		systemProgram: { value: input.systemProgram ?? null, isWritable: false },
 	};
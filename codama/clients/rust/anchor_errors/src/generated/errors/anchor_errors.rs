@@ -13,8 +13,8 @@ use thiserror::Error;
 
 #[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
 pub enum AnchorErrorsError {
-	/// 6000 -
-	#[error("")]
+	/// 6000 - This is an error message clients will automatically display
+	#[error("This is an error message clients will automatically display")]
 	Hello = 0x1770,
 	/// 6123 -
 	#[error("")]
@@ -44,10 +44,7 @@ impl Realloc2 {
 		remaining_accounts: &[solana_instruction::AccountMeta],
 	) -> solana_instruction::Instruction {
 		let mut accounts = Vec::with_capacity(4 + remaining_accounts.len());
-		accounts.push(solana_instruction::AccountMeta::new_readonly(
-			self.authority,
-			true,
-		));
+		accounts.push(solana_instruction::AccountMeta::new(self.authority, true));
 		accounts.push(solana_instruction::AccountMeta::new(self.sample1, false));
 		accounts.push(solana_instruction::AccountMeta::new(self.sample2, false));
 		accounts.push(solana_instruction::AccountMeta::new_readonly(
@@ -0,0 +1,389 @@
+//! Typed account fields for `#[derive(Accounts)]`.
+//!
+//! Each wrapper holds an `&'a AccountView` that has passed the checks its type
+//! names: [`SignerAccount`] signed the transaction, [`Writable`] is writable and
+//! [`Readonly`] is not, [`Program`] is the program `P`, [`Sysvar`] is the
+//! sysvar `S`, and, with the `token` feature, [`Mint`] and [`TokenAccount`]
+//! are initialized token program state of the right layout. The checks run
+//! once, when the wrapper is built, so a processor taking a `Mint<'a>` no
+//! longer has to remember the owner check before reading the mint, and a
+//! helper taking a `SignerAccount<'a>` cannot be handed an account nobody checked.
+//!
+//! Wrappers are built by `#[derive(Accounts)]` when they are used as field
+//! types, or by hand through `TryFrom<&AccountView>`. They dereference to the
+//! `AccountView`, so every `AccountInfoValidation` assertion still applies.
+//!
+//! ```ignore
+//! use pina::accounts::Mint;
+//! use pina::accounts::Program;
+//! use pina::accounts::SignerAccount;
+//! use pina::accounts::Sysvar;
+//! use pina::accounts::System;
+//! use pina::sysvars::clock::Clock;
+//!
+//! #[derive(Accounts)]
+//! pub struct MintRewardsAccounts<'a> {
+//! 	pub authority: SignerAccount<'a>,
+//! 	#[pina(writable)]
+//! 	pub mint: Mint<'a>,
+//! 	pub clock: Sysvar<'a, Clock>,
+//! 	pub system_program: Program<'a, System>,
+//! }
+//! ```
+
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use pinocchio::AccountView;
+use pinocchio::Address;
+use pinocchio::error::ProgramError;
+
+use crate::AccountInfoValidation;
+use crate::AccountsCursor;
+use crate::ParseAccounts;
+use crate::log;
+use crate::log_caller;
+use crate::sysvars::SysvarAccount;
+
+/// A program whose address is known at compile time.
+pub trait ProgramId {
+	/// The program's address.
+	const ID: Address;
+}
+
+/// The system program.
+pub struct System;
+
+impl ProgramId for System {
+	const ID: Address = crate::system::ID;
+}
+
+/// The SPL Token program.
+#[cfg(feature = "token")]
+pub struct Token;
+
+#[cfg(feature = "token")]
+impl ProgramId for Token {
+	const ID: Address = crate::token::ID;
+}
+
+/// The SPL Token-2022 program.
+#[cfg(feature = "token")]
+pub struct Token2022;
+
+#[cfg(feature = "token")]
+impl ProgramId for Token2022 {
+	const ID: Address = crate::token_2022::ID;
+}
+
+/// The associated token account program.
+#[cfg(feature = "token")]
+pub struct AssociatedToken;
+
+#[cfg(feature = "token")]
+impl ProgramId for AssociatedToken {
+	const ID: Address = crate::associated_token_account::ID;
+}
+
+/// Implements the accessors, `Debug`, `Deref`, `TryFrom` and `ParseAccounts`
+/// shared by every wrapper from a `validate` function that checks the account.
+macro_rules! account_wrapper {
+	(
+		$wrapper:ident $(<$param:ident: $bound:path>)?,
+		$validate:path $(, $marker:ident)?
+	) => {
+		impl<'a $(, $param: $bound)?> $wrapper<'a $(, $param)?> {
+			/// The validated account.
+			pub fn account(&self) -> &'a AccountView {
+				self.account
+			}
+		}
+
+		impl<'a $(, $param: $bound)?> Clone for $wrapper<'a $(, $param)?> {
+			fn clone(&self) -> Self {
+				*self
+			}
+		}
+
+		impl<'a $(, $param: $bound)?> Copy for $wrapper<'a $(, $param)?> {}
+
+		impl<'a $(, $param: $bound)?> core::fmt::Debug for $wrapper<'a $(, $param)?> {
+			fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+				f.debug_tuple(stringify!($wrapper))
+					.field(self.account)
+					.finish()
+			}
+		}
+
+		impl<'a $(, $param: $bound)?> Deref for $wrapper<'a $(, $param)?> {
+			type Target = AccountView;
+
+			fn deref(&self) -> &AccountView {
+				self.account
+			}
+		}
+
+		impl<'a $(, $param: $bound)?> TryFrom<&'a AccountView> for $wrapper<'a $(, $param)?> {
+			type Error = ProgramError;
+
+			#[track_caller]
+			fn try_from(account: &'a AccountView) -> Result<Self, ProgramError> {
+				$validate(account)?;
+
+				Ok(Self {
+					account,
+					$($marker: PhantomData,)?
+				})
+			}
+		}
+
+		impl<'a $(, $param: $bound)?> ParseAccounts<'a> for $wrapper<'a $(, $param)?> {
+			#[track_caller]
+			fn parse_accounts(cursor: &mut AccountsCursor<'a>) -> Result<Self, ProgramError> {
+				Self::try_from(cursor.next()?)
+			}
+		}
+	};
+}
+
+/// An account checked to have signed the transaction.
+///
+/// Named apart from [`crate::Signer`], the signer seeds passed to a CPI, so
+/// both can be used through `use pina::*`.
+pub struct SignerAccount<'a> {
+	account: &'a AccountView,
+}
+
+#[track_caller]
+fn validate_signer(account: &AccountView) -> Result<(), ProgramError> {
+	account.assert_signer()?;
+
+	Ok(())
+}
+
+account_wrapper!(SignerAccount, validate_signer);
+
+/// An account checked to be writable.
+pub struct Writable<'a> {
+	account: &'a AccountView,
+}
+
+#[track_caller]
+fn validate_writable(account: &AccountView) -> Result<(), ProgramError> {
+	account.assert_writable()?;
+
+	Ok(())
+}
+
+account_wrapper!(Writable, validate_writable);
+
+/// An account checked not to be writable, for accounts the instruction only
+/// reads.
+pub struct Readonly<'a> {
+	account: &'a AccountView,
+}
+
+#[track_caller]
+fn validate_readonly(account: &AccountView) -> Result<(), ProgramError> {
+	if account.is_writable() {
+		log!(
+			"address: {} must not be writable",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(())
+}
+
+account_wrapper!(Readonly, validate_readonly);
+
+/// An account checked to be the executable program `P`.
+///
+/// The client side of `#[derive(Accounts)]` fills the field in from
+/// [`ProgramId::ID`], so it is not part of the generated `Keys` struct.
+pub struct Program<'a, P> {
+	account: &'a AccountView,
+	program: PhantomData<P>,
+}
+
+#[track_caller]
+fn validate_program<P: ProgramId>(account: &AccountView) -> Result<(), ProgramError> {
+	account.assert_program(&P::ID)?;
+
+	Ok(())
+}
+
+account_wrapper!(Program<P: ProgramId>, validate_program::<P>, program);
+
+/// An account checked to be the sysvar `S`.
+///
+/// Like [`Program`], the field is filled in from [`SysvarAccount::ADDRESS`] on
+/// the client side.
+pub struct Sysvar<'a, S> {
+	account: &'a AccountView,
+	sysvar: PhantomData<S>,
+}
+
+impl<S: SysvarAccount> Sysvar<'_, S> {
+	/// Decode the sysvar from the account data.
+	///
+	/// # Errors
+	///
+	/// Returns the errors from [`SysvarAccount::from_account`].
+	pub fn get(&self) -> Result<S, ProgramError> {
+		S::from_account(self.account)
+	}
+}
+
+#[track_caller]
+fn validate_sysvar<S: SysvarAccount>(account: &AccountView) -> Result<(), ProgramError> {
+	account.assert_sysvar(&S::ADDRESS)?;
+
+	Ok(())
+}
+
+account_wrapper!(Sysvar<S: SysvarAccount>, validate_sysvar::<S>, sysvar);
+
+/// An initialized mint owned by the SPL Token or Token-2022 program.
+///
+/// SPL Token mints must be exactly the base mint length. Token-2022 mints may
+/// carry extensions, in which case the account type byte after the base
+/// account length must mark the data as a mint, so a token account can never
+/// pass as a mint.
+#[cfg(feature = "token")]
+pub struct Mint<'a> {
+	account: &'a AccountView,
+}
+
+#[cfg(feature = "token")]
+impl<'a> Mint<'a> {
+	/// Borrow the mint state. The Token-2022 layout shares the base fields of
+	/// SPL Token, so it reads mints of either program.
+	///
+	/// # Errors
+	///
+	/// Returns a borrow error while the data is mutably borrowed.
+	#[track_caller]
+	pub fn state(&self) -> Result<crate::Ref<'a, crate::token_2022::state::Mint>, ProgramError> {
+		crate::AsTokenAccount::as_token_2022_mint_checked_with_owners(
+			self.account,
+			&crate::impls::TOKEN_PROGRAM_IDS,
+		)
+	}
+}
+
+#[cfg(feature = "token")]
+#[track_caller]
+fn validate_mint(account: &AccountView) -> Result<(), ProgramError> {
+	validate_token_layout(
+		account,
+		crate::token_2022::state::Mint::BASE_LEN,
+		TOKEN_2022_MINT_TYPE,
+	)?;
+
+	if !(Mint { account }).state()?.is_initialized() {
+		log!("address: {} is not initialized", account.address().as_ref());
+		log_caller();
+
+		return Err(ProgramError::UninitializedAccount);
+	}
+
+	Ok(())
+}
+
+#[cfg(feature = "token")]
+account_wrapper!(Mint, validate_mint);
+
+/// An initialized token account owned by the SPL Token or Token-2022 program.
+///
+/// The layout is checked the same way as for [`Mint`].
+#[cfg(feature = "token")]
+pub struct TokenAccount<'a> {
+	account: &'a AccountView,
+}
+
+#[cfg(feature = "token")]
+impl<'a> TokenAccount<'a> {
+	/// Borrow the token account state, read with the Token-2022 layout like
+	/// [`Mint::state`].
+	///
+	/// # Errors
+	///
+	/// Returns a borrow error while the data is mutably borrowed.
+	#[track_caller]
+	pub fn state(
+		&self,
+	) -> Result<crate::Ref<'a, crate::token_2022::state::TokenAccount>, ProgramError> {
+		crate::AsTokenAccount::as_token_2022_account_checked_with_owners(
+			self.account,
+			&crate::impls::TOKEN_PROGRAM_IDS,
+		)
+	}
+}
+
+#[cfg(feature = "token")]
+#[track_caller]
+fn validate_token_account(account: &AccountView) -> Result<(), ProgramError> {
+	validate_token_layout(
+		account,
+		crate::token_2022::state::TokenAccount::BASE_LEN,
+		TOKEN_2022_ACCOUNT_TYPE,
+	)?;
+
+	if !(TokenAccount { account }).state()?.is_initialized() {
+		log!("address: {} is not initialized", account.address().as_ref());
+		log_caller();
+
+		return Err(ProgramError::UninitializedAccount);
+	}
+
+	Ok(())
+}
+
+#[cfg(feature = "token")]
+account_wrapper!(TokenAccount, validate_token_account);
+
+/// The Token-2022 account type byte of an extended mint.
+#[cfg(feature = "token")]
+const TOKEN_2022_MINT_TYPE: u8 = 1;
+
+/// The Token-2022 account type byte of an extended token account.
+#[cfg(feature = "token")]
+const TOKEN_2022_ACCOUNT_TYPE: u8 = 2;
+
+/// Check that a token program owns `account` and that its data is
+/// `base_len` bytes or, for Token-2022 only, an extended account of
+/// `account_type`.
+#[cfg(feature = "token")]
+#[track_caller]
+fn validate_token_layout(
+	account: &AccountView,
+	base_len: usize,
+	account_type: u8,
+) -> Result<(), ProgramError> {
+	use crate::token_2022::state::Multisig;
+	use crate::token_2022::state::TokenAccount as BaseAccount;
+
+	account.assert_owners(&crate::impls::TOKEN_PROGRAM_IDS)?;
+
+	let data = account.try_borrow()?;
+	let extended = account.owned_by(&crate::token_2022::ID)
+		&& data.len() > BaseAccount::BASE_LEN
+		&& data.len() != Multisig::LEN
+		&& data[BaseAccount::BASE_LEN] == account_type;
+
+	if data.len() != base_len && !extended {
+		log!(
+			"address: {} is not a token account of the expected type",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(())
+}
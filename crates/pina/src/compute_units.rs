@@ -0,0 +1,139 @@
+//! Per-instruction compute unit reports for regression checks in CI.
+//!
+//! Test harnesses run each instruction of a program, record how many compute
+//! units it consumed under the instruction's name, and write the resulting
+//! [`ComputeUnitReport`] as JSON. CI can then diff the report against a
+//! baseline from the target branch.
+//!
+//! Units can be recorded directly, for example from a simulator result, or
+//! collected from the `Function {name} consumed {units} compute units` lines
+//! that [`log_cu_usage`](crate::log_cu_usage) writes to the program logs.
+//!
+//! This module requires the `test-utils` feature because it allocates.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// The prefix the runtime adds to messages logged with `sol_log`.
+const PROGRAM_LOG_PREFIX: &str = "Program log: ";
+
+/// Parse a log line written by [`log_cu_usage`](crate::log_cu_usage).
+///
+/// Accepts either the full `Program log: ...` line or the bare message and
+/// returns the function name and the compute units it consumed. Returns
+/// `None` for any other log line.
+///
+/// # Examples
+///
+/// ```ignore
+/// let usage = parse_cu_usage("Program log: Function process consumed 312 compute units");
+/// assert_eq!(usage, Some(("process", 312)));
+/// ```
+pub fn parse_cu_usage(log_line: &str) -> Option<(&str, u64)> {
+	let message = log_line.trim();
+	let message = message.strip_prefix(PROGRAM_LOG_PREFIX).unwrap_or(message);
+	let rest = message.strip_prefix("Function ")?;
+	let (name, rest) = rest.split_once(" consumed ")?;
+	let units = rest.strip_suffix(" compute units")?;
+
+	if name.is_empty() {
+		return None;
+	}
+
+	Some((name, units.parse().ok()?))
+}
+
+/// Compute units consumed per instruction, keyed by instruction name.
+///
+/// Entries are kept in name order so the JSON output is stable across runs
+/// and diffs cleanly against a stored baseline.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut report = ComputeUnitReport::new();
+/// report.record("Initialize", initialize_result.compute_units_consumed);
+/// report.record("Increment", increment_result.compute_units_consumed);
+/// std::fs::write("target/cu/counter_program.json", report.to_json())?;
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ComputeUnitReport {
+	entries: BTreeMap<String, u64>,
+}
+
+impl ComputeUnitReport {
+	/// Create an empty report.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Record the compute units consumed by `instruction`.
+	///
+	/// Recording the same instruction again replaces the previous value.
+	pub fn record(&mut self, instruction: &str, units: u64) {
+		self.entries.insert(String::from(instruction), units);
+	}
+
+	/// Record every [`log_cu_usage`](crate::log_cu_usage) line in `logs`.
+	///
+	/// Each entry is keyed by the logged function name. Other log lines are
+	/// ignored.
+	pub fn record_logs<'a>(&mut self, logs: impl IntoIterator<Item = &'a str>) {
+		for (name, units) in logs.into_iter().filter_map(parse_cu_usage) {
+			self.record(name, units);
+		}
+	}
+
+	/// The compute units recorded for `instruction`, if any.
+	pub fn get(&self, instruction: &str) -> Option<u64> {
+		self.entries.get(instruction).copied()
+	}
+
+	/// Iterate over `(instruction, units)` pairs in name order.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+		self.entries
+			.iter()
+			.map(|(name, units)| (name.as_str(), *units))
+	}
+
+	/// Returns `true` when nothing has been recorded.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Serialize the report as a JSON object mapping each instruction name to
+	/// its compute units.
+	pub fn to_json(&self) -> String {
+		let mut json = String::from("{");
+
+		for (index, (name, units)) in self.iter().enumerate() {
+			if index > 0 {
+				json.push(',');
+			}
+
+			json.push_str("\n\t\"");
+			push_escaped(&mut json, name);
+			let _ = write!(json, "\": {units}");
+		}
+
+		if !self.is_empty() {
+			json.push('\n');
+		}
+
+		json.push_str("}\n");
+		json
+	}
+}
+
+fn push_escaped(json: &mut String, value: &str) {
+	for character in value.chars() {
+		match character {
+			'"' => json.push_str("\\\""),
+			'\\' => json.push_str("\\\\"),
+			'\n' => json.push_str("\\n"),
+			'\t' => json.push_str("\\t"),
+			_ => json.push(character),
+		}
+	}
+}
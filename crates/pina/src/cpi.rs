@@ -26,11 +26,17 @@ use pinocchio_system::instructions::Assign;
 use pinocchio_system::instructions::CreateAccount;
 use pinocchio_system::instructions::Transfer;
 
+#[cfg(feature = "account-resize")]
+use crate::AsAccount;
 use crate::CloseAccountWithRecipient;
+use crate::DynamicSpace;
 use crate::HasDiscriminator;
 #[cfg(feature = "account-resize")]
 use crate::LamportTransfer;
 use crate::MAX_SEEDS;
+#[cfg(feature = "account-resize")]
+use crate::Migrate;
+use crate::PinaProgramError;
 use crate::ProgramResult;
 
 /// Creates a new system account owned by `owner`.
@@ -70,6 +76,48 @@ pub fn create_account<'a>(
 	.invoke()
 }
 
+/// Creates a new keypair-backed program account sized for `T`.
+///
+/// This is the non-PDA counterpart of [`create_program_account`]: the new
+/// account signs the transaction itself, so no seeds are involved. The
+/// rent-exempt balance for `size_of::<T>()` is funded by `payer` through a
+/// single `CreateAccount` CPI that also assigns the account to `owner`.
+///
+/// # Errors
+///
+/// Returns `MissingRequiredSignature` when `target_account` did not sign,
+/// plus any error from [`create_account`].
+///
+/// Fails to compile when `size_of::<T>()` exceeds
+/// [`MAX_PERMITTED_DATA_INCREASE`].
+///
+/// # Examples
+///
+/// ```ignore
+/// // The client generated a fresh keypair for `game_account`:
+/// create_rent_exempt_account::<GameState>(game_account, payer, &program_id)?;
+/// ```
+#[inline(always)]
+pub fn create_rent_exempt_account<'a, T: HasDiscriminator + Pod>(
+	target_account: &'a AccountView,
+	payer: &'a AccountView,
+	owner: &Address,
+) -> ProgramResult {
+	const {
+		assert!(
+			size_of::<T>() <= MAX_PERMITTED_DATA_INCREASE,
+			"account type is larger than `MAX_PERMITTED_DATA_INCREASE` and cannot be created in a \
+			 single CPI"
+		);
+	}
+
+	use crate::AccountInfoValidation;
+
+	target_account.assert_signer()?;
+
+	create_account(payer, target_account, size_of::<T>(), owner)
+}
+
 /// Creates a new PDA-backed program account and returns `(address, bump)`.
 ///
 /// This helper derives the canonical PDA for `seeds` + `owner`, allocates
@@ -112,6 +160,94 @@ pub fn create_program_account<'a, T: HasDiscriminator + Pod>(
 	Ok((address, bump))
 }
 
+/// Creates a new PDA-backed program account like [`create_program_account`],
+/// then confirms the CPI left it owned by `owner` with `size_of::<T>()` bytes.
+///
+/// The system program is trusted to do what it was asked, so the plain helper
+/// does not look at the account again. This variant re-reads it with
+/// [`verify_created_account`] for instructions that would rather fail than
+/// write into an account that came out of the CPI in an unexpected state.
+///
+/// <!-- {=pinaPdaSeedContract|trim|linePrefix:"/// ":true} -->/// Seed-based APIs require deterministic seed ordering.
+///
+/// Program IDs must stay consistent across derivation and verification.
+///
+/// When a bump is required, prefer canonical bump derivation.
+///
+/// Use explicit bumps when needed.<!-- {/pinaPdaSeedContract} -->
+///
+/// # Errors
+///
+/// Returns any error from [`create_program_account`] or
+/// [`verify_created_account`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let seeds: &[&[u8]] = &[b"escrow", authority.address().as_ref()];
+/// let (address, bump) =
+/// 	create_program_account_verified::<EscrowState>(escrow_account, payer, &program_id, seeds)?;
+/// ```
+#[inline(always)]
+#[track_caller]
+pub fn create_program_account_verified<'a, T: HasDiscriminator + Pod>(
+	target_account: &'a AccountView,
+	payer: &'a AccountView,
+	owner: &Address,
+	seeds: &[&[u8]],
+) -> Result<(Address, u8), ProgramError> {
+	let created = create_program_account::<T>(target_account, payer, owner, seeds)?;
+	verify_created_account::<T>(target_account, owner)?;
+
+	Ok(created)
+}
+
+/// Confirms a freshly created account is owned by `owner` and holds exactly
+/// `size_of::<T>()` bytes.
+///
+/// The discriminator is not checked because new account data is still zeroed.
+///
+/// # Errors
+///
+/// Returns `InvalidAccountOwner` when the owner differs and
+/// [`PinaProgramError::InvalidAccountSize`] when the data length differs.
+///
+/// # Examples
+///
+/// ```ignore
+/// create_program_account_with_bump::<EscrowState>(escrow_account, payer, &program_id, seeds, bump)?;
+/// verify_created_account::<EscrowState>(escrow_account, &program_id)?;
+/// ```
+#[track_caller]
+pub fn verify_created_account<T: HasDiscriminator + Pod>(
+	account: &AccountView,
+	owner: &Address,
+) -> ProgramResult {
+	if !account.owned_by(owner) {
+		crate::log!(
+			"address: {} is not owned by the program after creation",
+			account.address().as_ref()
+		);
+		crate::log_caller();
+
+		return Err(ProgramError::InvalidAccountOwner);
+	}
+
+	if account.data_len() != size_of::<T>() {
+		crate::log!(
+			"address: {} has {} bytes after creation, expected {}",
+			account.address().as_ref(),
+			account.data_len(),
+			size_of::<T>()
+		);
+		crate::log_caller();
+
+		return Err(PinaProgramError::InvalidAccountSize.into());
+	}
+
+	Ok(())
+}
+
 /// Creates a new PDA-backed program account using a caller-provided `bump`.
 ///
 /// Prefer [`create_program_account`] when you want canonical bump derivation.
@@ -130,6 +266,10 @@ pub fn create_program_account<'a, T: HasDiscriminator + Pod>(
 /// Returns any error produced by [`allocate_account_with_bump`], including
 /// invalid seed layouts and system-program CPI failures.
 ///
+/// Fails to compile when `size_of::<T>()` exceeds
+/// [`MAX_PERMITTED_DATA_INCREASE`], since the runtime would reject the
+/// allocation anyway.
+///
 /// # Examples
 ///
 /// ```ignore
@@ -147,12 +287,143 @@ pub fn create_program_account_with_bump<'a, T: HasDiscriminator + Pod>(
 	seeds: &[&[u8]],
 	bump: u8,
 ) -> ProgramResult {
+	const {
+		assert!(
+			size_of::<T>() <= MAX_PERMITTED_DATA_INCREASE,
+			"account type is larger than `MAX_PERMITTED_DATA_INCREASE` and cannot be created in a \
+			 single CPI"
+		);
+	}
+
 	// Allocate space.
 	allocate_account_with_bump(target_account, payer, size_of::<T>(), owner, seeds, bump)?;
 
 	Ok(())
 }
 
+/// Creates a new PDA-backed program account and writes the value produced by
+/// `init` into it.
+///
+/// Fuses [`create_program_account_with_bump`] with the first write of the
+/// account data. The freshly allocated data is zeroed, so the value is copied
+/// in as raw bytes rather than through a typed, discriminator-checked view.
+/// The value returned by `init` must carry its own discriminator, as values
+/// produced by the `#[account]` builder do. A value that does not, such as
+/// `Zeroable::zeroed()`, is rejected instead of leaving an account behind
+/// that `assert_type::<T>` would never accept.
+///
+/// `init` only runs once the account has been created.
+///
+/// <!-- {=pinaPdaSeedContract|trim|linePrefix:"/// ":true} -->/// Seed-based APIs require deterministic seed ordering.
+///
+/// Program IDs must stay consistent across derivation and verification.
+///
+/// When a bump is required, prefer canonical bump derivation.
+///
+/// Use explicit bumps when needed.<!-- {/pinaPdaSeedContract} -->
+///
+/// # Errors
+///
+/// Returns any error produced by [`create_program_account_with_bump`],
+/// `AccountBorrowFailed` when the account data is already borrowed, or
+/// `InvalidAccountData` when the allocated data is smaller than `T` or the
+/// value does not start with `T`'s discriminator.
+///
+/// # Examples
+///
+/// ```ignore
+/// let seeds: &[&[u8]] = &[b"counter", authority.address().as_ref()];
+/// create_and_init(counter_account, authority, &program_id, seeds, bump, || {
+/// 	CounterState::builder().bump(bump).count(0u64.into()).build()
+/// })?;
+/// ```
+#[inline(always)]
+#[track_caller]
+pub fn create_and_init<T, F>(
+	target_account: &mut AccountView,
+	payer: &AccountView,
+	owner: &Address,
+	seeds: &[&[u8]],
+	bump: u8,
+	init: F,
+) -> ProgramResult
+where
+	T: HasDiscriminator + Pod,
+	F: FnOnce() -> T,
+{
+	create_program_account_with_bump::<T>(target_account, payer, owner, seeds, bump)?;
+
+	let value = init();
+	let mut data = target_account.try_borrow_mut()?;
+
+	write_initial_state(&mut data, &value)
+}
+
+/// Copy `value` into the leading bytes of freshly created account data after
+/// checking that it carries `T`'s discriminator.
+#[track_caller]
+fn write_initial_state<T: HasDiscriminator + Pod>(data: &mut [u8], value: &T) -> ProgramResult {
+	let bytes = bytemuck::bytes_of(value);
+
+	if !T::matches_discriminator(bytes) {
+		crate::log!("initial account state is missing its discriminator");
+		crate::log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	data.get_mut(..size_of::<T>())
+		.ok_or(ProgramError::InvalidAccountData)?
+		.copy_from_slice(bytes);
+
+	Ok(())
+}
+
+/// Creates a new PDA-backed program account sized for `element_count`
+/// elements of a variable-size layout, returning `(address, bump)`.
+///
+/// The account is allocated with `T::space(element_count)` bytes and left
+/// zeroed; the caller writes the header and elements afterwards.
+///
+/// <!-- {=pinaPdaSeedContract|trim|linePrefix:"/// ":true} -->/// Seed-based APIs require deterministic seed ordering.
+///
+/// Program IDs must stay consistent across derivation and verification.
+///
+/// When a bump is required, prefer canonical bump derivation.
+///
+/// Use explicit bumps when needed.<!-- {/pinaPdaSeedContract} -->
+///
+/// # Errors
+///
+/// Returns `PinaProgramError::InvalidAccountSize` when the computed space
+/// exceeds [`MAX_PERMITTED_DATA_INCREASE`], plus any error from
+/// [`allocate_account`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let seeds: &[&[u8]] = &[b"whitelist", authority.address().as_ref()];
+/// let (address, bump) = create_dynamic_account::<Whitelist>(
+/// 	whitelist_account, payer, &program_id, seeds, args.capacity as usize,
+/// )?;
+/// ```
+#[inline(always)]
+pub fn create_dynamic_account<'a, T: DynamicSpace>(
+	target_account: &'a AccountView,
+	payer: &'a AccountView,
+	owner: &Address,
+	seeds: &[&[u8]],
+	element_count: usize,
+) -> Result<(Address, u8), ProgramError> {
+	let space = T::space(element_count);
+
+	if space > MAX_PERMITTED_DATA_INCREASE {
+		return Err(PinaProgramError::InvalidAccountSize.into());
+	}
+
+	allocate_account(target_account, payer, space, owner, seeds)
+}
+
 /// Allocates space for a new program account, returning the derived `address`
 /// and the canonical `bump`.
 ///
@@ -250,8 +521,9 @@ pub fn combine_seeds_with_bump<'a>(
 ///
 /// - **Zero balance** -- a single `CreateAccount` CPI is issued.
 /// - **Non-zero balance** -- a `Transfer` (to top up rent), `Allocate`, and
-///   `Assign` are issued separately. This covers the case where the account was
-///   pre-funded (e.g. by a previous failed transaction).
+///   `Assign` are issued separately through [`fund_and_allocate`]. This covers
+///   the case where the account was pre-funded (e.g. by a previous failed
+///   transaction).
 ///
 /// <!-- {=pinaPdaSeedContract|trim|linePrefix:"/// ":true} -->/// Seed-based APIs require deterministic seed ordering.
 ///
@@ -307,8 +579,68 @@ pub fn allocate_account_with_bump<'a>(
 		return Ok(());
 	}
 
-	// Otherwise, if balance is nonzero:
+	// Otherwise the balance is nonzero and `CreateAccount` would fail.
+	fund_and_allocate_signed(payer, target_account, space, owner, &rent, signers)
+}
+
+/// Funds, allocates and assigns a PDA-backed program account as three
+/// separate signed system-program CPIs.
+///
+/// This is the manual path [`allocate_account_with_bump`] takes for accounts
+/// that already hold lamports, where `CreateAccount` would fail. Call it
+/// directly when a custom flow always needs the three-step sequence:
+///
+/// 1. `Transfer` from `payer` to top the account up to the rent-exempt minimum
+///    for `space`, skipped when it is already rent exempt.
+/// 2. `Allocate` `space` bytes.
+/// 3. `Assign` ownership to `program_id`.
+///
+/// <!-- {=pinaPdaSeedContract|trim|linePrefix:"/// ":true} -->/// Seed-based APIs require deterministic seed ordering.
+///
+/// Program IDs must stay consistent across derivation and verification.
+///
+/// When a bump is required, prefer canonical bump derivation.
+///
+/// Use explicit bumps when needed.<!-- {/pinaPdaSeedContract} -->
+///
+/// # Errors
+///
+/// Returns seed-validation errors, rent sysvar access errors, and any
+/// system-program CPI failure from `Transfer`, `Allocate`, or `Assign`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let seeds: &[&[u8]] = &[b"vault", authority.address().as_ref()];
+/// fund_and_allocate(payer, vault_account, 64, &program_id, seeds, bump)?;
+/// ```
+#[inline(always)]
+pub fn fund_and_allocate<'a>(
+	payer: &'a AccountView,
+	target_account: &'a AccountView,
+	space: usize,
+	program_id: &Address,
+	seeds: &[&[u8]],
+	bump: u8,
+) -> ProgramResult {
+	let bump_array = [bump];
+	let combined_seeds = combine_seeds_with_bump(seeds, &bump_array)?;
+	let seeds_slice = &combined_seeds[..=seeds.len()];
+	let signer = Signer::from(seeds_slice);
+	let rent = Rent::get()?;
+
+	fund_and_allocate_signed(payer, target_account, space, program_id, &rent, &[signer])
+}
 
+#[inline(always)]
+fn fund_and_allocate_signed(
+	payer: &AccountView,
+	target_account: &AccountView,
+	space: usize,
+	owner: &Address,
+	rent: &Rent,
+	signers: &[Signer],
+) -> ProgramResult {
 	// 1) transfer sufficient lamports for rent exemption
 	let rent_exempt_balance = rent
 		.try_minimum_balance(space)?
@@ -344,8 +676,8 @@ pub fn allocate_account_with_bump<'a>(
 ///
 /// This limit is enforced by the Solana runtime. Attempting to grow an account
 /// by more than this amount will cause `resize` to return
-/// `ProgramError::InvalidRealloc`.
-#[cfg(feature = "account-resize")]
+/// `ProgramError::InvalidRealloc`. It also caps the size of accounts created
+/// through a system-program CPI.
 pub const MAX_PERMITTED_DATA_INCREASE: usize = 10_240;
 
 /// Reallocates an account to `new_size` bytes, adjusting rent automatically.
@@ -378,7 +710,13 @@ pub fn realloc_account(
 	payer: &mut AccountView,
 	program_id: &Address,
 ) -> ProgramResult {
-	realloc_account_inner(account, new_size, payer, program_id)
+	realloc_account_inner(
+		account,
+		new_size,
+		payer,
+		|len| Rent::get()?.try_minimum_balance(len),
+		program_id,
+	)
 }
 
 /// Reallocates an account to `new_size` bytes with explicit zero-initialization,
@@ -416,7 +754,144 @@ pub fn realloc_account_zero(
 	payer: &mut AccountView,
 	program_id: &Address,
 ) -> ProgramResult {
-	realloc_account_inner(account, new_size, payer, program_id)
+	realloc_account_inner(
+		account,
+		new_size,
+		payer,
+		|len| Rent::get()?.try_minimum_balance(len),
+		program_id,
+	)
+}
+
+/// Resizes an account to `new_size` bytes and settles the rent-exempt minimum
+/// for the new size.
+///
+/// When **growing**, `payer` funds the missing rent through a system-program
+/// CPI. When **shrinking**, the lamports above the new minimum go to
+/// `refund_recipient`, or stay in the account when it is `None`. Unlike
+/// [`realloc_account`], the per-instruction growth limit is checked before
+/// any lamports move.
+///
+/// # Errors
+///
+/// Returns `ProgramError::InvalidRealloc` when the account would grow by more
+/// than [`MAX_PERMITTED_DATA_INCREASE`] bytes, plus the errors of
+/// [`realloc_account`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let len = Registry::space(entries);
+/// resize_with_rent(self.registry, len, self.payer, Some(self.authority), &ID)?;
+/// ```
+#[cfg(feature = "account-resize")]
+#[track_caller]
+pub fn resize_with_rent(
+	account: &mut AccountView,
+	new_size: usize,
+	payer: &mut AccountView,
+	refund_recipient: Option<&mut AccountView>,
+	program_id: &Address,
+) -> ProgramResult {
+	resize_with_rent_inner(
+		account,
+		new_size,
+		payer,
+		refund_recipient,
+		|len| Rent::get()?.try_minimum_balance(len),
+		program_id,
+	)
+}
+
+/// Resizes an account like [`resize_with_rent`], reading the rent-exempt
+/// minimum from an already loaded `rent` instead of the sysvar.
+///
+/// # Errors
+///
+/// Returns the same errors as [`resize_with_rent`], apart from rent sysvar
+/// access.
+///
+/// # Examples
+///
+/// ```ignore
+/// let rent = Rent::get()?;
+/// resize_with_rent_using(self.registry, len, self.payer, None, &rent, &ID)?;
+/// ```
+#[cfg(feature = "account-resize")]
+#[track_caller]
+pub fn resize_with_rent_using(
+	account: &mut AccountView,
+	new_size: usize,
+	payer: &mut AccountView,
+	refund_recipient: Option<&mut AccountView>,
+	rent: &Rent,
+	program_id: &Address,
+) -> ProgramResult {
+	resize_with_rent_inner(
+		account,
+		new_size,
+		payer,
+		refund_recipient,
+		|len| rent.try_minimum_balance(len),
+		program_id,
+	)
+}
+
+/// Shared implementation for [`resize_with_rent`] and
+/// [`resize_with_rent_using`]. `minimum_balance` is only called once lamports
+/// need to move.
+#[cfg(feature = "account-resize")]
+#[track_caller]
+fn resize_with_rent_inner(
+	account: &mut AccountView,
+	new_size: usize,
+	payer: &mut AccountView,
+	refund_recipient: Option<&mut AccountView>,
+	minimum_balance: impl FnOnce(usize) -> Result<u64, ProgramError>,
+	program_id: &Address,
+) -> ProgramResult {
+	use crate::AccountInfoValidation;
+
+	account.assert_writable()?.assert_owner(program_id)?;
+
+	let current_size = account.data_len();
+
+	if new_size.saturating_sub(current_size) > MAX_PERMITTED_DATA_INCREASE {
+		crate::log!(
+			"address: {} cannot grow by more than {} bytes in one instruction",
+			account.address().as_ref(),
+			MAX_PERMITTED_DATA_INCREASE
+		);
+		crate::log_caller();
+
+		return Err(ProgramError::InvalidRealloc);
+	}
+
+	if new_size == current_size {
+		return Ok(());
+	}
+
+	let new_minimum_balance = minimum_balance(new_size)?;
+	let current_lamports = account.lamports();
+
+	if new_size > current_size {
+		let required_lamports = new_minimum_balance.saturating_sub(current_lamports);
+		if required_lamports > 0 {
+			Transfer {
+				from: payer,
+				to: account,
+				lamports: required_lamports,
+			}
+			.invoke()?;
+		}
+	} else if let Some(recipient) = refund_recipient {
+		let excess_lamports = current_lamports.saturating_sub(new_minimum_balance);
+		if excess_lamports > 0 {
+			account.send(excess_lamports, recipient)?;
+		}
+	}
+
+	account.resize(new_size)
 }
 
 /// Shared implementation for [`realloc_account`] and [`realloc_account_zero`].
@@ -429,6 +904,7 @@ fn realloc_account_inner(
 	account: &mut AccountView,
 	new_size: usize,
 	payer: &mut AccountView,
+	minimum_balance: impl FnOnce(usize) -> Result<u64, ProgramError>,
 	program_id: &Address,
 ) -> ProgramResult {
 	use crate::AccountInfoValidation;
@@ -443,8 +919,7 @@ fn realloc_account_inner(
 		return Ok(());
 	}
 
-	let rent = Rent::get()?;
-	let new_minimum_balance = rent.try_minimum_balance(new_size)?;
+	let new_minimum_balance = minimum_balance(new_size)?;
 	let current_lamports = account.lamports();
 
 	if new_size > current_size {
@@ -470,6 +945,91 @@ fn realloc_account_inner(
 	account.resize(new_size)
 }
 
+/// Migrates `account` from [`Migrate::Previous`] to the layout `T` in place.
+///
+/// The stored previous layout is read and converted with [`Migrate::migrate`]
+/// before anything is changed. The account is then resized to `size_of::<T>()`
+/// with [`realloc_account`], which settles the rent difference with `payer`,
+/// and the new bytes, discriminator and version included, are written in a
+/// single copy.
+///
+/// An account that has already been migrated no longer carries the previous
+/// discriminator, so calling this twice fails instead of converting twice.
+///
+/// # Errors
+///
+/// Returns the [`AsAccount::as_account`] errors when `account` does not hold
+/// a `T::Previous` owned by `program_id`, and propagates any error from
+/// [`realloc_account`].
+///
+/// # Examples
+///
+/// ```ignore
+/// migrate_account::<CounterState>(self.counter, self.payer, &ID)?;
+/// ```
+#[cfg(feature = "account-resize")]
+pub fn migrate_account<T: Migrate>(
+	account: &mut AccountView,
+	payer: &mut AccountView,
+	program_id: &Address,
+) -> ProgramResult {
+	migrate_account_inner::<T>(
+		account,
+		payer,
+		|len| Rent::get()?.try_minimum_balance(len),
+		program_id,
+	)
+}
+
+/// Migrates an account like [`migrate_account`], reading the rent-exempt
+/// minimum from an already loaded `rent` instead of the sysvar.
+///
+/// # Errors
+///
+/// Returns the same errors as [`migrate_account`], apart from rent sysvar
+/// access.
+///
+/// # Examples
+///
+/// ```ignore
+/// let rent = Rent::get()?;
+/// migrate_account_using::<CounterState>(self.counter, self.payer, &rent, &ID)?;
+/// ```
+#[cfg(feature = "account-resize")]
+pub fn migrate_account_using<T: Migrate>(
+	account: &mut AccountView,
+	payer: &mut AccountView,
+	rent: &Rent,
+	program_id: &Address,
+) -> ProgramResult {
+	migrate_account_inner::<T>(
+		account,
+		payer,
+		|len| rent.try_minimum_balance(len),
+		program_id,
+	)
+}
+
+/// Shared implementation for [`migrate_account`] and
+/// [`migrate_account_using`].
+#[cfg(feature = "account-resize")]
+#[inline(always)]
+fn migrate_account_inner<T: Migrate>(
+	account: &mut AccountView,
+	payer: &mut AccountView,
+	minimum_balance: impl FnOnce(usize) -> Result<u64, ProgramError>,
+	program_id: &Address,
+) -> ProgramResult {
+	let next = T::migrate(&*account.as_account::<T::Previous>(program_id)?);
+
+	realloc_account_inner(account, size_of::<T>(), payer, minimum_balance, program_id)?;
+	account
+		.try_borrow_mut()?
+		.copy_from_slice(bytemuck::bytes_of(&next));
+
+	Ok(())
+}
+
 /// Closes an account and returns the remaining rent lamports to the provided
 /// recipient.
 ///
@@ -603,6 +1163,43 @@ impl<'a> CpiHandle<'a> {
 	}
 }
 
+/// Reject a CPI account the calling program still holds a conflicting borrow
+/// on.
+///
+/// pinocchio's checked invoke already refuses such accounts, but with a
+/// generic `AccountBorrowFailed` that does not say which account is affected.
+/// Debug builds check first, log the account and return
+/// [`PinaProgramError::AccountBorrowedDuringCpi`], which usually means an
+/// `as_account_mut` guard is still alive. Release builds skip the check and
+/// leave it to pinocchio.
+#[cfg(debug_assertions)]
+#[track_caller]
+fn check_cpi_borrow(account: &AccountView, writable: bool) -> ProgramResult {
+	let conflicting = if writable {
+		account.is_borrowed()
+	} else {
+		account.is_borrowed_mut()
+	};
+
+	if !conflicting {
+		return Ok(());
+	}
+
+	crate::log!(
+		"account: {} is still borrowed during a CPI, drop its guard before invoking",
+		account.address().as_ref()
+	);
+	crate::log_caller();
+
+	Err(PinaProgramError::AccountBorrowedDuringCpi.into())
+}
+
+#[cfg(not(debug_assertions))]
+#[inline(always)]
+fn check_cpi_borrow(_account: &AccountView, _writable: bool) -> ProgramResult {
+	Ok(())
+}
+
 /// Convert a typed CPI accounts struct into a fixed-size array of handles.
 ///
 /// This is the no-allocation counterpart to Anchor lang-v2's `ToCpiAccounts`.
@@ -644,9 +1241,18 @@ where
 	/// This keeps the prototype allocator-free and avoids introducing unsafe
 	/// unchecked invocation until Pina has a stronger typed account runtime for
 	/// duplicate-account and alias analysis.
+	///
+	/// In debug builds an account that is still borrowed by the caller is
+	/// reported as [`PinaProgramError::AccountBorrowedDuringCpi`].
 	#[inline(always)]
+	#[track_caller]
 	pub fn invoke(&self, data: &[u8], signers: &[Signer<'_, '_>]) -> ProgramResult {
 		let handles = self.accounts.to_cpi_handles();
+
+		for handle in handles {
+			check_cpi_borrow(handle.view, handle.writable)?;
+		}
+
 		let instruction_accounts = handles.map(CpiHandle::instruction_account);
 		let account_views = handles.map(CpiHandle::account_view);
 		let instruction = InstructionView {
@@ -658,3 +1264,734 @@ where
 		pinocchio::cpi::invoke_signed::<ACCOUNTS, _>(&instruction, &account_views, signers)
 	}
 }
+
+/// Shared accounts for a sequence of token-program CPIs.
+///
+/// Instructions like an escrow `Take` often transfer out of a vault and then
+/// close it, passing the same token program, authority and mint to every
+/// call. `TokenCpi` holds those accounts once so each CPI only names the
+/// accounts that differ.
+///
+/// The instructions are built with the token-2022 layouts, which are
+/// identical to SPL Token for transfers and closes, and are sent to whichever
+/// token program account the context holds. Like [`CpiContext::invoke`],
+/// debug builds report accounts the caller still borrows as
+/// [`PinaProgramError::AccountBorrowedDuringCpi`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let vault_cpi = TokenCpi::new(self.token_program, self.escrow, self.mint_a);
+///
+/// vault_cpi.transfer_checked_signed(self.vault, self.taker_ata_a, amount, decimals, &signers)?;
+/// vault_cpi.close_signed(self.vault, self.maker, &signers)?;
+/// ```
+#[cfg(feature = "token")]
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct TokenCpi<'a> {
+	/// The token program the CPIs are sent to.
+	pub token_program: &'a AccountView,
+	/// The owner or delegate authorizing each CPI.
+	pub authority: &'a AccountView,
+	/// The mint of the token accounts being moved or closed.
+	pub mint: &'a AccountView,
+}
+
+#[cfg(feature = "token")]
+impl<'a> TokenCpi<'a> {
+	#[inline(always)]
+	pub const fn new(
+		token_program: &'a AccountView,
+		authority: &'a AccountView,
+		mint: &'a AccountView,
+	) -> Self {
+		Self {
+			token_program,
+			authority,
+			mint,
+		}
+	}
+
+	/// Transfer `amount` tokens from `from` to `to` with a `TransferChecked`
+	/// CPI signed by the authority.
+	///
+	/// # Errors
+	///
+	/// Returns errors from the underlying token-program CPI.
+	#[inline(always)]
+	pub fn transfer_checked(
+		&self,
+		from: &'a AccountView,
+		to: &'a AccountView,
+		amount: u64,
+		decimals: u8,
+	) -> ProgramResult {
+		self.transfer_checked_signed(from, to, amount, decimals, &[])
+	}
+
+	/// Transfer `amount` tokens from `from` to `to` with a `TransferChecked`
+	/// CPI, signing for a PDA authority with `signers`.
+	///
+	/// # Errors
+	///
+	/// Returns errors from the underlying token-program CPI.
+	#[inline(always)]
+	pub fn transfer_checked_signed(
+		&self,
+		from: &'a AccountView,
+		to: &'a AccountView,
+		amount: u64,
+		decimals: u8,
+		signers: &[Signer<'_, '_>],
+	) -> ProgramResult {
+		check_cpi_borrow(from, true)?;
+		check_cpi_borrow(to, true)?;
+		check_cpi_borrow(self.mint, false)?;
+		check_cpi_borrow(self.authority, false)?;
+
+		pinocchio_token_2022::instructions::TransferChecked {
+			from,
+			mint: self.mint,
+			to,
+			authority: self.authority,
+			amount,
+			decimals,
+			token_program: self.token_program.address(),
+		}
+		.invoke_signed(signers)
+	}
+
+	/// Close `account` and send its lamports to `destination` with a
+	/// `CloseAccount` CPI signed by the authority.
+	///
+	/// # Errors
+	///
+	/// Returns errors from the underlying token-program CPI.
+	#[inline(always)]
+	pub fn close(&self, account: &'a AccountView, destination: &'a AccountView) -> ProgramResult {
+		self.close_signed(account, destination, &[])
+	}
+
+	/// Close `account` and send its lamports to `destination` with a
+	/// `CloseAccount` CPI, signing for a PDA authority with `signers`.
+	///
+	/// # Errors
+	///
+	/// Returns errors from the underlying token-program CPI.
+	#[inline(always)]
+	pub fn close_signed(
+		&self,
+		account: &'a AccountView,
+		destination: &'a AccountView,
+		signers: &[Signer<'_, '_>],
+	) -> ProgramResult {
+		check_cpi_borrow(account, true)?;
+		check_cpi_borrow(destination, true)?;
+		check_cpi_borrow(self.authority, false)?;
+
+		pinocchio_token_2022::instructions::CloseAccount {
+			account,
+			destination,
+			authority: self.authority,
+			token_program: self.token_program.address(),
+		}
+		.invoke_signed(signers)
+	}
+}
+
+/// Byte offset of the `amount` field shared by SPL Token and Token-2022
+/// accounts, after the mint and owner addresses.
+#[cfg(feature = "token")]
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+#[cfg(feature = "token")]
+fn token_amount(account: &AccountView) -> Result<u64, ProgramError> {
+	let data = account.try_borrow()?;
+	let bytes = data
+		.get(TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + size_of::<u64>())
+		.ok_or(PinaProgramError::DataTooShort)?;
+	let mut amount = [0u8; size_of::<u64>()];
+	amount.copy_from_slice(bytes);
+
+	Ok(u64::from_le_bytes(amount))
+}
+
+/// Run `transfer` and assert it moved exactly `amount` tokens from `from` to
+/// `to`.
+///
+/// Both token balances are read before and after `transfer` runs. `from` must
+/// decrease and `to` increase by exactly `amount`; when both are the same
+/// account its balance must not change. This is the check behind
+/// [`transfer_checked_verified`], exposed for transfers issued through other
+/// instructions.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns errors from `transfer`, `DataTooShort` when either account is too
+/// short to hold a token balance, and
+/// [`PinaProgramError::TokenTransferMismatch`] when the balances moved by any
+/// other amount.
+///
+/// # Examples
+///
+/// ```ignore
+/// verify_token_transfer(from, to, amount, || {
+/// 	TokenCpi::new(token_program, authority, mint).transfer_checked(from, to, amount, decimals)
+/// })?;
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn verify_token_transfer(
+	from: &AccountView,
+	to: &AccountView,
+	amount: u64,
+	transfer: impl FnOnce() -> ProgramResult,
+) -> ProgramResult {
+	let from_before = token_amount(from)?;
+	let to_before = token_amount(to)?;
+
+	transfer()?;
+
+	let from_after = token_amount(from)?;
+	let to_after = token_amount(to)?;
+	let exact = if from.address() == to.address() {
+		from_after == from_before
+	} else {
+		from_before.checked_sub(from_after) == Some(amount)
+			&& to_after.checked_sub(to_before) == Some(amount)
+	};
+
+	if exact {
+		return Ok(());
+	}
+
+	crate::log!(
+		"token transfer of {} moved {} out and {} in",
+		amount,
+		from_before.wrapping_sub(from_after),
+		to_after.wrapping_sub(to_before)
+	);
+	crate::log_caller();
+
+	Err(PinaProgramError::TokenTransferMismatch.into())
+}
+
+/// Transfer `amount` tokens with a `TransferChecked` CPI and assert both
+/// balances moved by exactly `amount`.
+///
+/// Token-2022 mints with a transfer fee withhold part of every transfer from
+/// the recipient, so a plain `TransferChecked` can credit less than the amount
+/// an instruction accounted for. Use this for transfers that must arrive in
+/// full.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns errors from the underlying token-program CPI and from
+/// [`verify_token_transfer`].
+///
+/// # Examples
+///
+/// ```ignore
+/// transfer_checked_verified(
+/// 	self.maker_ata_a,
+/// 	self.vault,
+/// 	self.mint_a,
+/// 	self.maker,
+/// 	amount,
+/// 	decimals,
+/// 	self.token_program,
+/// )?;
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn transfer_checked_verified<'a>(
+	from: &'a AccountView,
+	to: &'a AccountView,
+	mint: &'a AccountView,
+	authority: &'a AccountView,
+	amount: u64,
+	decimals: u8,
+	token_program: &'a AccountView,
+) -> ProgramResult {
+	verify_token_transfer(from, to, amount, || {
+		TokenCpi::new(token_program, authority, mint).transfer_checked(from, to, amount, decimals)
+	})
+}
+
+/// Transfer `amount` tokens from `from` to `to` with a `TransferChecked` CPI
+/// to whichever token program `token_program` is.
+///
+/// `token_program` must be SPL Token or Token-2022, and the mint and both
+/// token accounts must be owned by it, so accounts of one program can never
+/// be moved through the other. The decimals are read from the mint with the
+/// layout of that program, so callers do not pass them in.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Errors
+///
+/// - `InvalidAccountData` when `token_program` is not a token program or the
+///   mint is too short to be one.
+/// - `InvalidAccountOwner` when the mint, `from` or `to` is owned by another
+///   program.
+/// - Errors from the underlying token-program CPI.
+///
+/// # Examples
+///
+/// ```ignore
+/// transfer_checked_tokens(
+/// 	self.maker_ata_a,
+/// 	self.vault,
+/// 	self.mint_a,
+/// 	self.maker,
+/// 	amount,
+/// 	self.token_program,
+/// )?;
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+#[inline(always)]
+pub fn transfer_checked_tokens<'a>(
+	from: &'a AccountView,
+	to: &'a AccountView,
+	mint: &'a AccountView,
+	authority: &'a AccountView,
+	amount: u64,
+	token_program: &'a AccountView,
+) -> ProgramResult {
+	transfer_checked_tokens_signed(from, to, mint, authority, amount, token_program, &[])
+}
+
+/// Transfer tokens like [`transfer_checked_tokens`], signing for a PDA
+/// authority with `signers`.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Errors
+///
+/// Returns the errors of [`transfer_checked_tokens`].
+///
+/// # Examples
+///
+/// ```ignore
+/// transfer_checked_tokens_signed(
+/// 	self.vault,
+/// 	self.taker_ata_a,
+/// 	self.mint_a,
+/// 	self.escrow,
+/// 	amount,
+/// 	self.token_program,
+/// 	&signers,
+/// )?;
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn transfer_checked_tokens_signed<'a>(
+	from: &'a AccountView,
+	to: &'a AccountView,
+	mint: &'a AccountView,
+	authority: &'a AccountView,
+	amount: u64,
+	token_program: &'a AccountView,
+	signers: &[Signer<'_, '_>],
+) -> ProgramResult {
+	use crate::AccountInfoValidation;
+	use crate::AsTokenAccount;
+
+	token_program.assert_addresses(&crate::impls::TOKEN_PROGRAM_IDS)?;
+	let program_id = token_program.address();
+	let is_token_2022 = program_id == &crate::token_2022::ID;
+	let owners = core::slice::from_ref(program_id);
+
+	let decimals = if is_token_2022 {
+		mint.as_token_2022_mint_checked_with_owners(owners)?
+			.decimals()
+	} else {
+		mint.as_token_mint_checked_with_owners(owners)?.decimals()
+	};
+
+	from.assert_owner(program_id)?;
+	to.assert_owner(program_id)?;
+
+	check_cpi_borrow(from, true)?;
+	check_cpi_borrow(to, true)?;
+	check_cpi_borrow(mint, false)?;
+	check_cpi_borrow(authority, false)?;
+
+	if is_token_2022 {
+		pinocchio_token_2022::instructions::TransferChecked {
+			from,
+			mint,
+			to,
+			authority,
+			amount,
+			decimals,
+			token_program: program_id,
+		}
+		.invoke_signed(signers)
+	} else {
+		crate::token::instructions::TransferChecked::new(
+			from, mint, to, authority, amount, decimals,
+		)
+		.invoke_signed(signers)
+	}
+}
+
+/// Returns the associated token account of `wallet` and `mint`, creating it
+/// with a `CreateIdempotent` CPI funded by `payer` when it does not exist yet.
+///
+/// The token program must be SPL Token or Token-2022 and `account` must be the
+/// canonical ATA derived with it. The returned state is read with the
+/// Token-2022 layout, which covers accounts of either program, and is checked
+/// to belong to `wallet` and `mint`, so an existing account handed in at the
+/// ATA address cannot have been reassigned to another owner.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Errors
+///
+/// - `InvalidAccountData` when `token_program` is not a token program, or the
+///   token account belongs to another owner or mint.
+/// - `InvalidSeeds` when `account` is not the canonical ATA address.
+/// - `InvalidAccountOwner` when the account is not owned by `token_program`.
+/// - Errors from the associated token account program CPI.
+///
+/// # Examples
+///
+/// ```ignore
+/// let vault = create_or_get_associated_token_account(
+/// 	self.vault,
+/// 	self.maker,
+/// 	self.escrow,
+/// 	self.mint_a,
+/// 	self.system_program,
+/// 	self.token_program,
+/// )?;
+/// let deposited = vault.amount();
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn create_or_get_associated_token_account<'a>(
+	account: &'a AccountView,
+	payer: &'a AccountView,
+	wallet: &'a AccountView,
+	mint: &'a AccountView,
+	system_program: &'a AccountView,
+	token_program: &'a AccountView,
+) -> Result<crate::Ref<'a, crate::token_2022::state::TokenAccount>, ProgramError> {
+	use crate::AccountInfoValidation;
+	use crate::AsTokenAccount;
+
+	token_program.assert_addresses(&crate::impls::TOKEN_PROGRAM_IDS)?;
+	account.assert_associated_token_address(
+		wallet.address(),
+		mint.address(),
+		token_program.address(),
+	)?;
+
+	if account.is_data_empty() {
+		check_cpi_borrow(account, true)?;
+		check_cpi_borrow(payer, true)?;
+
+		pinocchio_associated_token_account::instructions::CreateIdempotent {
+			funding_account: payer,
+			account,
+			wallet,
+			mint,
+			system_program,
+			token_program,
+		}
+		.invoke()?;
+	}
+
+	let state = account.as_token_2022_account_checked_with_owners(core::slice::from_ref(
+		token_program.address(),
+	))?;
+
+	if state.owner() != wallet.address() || state.mint() != mint.address() {
+		crate::log!(
+			"address: {} is not the token account of the wallet and mint",
+			account.address().as_ref()
+		);
+		crate::log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(state)
+}
+
+/// Creates the associated token account of `wallet` and `mint` when it does
+/// not exist yet, like [`create_or_get_associated_token_account`], without
+/// keeping its data borrowed.
+///
+/// Use it when the account is only the destination of a later CPI, which
+/// cannot run while the returned state of
+/// [`create_or_get_associated_token_account`] is still alive.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Errors
+///
+/// Returns the errors of [`create_or_get_associated_token_account`].
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn create_associated_token_account_if_needed(
+	account: &AccountView,
+	payer: &AccountView,
+	wallet: &AccountView,
+	mint: &AccountView,
+	system_program: &AccountView,
+	token_program: &AccountView,
+) -> ProgramResult {
+	create_or_get_associated_token_account(
+		account,
+		payer,
+		wallet,
+		mint,
+		system_program,
+		token_program,
+	)
+	.map(drop)
+}
+
+/// Seed of the event authority PDA that signs [`emit_event_cpi`]
+/// self-invocations. Matches Anchor's `__event_authority`.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Instruction data prefix of an event self-invocation.
+///
+/// These are the bytes of Anchor's `EVENT_IX_TAG_LE`, so indexers that
+/// already decode Anchor's `emit_cpi!` events read pina events the same way.
+pub const EVENT_IX_TAG: [u8; 8] = 0x1d9a_cb51_2ea5_45e4_u64.to_le_bytes();
+
+/// The largest event, in bytes, [`emit_event_cpi`] can send.
+///
+/// The instruction data is assembled in a stack buffer of
+/// `EVENT_IX_TAG.len() + MAX_CPI_EVENT_LEN` bytes.
+pub const MAX_CPI_EVENT_LEN: usize = 1024;
+
+/// Derives the event authority PDA of `program_id` and its bump.
+///
+/// Returns `None` if no valid PDA exists.
+pub fn find_event_authority(program_id: &Address) -> Option<(Address, u8)> {
+	crate::try_find_program_address(&[EVENT_AUTHORITY_SEED], program_id)
+}
+
+/// Emits `event` by invoking `program_id` itself with the event bytes.
+///
+/// The inner instruction carries [`EVENT_IX_TAG`] followed by the Pod event,
+/// discriminator first, and is signed by the event authority PDA derived from
+/// [`EVENT_AUTHORITY_SEED`] and `bump`. Indexers read it from the
+/// transaction's inner instructions, which survive log truncation. The
+/// program's entrypoint must route these instructions to
+/// [`process_emit_event`].
+///
+/// Like accounts structs marked with Anchor's `#[event_cpi]`, the instruction
+/// must pass both the event authority and the program's own account, since
+/// the runtime only invokes programs whose account is in the transaction.
+/// Store the bump, or hardcode it, rather than calling
+/// [`find_event_authority`] on every emit.
+///
+/// Prefer the [`emit_cpi!`](crate::emit_cpi) macro inside processors. Events
+/// larger than [`MAX_CPI_EVENT_LEN`] fail to compile.
+///
+/// # Errors
+///
+/// Returns `InvalidAccountData` when `program` is not the executable
+/// `program_id` account and errors from the self-invocation.
+///
+/// # Examples
+///
+/// ```ignore
+/// emit_event_cpi(&event, &ID, self.program, self.event_authority, bump)?;
+/// ```
+#[track_caller]
+pub fn emit_event_cpi<T: HasDiscriminator + Pod>(
+	event: &T,
+	program_id: &Address,
+	program: &AccountView,
+	event_authority: &AccountView,
+	bump: u8,
+) -> ProgramResult {
+	const {
+		assert!(
+			size_of::<T>() <= MAX_CPI_EVENT_LEN,
+			"emit_event_cpi: the event is larger than MAX_CPI_EVENT_LEN"
+		);
+	}
+
+	use crate::AccountInfoValidation;
+
+	program.assert_program(program_id)?;
+	check_cpi_borrow(event_authority, false)?;
+
+	let event = bytemuck::bytes_of(event);
+	let len = EVENT_IX_TAG.len() + event.len();
+	let mut buffer = [0u8; EVENT_IX_TAG.len() + MAX_CPI_EVENT_LEN];
+	let (tag, payload) = buffer.split_at_mut(EVENT_IX_TAG.len());
+	tag.copy_from_slice(&EVENT_IX_TAG);
+	payload[..event.len()].copy_from_slice(event);
+
+	let bump = [bump];
+	let seeds = [
+		Seed::from(EVENT_AUTHORITY_SEED),
+		Seed::from(bump.as_slice()),
+	];
+	let signer = Signer::from(&seeds[..]);
+	let accounts = [InstructionAccount::readonly_signer(
+		event_authority.address(),
+	)];
+	let instruction = InstructionView {
+		program_id,
+		data: &buffer[..len],
+		accounts: &accounts,
+	};
+
+	pinocchio::cpi::invoke_signed::<2, _>(&instruction, &[event_authority, program], &[signer])
+}
+
+/// Returns `true` when `data` is an event self-invocation sent by
+/// [`emit_event_cpi`].
+///
+/// Check this in the entrypoint before parsing the regular instruction
+/// discriminator:
+///
+/// ```ignore
+/// if is_emit_event_instruction(data) {
+/// 	return process_emit_event(program_id, accounts, EVENT_AUTHORITY_BUMP);
+/// }
+/// ```
+pub fn is_emit_event_instruction(data: &[u8]) -> bool {
+	data.starts_with(&EVENT_IX_TAG)
+}
+
+/// Accepts an event self-invocation.
+///
+/// The event bytes only need to reach the inner instruction, so there is
+/// nothing to execute. The first account must be the program's event
+/// authority and must have signed, which only the program itself can do
+/// through [`emit_event_cpi`]. This stops anyone else from forging events
+/// with a direct call.
+///
+/// `bump` is the event authority bump passed to [`emit_event_cpi`]. The
+/// address is checked with [`create_program_address`](crate::create_program_address)
+/// from it, so pass the stored or hardcoded bump rather than calling
+/// [`find_event_authority`] here.
+///
+/// # Errors
+///
+/// Returns `NotEnoughAccountKeys` without accounts, `MissingRequiredSignature`
+/// when the first account did not sign and `InvalidSeeds` when it is not the
+/// event authority of `program_id` with `bump`.
+///
+/// # Examples
+///
+/// ```ignore
+/// if is_emit_event_instruction(data) {
+/// 	return process_emit_event(program_id, accounts, EVENT_AUTHORITY_BUMP);
+/// }
+/// ```
+#[track_caller]
+pub fn process_emit_event(
+	program_id: &Address,
+	accounts: &[AccountView],
+	bump: u8,
+) -> ProgramResult {
+	let Some(event_authority) = accounts.first() else {
+		return Err(ProgramError::NotEnoughAccountKeys);
+	};
+
+	if !event_authority.is_signer() {
+		crate::log!("event authority did not sign the event instruction");
+		crate::log_caller();
+
+		return Err(ProgramError::MissingRequiredSignature);
+	}
+
+	let bump = [bump];
+	match crate::create_program_address(&[EVENT_AUTHORITY_SEED, &bump], program_id) {
+		Ok(address) if &address == event_authority.address() => Ok(()),
+		_ => {
+			crate::log!(
+				"address: {} is not the event authority",
+				event_authority.address().as_ref()
+			);
+			crate::log_caller();
+
+			Err(ProgramError::InvalidSeeds)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[repr(C)]
+	#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+	struct Tagged {
+		discriminator: u8,
+		value: u8,
+	}
+
+	impl HasDiscriminator for Tagged {
+		type Type = u8;
+
+		const VALUE: u8 = 7;
+	}
+
+	#[test]
+	fn write_initial_state_copies_tagged_values() {
+		let mut data = [0u8; 3];
+		let value = Tagged {
+			discriminator: 7,
+			value: 42,
+		};
+
+		assert_eq!(write_initial_state(&mut data, &value), Ok(()));
+		assert_eq!(data, [7, 42, 0]);
+	}
+
+	#[test]
+	fn write_initial_state_rejects_values_without_the_discriminator() {
+		let mut data = [0u8; 2];
+		let value = Tagged {
+			discriminator: 0,
+			value: 42,
+		};
+
+		assert_eq!(
+			write_initial_state(&mut data, &value),
+			Err(ProgramError::InvalidAccountData)
+		);
+		assert_eq!(data, [0, 0]);
+	}
+
+	#[test]
+	fn write_initial_state_rejects_short_data() {
+		let mut data = [0u8; 1];
+		let value = Tagged {
+			discriminator: 7,
+			value: 42,
+		};
+
+		assert_eq!(
+			write_initial_state(&mut data, &value),
+			Err(ProgramError::InvalidAccountData)
+		);
+	}
+}
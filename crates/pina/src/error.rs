@@ -13,6 +13,21 @@
 #[non_exhaustive]
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum PinaProgramError {
+	/// The account was closed by `close_account_secure` earlier in the
+	/// transaction and still carries the closed-account discriminator.
+	AccountClosed = 0xFFFF_FFF4,
+	/// A mint has no authority of the kind being checked, so no account can
+	/// act as it.
+	AuthorityNotSet = 0xFFFF_FFF5,
+	/// The total lamports across a set of accounts changed by more than the
+	/// expected outflow during an instruction.
+	LamportsNotConserved = 0xFFFF_FFF6,
+	/// A token transfer moved a different amount than requested, for example
+	/// because a Token-2022 transfer fee was withheld.
+	TokenTransferMismatch = 0xFFFF_FFF7,
+	/// An account passed to a CPI is still borrowed by the calling program.
+	/// Only reported in debug builds.
+	AccountBorrowedDuringCpi = 0xFFFF_FFF8,
 	/// Two mutable account fields point at the same runtime account.
 	DuplicateMutableAccount = 0xFFFF_FFF9,
 	/// Account or instruction data is shorter than the expected minimum.
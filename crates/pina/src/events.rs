@@ -0,0 +1,110 @@
+//! Off-chain decoding of events emitted into transaction logs.
+//!
+//! Programs emit events with `sol_log_data`, which the runtime records as a
+//! `Program data: <base64>` log line. The helpers here reverse that process so
+//! clients and tests can turn captured log lines back into typed events.
+//!
+//! This module requires the `client` feature because it allocates.
+
+use alloc::vec::Vec;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bytemuck::Pod;
+
+use crate::HasDiscriminator;
+use crate::IntoDiscriminator;
+
+/// The prefix the runtime adds to data logged with `sol_log_data`.
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Decode the raw bytes of an emitted event log line.
+///
+/// Accepts either the full `Program data: <base64>` line or the bare base64
+/// payload. When the program logged several slices the runtime separates them
+/// with spaces; their decoded bytes are concatenated in order.
+///
+/// Returns `None` when the line contains no data or is not valid base64.
+pub fn decode_event_bytes(log_line: &str) -> Option<Vec<u8>> {
+	let encoded = log_line.trim();
+	let encoded = encoded.strip_prefix(PROGRAM_DATA_PREFIX).unwrap_or(encoded);
+	let mut bytes = Vec::new();
+
+	for chunk in encoded.split_ascii_whitespace() {
+		STANDARD.decode_vec(chunk, &mut bytes).ok()?;
+	}
+
+	if bytes.is_empty() {
+		return None;
+	}
+
+	Some(bytes)
+}
+
+/// Split an emitted event log line into its discriminator and payload bytes.
+///
+/// The payload is everything after the leading `D::BYTES` discriminator
+/// bytes. Returns `None` when the line cannot be decoded or the decoded data
+/// does not start with a known discriminator of type `D`.
+///
+/// # Examples
+///
+/// ```ignore
+/// let (kind, payload) = decode_event::<MyEventKind>(log_line)?;
+/// ```
+pub fn decode_event<D: IntoDiscriminator>(log_line: &str) -> Option<(D, Vec<u8>)> {
+	let mut bytes = decode_event_bytes(log_line)?;
+
+	if bytes.len() < D::BYTES {
+		return None;
+	}
+
+	let discriminator = D::discriminator_from_bytes(&bytes).ok()?;
+	bytes.drain(..D::BYTES);
+
+	Some((discriminator, bytes))
+}
+
+/// Reconstruct a typed event from an emitted log line.
+///
+/// Implemented for every [`HasDiscriminator`] + [`Pod`] type, which includes
+/// all `#[event]` structs.
+pub trait EventLog: HasDiscriminator + Pod {
+	/// Decode `log_line` into `Self`.
+	///
+	/// Returns `None` when the line cannot be decoded, carries a different
+	/// discriminator, or does not match the size of `Self`.
+	///
+	/// # Examples
+	///
+	/// ```ignore
+	/// let event = MyEvent::from_log("Program data: AQoAAAA=").unwrap();
+	/// ```
+	fn from_log(log_line: &str) -> Option<Self> {
+		let bytes = decode_event_bytes(log_line)?;
+
+		if !Self::VALUE.matches_discriminator(&bytes) {
+			return None;
+		}
+
+		bytemuck::try_pod_read_unaligned(&bytes).ok()
+	}
+
+	/// Decode the data of an inner instruction sent by
+	/// [`emit_event_cpi`](crate::emit_event_cpi) into `Self`.
+	///
+	/// Returns `None` when `data` does not start with
+	/// [`EVENT_IX_TAG`](crate::EVENT_IX_TAG), carries a different
+	/// discriminator, or does not match the size of `Self`.
+	fn from_cpi_data(data: &[u8]) -> Option<Self> {
+		let bytes = data.strip_prefix(&crate::EVENT_IX_TAG)?;
+
+		if !Self::VALUE.matches_discriminator(bytes) {
+			return None;
+		}
+
+		bytemuck::try_pod_read_unaligned(bytes).ok()
+	}
+}
+
+impl<T: HasDiscriminator + Pod> EventLog for T {}
@@ -5,6 +5,8 @@ use core::mem::size_of;
 use pinocchio::ProgramResult;
 #[cfg(feature = "token")]
 use pinocchio::account::Ref as AccountRef;
+use pinocchio::sysvars::Sysvar;
+use pinocchio::sysvars::rent::Rent;
 use pinocchio_system::instructions::Transfer;
 
 use crate::AccountDeserialize;
@@ -16,9 +18,14 @@ use crate::Address;
 use crate::AsAccount;
 #[cfg(feature = "token")]
 use crate::AsTokenAccount;
+use crate::CLOSED_ACCOUNT_DISCRIMINATOR;
 use crate::CloseAccountWithRecipient;
+use crate::ExternalAccount;
 use crate::HasDiscriminator;
+use crate::IntoDiscriminator;
 use crate::LamportTransfer;
+use crate::MAX_DISCRIMINATOR_SPACE;
+use crate::PinaProgramError;
 use crate::Pod;
 use crate::ProgramError;
 use crate::Ref;
@@ -70,6 +77,39 @@ fn validate_executable(account: &AccountView) -> ProgramResult {
 	Ok(())
 }
 
+#[track_caller]
+fn validate_not_executable(account: &AccountView) -> ProgramResult {
+	if account.executable() {
+		log!(
+			"address: {} is executable, expected a data account",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(())
+}
+
+#[track_caller]
+fn validate_not_closed(account: &AccountView) -> ProgramResult {
+	let data = account.try_borrow()?;
+	let len = data.len().min(MAX_DISCRIMINATOR_SPACE);
+
+	if len > 0 && data[..len] == CLOSED_ACCOUNT_DISCRIMINATOR[..len] {
+		log!(
+			"address: {} was closed earlier in the transaction",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(PinaProgramError::AccountClosed.into());
+	}
+
+	Ok(())
+}
+
 #[track_caller]
 fn validate_data_len(account: &AccountView, len: usize) -> ProgramResult {
 	if account.data_len() != len {
@@ -85,6 +125,92 @@ fn validate_data_len(account: &AccountView, len: usize) -> ProgramResult {
 	Ok(())
 }
 
+#[track_caller]
+fn validate_array<D: HasDiscriminator, T, const N: usize>(
+	account: &AccountView,
+	program_id: &Address,
+) -> ProgramResult {
+	validate_owner(account, program_id)?;
+
+	let data = account.try_borrow()?;
+
+	if !D::matches_discriminator(&data) {
+		log!(
+			"address: {} has invalid discriminator",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	let expected = size_of::<[T; N]>().checked_add(<D::Type as IntoDiscriminator>::BYTES);
+
+	if expected != Some(data.len()) {
+		log!(
+			"address: {} has invalid data length for the array",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(())
+}
+
+#[track_caller]
+fn validate_capacity(
+	account: &AccountView,
+	header: usize,
+	current_len: usize,
+	element_size: usize,
+) -> ProgramResult {
+	let required = current_len
+		.checked_add(1)
+		.and_then(|len| len.checked_mul(element_size))
+		.and_then(|elements| elements.checked_add(header));
+
+	if required.is_none_or(|required| account.data_len() < required) {
+		log!(
+			"address: {} has no capacity for another element",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::AccountDataTooSmall);
+	}
+
+	Ok(())
+}
+
+#[track_caller]
+fn validate_data_prefix(account: &AccountView, prefix: &[u8]) -> ProgramResult {
+	let data = account.try_borrow()?;
+
+	let Some(head) = data.get(..prefix.len()) else {
+		log!(
+			"address: {} is too short for the expected data prefix",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(PinaProgramError::DataTooShort.into());
+	};
+
+	if head != prefix {
+		log!(
+			"address: {} does not start with the expected data prefix",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(())
+}
+
 #[track_caller]
 fn validate_empty(account: &AccountView) -> ProgramResult {
 	if !account.is_data_empty() {
@@ -97,6 +223,40 @@ fn validate_empty(account: &AccountView) -> ProgramResult {
 	Ok(())
 }
 
+#[track_caller]
+fn validate_strictly_uninitialized(account: &AccountView) -> ProgramResult {
+	validate_owner(account, &crate::system::ID)?;
+	validate_empty(account)
+}
+
+/// Check that `account` holds the rent-exempt minimum for `len` bytes plus
+/// `buffer`, failing with `error` otherwise.
+#[track_caller]
+fn validate_minimum_balance(
+	account: &AccountView,
+	rent: &Rent,
+	len: usize,
+	buffer: u64,
+	error: ProgramError,
+) -> ProgramResult {
+	let required = rent.try_minimum_balance(len)?.checked_add(buffer);
+
+	if required.is_none_or(|required| account.lamports() < required) {
+		log!(
+			"address: {} holds {} lamports, less than rent for {} bytes plus a buffer of {}",
+			account.address().as_ref(),
+			account.lamports(),
+			len,
+			buffer
+		);
+		log_caller();
+
+		return Err(error);
+	}
+
+	Ok(())
+}
+
 #[track_caller]
 fn validate_not_empty(account: &AccountView) -> ProgramResult {
 	if account.is_data_empty() {
@@ -121,10 +281,17 @@ fn validate_type<T: HasDiscriminator>(
 	program_id: &Address,
 ) -> ProgramResult {
 	validate_owner(account, program_id)?;
+	validate_type_data::<T>(account, &account.try_borrow()?)
+}
 
-	let data = account.try_borrow()?;
+/// The discriminator and size half of [`validate_type`], for callers that
+/// already hold the data borrow.
+#[track_caller]
+fn validate_type_data<T: HasDiscriminator>(account: &AccountView, data: &[u8]) -> ProgramResult {
+	#[cfg(not(feature = "logs"))]
+	let _ = account;
 
-	if !T::matches_discriminator(&data) {
+	if !T::matches_discriminator(data) {
 		log!(
 			"address: {} has invalid discriminator",
 			account.address().as_ref()
@@ -342,6 +509,94 @@ fn validate_associated_token_address(
 	Err(ProgramError::InvalidSeeds)
 }
 
+/// The program ids that own SPL token accounts and mints.
+#[cfg(feature = "token")]
+pub(crate) const TOKEN_PROGRAM_IDS: [Address; 2] = [crate::token::ID, crate::token_2022::ID];
+
+/// Load `account` as a token account of either token program and check that
+/// the address `read` from it, if any, is `expected`. Both programs share the
+/// base token account layout.
+#[cfg(feature = "token")]
+#[track_caller]
+fn validate_token_account_field(
+	account: &AccountView,
+	field: &str,
+	expected: &Address,
+	read: impl FnOnce(&crate::token_2022::state::TokenAccount) -> Option<&Address>,
+) -> ProgramResult {
+	#[cfg(not(feature = "logs"))]
+	let _ = field;
+
+	let state = account.as_token_2022_account_checked_with_owners(&TOKEN_PROGRAM_IDS)?;
+
+	if read(&state) != Some(expected) {
+		log!(
+			"address: {} has an unexpected token {}",
+			account.address().as_ref(),
+			field
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(())
+}
+
+/// Load `account` as a mint of either token program and check that the
+/// optional authority `read` from it is set to `expected`.
+#[cfg(feature = "token")]
+#[track_caller]
+fn validate_mint_authority(
+	account: &AccountView,
+	kind: &str,
+	expected: &Address,
+	read: impl FnOnce(&crate::token_2022::state::Mint) -> Option<&Address>,
+) -> ProgramResult {
+	#[cfg(not(feature = "logs"))]
+	let _ = kind;
+
+	let mint = account.as_token_2022_mint_checked_with_owners(&TOKEN_PROGRAM_IDS)?;
+
+	let Some(authority) = read(&mint) else {
+		log!(
+			"address: {} has no {} authority",
+			account.address().as_ref(),
+			kind
+		);
+		log_caller();
+
+		return Err(PinaProgramError::AuthorityNotSet.into());
+	};
+
+	if authority != expected {
+		log!(
+			"address: {} has {} authority: {}, required: {}",
+			account.address().as_ref(),
+			kind,
+			authority.as_ref(),
+			expected.as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::IncorrectAuthority);
+	}
+
+	Ok(())
+}
+
+#[cfg(feature = "token")]
+#[track_caller]
+fn validate_ata_consistent(
+	account: &AccountView,
+	wallet: &Address,
+	mint: &Address,
+	token_program: &Address,
+) -> ProgramResult {
+	validate_associated_token_address(account, wallet, mint, token_program)?;
+	validate_owner(account, token_program)
+}
+
 macro_rules! impl_account_info_validation {
 	($type:ty) => {
 		impl<'a> AccountInfoValidation for $type {
@@ -366,6 +621,20 @@ macro_rules! impl_account_info_validation {
 				Ok(self)
 			}
 
+			#[track_caller]
+			fn assert_not_executable(self) -> Result<Self, ProgramError> {
+				validate_not_executable(self)?;
+
+				Ok(self)
+			}
+
+			#[track_caller]
+			fn assert_not_closed(self) -> Result<Self, ProgramError> {
+				validate_not_closed(self)?;
+
+				Ok(self)
+			}
+
 			#[track_caller]
 			fn assert_data_len(self, len: usize) -> Result<Self, ProgramError> {
 				validate_data_len(self, len)?;
@@ -373,6 +642,24 @@ macro_rules! impl_account_info_validation {
 				Ok(self)
 			}
 
+			#[track_caller]
+			fn assert_data_prefix(self, prefix: &[u8]) -> Result<Self, ProgramError> {
+				validate_data_prefix(self, prefix)?;
+
+				Ok(self)
+			}
+
+			#[track_caller]
+			fn assert_capacity_for<T>(
+				self,
+				header: usize,
+				current_len: usize,
+			) -> Result<Self, ProgramError> {
+				validate_capacity(self, header, current_len, size_of::<T>())?;
+
+				Ok(self)
+			}
+
 			#[track_caller]
 			fn assert_empty(self) -> Result<Self, ProgramError> {
 				validate_empty(self)?;
@@ -387,6 +674,80 @@ macro_rules! impl_account_info_validation {
 				Ok(self)
 			}
 
+			#[track_caller]
+			fn assert_strictly_uninitialized(self) -> Result<Self, ProgramError> {
+				validate_strictly_uninitialized(self)?;
+
+				Ok(self)
+			}
+
+			#[track_caller]
+			fn assert_lamports_above_rent_by(self, buffer: u64) -> Result<Self, ProgramError> {
+				validate_minimum_balance(
+					self,
+					&Rent::get()?,
+					self.data_len(),
+					buffer,
+					ProgramError::InsufficientFunds,
+				)?;
+
+				Ok(self)
+			}
+
+			#[track_caller]
+			fn assert_lamports_above_rent_with(
+				self,
+				rent: &Rent,
+				buffer: u64,
+			) -> Result<Self, ProgramError> {
+				validate_minimum_balance(
+					self,
+					rent,
+					self.data_len(),
+					buffer,
+					ProgramError::InsufficientFunds,
+				)?;
+
+				Ok(self)
+			}
+
+			#[track_caller]
+			fn assert_rent_exempt(self) -> Result<Self, ProgramError> {
+				validate_minimum_balance(
+					self,
+					&Rent::get()?,
+					self.data_len(),
+					0,
+					ProgramError::AccountNotRentExempt,
+				)?;
+
+				Ok(self)
+			}
+
+			#[track_caller]
+			fn assert_rent_exempt_for_len(self, len: usize) -> Result<Self, ProgramError> {
+				validate_minimum_balance(
+					self,
+					&Rent::get()?,
+					len,
+					0,
+					ProgramError::AccountNotRentExempt,
+				)?;
+
+				Ok(self)
+			}
+
+			#[track_caller]
+			fn assert_rent_exempt_with(
+				self,
+				rent: &Rent,
+				len: usize,
+			) -> Result<Self, ProgramError> {
+				validate_minimum_balance(self, rent, len, 0, ProgramError::AccountNotRentExempt)?;
+
+				Ok(self)
+			}
+
 			#[track_caller]
 			fn assert_type<T: HasDiscriminator>(
 				self,
@@ -482,6 +843,67 @@ macro_rules! impl_account_info_validation {
 
 				Ok(self)
 			}
+
+			#[cfg(feature = "token")]
+			#[track_caller]
+			fn assert_ata_consistent(
+				self,
+				wallet: &Address,
+				mint: &Address,
+				token_program: &Address,
+			) -> Result<Self, ProgramError> {
+				validate_ata_consistent(self, wallet, mint, token_program)?;
+
+				Ok(self)
+			}
+
+			#[cfg(feature = "token")]
+			#[track_caller]
+			fn assert_token_owned(self) -> Result<Self, ProgramError> {
+				validate_owners(self, &TOKEN_PROGRAM_IDS)?;
+
+				Ok(self)
+			}
+
+			#[cfg(feature = "token")]
+			#[track_caller]
+			fn assert_token_mint(self, mint: &Address) -> Result<Self, ProgramError> {
+				validate_token_account_field(self, "mint", mint, |state| Some(state.mint()))?;
+
+				Ok(self)
+			}
+
+			#[cfg(feature = "token")]
+			#[track_caller]
+			fn assert_token_owner(self, owner: &Address) -> Result<Self, ProgramError> {
+				validate_token_account_field(self, "owner", owner, |state| Some(state.owner()))?;
+
+				Ok(self)
+			}
+
+			#[cfg(feature = "token")]
+			#[track_caller]
+			fn assert_token_delegate(self, delegate: &Address) -> Result<Self, ProgramError> {
+				validate_token_account_field(self, "delegate", delegate, |state| state.delegate())?;
+
+				Ok(self)
+			}
+
+			#[cfg(feature = "token")]
+			#[track_caller]
+			fn assert_mint_authority(self, authority: &Address) -> Result<Self, ProgramError> {
+				validate_mint_authority(self, "mint", authority, |mint| mint.mint_authority())?;
+
+				Ok(self)
+			}
+
+			#[cfg(feature = "token")]
+			#[track_caller]
+			fn assert_freeze_authority(self, authority: &Address) -> Result<Self, ProgramError> {
+				validate_mint_authority(self, "freeze", authority, |mint| mint.freeze_authority())?;
+
+				Ok(self)
+			}
 		}
 	};
 }
@@ -513,6 +935,214 @@ impl AsAccount for AccountView {
 		RefMut::try_map(self.try_borrow_mut()?, |data| T::try_from_bytes_mut(data))
 			.map_err(|(_guard, error)| error)
 	}
+
+	#[track_caller]
+	fn load_account<T>(&self, program_id: &Address) -> Result<Ref<'_, T>, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod,
+	{
+		validate_owner(self, program_id)?;
+
+		let data = self.try_borrow()?;
+		validate_type_data::<T>(self, &data)?;
+
+		Ref::try_map(data, |data| {
+			bytemuck::try_from_bytes(data).or(Err(ProgramError::InvalidAccountData))
+		})
+		.map_err(|(_guard, error)| error)
+	}
+
+	#[track_caller]
+	fn try_as_account<T>(&self, program_id: &Address) -> Result<Option<Ref<'_, T>>, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod,
+	{
+		validate_owner(self, program_id)?;
+
+		let data = self.try_borrow()?;
+
+		if !T::matches_discriminator(&data) {
+			return Ok(None);
+		}
+
+		validate_type_data::<T>(self, &data)?;
+
+		Ref::try_map(data, |data| {
+			bytemuck::try_from_bytes(data).or(Err(ProgramError::InvalidAccountData))
+		})
+		.map(Some)
+		.map_err(|(_guard, error)| error)
+	}
+
+	#[track_caller]
+	fn load_account_mut<T>(&mut self, program_id: &Address) -> Result<RefMut<'_, T>, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod,
+	{
+		let account = *self;
+		validate_owner(&account, program_id)?;
+
+		let data = self.try_borrow_mut()?;
+		validate_type_data::<T>(&account, &data)?;
+
+		RefMut::try_map(data, |data| {
+			bytemuck::try_from_bytes_mut(data).or(Err(ProgramError::InvalidAccountData))
+		})
+		.map_err(|(_guard, error)| error)
+	}
+
+	#[track_caller]
+	fn as_external_account<T>(&self) -> Result<Ref<'_, T>, ProgramError>
+	where
+		T: ExternalAccount,
+	{
+		self.as_external_account_with_owner::<T>(&T::OWNER)
+	}
+
+	#[track_caller]
+	fn as_external_account_with_owner<T>(&self, owner: &Address) -> Result<Ref<'_, T>, ProgramError>
+	where
+		T: ExternalAccount,
+	{
+		self.assert_owner(owner)?;
+
+		Ref::try_map(self.try_borrow()?, |data| {
+			data.get(..size_of::<T>())
+				.and_then(|data| bytemuck::try_from_bytes(data).ok())
+				.ok_or(ProgramError::InvalidAccountData)
+		})
+		.map_err(|(_guard, error)| error)
+	}
+
+	fn with_account_mut<T, R>(
+		&mut self,
+		program_id: &Address,
+		update: impl FnOnce(&mut T) -> R,
+	) -> Result<R, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod,
+	{
+		let mut state = self.as_account_mut::<T>(program_id)?;
+
+		Ok(update(&mut state))
+	}
+
+	#[track_caller]
+	fn invalidate_discriminator<T>(&mut self, program_id: &Address) -> ProgramResult
+	where
+		T: HasDiscriminator,
+	{
+		self.assert_type::<T>(program_id)?;
+
+		if T::VALUE.matches_discriminator(&[0; MAX_DISCRIMINATOR_SPACE]) {
+			log!(
+				"address: {} cannot be invalidated because its discriminator is zero",
+				self.address().as_ref()
+			);
+			log_caller();
+
+			return Err(ProgramError::InvalidArgument);
+		}
+
+		self.try_borrow_mut()?[..<T::Type as IntoDiscriminator>::BYTES].fill(0);
+
+		Ok(())
+	}
+
+	#[track_caller]
+	fn reinterpret_as<Old, New>(
+		&mut self,
+		program_id: &Address,
+	) -> Result<RefMut<'_, New>, ProgramError>
+	where
+		Old: AccountDeserialize + HasDiscriminator + Pod,
+		New: AccountDeserialize + HasDiscriminator + Pod,
+	{
+		const {
+			assert!(
+				size_of::<Old>() == size_of::<New>(),
+				"`reinterpret_as` requires both account types to have the same size"
+			);
+			assert!(
+				<Old::Type as IntoDiscriminator>::BYTES == <New::Type as IntoDiscriminator>::BYTES,
+				"`reinterpret_as` requires both discriminators to have the same width"
+			);
+		}
+
+		drop(self.as_account::<Old>(program_id)?);
+
+		let mut data = self.try_borrow_mut()?;
+		New::VALUE.write_discriminator(&mut data);
+
+		RefMut::try_map(data, |data| New::try_from_bytes_mut(data)).map_err(|(_guard, error)| error)
+	}
+
+	#[track_caller]
+	fn assert_type_or_empty<T>(&self, program_id: &Address) -> Result<bool, ProgramError>
+	where
+		T: HasDiscriminator,
+	{
+		if self.is_data_empty() {
+			return Ok(true);
+		}
+
+		validate_type::<T>(self, program_id)?;
+
+		Ok(false)
+	}
+
+	#[track_caller]
+	fn as_array<D, T, const N: usize>(
+		&self,
+		program_id: &Address,
+	) -> Result<Ref<'_, [T; N]>, ProgramError>
+	where
+		D: HasDiscriminator,
+		T: Pod,
+	{
+		validate_array::<D, T, N>(self, program_id)?;
+
+		Ref::try_map(self.try_borrow()?, |data| {
+			bytemuck::try_cast_slice::<u8, T>(&data[<D::Type as IntoDiscriminator>::BYTES..])
+				.ok()
+				.and_then(|elements| <&[T; N]>::try_from(elements).ok())
+				.ok_or(ProgramError::InvalidAccountData)
+		})
+		.map_err(|(_guard, error)| error)
+	}
+
+	#[track_caller]
+	fn as_array_mut<D, T, const N: usize>(
+		&mut self,
+		program_id: &Address,
+	) -> Result<RefMut<'_, [T; N]>, ProgramError>
+	where
+		D: HasDiscriminator,
+		T: Pod,
+	{
+		validate_array::<D, T, N>(self, program_id)?;
+
+		RefMut::try_map(self.try_borrow_mut()?, |data| {
+			bytemuck::try_cast_slice_mut::<u8, T>(
+				&mut data[<D::Type as IntoDiscriminator>::BYTES..],
+			)
+			.ok()
+			.and_then(|elements| <&mut [T; N]>::try_from(elements).ok())
+			.ok_or(ProgramError::InvalidAccountData)
+		})
+		.map_err(|(_guard, error)| error)
+	}
+
+	fn space_utilization_bps(&self, logical_len: usize) -> u16 {
+		let allocated = self.data_len();
+
+		if allocated == 0 || logical_len >= allocated {
+			return crate::PodBps::MAX;
+		}
+
+		// `logical_len < allocated`, so the ratio is below `PodBps::MAX`.
+		(logical_len as u128 * u128::from(crate::PodBps::MAX) / allocated as u128) as u16
+	}
 }
 
 /// Implements `AccountValidation` for a token-related type. All four assertion
@@ -634,6 +1264,10 @@ fn load_token_state<'a, T: ?Sized>(
 
 #[cfg(feature = "token")]
 impl AsTokenAccount for AccountView {
+	fn is_token_owned(&self) -> bool {
+		TOKEN_PROGRAM_IDS.contains(self.owner())
+	}
+
 	#[track_caller]
 	fn as_token_mint(&self) -> Result<Ref<'_, crate::token::state::Mint>, ProgramError> {
 		crate::token::state::Mint::from_account_view(self)
@@ -881,6 +1515,24 @@ impl CloseAccountWithRecipient for AccountView {
 		self.set_lamports(0);
 		self.close()
 	}
+
+	#[track_caller]
+	fn close_account_secure(&mut self, recipient: &mut AccountView) -> ProgramResult {
+		let new_balance = checked_close_recipient_balance(self, recipient)?;
+
+		{
+			let mut data = self.try_borrow_mut()?;
+			data.fill(0);
+
+			let len = data.len().min(MAX_DISCRIMINATOR_SPACE);
+			data[..len].copy_from_slice(&CLOSED_ACCOUNT_DISCRIMINATOR[..len]);
+		}
+
+		recipient.set_lamports(new_balance);
+		self.set_lamports(0);
+
+		Ok(())
+	}
 }
 
 #[cfg(test)]
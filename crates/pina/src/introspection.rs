@@ -6,8 +6,12 @@
 //! - **Flash loan guards** — verify the current instruction is not being invoked
 //!   via CPI so that atomic flash-loan exploits are prevented.
 //! - **CPI depth checks** — ensure instructions are top-level calls.
+//! - **Trusted callers** — require that a trusted parent program issued the
+//!   instruction currently invoking this program.
 //! - **Sandwich detection** — check whether a specific program appears before or
 //!   after the current instruction in the transaction.
+//! - **Signed authorizations** — confirm the ed25519 program verified a
+//!   signature over an expected message, as used by meta-transactions.
 //!
 //! All functions accept a reference to the Instructions sysvar account
 //! (`&AccountView`) and validate its address before reading data.
@@ -68,6 +72,65 @@ pub fn assert_no_cpi(instructions_account: &AccountView, program_id: &Address) -
 	Ok(())
 }
 
+/// Verifies the top-level instruction being executed belongs to
+/// `expected_caller`.
+///
+/// Programs that must only run through a CPI from a trusted parent compare
+/// the program ID of the top-level instruction being executed. When the
+/// parent invokes this program directly, that instruction belongs to the
+/// parent.
+///
+/// The Instructions sysvar only records top-level instructions, so this does
+/// not identify the immediate caller. In a deeper CPI chain it passes for any
+/// program the trusted parent invokes, including one that then calls this
+/// program, so only rely on it when every program the parent calls is trusted
+/// as well.
+///
+/// # Arguments
+///
+/// * `instructions_account` - The Instructions sysvar account.
+/// * `expected_caller` - The program that the current top-level instruction
+///   must belong to.
+///
+/// # Errors
+///
+/// Returns `ProgramError::UnsupportedSysvar` if the account address does not
+/// match the Instructions sysvar ID.
+///
+/// Returns `ProgramError::InvalidInstructionData` if the current instruction
+/// index is out of bounds.
+///
+/// Returns `ProgramError::IncorrectProgramId` if the current top-level
+/// instruction belongs to any other program, including a direct call to this
+/// program.
+///
+/// # Example
+///
+/// ```ignore
+/// use pina::introspection::assert_top_level_caller;
+///
+/// fn process(accounts: &mut [AccountView]) -> ProgramResult {
+///     let instructions_account = &accounts[0];
+///     // Only the vault program may settle through this instruction
+///     assert_top_level_caller(instructions_account, &VAULT_PROGRAM_ID)?;
+///     Ok(())
+/// }
+/// ```
+pub fn assert_top_level_caller(
+	instructions_account: &AccountView,
+	expected_caller: &Address,
+) -> ProgramResult {
+	let instructions = Instructions::try_from(instructions_account)?;
+	let current_index = instructions.load_current_index();
+	let current_ix = instructions.load_instruction_at(current_index as usize)?;
+
+	if current_ix.get_program_id() != expected_caller {
+		return Err(ProgramError::IncorrectProgramId);
+	}
+
+	Ok(())
+}
+
 /// Returns the total number of instructions in the transaction.
 ///
 /// # Arguments
@@ -240,3 +303,114 @@ pub fn has_instruction_after(
 
 	Ok(false)
 }
+
+/// Size of the ed25519 instruction header: a `u8` signature count followed by
+/// a padding byte.
+const ED25519_HEADER_LEN: usize = 2;
+/// Size of one serialized `Ed25519SignatureOffsets` entry (seven `u16`s).
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_SIGNATURE_LEN: usize = 64;
+const ED25519_PUBKEY_LEN: usize = 32;
+/// Instruction index the ed25519 program uses to refer to its own data.
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Verifies that the ed25519 program checked a signature by `pubkey` over
+/// `message` in this transaction.
+///
+/// The ed25519 program is a precompile: the runtime rejects the whole
+/// transaction when any signature it lists is invalid. A program accepting an
+/// off-chain signed authorization therefore only needs to confirm that an
+/// ed25519 instruction lists the expected public key and message. This
+/// function scans every instruction in the transaction for such an entry.
+///
+/// Only signature entries whose signature, public key and message all live in
+/// the ed25519 instruction's own data are considered. Entries referencing
+/// data in other instructions are ignored.
+///
+/// # Arguments
+///
+/// * `instructions_account` - The Instructions sysvar account.
+/// * `pubkey` - The public key that must have signed `message`.
+/// * `message` - The exact message bytes that must have been signed.
+///
+/// # Errors
+///
+/// Returns `ProgramError::UnsupportedSysvar` if the account address does not
+/// match the Instructions sysvar ID.
+///
+/// Returns `ProgramError::MissingRequiredSignature` if no ed25519 instruction
+/// in the transaction verifies `message` for `pubkey`.
+///
+/// # Example
+///
+/// ```ignore
+/// use pina::introspection::assert_ed25519_verified;
+///
+/// fn process(accounts: &mut [AccountView], authority: &Address) -> ProgramResult {
+///     let instructions_account = &accounts[0];
+///     // The client placed an ed25519 verify instruction for this message.
+///     assert_ed25519_verified(instructions_account, authority, b"withdraw:100")?;
+///     Ok(())
+/// }
+/// ```
+pub fn assert_ed25519_verified(
+	instructions_account: &AccountView,
+	pubkey: &Address,
+	message: &[u8],
+) -> ProgramResult {
+	let instructions = Instructions::try_from(instructions_account)?;
+
+	for i in 0..instructions.num_instructions() {
+		let ix = instructions.load_instruction_at(i)?;
+
+		if ix.get_program_id() == &pina_sdk_ids::ed25519_program::ID
+			&& ed25519_data_verifies(ix.get_instruction_data(), i, pubkey, message)
+		{
+			return Ok(());
+		}
+	}
+
+	Err(ProgramError::MissingRequiredSignature)
+}
+
+/// Returns `true` when the ed25519 instruction `data` at `index` lists a
+/// signature by `pubkey` over `message`.
+fn ed25519_data_verifies(data: &[u8], index: usize, pubkey: &Address, message: &[u8]) -> bool {
+	let Some(&count) = data.first() else {
+		return false;
+	};
+	let is_local = |ix: u16| ix == ED25519_CURRENT_INSTRUCTION || usize::from(ix) == index;
+	let slice = |offset: u16, len: usize| {
+		let start = usize::from(offset);
+		data.get(start..start.checked_add(len)?)
+	};
+
+	for n in 0..usize::from(count) {
+		let start = ED25519_HEADER_LEN + n * ED25519_OFFSETS_LEN;
+		let Some(offsets) = data.get(start..start + ED25519_OFFSETS_LEN) else {
+			return false;
+		};
+		let [
+			signature_offset,
+			signature_ix,
+			pubkey_offset,
+			pubkey_ix,
+			message_offset,
+			message_size,
+			message_ix,
+		]: [u16; 7] = core::array::from_fn(|j| u16::from_le_bytes([offsets[j * 2], offsets[j * 2 + 1]]));
+
+		if !(is_local(signature_ix) && is_local(pubkey_ix) && is_local(message_ix)) {
+			continue;
+		}
+
+		if slice(signature_offset, ED25519_SIGNATURE_LEN).is_some()
+			&& slice(pubkey_offset, ED25519_PUBKEY_LEN) == Some(pubkey.as_ref())
+			&& slice(message_offset, usize::from(message_size)) == Some(message)
+		{
+			return true;
+		}
+	}
+
+	false
+}
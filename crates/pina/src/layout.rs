@@ -0,0 +1,117 @@
+//! Golden snapshots of account layouts for regression checks in tests.
+//!
+//! Deployed accounts keep their bytes forever, so reordering, resizing or
+//! inserting a field in an `#[account]` struct breaks every client decoding
+//! existing accounts. The alignment and padding assertions generated by
+//! `#[account]` cannot notice that kind of change, because the new layout is
+//! still valid on its own.
+//!
+//! A [`LayoutSnapshot`] records the size and [`HasLayout`] field offsets of
+//! each account type. Tests render it with [`LayoutSnapshot::to_text`], commit
+//! the output as a golden file and call [`LayoutSnapshot::compare`] to fail on
+//! any drift.
+//!
+//! This module requires the `test-utils` feature because it allocates.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::HasLayout;
+
+/// `(field name, byte offset)` pairs as exposed by [`HasLayout`].
+type FieldOffsets = &'static [(&'static str, usize)];
+
+/// The size and field offsets of a set of account types, keyed by type name.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut snapshot = LayoutSnapshot::new();
+/// snapshot.record::<CounterState>();
+///
+/// if let Err(diff) = snapshot.compare(include_str!("layouts.golden")) {
+/// 	panic!("{diff}");
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LayoutSnapshot {
+	layouts: BTreeMap<String, (usize, FieldOffsets)>,
+}
+
+impl LayoutSnapshot {
+	/// Create an empty snapshot.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Record the layout of `T` under its type name without the module path.
+	pub fn record<T: HasLayout>(&mut self) {
+		let type_name = core::any::type_name::<T>();
+		let name = type_name.rsplit("::").next().unwrap_or(type_name);
+
+		self.layouts
+			.insert(String::from(name), (size_of::<T>(), T::FIELD_OFFSETS));
+	}
+
+	/// Render the snapshot in the golden file format.
+	///
+	/// Each type contributes a `<Type> size <bytes>` line followed by one
+	/// `<Type>.<field> <offset>` line per field, so every line is unique and a
+	/// diff points straight at the field that moved.
+	pub fn to_text(&self) -> String {
+		let mut text = String::new();
+
+		for (name, (size, fields)) in &self.layouts {
+			let _ = writeln!(text, "{name} size {size}");
+
+			for (field, offset) in *fields {
+				let _ = writeln!(text, "{name}.{field} {offset}");
+			}
+		}
+
+		text
+	}
+
+	/// Compare the snapshot against the contents of a golden file.
+	///
+	/// Line endings and surrounding whitespace are ignored.
+	///
+	/// # Errors
+	///
+	/// Returns a message listing the golden lines that are missing (`-`) and
+	/// the recorded lines that are new (`+`) when the layouts differ.
+	pub fn compare(&self, golden: &str) -> Result<(), String> {
+		let actual = self.to_text();
+		let expected_lines = golden
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty());
+		let actual_lines = actual.lines();
+
+		if expected_lines.clone().eq(actual_lines.clone()) {
+			return Ok(());
+		}
+
+		let mut message = String::from("account layouts changed:\n");
+
+		for line in expected_lines.clone() {
+			if !actual_lines.clone().any(|actual_line| actual_line == line) {
+				let _ = writeln!(message, "- {line}");
+			}
+		}
+
+		for line in actual_lines {
+			if !expected_lines
+				.clone()
+				.any(|expected_line| expected_line == line)
+			{
+				let _ = writeln!(message, "+ {line}");
+			}
+		}
+
+		message.push_str("re-record the golden file if the change is intentional");
+
+		Err(message)
+	}
+}
@@ -28,16 +28,35 @@
 //! - `memo` — enables memo program helpers.
 //! - `account-resize` — enables account realloc helpers on top of Pinocchio's
 //!   safe account resize support.
+//! - `client` — enables off-chain helpers in `pina::events` for decoding emitted
+//!   events from transaction logs, and the `AccountMeta` lists generated by
+//!   `#[derive(Accounts)]`. Requires an allocator.
+//! - `test-utils` — enables `pina::compute_units` for recording per-instruction
+//!   compute unit reports and `pina::layout` for golden account layout
+//!   snapshots in test harnesses. Implies `client` and `logs`.
 
 #![no_std]
 #![allow(clippy::inline_always)]
 
+#[cfg(feature = "client")]
+#[doc(hidden)]
+pub extern crate alloc;
+
+pub mod accounts;
+#[cfg(feature = "test-utils")]
+pub mod compute_units;
 mod cpi;
 mod error;
+#[cfg(feature = "client")]
+pub mod events;
 mod impls;
 pub mod introspection;
+#[cfg(feature = "test-utils")]
+pub mod layout;
+mod loader;
 mod pda;
 mod pod;
+pub mod sysvars;
 #[cfg(feature = "token")]
 pub mod token;
 #[cfg(feature = "token")]
@@ -93,8 +112,6 @@ pub use pinocchio::instruction::InstructionAccount;
 pub use pinocchio::instruction::InstructionView;
 /// Macro for declaring a Solana program entrypoint.
 pub use pinocchio::program_entrypoint;
-/// Solana sysvar access utilities.
-pub use pinocchio::sysvars;
 /// Re-export of `pinocchio_associated_token_account` for ATA operations.
 #[cfg(feature = "token")]
 pub use pinocchio_associated_token_account as associated_token_account;
@@ -138,6 +155,8 @@ pub use typed_builder::TypedBuilder;
 pub use crate::cpi::*;
 /// Built-in framework error types.
 pub use crate::error::*;
+/// Zero-copy loader for accounts too large for the stack.
+pub use crate::loader::AccountLoader;
 /// PDA (Program Derived Address) derivation and verification functions.
 pub use crate::pda::*;
 /// Core traits for account validation, deserialization, and instruction
@@ -208,6 +227,196 @@ macro_rules! log {
 	($($arg:tt)*) => {};
 }
 
+/// Emits an `#[event]` into the transaction logs.
+///
+/// Expands to [`emit_event`], which logs the event's bytes with
+/// `sol_log_data`. Unlike [`log!`] it is not affected by the `logs` feature:
+/// events are part of a program's interface, not diagnostics. Off-chain it is
+/// a no-op.
+///
+/// ```ignore
+/// emit!(DepositEvent::builder().amount(amount.into()).build());
+/// ```
+#[macro_export]
+macro_rules! emit {
+	($event:expr) => {
+		$crate::emit_event(&$event)
+	};
+}
+
+/// Emits an `#[event]` through a self-invocation signed by the event
+/// authority PDA.
+///
+/// Expands to [`emit_event_cpi`] with the invoking crate's `ID`, like
+/// Anchor's `emit_cpi!`. Pass the program's own account, the event authority
+/// account and its bump, and route [`is_emit_event_instruction`] data to
+/// [`process_emit_event`] with the same bump in the entrypoint. Use it over
+/// [`emit!`] when events must survive log truncation.
+///
+/// ```ignore
+/// emit_cpi!(event, self.program, self.event_authority, EVENT_AUTHORITY_BUMP)?;
+/// ```
+// `crate::ID` deliberately names the invoking program's ID, not pina's.
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! emit_cpi {
+	($event:expr, $program:expr, $event_authority:expr, $bump:expr $(,)?) => {
+		$crate::emit_event_cpi(&$event, &crate::ID, $program, $event_authority, $bump)
+	};
+}
+
+/// Expands to its items only when pina's `client` feature is enabled.
+///
+/// Macro-generated off-chain code goes through this so that it follows pina's
+/// features rather than the features of the crate it expands in.
+#[cfg(feature = "client")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __client {
+	($($item:item)*) => {
+		$($item)*
+	};
+}
+
+#[cfg(not(feature = "client"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __client {
+	($($item:item)*) => {};
+}
+
+/// Loads an account as whichever of several account types its discriminator
+/// matches.
+///
+/// Instructions that accept more than one kind of account in the same slot
+/// list the candidate types, two to four of them. Each type is tried in order
+/// with [`AsAccount::try_as_account`], so owner and size checks still apply,
+/// and the account is returned as the [`OneOf`] variant of the first match.
+/// `#[derive(AccountUnion)]` does the same for a named enum, and both fail the
+/// same way when nothing matches.
+///
+/// Evaluates to `Result<OneOf<Ref<'_, A>, Ref<'_, B>, ..>, ProgramError>`. The
+/// error is `InvalidAccountData` when no listed discriminator matches, or the
+/// error from loading the matched type.
+///
+/// ```ignore
+/// match load_one_of!(self.target, &ID, [UserConfig, UserVault])? {
+/// 	OneOf::First(config) => apply_config(&config),
+/// 	OneOf::Second(vault) => apply_vault(&vault),
+/// }
+/// ```
+#[macro_export]
+macro_rules! load_one_of {
+	(@load $account:expr, $program_id:expr, $loaded:ty, [$($variant:ident $type:ty),+]) => {{
+		let account: &$crate::AccountView = $account;
+		let program_id: &$crate::Address = $program_id;
+		let loaded: ::core::result::Result<$loaded, $crate::ProgramError> = 'load: {
+			$(
+				match $crate::AsAccount::try_as_account::<$type>(account, program_id) {
+					::core::result::Result::Ok(::core::option::Option::Some(state)) => {
+						break 'load ::core::result::Result::Ok($crate::OneOf::$variant(state));
+					}
+					::core::result::Result::Ok(::core::option::Option::None) => {}
+					::core::result::Result::Err(error) => break 'load ::core::result::Result::Err(error),
+				}
+			)+
+
+			::core::result::Result::Err($crate::account_type_mismatch(account))
+		};
+
+		loaded
+	}};
+	($account:expr, $program_id:expr, [$a:ty, $b:ty $(,)?]) => {
+		$crate::load_one_of!(
+			@load $account,
+			$program_id,
+			$crate::OneOf<$crate::Ref<'_, $a>, $crate::Ref<'_, $b>>,
+			[First $a, Second $b]
+		)
+	};
+	($account:expr, $program_id:expr, [$a:ty, $b:ty, $c:ty $(,)?]) => {
+		$crate::load_one_of!(
+			@load $account,
+			$program_id,
+			$crate::OneOf<$crate::Ref<'_, $a>, $crate::Ref<'_, $b>, $crate::Ref<'_, $c>>,
+			[First $a, Second $b, Third $c]
+		)
+	};
+	($account:expr, $program_id:expr, [$a:ty, $b:ty, $c:ty, $d:ty $(,)?]) => {
+		$crate::load_one_of!(
+			@load $account,
+			$program_id,
+			$crate::OneOf<
+				$crate::Ref<'_, $a>,
+				$crate::Ref<'_, $b>,
+				$crate::Ref<'_, $c>,
+				$crate::Ref<'_, $d>,
+			>,
+			[First $a, Second $b, Third $c, Fourth $d]
+		)
+	};
+}
+
+/// Implements [`ProcessAccountInfos`] for an accounts struct whose
+/// instruction only validates its accounts.
+///
+/// Each entry names an account view field and the chainable assertions to run
+/// on it, in order. An optional instruction type between the struct and the
+/// assertions is parsed from the instruction data first. The generated
+/// `process` returns the first failing assertion's error, or `Ok(())`.
+///
+/// `pina idl` infers signer and writable flags from hand-written `process`
+/// bodies only, so declare those flags another way for accounts validated
+/// here.
+///
+/// ```ignore
+/// validation_processor!(InitializeAccounts, InitializeInstruction, {
+/// 	authority: [assert_signer()],
+/// 	wallet: [assert_owner(&system::ID), assert_writable()],
+/// });
+/// ```
+#[macro_export]
+macro_rules! validation_processor {
+	($accounts:ident, {
+		$($field:ident: [
+			$($method:ident $(::<$($generic:ty),+ $(,)?>)? ($($arg:expr),* $(,)?)),* $(,)?
+		]),* $(,)?
+	} $(,)?) => {
+		impl<'a> $crate::ProcessAccountInfos<'a> for $accounts<'a> {
+			fn process(self, data: &[u8]) -> $crate::ProgramResult {
+				#[allow(unused_imports)]
+				use $crate::prelude::*;
+
+				let _ = data;
+				$($(
+					(&*self.$field).$method $(::<$($generic),+>)? ($($arg),*)?;
+				)*)*
+
+				Ok(())
+			}
+		}
+	};
+	($accounts:ident, $instruction:ty, {
+		$($field:ident: [
+			$($method:ident $(::<$($generic:ty),+ $(,)?>)? ($($arg:expr),* $(,)?)),* $(,)?
+		]),* $(,)?
+	} $(,)?) => {
+		impl<'a> $crate::ProcessAccountInfos<'a> for $accounts<'a> {
+			fn process(self, data: &[u8]) -> $crate::ProgramResult {
+				#[allow(unused_imports)]
+				use $crate::prelude::*;
+
+				let _ = <$instruction as $crate::AccountDeserialize>::try_from_bytes(data)?;
+				$($(
+					(&*self.$field).$method $(::<$($generic),+>)? ($($arg),*)?;
+				)*)*
+
+				Ok(())
+			}
+		}
+	};
+}
+
 /// Re-exports commonly used traits and helpers for instruction modules.
 ///
 /// `use pina::prelude::*;` is the recommended import style inside on-chain
@@ -0,0 +1,146 @@
+//! Zero-copy access to accounts too large to build on the stack.
+//!
+//! SBF programs get 4 KiB stack frames, so an order book or game board of
+//! tens of kilobytes can never exist as a value. Its bytes can still be read
+//! and written in place: [`AccountLoader`] only ever hands out `Ref` and
+//! `RefMut` guards over the account data, and
+//! [`load_init`](AccountLoader::load_init) initializes an account by writing
+//! its discriminator into the zeroed data rather than copying in a built
+//! struct. Pair it with `#[account(zero_copy)]`, which leaves out the
+//! by-value builder.
+
+use core::marker::PhantomData;
+
+use pinocchio::AccountView;
+use pinocchio::Address;
+use pinocchio::account::Ref;
+use pinocchio::account::RefMut;
+use pinocchio::error::ProgramError;
+
+use crate::AccountDeserialize;
+use crate::AccountInfoValidation;
+use crate::HasDiscriminator;
+use crate::IntoDiscriminator;
+use crate::Pod;
+use crate::log;
+use crate::log_caller;
+
+/// An account validated as holding a `T`, loaded on demand without copying.
+///
+/// Construction checks the owner and that the data is exactly
+/// `size_of::<T>()` bytes. [`load`](Self::load) and
+/// [`load_mut`](Self::load_mut) then check the discriminator on every access,
+/// while [`load_init`](Self::load_init) writes it into an account that has
+/// none yet.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[account(discriminator = GameAccount, zero_copy)]
+/// pub struct Board {
+/// 	pub cells: [u8; 16_384],
+/// }
+///
+/// let mut board = AccountLoader::<Board>::try_new(self.board, &ID)?;
+/// let mut cells = board.load_init()?;
+/// cells.cells[0] = 1;
+/// ```
+pub struct AccountLoader<'a, T> {
+	account: &'a mut AccountView,
+	state: PhantomData<T>,
+}
+
+impl<'a, T> AccountLoader<'a, T>
+where
+	T: AccountDeserialize + HasDiscriminator + Pod,
+{
+	/// Wrap `account` after checking that `program_id` owns it and that its
+	/// data is exactly the size of `T`.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidAccountOwner` for a foreign account and
+	/// `InvalidAccountData` for data of the wrong length.
+	#[track_caller]
+	pub fn try_new(
+		account: &'a mut AccountView,
+		program_id: &Address,
+	) -> Result<Self, ProgramError> {
+		let account = account
+			.assert_owner(program_id)?
+			.assert_data_len(size_of::<T>())?;
+
+		Ok(Self {
+			account,
+			state: PhantomData,
+		})
+	}
+
+	/// The address of the loaded account.
+	pub fn address(&self) -> &Address {
+		self.account.address()
+	}
+
+	/// Borrow the account data as an initialized `T`.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidAccountData` when the discriminator is not the one of
+	/// `T`, or a borrow error while a mutable guard is alive.
+	#[track_caller]
+	pub fn load(&self) -> Result<Ref<'_, T>, ProgramError> {
+		Ref::try_map(self.account.try_borrow()?, |data| T::try_from_bytes(data))
+			.map_err(|(_guard, error)| error)
+	}
+
+	/// Mutably borrow the account data as an initialized `T`.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidAccountData` when the account is not writable or the
+	/// discriminator is not the one of `T`, or a borrow error while another
+	/// guard is alive.
+	#[track_caller]
+	pub fn load_mut(&mut self) -> Result<RefMut<'_, T>, ProgramError> {
+		let account = (&mut *self.account).assert_writable()?;
+
+		RefMut::try_map(account.try_borrow_mut()?, |data| {
+			T::try_from_bytes_mut(data)
+		})
+		.map_err(|(_guard, error)| error)
+	}
+
+	/// Initialize the account as a `T` and mutably borrow it.
+	///
+	/// The account must not carry a discriminator yet, which holds for the
+	/// zeroed data of a freshly created account. The discriminator of `T` is
+	/// written in place and every other byte is left as it is.
+	///
+	/// # Errors
+	///
+	/// Returns `AccountAlreadyInitialized` when the discriminator bytes are
+	/// not all zero, `InvalidAccountData` when the account is not writable,
+	/// or a borrow error while another guard is alive.
+	#[track_caller]
+	pub fn load_init(&mut self) -> Result<RefMut<'_, T>, ProgramError> {
+		let account = (&mut *self.account).assert_writable()?;
+		let initialized = account.try_borrow()?[..<T::Type as IntoDiscriminator>::BYTES]
+			.iter()
+			.any(|byte| *byte != 0);
+
+		if initialized {
+			log!(
+				"address: {} is already initialized",
+				account.address().as_ref()
+			);
+			log_caller();
+
+			return Err(ProgramError::AccountAlreadyInitialized);
+		}
+
+		let mut data = account.try_borrow_mut()?;
+		T::write_discriminator(&mut data);
+
+		RefMut::try_map(data, |data| T::try_from_bytes_mut(data)).map_err(|(_guard, error)| error)
+	}
+}
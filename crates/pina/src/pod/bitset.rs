@@ -0,0 +1,155 @@
+use bytemuck::Pod;
+use bytemuck::Zeroable;
+use pinocchio::error::ProgramError;
+
+/// A fixed-width set of `BYTES * 8` bit flags stored as a plain byte array.
+///
+/// Bit `index` lives in byte `index / 8` at position `index % 8`, counting
+/// from the least significant bit, so the layout matches a little-endian
+/// integer of the same width. Every accessor is bounds-checked against
+/// [`PodBitset::BITS`].
+///
+/// # Examples
+///
+/// ```
+/// use pina::PodBitset;
+///
+/// let mut flags = PodBitset::<2>::default();
+/// flags
+/// 	.set(9, true)
+/// 	.unwrap_or_else(|e| panic!("bit 9 fits: {e:?}"));
+///
+/// assert_eq!(flags.get(9), Some(true));
+/// assert_eq!(flags.count_ones(), 1);
+/// assert!(flags.set(16, true).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodBitset<const BYTES: usize>(pub [u8; BYTES]);
+
+impl<const BYTES: usize> Default for PodBitset<BYTES> {
+	fn default() -> Self {
+		Self([0; BYTES])
+	}
+}
+
+impl<const BYTES: usize> PodBitset<BYTES> {
+	/// The number of bits in the set.
+	pub const BITS: usize = BYTES * 8;
+
+	/// The value of bit `index`, or `None` when it is out of range.
+	pub const fn get(&self, index: usize) -> Option<bool> {
+		if index >= Self::BITS {
+			return None;
+		}
+
+		Some(self.0[index / 8] & (1 << (index % 8)) != 0)
+	}
+
+	/// Set bit `index` to `value`.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidArgument` when `index` is not below
+	/// [`PodBitset::BITS`].
+	pub const fn set(&mut self, index: usize, value: bool) -> Result<(), ProgramError> {
+		if index >= Self::BITS {
+			return Err(ProgramError::InvalidArgument);
+		}
+
+		let mask = 1 << (index % 8);
+
+		if value {
+			self.0[index / 8] |= mask;
+		} else {
+			self.0[index / 8] &= !mask;
+		}
+
+		Ok(())
+	}
+
+	/// Flip bit `index` and return its new value.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidArgument` when `index` is not below
+	/// [`PodBitset::BITS`].
+	pub const fn toggle(&mut self, index: usize) -> Result<bool, ProgramError> {
+		if index >= Self::BITS {
+			return Err(ProgramError::InvalidArgument);
+		}
+
+		let mask = 1 << (index % 8);
+		self.0[index / 8] ^= mask;
+
+		Ok(self.0[index / 8] & mask != 0)
+	}
+
+	/// The number of bits that are set.
+	pub const fn count_ones(&self) -> u32 {
+		let mut count = 0;
+		let mut byte = 0;
+
+		while byte < BYTES {
+			count += self.0[byte].count_ones();
+			byte += 1;
+		}
+
+		count
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bits_across_byte_boundaries() {
+		let mut flags = PodBitset::<3>::default();
+
+		for index in [0, 7, 8, 15, 16, 23] {
+			assert_eq!(flags.set(index, true), Ok(()));
+		}
+
+		assert_eq!(flags.0, [0b1000_0001; 3]);
+		assert_eq!(flags.get(7), Some(true));
+		assert_eq!(flags.get(8), Some(true));
+		assert_eq!(flags.get(9), Some(false));
+
+		assert_eq!(flags.set(8, false), Ok(()));
+		assert_eq!(flags.0, [0b1000_0001, 0b1000_0000, 0b1000_0001]);
+	}
+
+	#[test]
+	fn toggle_flips_and_reports_new_value() {
+		let mut flags = PodBitset::<2>::default();
+
+		assert_eq!(flags.toggle(7), Ok(true));
+		assert_eq!(flags.toggle(8), Ok(true));
+		assert_eq!(flags.0, [0b1000_0000, 0b0000_0001]);
+		assert_eq!(flags.toggle(7), Ok(false));
+		assert_eq!(flags.get(7), Some(false));
+	}
+
+	#[test]
+	fn count_ones_spans_all_bytes() {
+		let mut flags = PodBitset::<4>::default();
+
+		for index in (0..PodBitset::<4>::BITS).step_by(3) {
+			assert_eq!(flags.set(index, true), Ok(()));
+		}
+
+		assert_eq!(flags.count_ones(), 11);
+		assert_eq!(PodBitset([0xFF; 4]).count_ones(), 32);
+	}
+
+	#[test]
+	fn out_of_range_indexes_are_rejected() {
+		let mut flags = PodBitset::<2>::default();
+
+		assert_eq!(flags.get(16), None);
+		assert_eq!(flags.set(16, true), Err(ProgramError::InvalidArgument));
+		assert_eq!(flags.toggle(usize::MAX), Err(ProgramError::InvalidArgument));
+		assert_eq!(flags.count_ones(), 0);
+	}
+}
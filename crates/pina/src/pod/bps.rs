@@ -0,0 +1,113 @@
+use bytemuck::Pod;
+use bytemuck::Zeroable;
+use pina_pod_primitives::PodU16;
+use pinocchio::error::ProgramError;
+
+/// A basis-points value (`0..=10_000`) stored as an alignment-safe `PodU16`.
+///
+/// Fee and share configs are commonly expressed in basis points, where
+/// `10_000` is 100%. [`PodBps::new`] rejects anything above that, and the
+/// accessors clamp to it so a value read from raw account bytes can never
+/// yield more than the whole amount.
+///
+/// # Examples
+///
+/// ```
+/// use pina::PodBps;
+///
+/// let fee = PodBps::new(250).unwrap_or_else(|e| panic!("valid bps: {e:?}"));
+/// assert_eq!(fee.apply(1_000_000), 25_000);
+/// assert_eq!(fee.as_fraction(), (250, 10_000));
+/// assert!(PodBps::new(10_001).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodBps(pub PodU16);
+
+impl PodBps {
+	/// The number of basis points in a whole (100%).
+	pub const MAX: u16 = 10_000;
+
+	/// Create a basis-points value.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidArgument` when `bps` is above [`PodBps::MAX`].
+	pub const fn new(bps: u16) -> Result<Self, ProgramError> {
+		if bps > Self::MAX {
+			return Err(ProgramError::InvalidArgument);
+		}
+
+		Ok(Self(PodU16::from_primitive(bps)))
+	}
+
+	/// The stored basis points, clamped to [`PodBps::MAX`].
+	pub const fn get(&self) -> u16 {
+		let bps = self.0.get();
+
+		if bps > Self::MAX { Self::MAX } else { bps }
+	}
+
+	/// Compute `amount * bps / 10_000`, rounding down.
+	///
+	/// The product is taken in `u128`, so this never overflows, and the result
+	/// never exceeds `amount`.
+	pub const fn apply(&self, amount: u64) -> u64 {
+		((amount as u128 * self.get() as u128) / Self::MAX as u128) as u64
+	}
+
+	/// The value as a `(numerator, denominator)` pair over `10_000`.
+	pub const fn as_fraction(&self) -> (u16, u16) {
+		(self.get(), Self::MAX)
+	}
+}
+
+impl TryFrom<u16> for PodBps {
+	type Error = ProgramError;
+
+	fn try_from(bps: u16) -> Result<Self, Self::Error> {
+		Self::new(bps)
+	}
+}
+
+impl From<PodBps> for u16 {
+	fn from(bps: PodBps) -> Self {
+		bps.get()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn new_accepts_valid_bps() {
+		assert_eq!(PodBps::new(0).map(|bps| bps.get()), Ok(0));
+		assert_eq!(PodBps::new(250).map(|bps| bps.get()), Ok(250));
+		assert_eq!(PodBps::new(10_000).map(|bps| bps.get()), Ok(10_000));
+	}
+
+	#[test]
+	fn new_rejects_over_range() {
+		assert_eq!(PodBps::new(10_001), Err(ProgramError::InvalidArgument));
+		assert_eq!(PodBps::new(u16::MAX), Err(ProgramError::InvalidArgument));
+	}
+
+	#[test]
+	fn apply_computes_fee() {
+		let fee = PodBps::new(30).unwrap_or_else(|e| panic!("valid bps: {e:?}"));
+
+		assert_eq!(fee.apply(1_000_000), 3_000);
+		assert_eq!(fee.apply(333), 0);
+		assert_eq!(fee.apply(u64::MAX), 55_340_232_221_128_654);
+		assert_eq!(fee.as_fraction(), (30, 10_000));
+	}
+
+	#[test]
+	fn raw_bytes_above_max_are_clamped() {
+		let bps = PodBps(PodU16::from_primitive(u16::MAX));
+
+		assert_eq!(bps.get(), PodBps::MAX);
+		assert_eq!(bps.apply(500), 500);
+	}
+}
@@ -0,0 +1,229 @@
+use core::mem::size_of;
+
+use bytemuck::Pod;
+use pina_pod_primitives::PodU16;
+use pinocchio::error::ProgramError;
+
+use crate::PinaProgramError;
+
+/// A fixed-capacity, sorted key/value map viewed over account data.
+///
+/// Small on-chain lookup tables, such as address to amount, can live in an
+/// account without a heap. The bytes hold a `PodU16` entry count followed by
+/// `N` keys and then `N` values, so [`PodMap::SPACE`] is
+/// `2 + N * (size_of::<K>() + size_of::<V>())`. Keys are kept in ascending
+/// order and found by binary search.
+///
+/// Keys and values must have alignment 1, which holds for the `Pod*` wrappers
+/// and `Address`.
+///
+/// # Examples
+///
+/// ```
+/// use pina::Address;
+/// use pina::PodMap;
+/// use pina::PodU64;
+///
+/// let mut data = [0u8; PodMap::<Address, PodU64, 4>::SPACE];
+/// let mut balances = PodMap::<Address, PodU64, 4>::from_bytes(&mut data)
+/// 	.unwrap_or_else(|e| panic!("map should fit: {e:?}"));
+/// let holder = Address::new_from_array([7; 32]);
+///
+/// assert_eq!(
+/// 	balances.insert(holder, PodU64::from_primitive(50)),
+/// 	Ok(None)
+/// );
+/// assert_eq!(balances.get(&holder), Some(&PodU64::from_primitive(50)));
+/// assert_eq!(balances.len(), 1);
+/// ```
+pub struct PodMap<'a, K: Pod + Ord, V: Pod, const N: usize> {
+	len: &'a mut PodU16,
+	keys: &'a mut [K],
+	values: &'a mut [V],
+}
+
+impl<'a, K: Pod + Ord, V: Pod, const N: usize> PodMap<'a, K, V, N> {
+	/// The number of bytes the map occupies.
+	pub const SPACE: usize = size_of::<PodU16>() + N * (size_of::<K>() + size_of::<V>());
+
+	/// View the first [`PodMap::SPACE`] bytes of `data` as a map.
+	///
+	/// Zeroed bytes are an empty map. A stored count above `N` is clamped to
+	/// `N`.
+	///
+	/// # Errors
+	///
+	/// Returns `DataTooShort` when `data` is shorter than [`PodMap::SPACE`] and
+	/// `InvalidAccountData` when `K` or `V` cannot be viewed over the bytes.
+	pub fn from_bytes(data: &'a mut [u8]) -> Result<Self, ProgramError> {
+		const {
+			assert!(
+				N <= u16::MAX as usize,
+				"PodMap<K, V, N>: N must fit the u16 entry count"
+			);
+		}
+
+		let data = data
+			.get_mut(..Self::SPACE)
+			.ok_or(PinaProgramError::DataTooShort)?;
+		let (len, entries) = data.split_at_mut(size_of::<PodU16>());
+		let (keys, values) = entries.split_at_mut(N * size_of::<K>());
+
+		Ok(Self {
+			len: bytemuck::from_bytes_mut(len),
+			keys: bytemuck::try_cast_slice_mut(keys)
+				.map_err(|_| ProgramError::InvalidAccountData)?,
+			values: bytemuck::try_cast_slice_mut(values)
+				.map_err(|_| ProgramError::InvalidAccountData)?,
+		})
+	}
+
+	/// The number of entries, clamped to the capacity.
+	pub fn len(&self) -> usize {
+		usize::from(self.len.get()).min(N)
+	}
+
+	/// Returns `true` when the map has no entries.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// The maximum number of entries.
+	pub const fn capacity(&self) -> usize {
+		N
+	}
+
+	/// The stored keys in ascending order.
+	pub fn keys(&self) -> &[K] {
+		&self.keys[..self.len()]
+	}
+
+	/// The value stored for `key`, if any.
+	pub fn get(&self, key: &K) -> Option<&V> {
+		let index = self.keys().binary_search(key).ok()?;
+
+		Some(&self.values[index])
+	}
+
+	/// A mutable reference to the value stored for `key`, if any.
+	pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+		let index = self.keys().binary_search(key).ok()?;
+
+		Some(&mut self.values[index])
+	}
+
+	/// Insert `value` under `key`, keeping the keys sorted.
+	///
+	/// Returns the previous value when `key` was already present.
+	///
+	/// # Errors
+	///
+	/// Returns `AccountDataTooSmall` when `key` is new and the map is full.
+	pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, ProgramError> {
+		let len = self.len();
+
+		match self.keys().binary_search(&key) {
+			Ok(index) => Ok(Some(core::mem::replace(&mut self.values[index], value))),
+			Err(_) if len == N => Err(ProgramError::AccountDataTooSmall),
+			Err(index) => {
+				self.keys.copy_within(index..len, index + 1);
+				self.values.copy_within(index..len, index + 1);
+				self.keys[index] = key;
+				self.values[index] = value;
+				*self.len = PodU16::from_primitive((len + 1) as u16);
+
+				Ok(None)
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use pina_pod_primitives::PodU64;
+
+	use super::*;
+
+	type Balances<'a> = PodMap<'a, PodU64, PodU64, 4>;
+
+	fn amount(value: u64) -> PodU64 {
+		PodU64::from_primitive(value)
+	}
+
+	#[test]
+	fn insert_out_of_order_keeps_keys_sorted() {
+		let mut data = [0u8; Balances::SPACE];
+		let mut map = Balances::from_bytes(&mut data).unwrap_or_else(|e| panic!("{e:?}"));
+
+		for key in [30, 10, 40, 20] {
+			assert_eq!(map.insert(amount(key), amount(key * 100)), Ok(None));
+		}
+
+		assert_eq!(map.len(), 4);
+		assert_eq!(map.keys(), [amount(10), amount(20), amount(30), amount(40)]);
+
+		for key in [10, 20, 30, 40] {
+			assert_eq!(map.get(&amount(key)), Some(&amount(key * 100)));
+		}
+
+		assert_eq!(map.get(&amount(25)), None);
+	}
+
+	#[test]
+	fn insert_existing_key_replaces_value() {
+		let mut data = [0u8; Balances::SPACE];
+		let mut map = Balances::from_bytes(&mut data).unwrap_or_else(|e| panic!("{e:?}"));
+
+		assert_eq!(map.insert(amount(5), amount(1)), Ok(None));
+		assert_eq!(map.insert(amount(5), amount(2)), Ok(Some(amount(1))));
+
+		if let Some(value) = map.get_mut(&amount(5)) {
+			*value = amount(3);
+		}
+
+		assert_eq!(map.len(), 1);
+		assert_eq!(map.get(&amount(5)), Some(&amount(3)));
+	}
+
+	#[test]
+	fn insert_into_full_map_fails() {
+		let mut data = [0u8; Balances::SPACE];
+		let mut map = Balances::from_bytes(&mut data).unwrap_or_else(|e| panic!("{e:?}"));
+
+		for key in 0..4 {
+			assert_eq!(map.insert(amount(key), amount(key)), Ok(None));
+		}
+
+		assert_eq!(
+			map.insert(amount(9), amount(9)),
+			Err(ProgramError::AccountDataTooSmall)
+		);
+		assert_eq!(map.insert(amount(2), amount(7)), Ok(Some(amount(2))));
+	}
+
+	#[test]
+	fn entries_persist_in_the_underlying_bytes() {
+		let mut data = [0u8; Balances::SPACE];
+
+		{
+			let mut map = Balances::from_bytes(&mut data).unwrap_or_else(|e| panic!("{e:?}"));
+			assert_eq!(map.insert(amount(2), amount(20)), Ok(None));
+			assert_eq!(map.insert(amount(1), amount(10)), Ok(None));
+		}
+
+		let map = Balances::from_bytes(&mut data).unwrap_or_else(|e| panic!("{e:?}"));
+
+		assert_eq!(map.keys(), [amount(1), amount(2)]);
+		assert_eq!(map.get(&amount(2)), Some(&amount(20)));
+	}
+
+	#[test]
+	fn from_bytes_rejects_short_data() {
+		let mut data = [0u8; Balances::SPACE - 1];
+
+		assert!(matches!(
+			Balances::from_bytes(&mut data),
+			Err(error) if error == PinaProgramError::DataTooShort.into()
+		));
+	}
+}
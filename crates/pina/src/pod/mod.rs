@@ -6,7 +6,13 @@
 //! `Pod*` types in this module wrap byte arrays and convert via little-endian
 //! encoding, making them safe to embed in any `#[repr(C)]` account layout.
 
+mod bitset;
+mod bps;
+mod map;
 mod primitives;
 
+pub use bitset::PodBitset;
+pub use bps::PodBps;
+pub use map::PodMap;
 pub use pina_pod_primitives::*;
 pub use primitives::*;
@@ -0,0 +1,190 @@
+//! Reader for the `EpochSchedule` sysvar.
+
+use pinocchio::AccountView;
+use pinocchio::Address;
+use pinocchio::error::ProgramError;
+
+use crate::AccountInfoValidation;
+use crate::sysvars::Sysvar;
+use crate::sysvars::SysvarAccount;
+use crate::sysvars::get_sysvar;
+
+/// The address of the `EpochSchedule` sysvar.
+pub const ID: Address = pina_sdk_ids::sysvar::epoch_schedule::ID;
+
+/// The shortest epoch length used during warmup.
+pub const MINIMUM_SLOTS_PER_EPOCH: u64 = 32;
+
+/// The serialized size of the `EpochSchedule` sysvar data.
+pub const EPOCH_SCHEDULE_LEN: usize = 33;
+
+/// The cluster's epoch schedule, decoded from the `EpochSchedule` sysvar.
+///
+/// When `warmup` is enabled, epochs start at [`MINIMUM_SLOTS_PER_EPOCH`] slots
+/// and double in length until `first_normal_epoch`, after which every epoch
+/// has `slots_per_epoch` slots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpochSchedule {
+	slots_per_epoch: u64,
+	leader_schedule_slot_offset: u64,
+	warmup: bool,
+	first_normal_epoch: u64,
+	first_normal_slot: u64,
+}
+
+impl EpochSchedule {
+	/// Decode the sysvar's serialized data.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidAccountData` when `data` is shorter than
+	/// [`EPOCH_SCHEDULE_LEN`] or `slots_per_epoch` is zero.
+	pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+		let Some(data) = data.get(..EPOCH_SCHEDULE_LEN) else {
+			return Err(ProgramError::InvalidAccountData);
+		};
+
+		let read_u64 = |offset: usize| {
+			let mut bytes = [0u8; 8];
+			bytes.copy_from_slice(&data[offset..offset + 8]);
+			u64::from_le_bytes(bytes)
+		};
+
+		let schedule = Self {
+			slots_per_epoch: read_u64(0),
+			leader_schedule_slot_offset: read_u64(8),
+			warmup: data[16] != 0,
+			first_normal_epoch: read_u64(17),
+			first_normal_slot: read_u64(25),
+		};
+
+		if schedule.slots_per_epoch == 0 {
+			return Err(ProgramError::InvalidAccountData);
+		}
+
+		Ok(schedule)
+	}
+
+	/// Decode the sysvar from its account, checking only the address like
+	/// `Clock::from_account_view`.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidArgument` when `account` is not the sysvar, a borrow
+	/// error when the data is already mutably borrowed, or
+	/// `InvalidAccountData` from [`EpochSchedule::from_bytes`].
+	pub fn from_account_view(account: &AccountView) -> Result<Self, ProgramError> {
+		if account.address() != &ID {
+			return Err(ProgramError::InvalidArgument);
+		}
+
+		Self::from_bytes(&account.try_borrow()?)
+	}
+
+	/// The number of slots in each epoch after warmup.
+	#[must_use]
+	pub const fn slots_per_epoch(&self) -> u64 {
+		self.slots_per_epoch
+	}
+
+	/// How many slots before an epoch its leader schedule is computed.
+	#[must_use]
+	pub const fn leader_schedule_slot_offset(&self) -> u64 {
+		self.leader_schedule_slot_offset
+	}
+
+	/// Whether the cluster started with shorter, doubling warmup epochs.
+	#[must_use]
+	pub const fn warmup(&self) -> bool {
+		self.warmup
+	}
+
+	/// The first epoch with `slots_per_epoch` slots.
+	#[must_use]
+	pub const fn first_normal_epoch(&self) -> u64 {
+		self.first_normal_epoch
+	}
+
+	/// The first slot of `first_normal_epoch`.
+	#[must_use]
+	pub const fn first_normal_slot(&self) -> u64 {
+		self.first_normal_slot
+	}
+
+	/// The epoch containing `slot`.
+	#[must_use]
+	pub const fn get_epoch(&self, slot: u64) -> u64 {
+		self.get_epoch_and_slot_index(slot).0
+	}
+
+	/// The epoch containing `slot` and the index of `slot` within it.
+	#[must_use]
+	pub const fn get_epoch_and_slot_index(&self, slot: u64) -> (u64, u64) {
+		if slot < self.first_normal_slot {
+			let epoch = (slot + MINIMUM_SLOTS_PER_EPOCH + 1)
+				.next_power_of_two()
+				.trailing_zeros()
+				- MINIMUM_SLOTS_PER_EPOCH.trailing_zeros()
+				- 1;
+			let epoch_len = 2u64.pow(epoch + MINIMUM_SLOTS_PER_EPOCH.trailing_zeros());
+
+			(epoch as u64, slot - (epoch_len - MINIMUM_SLOTS_PER_EPOCH))
+		} else {
+			let normal_slot_index = slot - self.first_normal_slot;
+
+			(
+				self.first_normal_epoch + normal_slot_index / self.slots_per_epoch,
+				normal_slot_index % self.slots_per_epoch,
+			)
+		}
+	}
+}
+
+impl Sysvar for EpochSchedule {
+	/// Read the sysvar through the `sol_get_sysvar` syscall, so the account
+	/// does not have to be passed to the instruction.
+	fn get() -> Result<Self, ProgramError> {
+		let mut data = [0u8; EPOCH_SCHEDULE_LEN];
+		get_sysvar(&mut data, &ID, 0)?;
+
+		Self::from_bytes(&data)
+	}
+}
+
+impl SysvarAccount for EpochSchedule {
+	const ADDRESS: Address = ID;
+
+	fn from_account(account: &AccountView) -> Result<Self, ProgramError> {
+		Self::from_bytes(&account.try_borrow()?)
+	}
+}
+
+/// Validate that `account` is the `EpochSchedule` sysvar and decode it.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns the errors from
+/// [`AccountInfoValidation::assert_sysvar`] when the account is not the
+/// sysvar, a borrow error when the data is already mutably borrowed, or
+/// `InvalidAccountData` from [`EpochSchedule::from_bytes`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use pina::sysvars::epoch_schedule::load_epoch_schedule;
+///
+/// let schedule = load_epoch_schedule(self.epoch_schedule)?;
+/// let epoch = schedule.get_epoch(clock.slot);
+/// ```
+#[track_caller]
+pub fn load_epoch_schedule(account: &AccountView) -> Result<EpochSchedule, ProgramError> {
+	account.assert_sysvar(&ID)?;
+
+	EpochSchedule::from_bytes(&account.try_borrow()?)
+}
@@ -0,0 +1,109 @@
+//! Reader for the `LastRestartSlot` sysvar.
+
+use pinocchio::AccountView;
+use pinocchio::Address;
+use pinocchio::error::ProgramError;
+
+use crate::AccountInfoValidation;
+use crate::sysvars::Sysvar;
+use crate::sysvars::SysvarAccount;
+use crate::sysvars::get_sysvar;
+
+/// The address of the `LastRestartSlot` sysvar.
+pub const ID: Address = pina_sdk_ids::sysvar::last_restart_slot::ID;
+
+/// The serialized size of the `LastRestartSlot` sysvar data.
+pub const LAST_RESTART_SLOT_LEN: usize = 8;
+
+/// Decode the slot of the cluster's most recent restart from the sysvar's
+/// serialized data.
+///
+/// # Errors
+///
+/// Returns `InvalidAccountData` when `data` is shorter than
+/// [`LAST_RESTART_SLOT_LEN`].
+pub fn last_restart_slot_from_bytes(data: &[u8]) -> Result<u64, ProgramError> {
+	let Some(bytes) = data.first_chunk::<LAST_RESTART_SLOT_LEN>() else {
+		return Err(ProgramError::InvalidAccountData);
+	};
+
+	Ok(u64::from_le_bytes(*bytes))
+}
+
+/// The `LastRestartSlot` sysvar: the slot of the cluster's most recent
+/// restart, or zero if it has never restarted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LastRestartSlot(pub u64);
+
+impl LastRestartSlot {
+	/// Decode the sysvar from its account, checking only the address like
+	/// `Clock::from_account_view`.
+	///
+	/// # Errors
+	///
+	/// Returns `InvalidArgument` when `account` is not the sysvar, a borrow
+	/// error when the data is already mutably borrowed, or
+	/// `InvalidAccountData` from [`last_restart_slot_from_bytes`].
+	pub fn from_account_view(account: &AccountView) -> Result<Self, ProgramError> {
+		if account.address() != &ID {
+			return Err(ProgramError::InvalidArgument);
+		}
+
+		Self::from_account(account)
+	}
+}
+
+impl Sysvar for LastRestartSlot {
+	/// Read the sysvar through the `sol_get_sysvar` syscall.
+	fn get() -> Result<Self, ProgramError> {
+		let mut data = [0u8; LAST_RESTART_SLOT_LEN];
+		get_sysvar(&mut data, &ID, 0)?;
+
+		last_restart_slot_from_bytes(&data).map(Self)
+	}
+}
+
+impl SysvarAccount for LastRestartSlot {
+	const ADDRESS: Address = ID;
+
+	fn from_account(account: &AccountView) -> Result<Self, ProgramError> {
+		last_restart_slot_from_bytes(&account.try_borrow()?).map(Self)
+	}
+}
+
+/// Validate that `account` is the `LastRestartSlot` sysvar and return the slot
+/// of the cluster's most recent restart.
+///
+/// State written before that slot may have been produced on a fork the
+/// restart discarded, so restart-sensitive programs such as oracles can compare
+/// it against a stored update slot and treat older data as stale.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns the errors from
+/// [`AccountInfoValidation::assert_sysvar`] when the account is not the
+/// sysvar, a borrow error when the data is already mutably borrowed, or
+/// `InvalidAccountData` from [`last_restart_slot_from_bytes`].
+///
+/// # Examples
+///
+/// ```ignore
+/// use pina::sysvars::last_restart_slot::load_last_restart_slot;
+///
+/// let restart_slot = load_last_restart_slot(self.last_restart_slot)?;
+/// if feed.updated_slot < restart_slot {
+/// 	return Err(OracleError::StaleFeed.into());
+/// }
+/// ```
+#[track_caller]
+pub fn load_last_restart_slot(account: &AccountView) -> Result<u64, ProgramError> {
+	account.assert_sysvar(&ID)?;
+
+	last_restart_slot_from_bytes(&account.try_borrow()?)
+}
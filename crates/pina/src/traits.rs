@@ -3,6 +3,7 @@ use pinocchio::ProgramResult;
 
 use crate::AccountView;
 use crate::Address;
+use crate::MAX_SEEDS;
 use crate::PinaProgramError;
 use crate::ProgramError;
 use crate::Ref;
@@ -156,12 +157,68 @@ pub trait AccountInfoValidation: Sized {
 	fn assert_writable(self) -> Result<Self, ProgramError>;
 	/// Assert that the account is executable.
 	fn assert_executable(self) -> Result<Self, ProgramError>;
+	/// Assert that the account is not executable. Use this where a data
+	/// account is expected so a program account cannot be passed in its place.
+	fn assert_not_executable(self) -> Result<Self, ProgramError>;
 	/// Assert that the data held by the account is of the specified length.
 	fn assert_data_len(self, len: usize) -> Result<Self, ProgramError>;
+	/// Assert that the account data starts with `prefix`. Returns
+	/// `PinaProgramError::DataTooShort` when the data is shorter than the
+	/// prefix.
+	fn assert_data_prefix(self, prefix: &[u8]) -> Result<Self, ProgramError>;
+	/// Assert that the account data has room for one more `T` element in a
+	/// collection of `current_len` elements stored after `header` bytes.
+	///
+	/// Call this before appending to an in-account collection. Returns
+	/// `AccountDataTooSmall` when the collection is already full.
+	fn assert_capacity_for<T>(
+		self,
+		header: usize,
+		current_len: usize,
+	) -> Result<Self, ProgramError>;
 	/// Assert that the account is empty.
 	fn assert_empty(self) -> Result<Self, ProgramError>;
 	/// Assert that the account is not empty.
 	fn assert_not_empty(self) -> Result<Self, ProgramError>;
+	/// Assert that the account was not closed by
+	/// [`close_account_secure`](CloseAccountWithRecipient::close_account_secure).
+	/// A closed account that is sent lamports again later in the same
+	/// transaction survives with the closed-account discriminator, and this
+	/// rejects it with `PinaProgramError::AccountClosed`.
+	fn assert_not_closed(self) -> Result<Self, ProgramError>;
+	/// Assert that the account is owned by the system program and has no data.
+	/// This is the strictest precondition for initializing a new account.
+	fn assert_strictly_uninitialized(self) -> Result<Self, ProgramError>;
+	/// Assert that the account holds at least `buffer` lamports above the
+	/// rent-exempt minimum for its current data length, reading the rent
+	/// sysvar. Programs that treat excess lamports as a bond use this to keep
+	/// a safety margin. Returns `InsufficientFunds` otherwise.
+	fn assert_lamports_above_rent_by(self, buffer: u64) -> Result<Self, ProgramError>;
+	/// Same as
+	/// [`assert_lamports_above_rent_by`](AccountInfoValidation::assert_lamports_above_rent_by)
+	/// with a `Rent` the instruction has already loaded.
+	fn assert_lamports_above_rent_with(
+		self,
+		rent: &pinocchio::sysvars::rent::Rent,
+		buffer: u64,
+	) -> Result<Self, ProgramError>;
+	/// Assert that the account holds at least the rent-exempt minimum for its
+	/// current data length, reading the rent sysvar. Guards PDAs that hold
+	/// lamport balances after a resize or a direct lamport debit. Returns
+	/// `AccountNotRentExempt` otherwise.
+	fn assert_rent_exempt(self) -> Result<Self, ProgramError>;
+	/// Same as [`assert_rent_exempt`](AccountInfoValidation::assert_rent_exempt)
+	/// for `len` bytes of data, such as the size the account is about to be
+	/// resized to.
+	fn assert_rent_exempt_for_len(self, len: usize) -> Result<Self, ProgramError>;
+	/// Same as
+	/// [`assert_rent_exempt_for_len`](AccountInfoValidation::assert_rent_exempt_for_len)
+	/// with a `Rent` the instruction has already loaded.
+	fn assert_rent_exempt_with(
+		self,
+		rent: &pinocchio::sysvars::rent::Rent,
+		len: usize,
+	) -> Result<Self, ProgramError>;
 	/// Assert that the account is of the type provided.
 	fn assert_type<T: HasDiscriminator>(self, program_id: &Address) -> Result<Self, ProgramError>;
 	/// Assert that the account is a program.
@@ -203,6 +260,47 @@ pub trait AccountInfoValidation: Sized {
 		mint: &Address,
 		token_program: &Address,
 	) -> Result<Self, ProgramError>;
+	/// Assert that the account is the associated token account of `wallet`
+	/// and `mint` derived with `token_program`, and that it is owned by that
+	/// same token program. This catches ATAs derived for one token program
+	/// but owned by the other.
+	#[cfg(feature = "token")]
+	fn assert_ata_consistent(
+		self,
+		wallet: &Address,
+		mint: &Address,
+		token_program: &Address,
+	) -> Result<Self, ProgramError>;
+	/// Assert that the account is owned by either the SPL Token or the
+	/// Token-2022 program.
+	#[cfg(feature = "token")]
+	fn assert_token_owned(self) -> Result<Self, ProgramError>;
+	/// Assert that the account is a token account of either token program
+	/// holding tokens of `mint`. Returns `InvalidAccountData` for another
+	/// mint.
+	#[cfg(feature = "token")]
+	fn assert_token_mint(self, mint: &Address) -> Result<Self, ProgramError>;
+	/// Assert that the account is a token account of either token program
+	/// whose stored owner is `owner`. Returns `InvalidAccountData` for
+	/// another owner.
+	#[cfg(feature = "token")]
+	fn assert_token_owner(self, owner: &Address) -> Result<Self, ProgramError>;
+	/// Assert that the account is a token account of either token program
+	/// that has approved `delegate`. Returns `InvalidAccountData` when another
+	/// delegate or none is set.
+	#[cfg(feature = "token")]
+	fn assert_token_delegate(self, delegate: &Address) -> Result<Self, ProgramError>;
+	/// Assert that the account is a mint of either token program whose mint
+	/// authority is `authority`. Returns
+	/// `PinaProgramError::AuthorityNotSet` when the mint has no mint
+	/// authority, and `IncorrectAuthority` when it is another address.
+	#[cfg(feature = "token")]
+	fn assert_mint_authority(self, authority: &Address) -> Result<Self, ProgramError>;
+	/// Assert that the account is a mint of either token program whose freeze
+	/// authority is `authority`, with the same errors as
+	/// [`assert_mint_authority`](AccountInfoValidation::assert_mint_authority).
+	#[cfg(feature = "token")]
+	fn assert_freeze_authority(self, authority: &Address) -> Result<Self, ProgramError>;
 }
 
 macro_rules! primitive_into_discriminator {
@@ -243,6 +341,41 @@ primitive_into_discriminator!(u16);
 primitive_into_discriminator!(u32);
 primitive_into_discriminator!(u64);
 
+/// Raw byte tags, such as the 8-byte `ArrayDiscriminator` of SPL interfaces or
+/// the leading bytes of a hash. The bytes are stored exactly as given, so the
+/// big-endian codec is the same as the default one.
+impl<const N: usize> IntoDiscriminator for [u8; N] {
+	fn discriminator_from_bytes(bytes: &[u8]) -> Result<Self, ProgramError> {
+		bytes
+			.get(..N)
+			.and_then(|tag| Self::try_from(tag).ok())
+			.ok_or(ProgramError::InvalidInstructionData)
+	}
+
+	fn write_discriminator(&self, bytes: &mut [u8]) {
+		debug_assert!(bytes.len() >= N);
+		if let Some(tag) = bytes.get_mut(..N) {
+			tag.copy_from_slice(self);
+		}
+	}
+
+	fn matches_discriminator(&self, bytes: &[u8]) -> bool {
+		bytes.get(..N).is_some_and(|tag| tag == self)
+	}
+
+	fn discriminator_from_bytes_be(bytes: &[u8]) -> Result<Self, ProgramError> {
+		Self::discriminator_from_bytes(bytes)
+	}
+
+	fn write_discriminator_be(&self, bytes: &mut [u8]) {
+		self.write_discriminator(bytes);
+	}
+
+	fn matches_discriminator_be(&self, bytes: &[u8]) -> bool {
+		self.matches_discriminator(bytes)
+	}
+}
+
 /// Wrap an enum to automatically make it into a discriminator.
 ///
 /// ```
@@ -279,9 +412,128 @@ primitive_into_discriminator!(u64);
 ///
 /// into_discriminator!(MyEnum, u64);
 /// ```
+///
+/// Pass `big` as a third argument to store the discriminator in big-endian
+/// order, e.g. `into_discriminator!(MyEnum, u64, big)`. Every read, write and
+/// match then goes through the `*_be` codec of the primitive.
+///
+/// Enums tagged with byte arrays instead of integers list the tag of every
+/// variant. The tags can be any constant expression, such as the leading bytes
+/// of a hash, and are written to the data exactly as given. Two variants with
+/// the same tag fail to compile:
+///
+/// ```
+/// use pina::*;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// pub enum OracleInstruction {
+/// 	Publish,
+/// 	Retire,
+/// }
+///
+/// into_discriminator!(OracleInstruction, [u8; 4], {
+/// 	Publish => *b"publ",
+/// 	Retire => [0xde, 0xad, 0xbe, 0xef],
+/// });
+///
+/// let data = [0xde, 0xad, 0xbe, 0xef, 42];
+/// assert_eq!(OracleInstruction::BYTES, 4);
+/// assert!(OracleInstruction::Retire.matches_discriminator(&data));
+/// assert_eq!(
+/// 	OracleInstruction::discriminator_from_bytes(&data),
+/// 	Ok(OracleInstruction::Retire)
+/// );
+/// ```
 #[macro_export]
 macro_rules! into_discriminator {
-	($enum:path, $type:ty) => {
+	($enum:path, [u8; $len:expr], { $($variant:ident => $tag:expr),+ $(,)? }) => {
+		const _: () = assert!(
+			$len <= $crate::MAX_DISCRIMINATOR_SPACE,
+			concat!(
+				"The byte tags of `",
+				stringify!($enum),
+				"` exceed `MAX_DISCRIMINATOR_SPACE`."
+			),
+		);
+
+		// Two variants with the same tag would make the second one unreachable.
+		const _: () = {
+			let tags: &[[u8; $len]] = &[$($tag),+];
+			let mut i = 0;
+			while i < tags.len() {
+				let mut j = i + 1;
+				while j < tags.len() {
+					let mut k = 0;
+					while k < $len && tags[i][k] == tags[j][k] {
+						k += 1;
+					}
+					assert!(
+						k < $len,
+						concat!("The byte tags of `", stringify!($enum), "` must be unique."),
+					);
+					j += 1;
+				}
+				i += 1;
+			}
+		};
+
+		impl $enum {
+			#[doc(hidden)]
+			#[inline]
+			const fn __pina_discriminator_tag(&self) -> [u8; $len] {
+				match self {
+					$(Self::$variant => $tag,)+
+				}
+			}
+		}
+
+		impl $crate::IntoDiscriminator for $enum {
+			const BYTES: usize = $len;
+
+			fn discriminator_from_bytes(
+				bytes: &[u8],
+			) -> ::core::result::Result<Self, $crate::ProgramError> {
+				let tag = <[u8; $len] as $crate::IntoDiscriminator>::discriminator_from_bytes(bytes)?;
+
+				$(
+					if tag == Self::$variant.__pina_discriminator_tag() {
+						return ::core::result::Result::Ok(Self::$variant);
+					}
+				)+
+
+				::core::result::Result::Err($crate::PinaProgramError::InvalidDiscriminator.into())
+			}
+
+			fn write_discriminator(&self, bytes: &mut [u8]) {
+				<[u8; $len] as $crate::IntoDiscriminator>::write_discriminator(
+					&self.__pina_discriminator_tag(),
+					bytes,
+				);
+			}
+
+			fn matches_discriminator(&self, bytes: &[u8]) -> bool {
+				<[u8; $len] as $crate::IntoDiscriminator>::matches_discriminator(
+					&self.__pina_discriminator_tag(),
+					bytes,
+				)
+			}
+
+			fn discriminator_from_bytes_be(
+				bytes: &[u8],
+			) -> ::core::result::Result<Self, $crate::ProgramError> {
+				<Self as $crate::IntoDiscriminator>::discriminator_from_bytes(bytes)
+			}
+
+			fn write_discriminator_be(&self, bytes: &mut [u8]) {
+				<Self as $crate::IntoDiscriminator>::write_discriminator(self, bytes);
+			}
+
+			fn matches_discriminator_be(&self, bytes: &[u8]) -> bool {
+				<Self as $crate::IntoDiscriminator>::matches_discriminator(self, bytes)
+			}
+		}
+	};
+	(@assert_size $enum:path, $type:ty) => {
 		// This block is evaluated at compile time.
 		// If the sizes don't match, the code will fail to compile.
 		const _: () = assert!(
@@ -295,6 +547,43 @@ macro_rules! into_discriminator {
 				"`."
 			),
 		);
+	};
+	($enum:path, $type:ty, big) => {
+		$crate::into_discriminator!(@assert_size $enum, $type);
+
+		impl $crate::IntoDiscriminator for $enum {
+			fn discriminator_from_bytes(
+				bytes: &[u8],
+			) -> ::core::result::Result<Self, $crate::ProgramError> {
+				<Self as $crate::IntoDiscriminator>::discriminator_from_bytes_be(bytes)
+			}
+
+			fn write_discriminator(&self, bytes: &mut [u8]) {
+				self.write_discriminator_be(bytes);
+			}
+
+			fn matches_discriminator(&self, bytes: &[u8]) -> bool {
+				self.matches_discriminator_be(bytes)
+			}
+
+			fn discriminator_from_bytes_be(
+				bytes: &[u8],
+			) -> ::core::result::Result<Self, $crate::ProgramError> {
+				<$type as $crate::IntoDiscriminator>::discriminator_from_bytes_be(bytes)
+					.and_then(|primitive| Self::try_from(primitive))
+			}
+
+			fn write_discriminator_be(&self, bytes: &mut [u8]) {
+				(*self as $type).write_discriminator_be(bytes);
+			}
+
+			fn matches_discriminator_be(&self, bytes: &[u8]) -> bool {
+				(*self as $type).matches_discriminator_be(bytes)
+			}
+		}
+	};
+	($enum:path, $type:ty) => {
+		$crate::into_discriminator!(@assert_size $enum, $type);
 
 		impl $crate::IntoDiscriminator for $enum {
 			fn discriminator_from_bytes(
@@ -315,6 +604,76 @@ macro_rules! into_discriminator {
 	};
 }
 
+/// Generate a test that checks every value of a discriminator's primitive
+/// against its `TryFrom` implementation.
+///
+/// The generated test asserts that each variant listed in the enum's
+/// `VARIANTS` constant parses back to itself and that every other value is
+/// rejected with `PinaProgramError::InvalidDiscriminator`. `u8` and `u16`
+/// discriminators are checked exhaustively; wider primitives are checked from
+/// zero up to 1024 past the largest variant, plus the primitive's maximum.
+///
+/// The test is named `test_discriminator_exhaustive` unless a name (optionally
+/// preceded by attributes) is passed as a third argument, which is needed when
+/// covering several enums in one module.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+/// 	use super::*;
+///
+/// 	pina::test_discriminator_exhaustive!(MyInstruction, u8);
+/// 	pina::test_discriminator_exhaustive!(MyAccount, u8, account_discriminator_is_exhaustive);
+/// }
+/// ```
+#[macro_export]
+macro_rules! test_discriminator_exhaustive {
+	($enum:ty, $primitive:ty) => {
+		$crate::test_discriminator_exhaustive!($enum, $primitive, test_discriminator_exhaustive);
+	};
+	($enum:ty, $primitive:ty, $(#[$attr:meta])* $name:ident) => {
+		#[test]
+		$(#[$attr])*
+		fn $name() {
+			let variants: &[$enum] = <$enum>::VARIANTS;
+			let assigned = |value: $primitive| {
+				variants.iter().any(|variant| *variant as $primitive == value)
+			};
+
+			for variant in variants {
+				let value = *variant as $primitive;
+				assert!(
+					<$enum>::try_from(value) == ::core::result::Result::Ok(*variant),
+					"discriminator value {value} does not parse back to its variant"
+				);
+			}
+
+			let largest = variants
+				.iter()
+				.map(|variant| *variant as $primitive)
+				.max()
+				.unwrap_or(0);
+			let upper = if ::core::mem::size_of::<$primitive>() <= 2 {
+				<$primitive>::MAX
+			} else {
+				largest.saturating_add(1024)
+			};
+
+			for value in (0..=upper).chain(::core::iter::once(<$primitive>::MAX)) {
+				if assigned(value) {
+					continue;
+				}
+
+				assert_eq!(
+					<$enum>::try_from(value).err(),
+					::core::option::Option::Some($crate::PinaProgramError::InvalidDiscriminator.into()),
+					"unassigned discriminator value {value} was not rejected"
+				);
+			}
+		}
+	};
+}
+
 /// Low-level discriminator codec.
 ///
 /// Implemented for the primitive types (`u8`, `u16`, `u32`, `u64`) and for
@@ -350,6 +709,48 @@ pub trait IntoDiscriminator: Sized {
 	/// Check if this discriminator matches the first `BYTES` of the provided
 	/// byte array.
 	fn matches_discriminator(&self, bytes: &[u8]) -> bool;
+
+	/// Read a big-endian discriminator from the first `BYTES` of the data
+	/// slice.
+	///
+	/// Pina stores discriminators little-endian by default. The big-endian
+	/// codec exists for programs whose account or instruction layouts are
+	/// shared with big-endian external systems, such as bridges and
+	/// cross-chain messaging formats.
+	fn discriminator_from_bytes_be(bytes: &[u8]) -> Result<Self, ProgramError> {
+		if bytes.len() < Self::BYTES || Self::BYTES > MAX_DISCRIMINATOR_SPACE {
+			return Err(ProgramError::InvalidInstructionData);
+		}
+
+		let mut swapped = [0u8; MAX_DISCRIMINATOR_SPACE];
+		swapped[..Self::BYTES].copy_from_slice(&bytes[..Self::BYTES]);
+		swapped[..Self::BYTES].reverse();
+
+		Self::discriminator_from_bytes(&swapped[..Self::BYTES])
+	}
+
+	/// Write the discriminator to the provided bytes in big-endian order.
+	fn write_discriminator_be(&self, bytes: &mut [u8]) {
+		self.write_discriminator(bytes);
+
+		if let Some(written) = bytes.get_mut(..Self::BYTES) {
+			written.reverse();
+		}
+	}
+
+	/// Check if this discriminator matches the first `BYTES` of the provided
+	/// byte array when read in big-endian order.
+	fn matches_discriminator_be(&self, bytes: &[u8]) -> bool {
+		if bytes.len() < Self::BYTES || Self::BYTES > MAX_DISCRIMINATOR_SPACE {
+			return false;
+		}
+
+		let mut swapped = [0u8; MAX_DISCRIMINATOR_SPACE];
+		swapped[..Self::BYTES].copy_from_slice(&bytes[..Self::BYTES]);
+		swapped[..Self::BYTES].reverse();
+
+		self.matches_discriminator(&swapped[..Self::BYTES])
+	}
 }
 
 /// The maximum number of bytes that a discriminator can occupy, chosen to
@@ -367,6 +768,14 @@ pub trait IntoDiscriminator: Sized {
 /// ```
 pub const MAX_DISCRIMINATOR_SPACE: usize = 8;
 
+/// The bytes that
+/// [`close_account_secure`](CloseAccountWithRecipient::close_account_secure)
+/// writes over the discriminator space of a closed account. They match
+/// Anchor's closed-account discriminator, so neither framework can mistake a
+/// revived account for live state.
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: [u8; MAX_DISCRIMINATOR_SPACE] =
+	[u8::MAX; MAX_DISCRIMINATOR_SPACE];
+
 /// Associates a concrete type (account / instruction / event struct) with its
 /// discriminator enum variant.
 ///
@@ -417,6 +826,17 @@ pub trait HasDiscriminator: Sized {
 	}
 }
 
+/// The layout of an account owned by another program, such as an oracle
+/// feed or a partner program's state.
+///
+/// Implemented by `#[account(external, owner = ...)]`. Foreign layouts carry
+/// no pina discriminator, so [`AsAccount::as_external_account`] guards them by
+/// their owner and size instead.
+pub trait ExternalAccount: Pod {
+	/// The program that owns accounts with this layout.
+	const OWNER: Address;
+}
+
 /// Backward-compatible alias for guard-backed immutable typed account access.
 pub type LoadedAccount<'a, T> = Ref<'a, T>;
 
@@ -462,6 +882,192 @@ pub trait AsAccount {
 	fn as_account_mut<T>(&mut self, program_id: &Address) -> Result<RefMut<'_, T>, ProgramError>
 	where
 		T: AccountDeserialize + HasDiscriminator + Pod;
+
+	/// Validate the account exactly like
+	/// [`assert_type::<T>`](AccountInfoValidation::assert_type) and return the
+	/// typed guard from the same borrow.
+	///
+	/// `assert_type::<T>()` followed by `as_account::<T>()` borrows the data
+	/// and checks the discriminator twice. This does the owner, discriminator
+	/// and size checks once, with the errors and logs of `assert_type`.
+	///
+	/// ```ignore
+	/// let counter = self.counter.assert_writable()?.load_account::<CounterState>(&ID)?;
+	/// ```
+	fn load_account<T>(&self, program_id: &Address) -> Result<Ref<'_, T>, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod;
+
+	/// Mutable counterpart of [`AsAccount::load_account`].
+	fn load_account_mut<T>(&mut self, program_id: &Address) -> Result<RefMut<'_, T>, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod;
+
+	/// Like [`load_account`](AsAccount::load_account), but returns `Ok(None)`
+	/// instead of an error when the stored discriminator is not `T`'s.
+	///
+	/// Instructions that accept one of several account types can try each in
+	/// turn and branch on the first match. A wrong owner, or a matching
+	/// discriminator with the wrong data size, is still an error, since
+	/// neither can be another valid account of this program.
+	/// [`load_one_of!`](crate::load_one_of) and `#[derive(AccountUnion)]` are
+	/// built on this for a fixed list of types.
+	///
+	/// ```ignore
+	/// if let Some(profile) = self.target.try_as_account::<UserProfile>(&ID)? {
+	/// 	return self.update_profile(&profile);
+	/// }
+	///
+	/// let config = self.target.load_account::<AdminConfig>(&ID)?;
+	/// ```
+	fn try_as_account<T>(&self, program_id: &Address) -> Result<Option<Ref<'_, T>>, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod;
+
+	/// Run `update` on a mutable view of the account data and release the
+	/// borrow before returning.
+	///
+	/// A guard from [`as_account_mut`](AsAccount::as_account_mut) that is
+	/// still alive when a CPI touching the same account is invoked makes the
+	/// CPI fail its borrow check. Scoping the borrow to a closure means no
+	/// guard can outlive the update, so invoking afterwards is always safe.
+	///
+	/// ```ignore
+	/// let amount = self.vault_state.with_account_mut::<VaultState, _>(&ID, |vault| {
+	/// 	vault.withdrawn = vault.amount;
+	/// 	vault.amount.get()
+	/// })?;
+	///
+	/// vault_cpi.transfer_checked_signed(self.vault, self.recipient, amount, decimals, &signers)?;
+	/// ```
+	fn with_account_mut<T, R>(
+		&mut self,
+		program_id: &Address,
+		update: impl FnOnce(&mut T) -> R,
+	) -> Result<R, ProgramError>
+	where
+		T: AccountDeserialize + HasDiscriminator + Pod;
+
+	/// View an account of another program as its external layout `T`.
+	///
+	/// Returns `InvalidAccountOwner` unless `T::OWNER` owns the account, and
+	/// `InvalidAccountData` when the data is shorter than `T`. Only the
+	/// leading `size_of::<T>()` bytes are mapped, since foreign programs often
+	/// reserve space past the fields a reader needs.
+	///
+	/// ```ignore
+	/// let feed = self.price_feed.as_external_account::<PriceFeed>()?;
+	/// let price = feed.price.get();
+	/// ```
+	fn as_external_account<T>(&self) -> Result<Ref<'_, T>, ProgramError>
+	where
+		T: ExternalAccount;
+
+	/// View an account as the external layout `T`, owned by `owner` instead of
+	/// `T::OWNER`.
+	///
+	/// For layouts that several programs share, such as a program deployed at
+	/// a different address per cluster or a fork of the original. The size
+	/// check matches [`AsAccount::as_external_account`].
+	///
+	/// ```ignore
+	/// let feed = self
+	/// 	.price_feed
+	/// 	.as_external_account_with_owner::<PriceFeed>(&config.oracle_program)?;
+	/// ```
+	fn as_external_account_with_owner<T>(
+		&self,
+		owner: &Address,
+	) -> Result<Ref<'_, T>, ProgramError>
+	where
+		T: ExternalAccount;
+
+	/// Soft-delete an account of type `T` by zeroing only its leading
+	/// discriminator bytes, so later `assert_type::<T>` and `as_account::<T>`
+	/// calls reject it.
+	///
+	/// This is not a close: the account keeps its lamports and the rest of its
+	/// data. Returns `InvalidArgument` when `T` uses an all-zero discriminator,
+	/// since zeroing it would leave the account looking valid.
+	fn invalidate_discriminator<T>(&mut self, program_id: &Address) -> ProgramResult
+	where
+		T: HasDiscriminator;
+
+	/// Convert an account holding an `Old` into a `New` of the same size by
+	/// re-stamping the discriminator, then view it as `New`.
+	///
+	/// This is the migration step for layout changes that keep the size, such
+	/// as renaming a field or splitting one into two of the same total width.
+	/// The bytes after the discriminator are reused as they are, so `New` must
+	/// read them compatibly. The account is first validated as an `Old` owned
+	/// by `program_id`. Fails to compile when the two types, or their
+	/// discriminators, differ in size.
+	///
+	/// ```ignore
+	/// let mut config = self.config.reinterpret_as::<ConfigV1, ConfigV2>(&ID)?;
+	/// config.flags = PodU16::from_primitive(0);
+	/// ```
+	fn reinterpret_as<Old, New>(
+		&mut self,
+		program_id: &Address,
+	) -> Result<RefMut<'_, New>, ProgramError>
+	where
+		Old: AccountDeserialize + HasDiscriminator + Pod,
+		New: AccountDeserialize + HasDiscriminator + Pod;
+
+	/// Assert that the account is either empty or already holds a `T` owned
+	/// by `program_id`, returning `true` when it was empty.
+	///
+	/// This is the precondition for reusing an account slot: an existing
+	/// account of a different type that happens to have the same size is
+	/// rejected with the same errors as `assert_type::<T>`.
+	fn assert_type_or_empty<T>(&self, program_id: &Address) -> Result<bool, ProgramError>
+	where
+		T: HasDiscriminator;
+
+	/// Validate ownership and the discriminator of `D`, then view the data
+	/// after the discriminator as an array of exactly `N` elements of `T`.
+	///
+	/// This suits accounts that are a discriminator followed by a fixed table
+	/// of slots. Returns `InvalidAccountData` when the discriminator does not
+	/// match, or when the data is not exactly the discriminator plus
+	/// `N * size_of::<T>()` bytes.
+	///
+	/// ```ignore
+	/// let slots = self.games.as_array::<GameTable, GameSlot, 8>(&ID)?;
+	/// ```
+	fn as_array<D, T, const N: usize>(
+		&self,
+		program_id: &Address,
+	) -> Result<Ref<'_, [T; N]>, ProgramError>
+	where
+		D: HasDiscriminator,
+		T: Pod;
+
+	/// Mutable counterpart of [`AsAccount::as_array`].
+	fn as_array_mut<D, T, const N: usize>(
+		&mut self,
+		program_id: &Address,
+	) -> Result<RefMut<'_, [T; N]>, ProgramError>
+	where
+		D: HasDiscriminator,
+		T: Pod;
+
+	/// The share of the allocated data that `logical_len` bytes occupy, in
+	/// basis points (`10_000` is 100%), rounded down.
+	///
+	/// Growable accounts can compare this against a threshold to decide when
+	/// shrinking is worth the resize. Basis points keep the check float-free
+	/// on-chain. An account without data reports `10_000`, and a `logical_len`
+	/// past the allocation is clamped to `10_000`.
+	///
+	/// ```ignore
+	/// let used = Registry::space(registry.len());
+	/// if self.registry.space_utilization_bps(used) < 5_000 {
+	/// 	realloc_account(self.registry, used, self.authority, &ID)?;
+	/// }
+	/// ```
+	fn space_utilization_bps(&self, logical_len: usize) -> u16;
 }
 
 /// Convenience methods for interpreting `AccountView` as SPL token account
@@ -477,6 +1083,9 @@ pub trait AsAccount {
 /// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
 #[cfg(feature = "token")]
 pub trait AsTokenAccount {
+	/// Returns `true` when the account is owned by either the SPL Token or
+	/// the Token-2022 program.
+	fn is_token_owned(&self) -> bool;
 	/// Interpret the account data as an SPL Token mint.
 	fn as_token_mint(&self) -> Result<Ref<'_, crate::token::state::Mint>, ProgramError>;
 	/// Interpret the account data as an SPL Token mint, validating owner.
@@ -610,6 +1219,230 @@ pub trait CloseAccountWithRecipient {
 	/// [`Self::close_with_recipient`]. It does not implicitly reallocate the
 	/// account, even when the `account-resize` feature is enabled.
 	fn close_account_zeroed(&mut self, recipient: &mut AccountView) -> ProgramResult;
+
+	/// Zero the account data, write [`CLOSED_ACCOUNT_DISCRIMINATOR`] over its
+	/// leading bytes, and transfer all lamports to the recipient.
+	///
+	/// Unlike [`Self::close_account_zeroed`], the account keeps its owner and
+	/// data length until the runtime removes it at the end of the
+	/// transaction. Resizing it to zero would also erase the sentinel, so a
+	/// later instruction that revives the account with a lamport transfer
+	/// would find an empty account instead of one marked as closed. Guard
+	/// every instruction that loads the account with
+	/// [`assert_not_closed`](AccountInfoValidation::assert_not_closed).
+	fn close_account_secure(&mut self, recipient: &mut AccountView) -> ProgramResult;
+}
+
+/// Account state that tracks a transferable `u64` balance.
+///
+/// Derive it with `#[derive(HasBalance)]`. The derive uses the field named
+/// `balance`, or the field annotated with `#[pina(balance)]`. The field may be
+/// any type convertible to and from `u64`, such as `PodU64`.
+///
+/// [`transfer_between`](crate::transfer_between) uses this trait to move value
+/// between two program accounts of the same type.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[account(discriminator = LedgerAccount)]
+/// #[derive(HasBalance)]
+/// pub struct Balance {
+/// 	pub owner: Address,
+/// 	#[pina(balance)]
+/// 	pub amount: PodU64,
+/// }
+/// ```
+pub trait HasBalance {
+	/// Read the current balance.
+	fn balance(&self) -> u64;
+	/// Overwrite the current balance.
+	fn set_balance(&mut self, balance: u64);
+}
+
+/// Account state that records the address allowed to modify it.
+///
+/// Derive it with `#[derive(HasAuthority)]`. The derive uses the field named
+/// `authority`, or the `Address` field annotated with `#[pina(authority)]`.
+///
+/// [`authorize_and_load_mut`](crate::authorize_and_load_mut) uses this trait to
+/// check the signer against the stored authority before handing out mutable
+/// access.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[account(discriminator = VaultAccount)]
+/// #[derive(HasAuthority)]
+/// pub struct VaultState {
+/// 	pub authority: Address,
+/// 	pub amount: PodU64,
+/// }
+/// ```
+pub trait HasAuthority {
+	/// The address that must sign to modify this account.
+	fn authority(&self) -> &Address;
+}
+
+/// Account state that carries a monotonically increasing sequence number.
+///
+/// Derive it with `#[derive(HasSequence)]`. The derive uses the field named
+/// `sequence`, or the field annotated with `#[pina(sequence)]`. The field may
+/// be any type convertible to and from `u64`, such as `PodU64`.
+///
+/// [`next_sequence`](crate::next_sequence) uses this trait to hand out the
+/// current value and advance the stored one.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[account(discriminator = OrderBookAccount)]
+/// #[derive(HasSequence)]
+/// pub struct OrderBook {
+/// 	pub authority: Address,
+/// 	#[pina(sequence)]
+/// 	pub next_order_id: PodU64,
+/// }
+/// ```
+pub trait HasSequence {
+	/// Read the current sequence number.
+	fn sequence(&self) -> u64;
+	/// Overwrite the current sequence number.
+	fn set_sequence(&mut self, sequence: u64);
+}
+
+/// The byte offset of every field in an account struct.
+///
+/// Implemented by `#[account]`. The offsets are what clients and indexers
+/// decode against, so a reordered or resized field silently breaks anything
+/// reading deployed accounts. Tests can record them with
+/// [`LayoutSnapshot`](crate::layout::LayoutSnapshot) (behind the `test-utils`
+/// feature) and compare against a committed golden file.
+pub trait HasLayout {
+	/// `(field name, byte offset)` pairs in declaration order, starting with
+	/// the injected `discriminator` field.
+	const FIELD_OFFSETS: &'static [(&'static str, usize)];
+}
+
+/// Account state that stores the bump of its own PDA.
+///
+/// Implemented by `#[account]` for the field marked `#[bump]`. Storing the
+/// bump lets later instructions verify the PDA with a single
+/// `create_program_address` instead of searching for the canonical bump.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[account(discriminator = VaultAccount)]
+/// pub struct VaultState {
+/// 	#[bump]
+/// 	pub bump: u8,
+/// 	pub amount: PodU64,
+/// }
+///
+/// let vault = vault_account.as_account::<VaultState>(&ID)?;
+/// vault.assert_pda(vault_account, &[b"vault", authority.address().as_ref()], &ID)?;
+/// ```
+pub trait HasBump {
+	/// The stored PDA bump.
+	fn bump(&self) -> u8;
+
+	/// Assert that `account` is the PDA for `seeds` (without the bump) and
+	/// the stored bump under `program_id`.
+	///
+	/// Returns `InvalidSeeds` when the seeds are too long to append the bump
+	/// to, or when the derived address does not match `account`.
+	#[track_caller]
+	fn assert_pda(
+		&self,
+		account: &AccountView,
+		seeds: &[&[u8]],
+		program_id: &Address,
+	) -> ProgramResult {
+		if seeds.len() >= MAX_SEEDS {
+			return Err(ProgramError::InvalidSeeds);
+		}
+
+		let bump = [self.bump()];
+		let mut seeds_with_bump: [&[u8]; MAX_SEEDS] = [&[]; MAX_SEEDS];
+		seeds_with_bump[..seeds.len()].copy_from_slice(seeds);
+		seeds_with_bump[seeds.len()] = &bump;
+
+		account.assert_seeds_with_bump(&seeds_with_bump[..=seeds.len()], program_id)?;
+
+		Ok(())
+	}
+}
+
+/// Account state that replaces an older layout stored at the same address.
+///
+/// Implemented by `#[account(version = N, migrate_from = Previous)]` for
+/// structs that declare a `version: u8` field and a `From<&Previous>`
+/// conversion. The generated [`Migrate::migrate`] runs the conversion and then
+/// stamps the new discriminator and version, so a hand-written conversion
+/// cannot leave either stale.
+/// [`migrate_account`](crate::migrate_account) uses it to rewrite a deployed
+/// account in place.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[account(discriminator = CounterAccount)]
+/// pub struct CounterStateV1 {
+/// 	pub count: u8,
+/// }
+///
+/// #[account(discriminator = CounterAccount, version = 2, migrate_from = CounterStateV1)]
+/// pub struct CounterState {
+/// 	pub version: u8,
+/// 	pub count: PodU64,
+/// }
+///
+/// impl From<&CounterStateV1> for CounterState {
+/// 	fn from(previous: &CounterStateV1) -> Self {
+/// 		Self::builder().count_u64(previous.count.into()).build()
+/// 	}
+/// }
+///
+/// migrate_account::<CounterState>(counter, payer, &ID)?;
+/// ```
+pub trait Migrate: HasDiscriminator + Pod {
+	/// The layout this one replaces.
+	type Previous: AccountDeserialize + HasDiscriminator + Pod;
+
+	/// The version written by [`Migrate::migrate`].
+	const VERSION: u8;
+
+	/// Build the new layout from `previous` with this layout's discriminator
+	/// and [`Migrate::VERSION`] set.
+	fn migrate(previous: &Self::Previous) -> Self;
+}
+
+/// Space calculation for accounts whose size depends on an element count,
+/// such as a fixed header followed by a list of entries.
+///
+/// [`create_dynamic_account`](crate::create_dynamic_account) uses this trait
+/// to size a new account at creation time.
+///
+/// # Examples
+///
+/// ```
+/// use pina::DynamicSpace;
+///
+/// struct Whitelist;
+///
+/// impl DynamicSpace for Whitelist {
+/// 	fn space(element_count: usize) -> usize {
+/// 		// 8 byte header followed by 32 byte addresses.
+/// 		8 + element_count * 32
+/// 	}
+/// }
+///
+/// assert_eq!(Whitelist::space(3), 104);
+/// ```
+pub trait DynamicSpace {
+	/// The number of bytes required to store `element_count` elements.
+	fn space(element_count: usize) -> usize;
 }
 
 /// Cursor for parsing instruction accounts exactly once.
@@ -658,6 +1491,50 @@ impl<'a> AccountsCursor<'a> {
 		Ok(account)
 	}
 
+	/// Parse the next account as an optional immutable account field.
+	///
+	/// Returns `None` when no accounts remain, or when the next account is
+	/// `program_id`, which clients pass as a placeholder for an omitted
+	/// account so that later accounts keep their positions. The placeholder is
+	/// consumed.
+	pub fn next_optional(
+		&mut self,
+		program_id: &Address,
+	) -> Result<Option<&'a AccountView>, ProgramError> {
+		if self.next_is_omitted(program_id) {
+			return self.skip_omitted();
+		}
+
+		self.next().map(Some)
+	}
+
+	/// Parse the next account as an optional mutable account field.
+	///
+	/// Omitted accounts are detected as in [`AccountsCursor::next_optional`].
+	pub fn next_optional_mut(
+		&mut self,
+		program_id: &Address,
+	) -> Result<Option<&'a mut AccountView>, ProgramError> {
+		if self.next_is_omitted(program_id) {
+			return self.skip_omitted();
+		}
+
+		self.next_mut().map(Some)
+	}
+
+	fn next_is_omitted(&self, program_id: &Address) -> bool {
+		self.peek()
+			.is_none_or(|account| account.address() == program_id)
+	}
+
+	fn skip_omitted<T>(&mut self) -> Result<Option<T>, ProgramError> {
+		if !self.remaining.is_empty() {
+			self.next()?;
+		}
+
+		Ok(None)
+	}
+
 	/// Return the unparsed trailing accounts without advancing the cursor.
 	pub fn remaining(&self) -> &[AccountView] {
 		self.remaining
@@ -702,6 +1579,111 @@ pub trait ParseAccounts<'a>: Sized {
 	fn parse_accounts(cursor: &mut AccountsCursor<'a>) -> Result<Self, ProgramError>;
 }
 
+/// The trailing accounts of an instruction, captured by a `#[derive(Accounts)]`
+/// field typed `RemainingAccounts<'a>`.
+///
+/// Programs with a variable number of accounts, such as transfer hooks or
+/// airdrops to a list of recipients, read the fixed accounts through the
+/// derive and walk the tail with this cursor. Writable accounts are checked
+/// for duplicates as in [`AccountsCursor`].
+///
+/// # Examples
+///
+/// ```ignore
+/// #[derive(Accounts)]
+/// pub struct AirdropAccounts<'a> {
+/// 	pub authority: &'a AccountView,
+/// 	pub remaining: RemainingAccounts<'a>,
+/// }
+///
+/// let mut accounts = AirdropAccounts::try_from_account_infos(accounts)?;
+/// while !accounts.remaining.is_empty() {
+/// 	let recipient = accounts.remaining.next_mut()?;
+/// 	// ...
+/// }
+/// ```
+pub struct RemainingAccounts<'a> {
+	cursor: AccountsCursor<'a>,
+}
+
+impl<'a> RemainingAccounts<'a> {
+	/// Wrap the unparsed accounts of an instruction.
+	pub fn new(accounts: &'a mut [AccountView]) -> Self {
+		Self {
+			cursor: AccountsCursor::new(accounts),
+		}
+	}
+
+	/// The number of accounts not yet consumed.
+	pub fn len(&self) -> usize {
+		self.cursor.remaining().len()
+	}
+
+	/// Returns `true` when every account has been consumed.
+	pub fn is_empty(&self) -> bool {
+		self.cursor.remaining().is_empty()
+	}
+
+	/// The accounts not yet consumed, without advancing the cursor.
+	pub fn as_slice(&self) -> &[AccountView] {
+		self.cursor.remaining()
+	}
+
+	/// Consume the next account as an immutable account, like
+	/// [`Iterator::next`] but failing when the account is missing.
+	///
+	/// # Errors
+	///
+	/// Returns `NotEnoughAccountKeys` when no accounts remain.
+	pub fn try_next(&mut self) -> Result<&'a AccountView, ProgramError> {
+		self.cursor.next()
+	}
+
+	/// Consume the next account as a mutable account.
+	///
+	/// # Errors
+	///
+	/// Returns `NotEnoughAccountKeys` when no accounts remain and
+	/// `DuplicateMutableAccount` when a later writable account has the same
+	/// address.
+	pub fn next_mut(&mut self) -> Result<&'a mut AccountView, ProgramError> {
+		self.cursor.next_mut()
+	}
+
+	/// Consume the next group of accounts as a typed accounts struct, such as
+	/// one derived with `#[derive(Accounts)]`.
+	///
+	/// # Errors
+	///
+	/// Returns the error of the first account in the group that fails to
+	/// parse.
+	pub fn next_as<T: ParseAccounts<'a>>(&mut self) -> Result<T, ProgramError> {
+		T::parse_accounts(&mut self.cursor)
+	}
+
+	/// Require that every account has been consumed.
+	///
+	/// # Errors
+	///
+	/// Returns `TooManyAccountKeys` when accounts remain.
+	pub fn finish(&self) -> Result<(), ProgramError> {
+		self.cursor.finish_exact()
+	}
+}
+
+/// Yields the remaining accounts as immutable accounts until none are left.
+impl<'a> Iterator for RemainingAccounts<'a> {
+	type Item = &'a AccountView;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.try_next().ok()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len(), Some(self.len()))
+	}
+}
+
 /// Destructures a slice of `AccountView` into a named accounts struct.
 ///
 /// Automatically derived by `#[derive(Accounts)]`.
@@ -1029,6 +2011,20 @@ mod tests {
 		assert!(original.matches_discriminator(&bytes));
 	}
 
+	#[test]
+	fn discriminator_roundtrip_be_u16() {
+		let original: u16 = 0x0102;
+		let mut bytes = [0u8; 2];
+		original.write_discriminator_be(&mut bytes);
+		assert_eq!(bytes, [0x01, 0x02]);
+
+		let decoded = u16::discriminator_from_bytes_be(&bytes).unwrap();
+		assert_eq!(decoded, original);
+		assert!(original.matches_discriminator_be(&bytes));
+		assert!(!original.matches_discriminator(&bytes));
+		assert!(u16::discriminator_from_bytes_be(&bytes[..1]).is_err());
+	}
+
 	#[test]
 	fn discriminator_roundtrip_u64() {
 		let original: u64 = 0x0123_4567_89AB_CDEF;
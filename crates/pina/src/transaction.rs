@@ -15,8 +15,27 @@
 //! [`InstructionBuilder::data`] returns the raw byte representation of a `Pod`
 //! instruction struct (which already includes the discriminator as its first
 //! field thanks to the `#[instruction]` macro).
+//!
+//! # Generated account lists
+//!
+//! With the `client` feature, `#[derive(Accounts)]` also emits a `<Name>Keys`
+//! struct holding one address per account field. Its [`ToAccountMetas`] impl
+//! returns the [`AccountMeta`] list in field order, with the signer and
+//! writable flags taken from the struct, so clients never maintain that order
+//! by hand.
+//!
+//! `#[instruction]` structs pair with those lists through
+//! `build_instruction(program_id, &keys)`, which returns the complete
+//! [`Instruction`] with the struct's bytes as its data.
+
+#[cfg(feature = "client")]
+use alloc::vec::Vec;
 
 use bytemuck::Pod;
+#[cfg(feature = "client")]
+pub use solana_instruction::AccountMeta;
+#[cfg(feature = "client")]
+pub use solana_instruction::Instruction;
 
 use crate::Address;
 use crate::HasDiscriminator;
@@ -159,6 +178,31 @@ pub fn readonly(address: &Address) -> AccountMetaTuple {
 	(*address, false, false)
 }
 
+/// Lists the account metas of an instruction's accounts, in order.
+///
+/// Implemented by the `<Name>Keys` structs that `#[derive(Accounts)]`
+/// generates. Nested accounts structs contribute their own metas in place.
+///
+/// # Examples
+///
+/// ```ignore
+/// // `system_program` is marked `#[pina(program = system)]`, so it is filled in.
+/// let metas = InitializeAccountsKeys { payer, counter }.to_account_metas();
+/// ```
+#[cfg(feature = "client")]
+pub trait ToAccountMetas {
+	/// Append the account metas to `metas`.
+	fn append_account_metas(&self, metas: &mut Vec<AccountMeta>);
+
+	/// Collect the account metas in field order.
+	fn to_account_metas(&self) -> Vec<AccountMeta> {
+		let mut metas = Vec::new();
+		self.append_account_metas(&mut metas);
+
+		metas
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	#![allow(unsafe_code)]
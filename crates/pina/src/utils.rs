@@ -1,10 +1,23 @@
 #[cfg(feature = "logs")]
 use core::panic::Location;
 
+use crate::AccountDeserialize;
+use crate::AccountInfoValidation;
+use crate::AccountValidation;
+use crate::AccountView;
 use crate::Address;
+use crate::AsAccount;
+#[cfg(feature = "token")]
+use crate::AsTokenAccount;
+use crate::HasAuthority;
+use crate::HasBalance;
+use crate::HasDiscriminator;
+use crate::HasSequence;
 use crate::IntoDiscriminator;
+use crate::Pod;
 use crate::ProgramError;
 use crate::ProgramResult;
+use crate::RefMut;
 use crate::log;
 
 /// Parses an instruction discriminator from the raw instruction data.
@@ -81,6 +94,59 @@ pub fn parse_instruction<'a, T: IntoDiscriminator>(
 	})
 }
 
+/// Parses an instruction discriminator like [`parse_instruction`], after first
+/// checking that `data` holds at least `T::BYTES` bytes.
+///
+/// Truncated instruction data is reported with a log line naming the expected
+/// and actual lengths, which makes malformed client transactions easier to
+/// diagnose than the bare error from [`parse_instruction`].
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// - `InvalidInstructionData` when `data` is shorter than the discriminator.
+/// - Any error from [`parse_instruction`].
+///
+/// # Examples
+///
+/// ```
+/// use pina::ProgramError;
+/// use pina::parse_instruction_checked;
+///
+/// let program_id = pina::system::ID;
+///
+/// let disc: u16 = parse_instruction_checked(&program_id, &program_id, &[7, 0])
+/// 	.unwrap_or_else(|e| panic!("parse failed: {e:?}"));
+/// assert_eq!(disc, 7);
+///
+/// let err = parse_instruction_checked::<u16>(&program_id, &program_id, &[7]).unwrap_err();
+/// assert_eq!(err, ProgramError::InvalidInstructionData);
+/// ```
+#[track_caller]
+pub fn parse_instruction_checked<'a, T: IntoDiscriminator>(
+	api_id: &'a Address,
+	program_id: &'a Address,
+	data: &'a [u8],
+) -> Result<T, ProgramError> {
+	if data.len() < T::BYTES {
+		log!(
+			"instruction data is too short: expected at least {} bytes, got {}",
+			T::BYTES,
+			data.len()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidInstructionData);
+	}
+
+	parse_instruction(api_id, program_id, data)
+}
+
 /// Asserts a boolean condition, logging `msg` and returning `err` on failure.
 ///
 /// Intended for compact guard checks inside instruction handlers.
@@ -119,6 +185,43 @@ pub fn assert(v: bool, err: impl Into<ProgramError>, msg: &str) -> ProgramResult
 	}
 }
 
+/// Logs `context` together with the address of `account` and returns `err`.
+///
+/// Custom validation that rejects an account usually logs only what went
+/// wrong, which leaves the failing account unnamed in instructions that take
+/// several of the same kind. This records both before handing back the error.
+///
+/// # Examples
+///
+/// ```ignore
+/// if u64::from(pool.fee_bps) > MAX_FEE_BPS {
+/// 	return Err(fail_with_account(self.pool, ProgramError::InvalidAccountData, "fee above max"));
+/// }
+/// ```
+#[track_caller]
+pub fn fail_with_account(account: &AccountView, err: ProgramError, context: &str) -> ProgramError {
+	#[cfg(feature = "logs")]
+	account_failure_log(account.address(), context).log();
+	#[cfg(not(feature = "logs"))]
+	let _ = (account, context);
+
+	log_caller();
+	err
+}
+
+/// Build the log line written by [`fail_with_account`].
+#[cfg(feature = "logs")]
+fn account_failure_log(address: &Address, context: &str) -> crate::Logger<256> {
+	let mut logger = crate::Logger::<256>::default();
+	logger
+		.append("address: ")
+		.append(address.as_ref())
+		.append(" failed: ")
+		.append(context);
+
+	logger
+}
+
 /// Logs caller file/line/column when `logs` feature is enabled.
 ///
 /// Used internally by assertion helpers and account validation methods.
@@ -168,3 +271,1059 @@ pub fn try_get_associated_token_address(
 		&pinocchio_associated_token_account::ID,
 	)
 }
+
+/// Derives the associated token account for `wallet_address` and each of the
+/// `mints`, returning the `(address, bump)` pairs in the same order.
+///
+/// Derive the full set once at the start of a multi-mint instruction instead
+/// of repeating [`try_get_associated_token_address`] for every check. Returns
+/// `None` if any of the addresses has no valid PDA.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Examples
+///
+/// ```ignore
+/// let [(base_ata, _), (quote_ata, _)] =
+/// 	derive_atas(&wallet, &[base_mint, quote_mint], &token::ID).ok_or(ProgramError::InvalidSeeds)?;
+/// ```
+#[cfg(feature = "token")]
+pub fn derive_atas<const N: usize>(
+	wallet_address: &Address,
+	mints: &[Address; N],
+	token_program_id: &Address,
+) -> Option<[(Address, u8); N]> {
+	let mut atas = [(Address::default(), 0u8); N];
+
+	for (ata, mint) in atas.iter_mut().zip(mints) {
+		*ata = try_get_associated_token_address(wallet_address, mint, token_program_id)?;
+	}
+
+	Some(atas)
+}
+
+/// Asserts that `token_account` is the canonical associated token account for
+/// `(owner, mint)` under `token_program` and that its stored owner is `owner`.
+///
+/// This folds the separate ATA address and token owner checks that escrow
+/// style instructions perform into a single call.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Errors
+///
+/// - `InvalidAccountData` when the stored token owner is not `owner`.
+/// - Any error from
+///   [`AsTokenAccount::as_associated_token_account_checked`], such as
+///   `InvalidSeeds` for a non-canonical address.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_owned_ata(self.maker_ata_a, self.maker.address(), self.mint_a.address(), &token::ID)?;
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn assert_owned_ata(
+	token_account: &AccountView,
+	owner: &Address,
+	mint: &Address,
+	token_program: &Address,
+) -> ProgramResult {
+	let state = token_account.as_associated_token_account_checked(owner, mint, token_program)?;
+
+	if state.owner() != owner {
+		log!(
+			"address: {} is not owned by the expected wallet",
+			token_account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidAccountData);
+	}
+
+	Ok(())
+}
+
+/// Asserts that `ata` is an initialized associated token account for
+/// `(wallet, mint)` owned by `token_program`.
+///
+/// Instructions that spend from an existing vault need three separate checks
+/// on it: the account holds data, its address is the canonical ATA, and the
+/// token program owns it. This runs all three in one call.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Errors
+///
+/// - `UninitializedAccount` when `ata` holds no data.
+/// - `InvalidSeeds` when `ata` is not the canonical ATA address.
+/// - `InvalidAccountOwner` when `ata` is not owned by `token_program`.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_initialized_ata(self.vault, self.escrow.address(), self.mint_a.address(), self.token_program.address())?;
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn assert_initialized_ata(
+	ata: &AccountView,
+	wallet: &Address,
+	mint: &Address,
+	token_program: &Address,
+) -> ProgramResult {
+	ata.assert_not_empty()?
+		.assert_ata_consistent(wallet, mint, token_program)?;
+
+	Ok(())
+}
+
+/// Loads a mint owned by `token_program` and returns its `(supply, decimals)`.
+///
+/// Issuance limits usually need both values, so they are read from a single
+/// validated mint load instead of two separate casts.
+///
+/// <!-- {=pinaTokenFeatureGateContract|trim|linePrefix:"/// ":true} -->/// This API is gated behind the `token` feature. Keep token-specific code behind `#[cfg(feature = "token")]` so on-chain programs that do not use SPL token interfaces can avoid extra dependencies.<!-- {/pinaTokenFeatureGateContract} -->
+///
+/// # Errors
+///
+/// - `InvalidAccountOwner` when `account` is not owned by `token_program`.
+/// - `InvalidAccountData` when the data is too short to hold a mint.
+///
+/// # Examples
+///
+/// ```ignore
+/// let (supply, decimals) = mint_info(self.mint, &token::ID)?;
+/// ```
+#[cfg(feature = "token")]
+#[track_caller]
+pub fn mint_info(
+	account: &AccountView,
+	token_program: &Address,
+) -> Result<(u64, u8), ProgramError> {
+	let mint = account.as_token_mint_checked_with_owners(core::slice::from_ref(token_program))?;
+
+	Ok((mint.supply(), mint.decimals()))
+}
+
+/// Moves `amount` from the `source` balance to the `dest` balance of two
+/// program accounts of the same type.
+///
+/// Both accounts must be writable, owned by `program_id`, and hold a `T`. The
+/// debit and credit are checked, and the two accounts must be distinct so a
+/// duplicated account can't be used to mint value out of thin air.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// - `InvalidArgument` when `source` and `dest` are the same account.
+/// - `InsufficientFunds` when `source` holds less than `amount`.
+/// - `ArithmeticOverflow` when crediting `dest` overflows.
+/// - Any error from the writable check or [`AsAccount::as_account_mut`].
+///
+/// # Examples
+///
+/// ```ignore
+/// transfer_between::<Balance>(self.source, self.dest, args.amount.into(), &ID)?;
+/// ```
+#[track_caller]
+pub fn transfer_between<T>(
+	source: &mut AccountView,
+	dest: &mut AccountView,
+	amount: u64,
+	program_id: &Address,
+) -> ProgramResult
+where
+	T: AccountDeserialize + HasDiscriminator + HasBalance + Pod,
+{
+	source.assert_writable()?;
+	dest.assert_writable()?;
+
+	if source.address() == dest.address() {
+		log!("Could not transfer balance: source and destination must differ");
+		log_caller();
+
+		return Err(ProgramError::InvalidArgument);
+	}
+
+	let mut source_state = source.as_account_mut::<T>(program_id)?;
+	let mut dest_state = dest.as_account_mut::<T>(program_id)?;
+
+	let Some(new_source_balance) = source_state.balance().checked_sub(amount) else {
+		log!("Could not debit balance: insufficient funds");
+		log_caller();
+
+		return Err(ProgramError::InsufficientFunds);
+	};
+
+	let Some(new_dest_balance) = dest_state.balance().checked_add(amount) else {
+		log!("Could not credit balance: arithmetic overflow");
+		log_caller();
+
+		return Err(ProgramError::ArithmeticOverflow);
+	};
+
+	source_state.set_balance(new_source_balance);
+	dest_state.set_balance(new_dest_balance);
+
+	Ok(())
+}
+
+/// Loads `account` as a mutable `T` after checking that `signer` is the
+/// authority recorded in the account state.
+///
+/// This folds the usual steps of an authority-gated update into one call:
+/// assert the signer, assert the account is writable, load the typed account,
+/// and compare the stored authority with the signer address.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// - `MissingRequiredSignature` when `signer` did not sign.
+/// - `InvalidAccountData` when `account` is not writable.
+/// - `IncorrectAuthority` when the stored authority is not `signer`.
+/// - Any error from [`AsAccount::as_account_mut`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut vault = authorize_and_load_mut::<VaultState>(self.vault, self.authority, &ID)?;
+/// vault.amount = PodU64::from_primitive(0);
+/// ```
+#[track_caller]
+pub fn authorize_and_load_mut<'a, T>(
+	account: &'a mut AccountView,
+	signer: &AccountView,
+	program_id: &Address,
+) -> Result<RefMut<'a, T>, ProgramError>
+where
+	T: AccountDeserialize + HasDiscriminator + HasAuthority + Pod,
+{
+	signer.assert_signer()?;
+	account.assert_writable()?;
+
+	let state = account.as_account_mut::<T>(program_id)?;
+
+	if state.authority() != signer.address() {
+		log!(
+			"address: {} is not the account authority",
+			signer.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::IncorrectAuthority);
+	}
+
+	Ok(state)
+}
+
+/// Applies `update` to the typed state of `account` and rejects the result
+/// unless `invariant` still holds.
+///
+/// `as_account_mut` hands out the state with no checks after the caller is
+/// done with it, so an update that forgets to enforce a bound is silently
+/// written back. This helper runs the invariant before releasing the borrow
+/// and restores the previous state when it fails, so a rejected update never
+/// leaves partially applied data behind.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// - `InvalidAccountData` when `invariant` returns `false` after the update.
+/// - Any error from [`AsAccount::as_account_mut`].
+///
+/// # Examples
+///
+/// ```ignore
+/// update_validated::<ConfigState>(
+/// 	self.config,
+/// 	&ID,
+/// 	|config| config.fee_bps = args.fee_bps,
+/// 	|config| u16::from(config.fee_bps) <= MAX_FEE_BPS,
+/// )?;
+/// ```
+#[track_caller]
+pub fn update_validated<T>(
+	account: &mut AccountView,
+	program_id: &Address,
+	update: impl FnOnce(&mut T),
+	invariant: impl Fn(&T) -> bool,
+) -> ProgramResult
+where
+	T: AccountDeserialize + AccountValidation + HasDiscriminator + Pod,
+{
+	let mut state = account.as_account_mut::<T>(program_id)?;
+	let previous = *state;
+
+	update(&mut state);
+
+	if let Err(error) = state.assert_mut(invariant) {
+		*state = previous;
+
+		return Err(error);
+	}
+
+	Ok(())
+}
+
+/// Returns the current sequence number stored in `account` and advances the
+/// stored value by one.
+///
+/// Programs that hand out order or ticket IDs read the counter, use it, and
+/// write back the next value. Doing all three here keeps the increment
+/// checked and makes it impossible to use an ID without consuming it.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// - `ArithmeticOverflow` when the stored sequence is already `u64::MAX`. The
+///   stored value is left unchanged.
+/// - Any error from [`AsAccount::as_account_mut`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let order_id = next_sequence::<OrderBook>(self.order_book, &ID)?;
+/// ```
+#[track_caller]
+pub fn next_sequence<T>(
+	account: &mut AccountView,
+	program_id: &Address,
+) -> Result<u64, ProgramError>
+where
+	T: AccountDeserialize + HasDiscriminator + HasSequence + Pod,
+{
+	let mut state = account.as_account_mut::<T>(program_id)?;
+	let current = state.sequence();
+
+	let Some(next) = current.checked_add(1) else {
+		drop(state);
+		log!(
+			"address: {} sequence is exhausted",
+			account.address().as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::ArithmeticOverflow);
+	};
+
+	state.set_sequence(next);
+
+	Ok(current)
+}
+
+/// Asserts that every pair of views sharing an address agrees on the signer
+/// and writable flags.
+///
+/// A program that reads flags from one view of a duplicated account and
+/// trusts them for another can be misled by crafted account metas. Call this
+/// over the accounts an instruction relies on before checking flags
+/// individually.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` when two views of the same address disagree on
+/// `is_signer` or `is_writable`.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_consistent_duplicates(&[self.source, self.destination, self.fee_vault])?;
+/// ```
+#[track_caller]
+pub fn assert_consistent_duplicates(accounts: &[&AccountView]) -> ProgramResult {
+	for (index, account) in accounts.iter().enumerate() {
+		for other in &accounts[index + 1..] {
+			if account.address() != other.address() {
+				continue;
+			}
+
+			if account.is_writable() != other.is_writable()
+				|| account.is_signer() != other.is_signer()
+			{
+				log!(
+					"address: {} has inconsistent flags across duplicate views",
+					account.address().as_ref()
+				);
+				log_caller();
+
+				return Err(ProgramError::InvalidArgument);
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Asserts that no writable account appears twice in `accounts`.
+///
+/// Two writable views of the same account let an instruction read a balance
+/// from one and write it through the other, which is how a transfer from an
+/// account to itself can mint funds. `#[derive(Accounts)]` calls this for
+/// `#[pina(deny_duplicates)]` structs and `#[pina(distinct_from = ...)]`
+/// fields.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns
+/// [`PinaProgramError::DuplicateMutableAccount`](crate::PinaProgramError::DuplicateMutableAccount)
+/// when two writable entries share an address.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_distinct_writable([self.source, self.destination])?;
+/// ```
+#[track_caller]
+pub fn assert_distinct_writable<'v, I>(accounts: I) -> ProgramResult
+where
+	I: IntoIterator<Item = &'v AccountView>,
+	I::IntoIter: Clone,
+{
+	let mut accounts = accounts.into_iter();
+
+	while let Some(account) = accounts.next() {
+		if !account.is_writable() {
+			continue;
+		}
+
+		if accounts
+			.clone()
+			.any(|other| other.is_writable() && other.address() == account.address())
+		{
+			log!(
+				"address: {} is passed as more than one writable account",
+				account.address().as_ref()
+			);
+			log_caller();
+
+			return Err(crate::PinaProgramError::DuplicateMutableAccount.into());
+		}
+	}
+
+	Ok(())
+}
+
+/// Asserts that `accounts` are in strictly ascending address order.
+///
+/// Instructions that aggregate a variable list of accounts, such as Merkle
+/// style commitments or deduplicated batches, need one canonical order so
+/// the same set always produces the same result. Strict ordering also rules
+/// out the same account being passed twice.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` when an address is not strictly greater than
+/// the one before it, which covers both disorder and duplicates.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_accounts_sorted(&[self.member_a, self.member_b, self.member_c])?;
+/// ```
+#[track_caller]
+pub fn assert_accounts_sorted(accounts: &[&AccountView]) -> ProgramResult {
+	for pair in accounts.windows(2) {
+		let (previous, current) = (pair[0], pair[1]);
+
+		if current.address().as_ref() <= previous.address().as_ref() {
+			log!(
+				"address: {} is not sorted after the previous account",
+				current.address().as_ref()
+			);
+			log_caller();
+
+			return Err(ProgramError::InvalidArgument);
+		}
+	}
+
+	Ok(())
+}
+
+/// Asserts that every account in `accounts` is owned by `owner`.
+///
+/// Instructions that operate on a homogeneous set, such as a batch of token
+/// accounts, can reject a mixed-owner set in one call. The failure log names
+/// the index of the first account with a different owner.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns `InvalidAccountOwner` for the first account not owned by `owner`.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_all_owned_by(&[self.source, self.destination, self.fee_vault], &token::ID)?;
+/// ```
+#[track_caller]
+pub fn assert_all_owned_by(accounts: &[&AccountView], owner: &Address) -> ProgramResult {
+	for (index, account) in accounts.iter().enumerate() {
+		if !account.owned_by(owner) {
+			#[cfg(not(feature = "logs"))]
+			let _ = index;
+
+			log!(
+				"account at index {} with address: {} has the wrong owner",
+				index,
+				account.address().as_ref()
+			);
+			log_caller();
+
+			return Err(ProgramError::InvalidAccountOwner);
+		}
+	}
+
+	Ok(())
+}
+
+/// Asserts that an instruction received between `min` and `max` accounts,
+/// inclusive.
+///
+/// Instructions with optional or remaining accounts accept a range of account
+/// counts. Calling this at the top of a processor rejects a malformed account
+/// list with a clear error before any account struct is parsed.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// - `NotEnoughAccountKeys` when fewer than `min` accounts were provided.
+/// - [`PinaProgramError::TooManyAccountKeys`](crate::PinaProgramError::TooManyAccountKeys)
+///   when more than `max` accounts were provided.
+///
+/// # Examples
+///
+/// ```ignore
+/// // Two required accounts followed by up to three optional signers.
+/// assert_account_count(accounts, 2, 5)?;
+/// ```
+#[track_caller]
+pub fn assert_account_count(accounts: &[AccountView], min: usize, max: usize) -> ProgramResult {
+	if accounts.len() < min {
+		log!("expected at least {} accounts, got {}", min, accounts.len());
+		log_caller();
+
+		return Err(ProgramError::NotEnoughAccountKeys);
+	}
+
+	if accounts.len() > max {
+		log!("expected at most {} accounts, got {}", max, accounts.len());
+		log_caller();
+
+		return Err(crate::PinaProgramError::TooManyAccountKeys.into());
+	}
+
+	Ok(())
+}
+
+/// Asserts that `provided_bump` is the canonical bump for
+/// `seeds_without_bump`.
+///
+/// Any bump that yields an off-curve address forms a valid PDA, so a program
+/// that trusts a caller-supplied bump can be handed a second, non-canonical
+/// address for the same logical seeds. This derives the canonical bump with
+/// [`try_find_program_address`](crate::try_find_program_address) and rejects
+/// every other value. Only the bump is checked; no account is created or
+/// inspected, which suits instruction arguments that are validated before an
+/// account is touched.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns `InvalidSeeds` when no PDA exists for the seeds or when
+/// `provided_bump` is not the canonical bump.
+///
+/// # Examples
+///
+/// ```ignore
+/// let seeds: &[&[u8]] = &[b"data", self.authority.address().as_ref()];
+/// assert_canonical_bump(seeds, args.bump, &ID)?;
+/// ```
+#[track_caller]
+pub fn assert_canonical_bump(
+	seeds_without_bump: &[&[u8]],
+	provided_bump: u8,
+	program_id: &Address,
+) -> ProgramResult {
+	let Some((_address, bump)) = crate::try_find_program_address(seeds_without_bump, program_id)
+	else {
+		log!(
+			"could not find program address from seeds with program id: {}",
+			program_id.as_ref()
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidSeeds);
+	};
+
+	if provided_bump != bump {
+		log!(
+			"bump: {} is not canonical, expected bump: {}",
+			provided_bump,
+			bump
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidSeeds);
+	}
+
+	Ok(())
+}
+
+/// Computes the 8-byte discriminator Anchor writes at the start of an account
+/// named `account_name`.
+///
+/// Anchor uses the first eight bytes of `sha256("account:<Name>")`. This is a
+/// `const fn`, so programs can compute the value once at compile time:
+///
+/// ```
+/// use pina::anchor_account_discriminator;
+///
+/// const VAULT_DISCRIMINATOR: [u8; 8] = anchor_account_discriminator("Vault");
+/// assert_eq!(VAULT_DISCRIMINATOR, anchor_account_discriminator("Vault"));
+/// ```
+pub const fn anchor_account_discriminator(account_name: &str) -> [u8; 8] {
+	anchor_sighash(b"account:", account_name)
+}
+
+/// Computes the 8-byte discriminator Anchor expects at the start of the
+/// instruction data for the method named `method`.
+///
+/// Anchor uses the first eight bytes of `sha256("global:<method>")`, where
+/// `method` is the snake case name of the handler. Bind the result to a
+/// `const` so the hash is computed at compile time:
+///
+/// ```
+/// use pina::anchor_instruction_discriminator;
+///
+/// const INITIALIZE: [u8; 8] = anchor_instruction_discriminator("initialize");
+/// assert_eq!(INITIALIZE, [175, 175, 109, 31, 13, 152, 155, 237]);
+/// ```
+pub const fn anchor_instruction_discriminator(method: &str) -> [u8; 8] {
+	anchor_sighash(b"global:", method)
+}
+
+/// Writes Anchor instruction data, the method `discriminator` followed by the
+/// borsh-encoded `args`, into `buffer` and returns the written prefix.
+///
+/// Pass the result to [`CpiContext::invoke`](crate::CpiContext::invoke) to
+/// call an Anchor program.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` when `buffer` is shorter than `8 + args.len()`.
+///
+/// # Examples
+///
+/// ```ignore
+/// const DEPOSIT: [u8; 8] = anchor_instruction_discriminator("deposit");
+///
+/// let mut buffer = [0u8; 16];
+/// let data = anchor_instruction_data(DEPOSIT, &amount.to_le_bytes(), &mut buffer)?;
+/// CpiContext::new(self.vault_program.address(), accounts).invoke(data, &[])?;
+/// ```
+#[track_caller]
+pub fn anchor_instruction_data<'b>(
+	discriminator: [u8; 8],
+	args: &[u8],
+	buffer: &'b mut [u8],
+) -> Result<&'b [u8], ProgramError> {
+	let len = discriminator.len() + args.len();
+
+	if buffer.len() < len {
+		log!(
+			"instruction buffer of {} bytes cannot hold {} bytes of anchor data",
+			buffer.len(),
+			len
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidArgument);
+	}
+
+	let data = &mut buffer[..len];
+	let (prefix, rest) = data.split_at_mut(discriminator.len());
+	prefix.copy_from_slice(&discriminator);
+	rest.copy_from_slice(args);
+
+	Ok(data)
+}
+
+/// The first eight bytes of `sha256("<namespace><name>")`.
+const fn anchor_sighash(namespace: &[u8], name: &str) -> [u8; 8] {
+	let hash = sha2_const_stable::Sha256::new()
+		.update(namespace)
+		.update(name.as_bytes())
+		.finalize();
+	let mut discriminator = [0u8; 8];
+	let mut index = 0;
+
+	while index < discriminator.len() {
+		discriminator[index] = hash[index];
+		index += 1;
+	}
+
+	discriminator
+}
+
+/// Returns `true` when `data` starts with the Anchor discriminator for the
+/// account named `account_name`.
+///
+/// Use this before reading an account written by an Anchor program. Data
+/// shorter than eight bytes never matches.
+///
+/// # Examples
+///
+/// ```ignore
+/// let data = self.anchor_vault.try_borrow()?;
+/// if !matches_anchor_discriminator(&data, "Vault") {
+/// 	return Err(ProgramError::InvalidAccountData);
+/// }
+/// ```
+pub fn matches_anchor_discriminator(data: &[u8], account_name: &str) -> bool {
+	data.starts_with(&anchor_account_discriminator(account_name))
+}
+
+/// The account type matched by [`load_one_of!`](crate::load_one_of).
+///
+/// Each variant holds the loaded account for the type at the same position in
+/// the macro's type list. Unused trailing variants default to the uninhabited
+/// [`Infallible`](core::convert::Infallible), so a `match` only needs arms for
+/// the types that were listed.
+#[derive(Debug)]
+pub enum OneOf<A, B, C = core::convert::Infallible, D = core::convert::Infallible> {
+	/// The account matched the first listed type.
+	First(A),
+	/// The account matched the second listed type.
+	Second(B),
+	/// The account matched the third listed type.
+	Third(C),
+	/// The account matched the fourth listed type.
+	Fourth(D),
+}
+
+/// The error for an account that holds none of the account types accepted by
+/// [`load_one_of!`](crate::load_one_of) or `#[derive(AccountUnion)]`.
+///
+/// Both try each type with [`AsAccount::try_as_account`]
+/// and end here when none matched, so they log and fail the same way.
+#[doc(hidden)]
+#[track_caller]
+pub fn account_type_mismatch(account: &AccountView) -> ProgramError {
+	fail_with_account(
+		account,
+		ProgramError::InvalidAccountData,
+		"discriminator matches none of the expected account types",
+	)
+}
+
+/// Emits `event` into the transaction logs with `sol_log_data`.
+///
+/// The logged bytes are the whole Pod event, discriminator first, so clients
+/// can read them back from the `Program data: <base64>` line with
+/// `EventLog::from_log`, available with the `client` feature. Off-chain the
+/// call does nothing, which keeps processors that emit events testable on the
+/// host.
+///
+/// Prefer the [`emit!`](crate::emit) macro inside processors.
+pub fn emit_event<T: HasDiscriminator + Pod>(event: &T) {
+	log_data(&event_log_data(event));
+}
+
+/// The byte slices [`emit_event`] hands to `sol_log_data`.
+fn event_log_data<T: HasDiscriminator + Pod>(event: &T) -> [&[u8]; 1] {
+	[bytemuck::bytes_of(event)]
+}
+
+/// Calls the `sol_log_data` syscall, which does not depend on the `logs`
+/// feature's logger.
+#[allow(unsafe_code)]
+#[inline(always)]
+fn log_data(data: &[&[u8]]) {
+	// SAFETY: `sol_log_data` reads `data.len()` (pointer, length) pairs, which
+	// is the layout of `&[u8]` on the SBF target.
+	#[cfg(any(target_os = "solana", target_arch = "bpf"))]
+	unsafe {
+		pinocchio::syscalls::sol_log_data(data.as_ptr().cast(), data.len() as u64);
+	}
+
+	#[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
+	let _ = data;
+}
+
+/// A relationship between two instruction-supplied amounts, checked with
+/// [`assert_relation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+	/// Both `a` and `b` are greater than zero.
+	BothNonZero,
+	/// `a == b`.
+	Equal,
+	/// `a != b`.
+	NotEqual,
+	/// `a < b`.
+	ALessThanB,
+	/// `a <= b`.
+	ALessThanOrEqualB,
+	/// `a > b`.
+	AGreaterThanB,
+	/// `a >= b`.
+	AGreaterThanOrEqualB,
+}
+
+impl Relation {
+	/// Returns `true` when `a` and `b` satisfy this relation.
+	#[must_use]
+	pub const fn holds(self, a: u64, b: u64) -> bool {
+		match self {
+			Self::BothNonZero => a > 0 && b > 0,
+			Self::Equal => a == b,
+			Self::NotEqual => a != b,
+			Self::ALessThanB => a < b,
+			Self::ALessThanOrEqualB => a <= b,
+			Self::AGreaterThanB => a > b,
+			Self::AGreaterThanOrEqualB => a >= b,
+		}
+	}
+}
+
+/// Asserts that two instruction-supplied amounts satisfy `relation`, such as
+/// requiring both sides of a swap to be non-zero.
+///
+/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+///
+/// They return `ProgramError` values for caller-side propagation with `?`.
+///
+/// No panics needed.<!-- {/pinaPublicResultContract} -->
+///
+/// # Errors
+///
+/// Returns `InvalidInstructionData` when the relation does not hold.
+///
+/// # Examples
+///
+/// ```
+/// use pina::ProgramError;
+/// use pina::Relation;
+/// use pina::assert_relation;
+///
+/// assert_eq!(assert_relation(10, 20, Relation::ALessThanB), Ok(()));
+/// assert_eq!(
+/// 	assert_relation(0, 20, Relation::BothNonZero),
+/// 	Err(ProgramError::InvalidInstructionData)
+/// );
+/// ```
+#[track_caller]
+pub fn assert_relation(a: u64, b: u64, relation: Relation) -> ProgramResult {
+	if !relation.holds(a, b) {
+		log!(
+			"Amounts {} and {} do not satisfy the required relation",
+			a,
+			b
+		);
+		log_caller();
+
+		return Err(ProgramError::InvalidInstructionData);
+	}
+
+	Ok(())
+}
+
+/// A snapshot of the total lamports held by a set of accounts, used to check
+/// that an instruction only moved lamports between them.
+///
+/// Take the snapshot before a sequence of lamport operations, such as the
+/// transfers and close in an escrow `Take`, and call
+/// [`LamportConservation::verify`] at the end. Any lamports that left the set
+/// other than the expected outflow, through a skim or an arithmetic slip,
+/// fail the check. The totals are summed as `u128`, so they cannot overflow.
+///
+/// The guard copies the account views, so the accounts remain free to be
+/// borrowed mutably while it is alive. An account listed twice is counted
+/// twice in both totals.
+///
+/// # Examples
+///
+/// ```ignore
+/// let conservation = LamportConservation::new([self.escrow, self.maker, self.taker]);
+///
+/// self.escrow.send(offer.amount, self.taker)?;
+/// self.escrow.close_with_recipient(self.maker)?;
+///
+/// conservation.verify(0)?;
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct LamportConservation<const N: usize> {
+	accounts: [AccountView; N],
+	total: u128,
+}
+
+impl<const N: usize> LamportConservation<N> {
+	/// Snapshot the total lamports currently held by `accounts`.
+	pub fn new(accounts: [&AccountView; N]) -> Self {
+		let accounts = accounts.map(|account| *account);
+
+		Self {
+			total: total_lamports(&accounts),
+			accounts,
+		}
+	}
+
+	/// The total lamports recorded when the snapshot was taken.
+	pub const fn total(&self) -> u128 {
+		self.total
+	}
+
+	/// Assert that the accounts now hold exactly the snapshot total minus
+	/// `expected_outflow`, the lamports the instruction deliberately sent to
+	/// accounts outside the set.
+	///
+	/// <!-- {=pinaPublicResultContract|trim|linePrefix:"/// ":true} -->/// All APIs in this section are designed for on-chain determinism.
+	///
+	/// They return `ProgramError` values for caller-side propagation with `?`.
+	///
+	/// No panics needed.<!-- {/pinaPublicResultContract} -->
+	///
+	/// # Errors
+	///
+	/// Returns [`PinaProgramError::LamportsNotConserved`] when more or fewer
+	/// lamports left the set than `expected_outflow`.
+	#[track_caller]
+	pub fn verify(&self, expected_outflow: u64) -> ProgramResult {
+		let current = total_lamports(&self.accounts);
+
+		if current + u128::from(expected_outflow) != self.total {
+			log!(
+				"lamports not conserved: {} before, {} after, {} expected outflow",
+				saturating_u64(self.total),
+				saturating_u64(current),
+				expected_outflow
+			);
+			log_caller();
+
+			return Err(crate::PinaProgramError::LamportsNotConserved.into());
+		}
+
+		Ok(())
+	}
+}
+
+/// The on-chain logger has no `u128` support; lamport totals fit in a `u64`
+/// in practice, since they cannot exceed the supply.
+#[cfg(feature = "logs")]
+fn saturating_u64(value: u128) -> u64 {
+	u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+fn total_lamports(accounts: &[AccountView]) -> u128 {
+	accounts
+		.iter()
+		.map(|account| u128::from(account.lamports()))
+		.sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[repr(C)]
+	#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+	struct Tagged {
+		discriminator: u8,
+		value: u8,
+	}
+
+	impl HasDiscriminator for Tagged {
+		type Type = u8;
+
+		const VALUE: u8 = 7;
+	}
+
+	#[test]
+	fn event_log_data_is_the_discriminator_then_the_event() {
+		let event = Tagged {
+			discriminator: Tagged::VALUE,
+			value: 42,
+		};
+		let data = event_log_data(&event);
+
+		assert_eq!(data, [bytemuck::bytes_of(&event)]);
+		assert_eq!(data[0], [Tagged::VALUE, 42]);
+		assert!(Tagged::matches_discriminator(data[0]));
+
+		emit_event(&event);
+	}
+
+	#[cfg(feature = "logs")]
+	#[test]
+	fn account_failure_log_names_address_and_context() {
+		let address = Address::new_from_array([7u8; 32]);
+		let logger = account_failure_log(&address, "fee above max");
+		let message = core::str::from_utf8(&logger)
+			.unwrap_or_else(|e| panic!("log line should be utf-8: {e}"));
+
+		assert!(message.starts_with("address: [7, 7, 7"));
+		assert!(message.ends_with(" failed: fee above max"));
+	}
+}
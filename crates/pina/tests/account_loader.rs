@@ -0,0 +1,189 @@
+#![allow(unsafe_code)]
+
+use pina::*;
+use pinocchio::account::NOT_BORROWED;
+use pinocchio::account::RuntimeAccount;
+
+const PROGRAM_ID: Address = Address::new_from_array([9u8; 32]);
+
+#[discriminator]
+pub enum GameAccount {
+	Board = 1,
+}
+
+#[account(discriminator = GameAccount, zero_copy)]
+pub struct Board {
+	pub turn: PodU64,
+	pub cells: [u8; 16_384],
+}
+
+const ORACLE_ID: Address = Address::new_from_array([7u8; 32]);
+
+#[account(external, owner = ORACLE_ID)]
+pub struct PriceFeed {
+	pub price: PodI64,
+	pub published_slot: PodU64,
+}
+
+#[repr(C)]
+struct TestAccount<const N: usize> {
+	header: RuntimeAccount,
+	data: [u8; N],
+}
+
+impl<const N: usize> TestAccount<N> {
+	fn new(owner: Address, is_writable: bool) -> Self {
+		Self {
+			header: RuntimeAccount {
+				borrow_state: NOT_BORROWED,
+				is_signer: 0,
+				is_writable: u8::from(is_writable),
+				executable: 0,
+				padding: [0; 4],
+				address: Address::new_from_array([1u8; 32]),
+				owner,
+				lamports: 1,
+				data_len: N as u64,
+			},
+			data: [0u8; N],
+		}
+	}
+
+	fn view(&mut self) -> AccountView {
+		unsafe { AccountView::new_unchecked(core::ptr::addr_of_mut!(self.header)) }
+	}
+}
+
+#[test]
+fn load_init_writes_the_discriminator_in_place() {
+	let mut account = TestAccount::<{ Board::LEN }>::new(PROGRAM_ID, true);
+	let mut view = account.view();
+	let mut board = AccountLoader::<Board>::try_new(&mut view, &PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("board should load: {e:?}"));
+
+	assert_eq!(board.load().err(), Some(ProgramError::InvalidAccountData));
+
+	{
+		let mut state = board
+			.load_init()
+			.unwrap_or_else(|e| panic!("init should succeed: {e:?}"));
+		state.turn = PodU64::from_primitive(1);
+		state.cells[16_383] = 7;
+	}
+
+	let state = board
+		.load()
+		.unwrap_or_else(|e| panic!("board should be initialized: {e:?}"));
+
+	assert_eq!(state.turn, PodU64::from_primitive(1));
+	assert_eq!(state.cells[16_383], 7);
+	drop(state);
+	assert_eq!(account.data[0], GameAccount::Board as u8);
+}
+
+#[test]
+fn load_init_rejects_initialized_accounts() {
+	let mut account = TestAccount::<{ Board::LEN }>::new(PROGRAM_ID, true);
+	account.data[0] = GameAccount::Board as u8;
+	let mut view = account.view();
+	let mut board = AccountLoader::<Board>::try_new(&mut view, &PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("board should load: {e:?}"));
+
+	assert_eq!(
+		board.load_init().err(),
+		Some(ProgramError::AccountAlreadyInitialized)
+	);
+	assert!(board.load_mut().is_ok());
+}
+
+#[test]
+fn mutable_loads_require_a_writable_account() {
+	let mut account = TestAccount::<{ Board::LEN }>::new(PROGRAM_ID, false);
+	account.data[0] = GameAccount::Board as u8;
+	let mut view = account.view();
+	let mut board = AccountLoader::<Board>::try_new(&mut view, &PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("board should load: {e:?}"));
+
+	assert!(board.load().is_ok());
+	assert_eq!(
+		board.load_mut().err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		board.load_init().err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn try_new_checks_owner_and_size() {
+	let mut foreign = TestAccount::<{ Board::LEN }>::new(Address::new_from_array([2u8; 32]), true);
+	let mut short = TestAccount::<{ Board::LEN - 1 }>::new(PROGRAM_ID, true);
+	let (mut foreign, mut short) = (foreign.view(), short.view());
+
+	assert_eq!(
+		AccountLoader::<Board>::try_new(&mut foreign, &PROGRAM_ID).err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(
+		AccountLoader::<Board>::try_new(&mut short, &PROGRAM_ID).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn external_accounts_map_the_leading_bytes() {
+	// The oracle reserves space past the fields this program reads.
+	let mut account = TestAccount::<{ PriceFeed::LEN + 32 }>::new(ORACLE_ID, false);
+	account.data[..8].copy_from_slice(&(-42i64).to_le_bytes());
+	account.data[8..16].copy_from_slice(&7u64.to_le_bytes());
+	let view = account.view();
+
+	let feed = view
+		.as_external_account::<PriceFeed>()
+		.unwrap_or_else(|e| panic!("feed should load: {e:?}"));
+
+	assert_eq!(feed.price, PodI64::from_primitive(-42));
+	assert_eq!(feed.published_slot, PodU64::from_primitive(7));
+}
+
+#[test]
+fn external_accounts_check_owner_and_size() {
+	let mut foreign = TestAccount::<{ PriceFeed::LEN }>::new(PROGRAM_ID, false);
+	let mut short = TestAccount::<{ PriceFeed::LEN - 1 }>::new(ORACLE_ID, false);
+	let (foreign, short) = (foreign.view(), short.view());
+
+	assert_eq!(
+		foreign.as_external_account::<PriceFeed>().err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(
+		short.as_external_account::<PriceFeed>().err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn external_accounts_accept_an_explicit_owner() {
+	// The same layout deployed by a fork of the oracle.
+	let fork_id = Address::new_from_array([3u8; 32]);
+	let mut account = TestAccount::<{ PriceFeed::LEN }>::new(fork_id, false);
+	account.data[8..16].copy_from_slice(&9u64.to_le_bytes());
+	let view = account.view();
+
+	let feed = view
+		.as_external_account_with_owner::<PriceFeed>(&fork_id)
+		.unwrap_or_else(|e| panic!("feed should load: {e:?}"));
+	assert_eq!(feed.published_slot, PodU64::from_primitive(9));
+	drop(feed);
+
+	assert_eq!(
+		view.as_external_account_with_owner::<PriceFeed>(&ORACLE_ID)
+			.err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(
+		view.as_external_account::<PriceFeed>().err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+}
@@ -5,6 +5,10 @@ use pina::*;
 #[discriminator(crate = ::pina, primitive = u8, final)]
 pub enum MyAccount {
 	ConfigState = 0,
+	FeeState = 1,
+	SignatureState = 2,
+	TaskState = 3,
+	VaultState = 4,
 }
 
 #[account(crate = ::pina, discriminator = MyAccount)]
@@ -18,6 +22,92 @@ pub struct ConfigState {
 	pub bump: u8,
 }
 
+const MAX_FEE_BPS: u16 = 1_000;
+
+#[account(crate = ::pina, discriminator = MyAccount)]
+#[derive(Debug)]
+pub struct FeeState {
+	/// The fee charged in basis points.
+	#[validate(u16::from(value) <= MAX_FEE_BPS)]
+	pub fee_bps: PodU16,
+	/// Unvalidated fields don't get a setter.
+	pub bump: u8,
+}
+
+/// Arrays longer than 32 elements historically lacked std trait impls, so this
+/// keeps the macro honest for signature-sized and larger buffers.
+#[account(crate = ::pina, discriminator = MyAccount)]
+#[derive(Debug)]
+pub struct SignatureState {
+	pub signer: Address,
+	pub signature: [u8; 64],
+	pub buffer: [u8; 128],
+}
+
+/// Only `owner` has to be set when building; the rest start from a default.
+#[account(crate = ::pina, discriminator = MyAccount)]
+#[derive(Debug)]
+pub struct TaskState {
+	pub owner: Address,
+	#[default(PodU64::from_primitive(0))]
+	pub count: PodU64,
+	#[default(PodBool::from(false))]
+	pub completed: PodBool,
+	#[default]
+	pub bump: u8,
+	#[default(PodU16::from_primitive(10))]
+	pub priority: PodU16,
+}
+
+const VAULT_SEED_VERSION: &[u8] = b"v1";
+
+#[account(
+	crate = ::pina,
+	discriminator = MyAccount,
+	seeds = ["vault", VAULT_SEED_VERSION, owner]
+)]
+#[derive(Debug)]
+pub struct VaultState {
+	pub owner: Address,
+	#[bump]
+	pub bump: u8,
+}
+
+#[discriminator(crate = ::pina, primitive = u8, final)]
+pub enum CounterAccount {
+	CounterStateV1 = 0,
+	CounterState = 1,
+}
+
+#[account(crate = ::pina, discriminator = CounterAccount)]
+#[derive(Debug)]
+pub struct CounterStateV1 {
+	pub count: u8,
+}
+
+/// The second layout widens the count and records its version.
+#[account(
+	crate = ::pina,
+	discriminator = CounterAccount,
+	version = 2,
+	migrate_from = CounterStateV1
+)]
+#[derive(Debug)]
+pub struct CounterState {
+	pub version: u8,
+	pub count: PodU64,
+}
+
+impl From<&CounterStateV1> for CounterState {
+	fn from(previous: &CounterStateV1) -> Self {
+		// The builder already writes the discriminator and version, so
+		// zeroed bytes are used to check that `migrate` stamps them itself.
+		let mut next: Self = Zeroable::zeroed();
+		next.count = PodU64::from_primitive(previous.count.into());
+		next
+	}
+}
+
 #[test]
 fn test_account_macro() {
 	let authority = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
@@ -38,6 +128,57 @@ fn test_account_macro() {
 	assert_eq!(config_state.discriminator, expected_discriminator);
 }
 
+#[test]
+fn test_account_len_is_the_packed_size() {
+	// Discriminator, version, authority and bump.
+	assert_eq!(ConfigState::LEN, 1 + 1 + 32 + 1);
+	assert_eq!(SignatureState::LEN, 1 + 32 + 64 + 128);
+	assert_eq!(TaskState::LEN, size_of::<TaskState>());
+
+	let config_state = ConfigState::builder()
+		.version(1)
+		.authority(Address::default())
+		.bump(1)
+		.build();
+	assert_eq!(config_state.to_bytes().len(), ConfigState::LEN);
+}
+
+#[test]
+fn test_account_minimum_balance_covers_len() {
+	let rent = sysvars::rent::Rent::from_bytes(&6_960u64.to_le_bytes())
+		.unwrap_or_else(|e| panic!("rent bytes are valid: {e:?}"));
+
+	// Rent exemption covers the 128 byte storage overhead plus `LEN` bytes.
+	assert_eq!(
+		ConfigState::minimum_balance(&rent),
+		Ok((128 + ConfigState::LEN as u64) * 6_960)
+	);
+	assert_eq!(
+		SignatureState::minimum_balance(&rent),
+		rent.try_minimum_balance(SignatureState::LEN)
+	);
+}
+
+#[test]
+fn test_account_version_defaults_in_builder() {
+	let counter = CounterState::builder().count_u64(3).build();
+
+	assert_eq!(CounterState::VERSION, 2);
+	assert_eq!(counter.version, CounterState::VERSION);
+}
+
+#[test]
+fn test_account_migrate_stamps_discriminator_and_version() {
+	let previous = CounterStateV1::builder().count(7).build();
+	let next = CounterState::migrate(&previous);
+
+	assert_eq!(<CounterState as Migrate>::VERSION, 2);
+	assert_eq!(next.version, 2);
+	assert_eq!(next.count, PodU64::from_primitive(7));
+	assert!(CounterState::try_from_bytes(next.to_bytes()).is_ok());
+	assert!(CounterStateV1::try_from_bytes(&next.to_bytes()[..CounterStateV1::LEN]).is_err());
+}
+
 #[test]
 fn test_account_assert_returns_ok_when_condition_true() {
 	let authority = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
@@ -130,3 +271,119 @@ fn test_account_assert_msg_returns_err_when_condition_false() {
 	assert!(result.is_err());
 	assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
 }
+
+#[test]
+fn test_account_validate_setter_accepts_valid_value() {
+	let mut fee_state = FeeState::builder()
+		.fee_bps(PodU16::from(30))
+		.bump(255)
+		.build();
+
+	let result = fee_state.set_fee_bps(PodU16::from(MAX_FEE_BPS));
+
+	assert!(result.is_ok());
+	assert_eq!(u16::from(fee_state.fee_bps), MAX_FEE_BPS);
+}
+
+#[test]
+fn test_account_validate_setter_rejects_fee_above_max() {
+	let mut fee_state = FeeState::builder()
+		.fee_bps(PodU16::from(30))
+		.bump(255)
+		.build();
+
+	let result = fee_state.set_fee_bps(PodU16::from(MAX_FEE_BPS + 1));
+
+	assert_eq!(result, Err(ProgramError::InvalidAccountData));
+	assert_eq!(u16::from(fee_state.fee_bps), 30);
+}
+
+#[test]
+fn test_account_large_array_fields_round_trip() {
+	let signer = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let mut signature = [0u8; 64];
+	for (index, byte) in signature.iter_mut().enumerate() {
+		*byte = u8::try_from(index).unwrap_or_else(|e| panic!("index fits in u8: {e}"));
+	}
+
+	let state = SignatureState::builder()
+		.signer(signer)
+		.signature(signature)
+		.buffer([0xAB; 128])
+		.build();
+
+	let bytes = state.to_bytes();
+	assert_eq!(bytes.len(), MyAccount::BYTES + 32 + 64 + 128);
+
+	let decoded = SignatureState::try_from_bytes(bytes)
+		.unwrap_or_else(|e| panic!("signature state should decode: {e:?}"));
+	assert_eq!(decoded, &state);
+	assert_eq!(decoded.signature, signature);
+	assert_eq!(decoded.buffer, [0xAB; 128]);
+
+	let mut zeroed = state;
+	zeroed.zeroed();
+	assert_eq!(zeroed.signature, [0u8; 64]);
+}
+
+#[test]
+fn test_account_eq_state_ignores_discriminator() {
+	let authority = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let config_state = ConfigState::builder()
+		.version(1)
+		.authority(authority)
+		.bump(255)
+		.build();
+	let mut relabeled = config_state;
+	relabeled.discriminator = [MyAccount::FeeState as u8];
+
+	assert_ne!(config_state, relabeled);
+	assert!(config_state.eq_state(&relabeled));
+
+	relabeled.bump = 254;
+	assert!(!config_state.eq_state(&relabeled));
+}
+
+#[test]
+fn test_account_default_fields_can_be_skipped_in_builder() {
+	let owner = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let task = TaskState::builder().owner(owner).build();
+
+	assert_eq!(task.owner, owner);
+	assert_eq!(task.count, PodU64::from_primitive(0));
+	assert_eq!(task.completed, PodBool::from(false));
+	assert_eq!(task.bump, 0);
+	assert_eq!(task.priority, PodU16::from_primitive(10));
+	assert_eq!(task.discriminator, [MyAccount::TaskState as u8]);
+
+	let overridden = TaskState::builder()
+		.owner(owner)
+		.count_u64(3)
+		.priority(PodU16::from_primitive(1))
+		.build();
+
+	assert_eq!(overridden.count, PodU64::from_primitive(3));
+	assert_eq!(overridden.priority, PodU16::from_primitive(1));
+	assert_eq!(overridden.completed, PodBool::from(false));
+}
+
+#[test]
+fn test_account_seeds_helpers_match_declared_schema() {
+	let owner = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let program_id = Address::new_from_array([7u8; 32]);
+
+	assert_eq!(
+		VaultState::seeds(owner.as_ref()),
+		[b"vault".as_slice(), b"v1", owner.as_ref()]
+	);
+
+	let (address, bump) = VaultState::find_address(owner.as_ref(), &program_id)
+		.unwrap_or_else(|| panic!("a PDA should exist for the vault seeds"));
+	let expected = try_find_program_address(&[b"vault", b"v1", owner.as_ref()], &program_id);
+	assert_eq!(Some((address, bump)), expected);
+
+	let bump_seed = [bump];
+	let with_bump = VaultState::seeds_with_bump(owner.as_ref(), &bump_seed);
+	assert_eq!(with_bump[3], [bump]);
+	assert_eq!(create_program_address(&with_bump, &program_id), Ok(address));
+}
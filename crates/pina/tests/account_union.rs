@@ -0,0 +1,121 @@
+#![allow(unsafe_code)]
+
+use pina::*;
+use pinocchio::account::NOT_BORROWED;
+use pinocchio::account::RuntimeAccount;
+
+const PROGRAM_ID: Address = Address::new_from_array([9u8; 32]);
+
+#[discriminator]
+pub enum AppAccount {
+	UserProfile = 1,
+	AdminConfig = 2,
+	Ledger = 3,
+}
+
+#[account(discriminator = AppAccount)]
+pub struct UserProfile {
+	pub owner: Address,
+}
+
+#[account(discriminator = AppAccount)]
+pub struct AdminConfig {
+	pub admin: Address,
+	pub fee_bps: PodU16,
+}
+
+#[account(discriminator = AppAccount)]
+pub struct Ledger {
+	pub entries: PodU64,
+}
+
+#[derive(AccountUnion)]
+#[pina(crate = pina)]
+pub enum AppState<'a> {
+	UserProfile(Ref<'a, UserProfile>),
+	AdminConfig(Ref<'a, AdminConfig>),
+}
+
+#[repr(C)]
+struct TestAccount<const N: usize> {
+	header: RuntimeAccount,
+	data: [u8; N],
+}
+
+impl<const N: usize> TestAccount<N> {
+	fn new(owner: Address, data: &[u8]) -> Self {
+		let mut account = Self {
+			header: RuntimeAccount {
+				borrow_state: NOT_BORROWED,
+				is_signer: 0,
+				is_writable: 0,
+				executable: 0,
+				padding: [0; 4],
+				address: Address::new_from_array([1u8; 32]),
+				owner,
+				lamports: 1,
+				data_len: N as u64,
+			},
+			data: [0u8; N],
+		};
+		account.data.copy_from_slice(data);
+		account
+	}
+
+	fn view(&mut self) -> AccountView {
+		unsafe { AccountView::new_unchecked(core::ptr::addr_of_mut!(self.header)) }
+	}
+}
+
+#[test]
+fn load_dispatches_on_the_stored_discriminator() {
+	let profile = UserProfile::builder()
+		.owner(Address::new_from_array([2u8; 32]))
+		.build();
+	let config = AdminConfig::builder()
+		.admin(Address::new_from_array([3u8; 32]))
+		.fee_bps(PodU16::from_primitive(30))
+		.build();
+	let mut profile =
+		TestAccount::<{ UserProfile::LEN }>::new(PROGRAM_ID, bytemuck::bytes_of(&profile));
+	let mut config =
+		TestAccount::<{ AdminConfig::LEN }>::new(PROGRAM_ID, bytemuck::bytes_of(&config));
+	let (profile, config) = (profile.view(), config.view());
+
+	match AppState::load(&profile, &PROGRAM_ID) {
+		Ok(AppState::UserProfile(state)) => {
+			assert_eq!(state.owner, Address::new_from_array([2u8; 32]));
+		}
+		Ok(AppState::AdminConfig(_)) => panic!("profile loaded as a config"),
+		Err(e) => panic!("profile should load: {e:?}"),
+	}
+
+	match AppState::load(&config, &PROGRAM_ID) {
+		Ok(AppState::AdminConfig(state)) => {
+			assert_eq!(state.fee_bps, PodU16::from_primitive(30));
+		}
+		Ok(AppState::UserProfile(_)) => panic!("config loaded as a profile"),
+		Err(e) => panic!("config should load: {e:?}"),
+	}
+}
+
+#[test]
+fn load_rejects_other_types_and_owners() {
+	let ledger = Ledger::builder().entries(PodU64::from_primitive(4)).build();
+	let profile = UserProfile::builder().owner(Address::default()).build();
+	let mut ledger = TestAccount::<{ Ledger::LEN }>::new(PROGRAM_ID, bytemuck::bytes_of(&ledger));
+	let mut foreign = TestAccount::<{ UserProfile::LEN }>::new(
+		Address::new_from_array([8u8; 32]),
+		bytemuck::bytes_of(&profile),
+	);
+	let (ledger, foreign) = (ledger.view(), foreign.view());
+
+	assert!(matches!(
+		AppState::load(&ledger, &PROGRAM_ID),
+		Err(ProgramError::InvalidAccountData)
+	));
+	assert!(matches!(
+		AppState::load(&foreign, &PROGRAM_ID),
+		Err(ProgramError::InvalidAccountOwner)
+	));
+}
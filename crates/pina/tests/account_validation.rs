@@ -0,0 +1,1208 @@
+#![allow(unsafe_code)]
+
+use pina::AccountInfoValidation;
+use pina::Address;
+use pina::AsAccount;
+#[cfg(feature = "token")]
+use pina::AsTokenAccount;
+use pina::HasBump;
+use pina::IntoDiscriminator;
+use pina::OneOf;
+use pina::PinaProgramError;
+use pina::ProgramError;
+use pina::account;
+use pina::anchor_account_discriminator;
+use pina::anchor_instruction_data;
+use pina::anchor_instruction_discriminator;
+use pina::assert_account_count;
+use pina::assert_accounts_sorted;
+use pina::assert_all_owned_by;
+use pina::assert_canonical_bump;
+use pina::assert_consistent_duplicates;
+use pina::bytemuck;
+use pina::discriminator;
+use pina::fail_with_account;
+use pina::load_one_of;
+use pina::matches_anchor_discriminator;
+use pina::system;
+#[cfg(feature = "token")]
+use pina::token;
+#[cfg(feature = "token")]
+use pina::token_2022;
+#[cfg(feature = "token")]
+use pina::try_get_associated_token_address;
+use pinocchio::AccountView;
+use pinocchio::account::NOT_BORROWED;
+use pinocchio::account::RuntimeAccount;
+
+const PROGRAM_ID: Address = Address::new_from_array([7u8; 32]);
+
+#[repr(C)]
+struct TestAccount<const N: usize> {
+	header: RuntimeAccount,
+	data: [u8; N],
+}
+
+impl<const N: usize> TestAccount<N> {
+	fn new(address: Address, data: [u8; N]) -> Self {
+		Self {
+			header: RuntimeAccount {
+				borrow_state: NOT_BORROWED,
+				is_signer: 0,
+				is_writable: 1,
+				executable: 0,
+				padding: [0; 4],
+				address,
+				owner: PROGRAM_ID,
+				lamports: 1_000_000,
+				data_len: N as u64,
+			},
+			data,
+		}
+	}
+
+	fn with_owner(mut self, owner: Address) -> Self {
+		self.header.owner = owner;
+		self
+	}
+
+	fn readonly(mut self) -> Self {
+		self.header.is_writable = 0;
+		self
+	}
+
+	fn view(&mut self) -> AccountView {
+		unsafe { AccountView::new_unchecked(core::ptr::addr_of_mut!(self.header)) }
+	}
+}
+
+// ---------------------------------------------------------------------------
+// assert_data_prefix
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_data_prefix_accepts_matching_prefix() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), *b"pinav1\0\0");
+	let view = account.view();
+
+	let result = view.assert_data_prefix(b"pinav1");
+
+	assert!(result.is_ok(), "matching prefix should pass: {result:?}");
+}
+
+#[test]
+fn assert_data_prefix_rejects_mismatched_prefix() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), *b"pinav2\0\0");
+	let view = account.view();
+
+	let result = view.assert_data_prefix(b"pinav1");
+
+	assert_eq!(result.err(), Some(ProgramError::InvalidAccountData));
+}
+
+#[test]
+fn assert_data_prefix_rejects_short_data() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), *b"pin");
+	let view = account.view();
+
+	let result = view.assert_data_prefix(b"pinav1");
+
+	assert_eq!(result.err(), Some(PinaProgramError::DataTooShort.into()));
+}
+
+// ---------------------------------------------------------------------------
+// fail_with_account
+// ---------------------------------------------------------------------------
+
+#[test]
+fn fail_with_account_returns_the_given_error() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let view = account.view();
+
+	assert_eq!(
+		fail_with_account(&view, ProgramError::InvalidAccountData, "fee above max"),
+		ProgramError::InvalidAccountData
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_capacity_for
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_capacity_for_accepts_available_slot() {
+	// A 4-byte header followed by room for three `u32` elements.
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 16]);
+	let view = account.view();
+
+	assert!(view.assert_capacity_for::<u32>(4, 0).is_ok());
+	assert!(view.assert_capacity_for::<u32>(4, 2).is_ok());
+}
+
+#[test]
+fn assert_capacity_for_rejects_full_collection() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 16]);
+	let view = account.view();
+
+	assert_eq!(
+		view.assert_capacity_for::<u32>(4, 3).err(),
+		Some(ProgramError::AccountDataTooSmall)
+	);
+	assert_eq!(
+		view.assert_capacity_for::<u32>(4, usize::MAX).err(),
+		Some(ProgramError::AccountDataTooSmall)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// space_utilization_bps
+// ---------------------------------------------------------------------------
+
+#[test]
+fn space_utilization_bps_reports_half_full_account() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 64]);
+	let view = account.view();
+
+	assert_eq!(view.space_utilization_bps(32), 5_000);
+	assert_eq!(view.space_utilization_bps(1), 156);
+	assert_eq!(view.space_utilization_bps(0), 0);
+}
+
+#[test]
+fn space_utilization_bps_clamps_full_and_empty_accounts() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 64]);
+	let view = account.view();
+	let mut empty = TestAccount::new(Address::new_from_array([2u8; 32]), []);
+	let empty_view = empty.view();
+
+	assert_eq!(view.space_utilization_bps(64), 10_000);
+	assert_eq!(view.space_utilization_bps(usize::MAX), 10_000);
+	assert_eq!(empty_view.space_utilization_bps(0), 10_000);
+}
+
+// ---------------------------------------------------------------------------
+// assert_strictly_uninitialized
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_strictly_uninitialized_accepts_empty_system_account() {
+	let mut account =
+		TestAccount::new(Address::new_from_array([1u8; 32]), []).with_owner(system::ID);
+	let view = account.view();
+
+	let result = view.assert_strictly_uninitialized();
+
+	assert!(
+		result.is_ok(),
+		"empty system account should pass: {result:?}"
+	);
+}
+
+#[test]
+fn assert_strictly_uninitialized_rejects_program_owned_empty_account() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let view = account.view();
+
+	let result = view.assert_strictly_uninitialized();
+
+	assert_eq!(result.err(), Some(ProgramError::InvalidAccountOwner));
+}
+
+#[test]
+fn assert_strictly_uninitialized_rejects_system_account_with_data() {
+	let mut account =
+		TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 8]).with_owner(system::ID);
+	let view = account.view();
+
+	let result = view.assert_strictly_uninitialized();
+
+	assert_eq!(result.err(), Some(ProgramError::AccountAlreadyInitialized));
+}
+
+// ---------------------------------------------------------------------------
+// assert_consistent_duplicates
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_consistent_duplicates_accepts_matching_views() {
+	let address = Address::new_from_array([1u8; 32]);
+	let mut first = TestAccount::new(address, [0u8; 8]);
+	let mut second = TestAccount::new(address, [0u8; 8]);
+	let mut other = TestAccount::new(Address::new_from_array([2u8; 32]), [0u8; 8]).readonly();
+	let (first, second, other) = (first.view(), second.view(), other.view());
+
+	let result = assert_consistent_duplicates(&[&first, &other, &second]);
+
+	assert!(
+		result.is_ok(),
+		"matching duplicate views should pass: {result:?}"
+	);
+}
+
+#[test]
+fn assert_consistent_duplicates_rejects_mismatched_writability() {
+	let address = Address::new_from_array([1u8; 32]);
+	let mut writable = TestAccount::new(address, [0u8; 8]);
+	let mut readonly = TestAccount::new(address, [0u8; 8]).readonly();
+	let (writable, readonly) = (writable.view(), readonly.view());
+
+	let result = assert_consistent_duplicates(&[&readonly, &writable]);
+
+	assert_eq!(result.err(), Some(ProgramError::InvalidArgument));
+}
+
+// ---------------------------------------------------------------------------
+// assert_accounts_sorted
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_accounts_sorted_accepts_ascending_addresses() {
+	let mut first = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let mut second = TestAccount::new(Address::new_from_array([2u8; 32]), []);
+	let mut third = TestAccount::new(Address::new_from_array([3u8; 32]), []);
+	let (first, second, third) = (first.view(), second.view(), third.view());
+
+	assert_eq!(assert_accounts_sorted(&[&first, &second, &third]), Ok(()));
+}
+
+#[test]
+fn assert_accounts_sorted_rejects_unsorted_addresses() {
+	let mut first = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let mut second = TestAccount::new(Address::new_from_array([2u8; 32]), []);
+	let (first, second) = (first.view(), second.view());
+
+	assert_eq!(
+		assert_accounts_sorted(&[&second, &first]),
+		Err(ProgramError::InvalidArgument)
+	);
+}
+
+#[test]
+fn assert_accounts_sorted_rejects_duplicates() {
+	let address = Address::new_from_array([1u8; 32]);
+	let mut first = TestAccount::new(address, []);
+	let mut duplicate = TestAccount::new(address, []);
+	let (first, duplicate) = (first.view(), duplicate.view());
+
+	assert_eq!(
+		assert_accounts_sorted(&[&first, &duplicate]),
+		Err(ProgramError::InvalidArgument)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_all_owned_by
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_all_owned_by_accepts_uniform_owner() {
+	let mut first = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let mut second = TestAccount::new(Address::new_from_array([2u8; 32]), []);
+	let (first, second) = (first.view(), second.view());
+
+	assert_eq!(assert_all_owned_by(&[&first, &second], &PROGRAM_ID), Ok(()));
+}
+
+#[test]
+fn assert_all_owned_by_rejects_foreign_owner() {
+	let mut first = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let mut foreign = TestAccount::new(Address::new_from_array([2u8; 32]), [])
+		.with_owner(Address::new_from_array([9u8; 32]));
+	let (first, foreign) = (first.view(), foreign.view());
+
+	assert_eq!(
+		assert_all_owned_by(&[&first, &foreign], &PROGRAM_ID),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_account_count
+// ---------------------------------------------------------------------------
+
+fn account_count_result(count: usize) -> Result<(), ProgramError> {
+	let mut first = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let mut second = TestAccount::new(Address::new_from_array([2u8; 32]), []);
+	let mut third = TestAccount::new(Address::new_from_array([3u8; 32]), []);
+	let mut fourth = TestAccount::new(Address::new_from_array([4u8; 32]), []);
+	let accounts = [first.view(), second.view(), third.view(), fourth.view()];
+
+	assert_account_count(&accounts[..count], 2, 3)
+}
+
+#[test]
+fn assert_account_count_rejects_below_min() {
+	assert_eq!(
+		account_count_result(1),
+		Err(ProgramError::NotEnoughAccountKeys)
+	);
+}
+
+#[test]
+fn assert_account_count_accepts_counts_in_range() {
+	assert_eq!(account_count_result(2), Ok(()));
+	assert_eq!(account_count_result(3), Ok(()));
+}
+
+#[test]
+fn assert_account_count_rejects_above_max() {
+	assert_eq!(
+		account_count_result(4),
+		Err(PinaProgramError::TooManyAccountKeys.into())
+	);
+}
+
+// ---------------------------------------------------------------------------
+// matches_anchor_discriminator
+// ---------------------------------------------------------------------------
+
+/// `sha256("account:NewAccount")[..8]`, as written by Anchor.
+const ANCHOR_NEW_ACCOUNT_DISCRIMINATOR: [u8; 8] = [176, 95, 4, 118, 91, 177, 125, 232];
+
+#[test]
+fn anchor_account_discriminator_matches_anchor_sighash() {
+	assert_eq!(
+		anchor_account_discriminator("NewAccount"),
+		ANCHOR_NEW_ACCOUNT_DISCRIMINATOR
+	);
+}
+
+#[test]
+fn matches_anchor_discriminator_checks_leading_bytes() {
+	let mut data = [0u8; 16];
+	data[..8].copy_from_slice(&ANCHOR_NEW_ACCOUNT_DISCRIMINATOR);
+
+	assert!(matches_anchor_discriminator(&data, "NewAccount"));
+	assert!(!matches_anchor_discriminator(&data, "OtherAccount"));
+	assert!(!matches_anchor_discriminator(&data[..7], "NewAccount"));
+}
+
+/// `sha256("global:initialize")[..8]`, as expected by Anchor.
+const ANCHOR_INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+#[test]
+fn anchor_instruction_discriminator_matches_anchor_sighash() {
+	const INITIALIZE: [u8; 8] = anchor_instruction_discriminator("initialize");
+
+	assert_eq!(INITIALIZE, ANCHOR_INITIALIZE_DISCRIMINATOR);
+	assert_ne!(
+		anchor_instruction_discriminator("initialize"),
+		anchor_account_discriminator("initialize")
+	);
+}
+
+#[test]
+fn anchor_instruction_data_prepends_discriminator() {
+	let mut buffer = [0xFFu8; 20];
+	let data = anchor_instruction_data(
+		ANCHOR_INITIALIZE_DISCRIMINATOR,
+		&42u64.to_le_bytes(),
+		&mut buffer,
+	)
+	.unwrap_or_else(|e| panic!("buffer should fit: {e:?}"));
+
+	assert_eq!(data.len(), 16);
+	assert_eq!(data[..8], ANCHOR_INITIALIZE_DISCRIMINATOR);
+	assert_eq!(data[8..], 42u64.to_le_bytes());
+	assert_eq!(buffer[16..], [0xFF; 4]);
+
+	let mut short = [0u8; 15];
+	assert_eq!(
+		anchor_instruction_data(
+			ANCHOR_INITIALIZE_DISCRIMINATOR,
+			&42u64.to_le_bytes(),
+			&mut short
+		),
+		Err(ProgramError::InvalidArgument)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// invalidate_discriminator
+// ---------------------------------------------------------------------------
+
+#[discriminator]
+pub enum ValidationAccount {
+	Placeholder = 0,
+	Ticket = 1,
+	Receipt = 2,
+	Vault = 3,
+	GameTable = 4,
+	SeededVault = 5,
+}
+
+#[account(discriminator = ValidationAccount)]
+pub struct Placeholder {
+	pub value: u8,
+}
+
+#[account(discriminator = ValidationAccount)]
+pub struct Ticket {
+	pub holder: Address,
+}
+
+/// Same size as [`Ticket`] but a different account type.
+#[account(discriminator = ValidationAccount)]
+pub struct Receipt {
+	pub payer: Address,
+}
+
+const TICKET_LEN: usize = size_of::<Ticket>();
+
+fn ticket_account() -> TestAccount<TICKET_LEN> {
+	let ticket = Ticket::builder()
+		.holder(Address::new_from_array([5u8; 32]))
+		.build();
+	let mut data = [0u8; TICKET_LEN];
+	data.copy_from_slice(bytemuck::bytes_of(&ticket));
+
+	TestAccount::new(Address::new_from_array([1u8; 32]), data)
+}
+
+#[test]
+fn invalidate_discriminator_rejects_later_type_checks() {
+	let mut account = ticket_account();
+	let mut view = account.view();
+	assert!(view.assert_type::<Ticket>(&PROGRAM_ID).is_ok());
+
+	let result = view.invalidate_discriminator::<Ticket>(&PROGRAM_ID);
+
+	assert!(result.is_ok(), "invalidation should succeed: {result:?}");
+	assert_eq!(
+		view.assert_type::<Ticket>(&PROGRAM_ID).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	// The account itself is untouched apart from the discriminator.
+	assert_eq!(view.lamports(), 1_000_000);
+	assert_eq!(view.data_len(), TICKET_LEN);
+	assert_eq!(&account.data[1..], &[5u8; 32]);
+}
+
+#[test]
+fn invalidate_discriminator_rejects_zero_discriminator_types() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8, 9]);
+	let mut view = account.view();
+
+	let result = view.invalidate_discriminator::<Placeholder>(&PROGRAM_ID);
+
+	assert_eq!(result.err(), Some(ProgramError::InvalidArgument));
+}
+
+// ---------------------------------------------------------------------------
+// assert_type_or_empty
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_type_or_empty_reports_empty_accounts() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), []);
+	let view = account.view();
+
+	assert_eq!(view.assert_type_or_empty::<Ticket>(&PROGRAM_ID), Ok(true));
+}
+
+#[test]
+fn assert_type_or_empty_accepts_matching_type() {
+	let mut account = ticket_account();
+	let view = account.view();
+
+	assert_eq!(view.assert_type_or_empty::<Ticket>(&PROGRAM_ID), Ok(false));
+}
+
+#[test]
+fn assert_type_or_empty_rejects_foreign_type_or_owner() {
+	let mut account = ticket_account();
+	let view = account.view();
+
+	assert_eq!(
+		view.assert_type_or_empty::<Receipt>(&PROGRAM_ID),
+		Err(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		view.assert_type_or_empty::<Ticket>(&Address::new_from_array([8u8; 32])),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// load_account
+// ---------------------------------------------------------------------------
+
+#[test]
+fn load_account_returns_the_typed_state() {
+	let mut account = ticket_account();
+	let mut view = account.view();
+
+	let ticket = view
+		.load_account::<Ticket>(&PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("ticket should load: {e:?}"));
+	assert_eq!(ticket.holder, Address::new_from_array([5u8; 32]));
+	drop(ticket);
+
+	view.load_account_mut::<Ticket>(&PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("ticket should load mutably: {e:?}"))
+		.holder = Address::new_from_array([6u8; 32]);
+	assert_eq!(&account.data[1..], &[6u8; 32]);
+}
+
+#[test]
+fn load_account_matches_assert_type_errors() {
+	let mut account = ticket_account();
+	let mut long = TestAccount::new(Address::new_from_array([1u8; 32]), [1u8; TICKET_LEN + 1]);
+	let (mut view, long) = (account.view(), long.view());
+	let other_program = Address::new_from_array([8u8; 32]);
+
+	assert_eq!(
+		view.load_account::<Receipt>(&PROGRAM_ID).err(),
+		view.assert_type::<Receipt>(&PROGRAM_ID).err()
+	);
+	assert_eq!(
+		view.load_account_mut::<Receipt>(&PROGRAM_ID).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		view.load_account::<Ticket>(&other_program).err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(
+		long.load_account::<Ticket>(&PROGRAM_ID).err(),
+		Some(ProgramError::AccountDataTooSmall)
+	);
+}
+
+#[test]
+fn try_as_account_skips_other_account_types() {
+	let mut account = ticket_account();
+	let view = account.view();
+
+	let ticket = view
+		.try_as_account::<Ticket>(&PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("ticket should load: {e:?}"));
+	assert_eq!(
+		ticket.map(|ticket| ticket.holder),
+		Some(Address::new_from_array([5u8; 32]))
+	);
+	assert!(matches!(
+		view.try_as_account::<Receipt>(&PROGRAM_ID),
+		Ok(None)
+	));
+	assert!(matches!(
+		view.try_as_account::<Placeholder>(&PROGRAM_ID),
+		Ok(None)
+	));
+}
+
+#[test]
+fn try_as_account_rejects_wrong_owner_and_size() {
+	let mut account = ticket_account();
+	let mut long = TestAccount::new(Address::new_from_array([1u8; 32]), [1u8; TICKET_LEN + 1]);
+	let (view, long) = (account.view(), long.view());
+
+	assert_eq!(
+		view.try_as_account::<Ticket>(&Address::new_from_array([8u8; 32]))
+			.err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(
+		long.try_as_account::<Ticket>(&PROGRAM_ID).err(),
+		Some(ProgramError::AccountDataTooSmall)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// HasBump::assert_pda
+// ---------------------------------------------------------------------------
+
+#[account(discriminator = ValidationAccount)]
+pub struct Vault {
+	pub authority: Address,
+	#[bump]
+	pub bump: u8,
+}
+
+fn vault_pda(authority: &Address) -> (Address, u8) {
+	pina::try_find_program_address(&[b"vault", authority.as_ref()], &PROGRAM_ID)
+		.unwrap_or_else(|| panic!("failed to derive vault pda"))
+}
+
+#[test]
+fn assert_pda_accepts_stored_bump() {
+	let authority = Address::new_from_array([5u8; 32]);
+	let (address, bump) = vault_pda(&authority);
+	let vault = Vault::builder().authority(authority).bump(bump).build();
+	let mut account = TestAccount::new(address, []);
+	let view = account.view();
+
+	assert_eq!(vault.bump(), bump);
+	assert_eq!(
+		vault.assert_pda(&view, &[b"vault", authority.as_ref()], &PROGRAM_ID),
+		Ok(())
+	);
+}
+
+#[test]
+fn assert_pda_rejects_tampered_bump() {
+	let authority = Address::new_from_array([5u8; 32]);
+	let (address, bump) = vault_pda(&authority);
+	let vault = Vault::builder()
+		.authority(authority)
+		.bump(bump.wrapping_sub(1))
+		.build();
+	let mut account = TestAccount::new(address, []);
+	let view = account.view();
+
+	assert_eq!(
+		vault.assert_pda(&view, &[b"vault", authority.as_ref()], &PROGRAM_ID),
+		Err(ProgramError::InvalidSeeds)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_canonical_bump
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_canonical_bump_accepts_canonical_bump() {
+	let authority = Address::new_from_array([5u8; 32]);
+	let (_address, bump) = vault_pda(&authority);
+
+	assert_eq!(
+		assert_canonical_bump(&[b"vault", authority.as_ref()], bump, &PROGRAM_ID),
+		Ok(())
+	);
+}
+
+#[test]
+fn assert_canonical_bump_rejects_lower_valid_bump() {
+	let authority = Address::new_from_array([5u8; 32]);
+	let seeds: &[&[u8]] = &[b"vault", authority.as_ref()];
+	let (_address, bump) = vault_pda(&authority);
+	// Find a lower bump that still yields a valid, off-curve PDA.
+	let lower_bump = (0..bump)
+		.rev()
+		.find(|candidate| {
+			pina::create_program_address(&[seeds[0], seeds[1], &[*candidate]], &PROGRAM_ID).is_ok()
+		})
+		.unwrap_or_else(|| panic!("expected a non-canonical bump below {bump}"));
+
+	assert_eq!(
+		assert_canonical_bump(seeds, lower_bump, &PROGRAM_ID),
+		Err(ProgramError::InvalidSeeds)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// #[account(seeds = [...])]
+// ---------------------------------------------------------------------------
+
+#[account(discriminator = ValidationAccount, seeds = ["vault", authority])]
+pub struct SeededVault {
+	pub authority: Address,
+	#[bump]
+	pub bump: u8,
+}
+
+#[test]
+fn account_seeds_assert_canonical_pda_returns_canonical_bump() {
+	let authority = Address::new_from_array([5u8; 32]);
+	let (address, bump) = vault_pda(&authority);
+	let mut account = TestAccount::new(address, [0u8; 8]);
+	let view = account.view();
+
+	assert_eq!(
+		SeededVault::find_address(authority.as_ref(), &PROGRAM_ID),
+		Some((address, bump))
+	);
+	assert_eq!(
+		SeededVault::assert_canonical_pda(&view, authority.as_ref(), &PROGRAM_ID),
+		Ok(bump)
+	);
+	assert_eq!(
+		SeededVault::assert_canonical_pda(&view, PROGRAM_ID.as_ref(), &PROGRAM_ID),
+		Err(ProgramError::InvalidSeeds)
+	);
+}
+
+#[test]
+fn account_seeds_keep_has_bump_assert_pda_reachable() {
+	let authority = Address::new_from_array([5u8; 32]);
+	let (address, bump) = vault_pda(&authority);
+	let vault = SeededVault::builder()
+		.authority(authority)
+		.bump(bump)
+		.build();
+	let mut account = TestAccount::new(address, []);
+	let view = account.view();
+
+	assert_eq!(
+		SeededVault::assert_pda(
+			&vault,
+			&view,
+			&SeededVault::seeds(authority.as_ref()),
+			&PROGRAM_ID
+		),
+		Ok(())
+	);
+}
+
+// ---------------------------------------------------------------------------
+// as_array / as_array_mut
+// ---------------------------------------------------------------------------
+
+/// A fixed table of eight `u64` slots following the discriminator byte.
+#[account(discriminator = ValidationAccount)]
+pub struct GameTable {
+	pub slots: [pina::PodU64; 8],
+}
+
+const GAME_TABLE_LEN: usize = 1 + 8 * size_of::<pina::PodU64>();
+
+fn game_table_account() -> TestAccount<GAME_TABLE_LEN> {
+	let mut data = [0u8; GAME_TABLE_LEN];
+	data[0] = ValidationAccount::GameTable as u8;
+
+	for (index, slot) in data[1..].chunks_exact_mut(8).enumerate() {
+		slot.copy_from_slice(&(index as u64 * 10).to_le_bytes());
+	}
+
+	TestAccount::new(Address::new_from_array([1u8; 32]), data)
+}
+
+#[test]
+fn as_array_reads_every_slot() {
+	let mut account = game_table_account();
+	let view = account.view();
+
+	let slots = view
+		.as_array::<GameTable, pina::PodU64, 8>(&PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("game table should load: {e:?}"));
+
+	let values: Vec<u64> = slots.iter().map(pina::PodU64::get).collect();
+	assert_eq!(values, [0, 10, 20, 30, 40, 50, 60, 70]);
+}
+
+#[test]
+fn as_array_mut_updates_a_single_slot() {
+	let mut account = game_table_account();
+	let mut view = account.view();
+
+	{
+		let mut slots = view
+			.as_array_mut::<GameTable, pina::PodU64, 8>(&PROGRAM_ID)
+			.unwrap_or_else(|e| panic!("game table should load: {e:?}"));
+		slots[3] = pina::PodU64::from_primitive(99);
+	}
+
+	let slots = view
+		.as_array::<GameTable, pina::PodU64, 8>(&PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("game table should load: {e:?}"));
+	assert_eq!(slots[2].get(), 20);
+	assert_eq!(slots[3].get(), 99);
+	assert_eq!(slots[4].get(), 40);
+}
+
+#[test]
+fn as_array_rejects_mismatched_length_or_type() {
+	let mut account = game_table_account();
+	let view = account.view();
+
+	assert_eq!(
+		view.as_array::<GameTable, pina::PodU64, 7>(&PROGRAM_ID)
+			.err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		view.as_array::<Vault, pina::PodU64, 8>(&PROGRAM_ID).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		view.as_array::<GameTable, pina::PodU64, 8>(&Address::new_from_array([8u8; 32]))
+			.err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// load_one_of!
+// ---------------------------------------------------------------------------
+
+fn receipt_account() -> TestAccount<TICKET_LEN> {
+	let receipt = Receipt::builder()
+		.payer(Address::new_from_array([6u8; 32]))
+		.build();
+	let mut data = [0u8; TICKET_LEN];
+	data.copy_from_slice(bytemuck::bytes_of(&receipt));
+
+	TestAccount::new(Address::new_from_array([2u8; 32]), data)
+}
+
+#[test]
+fn load_one_of_matches_each_listed_type() {
+	let mut ticket = ticket_account();
+	let mut receipt = receipt_account();
+	let (ticket, receipt) = (ticket.view(), receipt.view());
+
+	let loaded = load_one_of!(&ticket, &PROGRAM_ID, [Ticket, Receipt])
+		.unwrap_or_else(|e| panic!("ticket should load: {e:?}"));
+	match loaded {
+		OneOf::First(ticket) => assert_eq!(ticket.holder, Address::new_from_array([5u8; 32])),
+		OneOf::Second(_) => panic!("ticket loaded as a receipt"),
+	}
+
+	let loaded = load_one_of!(&receipt, &PROGRAM_ID, [Ticket, Receipt])
+		.unwrap_or_else(|e| panic!("receipt should load: {e:?}"));
+	match loaded {
+		OneOf::First(_) => panic!("receipt loaded as a ticket"),
+		OneOf::Second(receipt) => assert_eq!(receipt.payer, Address::new_from_array([6u8; 32])),
+	}
+}
+
+#[test]
+fn load_one_of_rejects_unlisted_discriminator() {
+	let mut table = game_table_account();
+	let table = table.view();
+
+	assert!(matches!(
+		load_one_of!(&table, &PROGRAM_ID, [Ticket, Receipt]),
+		Err(ProgramError::InvalidAccountData)
+	));
+}
+
+#[test]
+fn load_one_of_keeps_owner_checks() {
+	let mut ticket = ticket_account().with_owner(Address::new_from_array([8u8; 32]));
+	let ticket = ticket.view();
+
+	assert!(matches!(
+		load_one_of!(&ticket, &PROGRAM_ID, [Receipt, Ticket, GameTable]),
+		Err(ProgramError::InvalidAccountOwner)
+	));
+}
+
+// ---------------------------------------------------------------------------
+// reinterpret_as
+// ---------------------------------------------------------------------------
+
+#[test]
+fn reinterpret_as_restamps_same_size_type() {
+	let mut account = ticket_account();
+	let mut view = account.view();
+
+	{
+		let receipt = view
+			.reinterpret_as::<Ticket, Receipt>(&PROGRAM_ID)
+			.unwrap_or_else(|e| panic!("ticket should convert: {e:?}"));
+		// The holder bytes are reused as the payer.
+		assert_eq!(receipt.payer, Address::new_from_array([5u8; 32]));
+	}
+
+	assert!(view.as_account::<Receipt>(&PROGRAM_ID).is_ok());
+	assert_eq!(
+		view.as_account::<Ticket>(&PROGRAM_ID).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn reinterpret_as_requires_the_old_type() {
+	let mut account = receipt_account();
+	let mut view = account.view();
+
+	assert_eq!(
+		view.reinterpret_as::<Ticket, Receipt>(&PROGRAM_ID).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		view.reinterpret_as::<Receipt, Ticket>(&Address::new_from_array([8u8; 32]))
+			.err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+	assert!(view.as_account::<Receipt>(&PROGRAM_ID).is_ok());
+}
+
+// ---------------------------------------------------------------------------
+// assert_lamports_above_rent_with
+// ---------------------------------------------------------------------------
+
+/// Rent at the default 6,960 lamports per byte, so an account with 8 data
+/// bytes needs `(128 + 8) * 6_960 = 946_560` lamports to be rent exempt.
+fn default_rent() -> pina::sysvars::rent::Rent {
+	pina::sysvars::rent::Rent::from_bytes(&6_960u64.to_le_bytes())
+		.unwrap_or_else(|e| panic!("rent should decode: {e:?}"))
+}
+
+#[test]
+fn assert_lamports_above_rent_with_accepts_sufficient_buffer() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 8]);
+	let view = account.view();
+	let rent = default_rent();
+
+	assert!(view.assert_lamports_above_rent_with(&rent, 0).is_ok());
+	assert!(view.assert_lamports_above_rent_with(&rent, 53_440).is_ok());
+}
+
+#[test]
+fn assert_lamports_above_rent_with_rejects_insufficient_buffer() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 8]);
+	let view = account.view();
+	let rent = default_rent();
+
+	assert_eq!(
+		view.assert_lamports_above_rent_with(&rent, 53_441).err(),
+		Some(ProgramError::InsufficientFunds)
+	);
+	assert_eq!(
+		view.assert_lamports_above_rent_with(&rent, u64::MAX).err(),
+		Some(ProgramError::InsufficientFunds)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_rent_exempt_with
+// ---------------------------------------------------------------------------
+
+#[test]
+fn assert_rent_exempt_with_accepts_funded_lengths() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 8]);
+	let view = account.view();
+	let rent = default_rent();
+
+	// 1_000_000 lamports cover `(128 + 15) * 6_960 = 995_280`.
+	assert!(view.assert_rent_exempt_with(&rent, 8).is_ok());
+	assert!(view.assert_rent_exempt_with(&rent, 15).is_ok());
+}
+
+#[test]
+fn assert_rent_exempt_with_rejects_underfunded_lengths() {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), [0u8; 8]);
+	let mut debited = TestAccount::new(Address::new_from_array([2u8; 32]), [0u8; 8]);
+	debited.header.lamports = 946_559;
+	let (view, debited) = (account.view(), debited.view());
+	let rent = default_rent();
+
+	assert_eq!(
+		view.assert_rent_exempt_with(&rent, 16).err(),
+		Some(ProgramError::AccountNotRentExempt)
+	);
+	assert_eq!(
+		debited
+			.assert_rent_exempt_with(&rent, debited.data_len())
+			.err(),
+		Some(ProgramError::AccountNotRentExempt)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_ata_consistent
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "token")]
+const WALLET: Address = Address::new_from_array([3u8; 32]);
+#[cfg(feature = "token")]
+const MINT: Address = Address::new_from_array([4u8; 32]);
+
+#[cfg(feature = "token")]
+fn derive_ata(token_program: &Address) -> Address {
+	try_get_associated_token_address(&WALLET, &MINT, token_program)
+		.unwrap_or_else(|| panic!("failed to derive ata"))
+		.0
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_ata_consistent_accepts_token_2022_ata() {
+	let mut account =
+		TestAccount::new(derive_ata(&token_2022::ID), [0u8; 8]).with_owner(token_2022::ID);
+	let view = account.view();
+
+	let result = view.assert_ata_consistent(&WALLET, &MINT, &token_2022::ID);
+
+	assert!(result.is_ok(), "consistent ata should pass: {result:?}");
+}
+
+/// Derived with token-2022 but owned by the classic token program.
+#[cfg(feature = "token")]
+#[test]
+fn assert_ata_consistent_rejects_cross_program_owner() {
+	let mut account = TestAccount::new(derive_ata(&token_2022::ID), [0u8; 8]).with_owner(token::ID);
+	let view = account.view();
+
+	let result = view.assert_ata_consistent(&WALLET, &MINT, &token_2022::ID);
+
+	assert_eq!(result.err(), Some(ProgramError::InvalidAccountOwner));
+}
+
+/// Owned by token-2022 but derived with the classic token program.
+#[cfg(feature = "token")]
+#[test]
+fn assert_ata_consistent_rejects_cross_program_derivation() {
+	let mut account = TestAccount::new(derive_ata(&token::ID), [0u8; 8]).with_owner(token_2022::ID);
+	let view = account.view();
+
+	let result = view.assert_ata_consistent(&WALLET, &MINT, &token_2022::ID);
+
+	assert_eq!(result.err(), Some(ProgramError::InvalidSeeds));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_token_owned_accepts_token_program() {
+	let mut account = TestAccount::new(WALLET, []).with_owner(token::ID);
+	let view = account.view();
+
+	assert!(view.is_token_owned());
+	assert!(view.assert_token_owned().is_ok());
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_token_owned_accepts_token_2022_program() {
+	let mut account = TestAccount::new(WALLET, []).with_owner(token_2022::ID);
+	let view = account.view();
+
+	assert!(view.is_token_owned());
+	assert!(view.assert_token_owned().is_ok());
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_token_owned_rejects_other_owner() {
+	let mut account = TestAccount::new(WALLET, []);
+	let view = account.view();
+
+	assert!(!view.is_token_owned());
+	assert_eq!(
+		view.assert_token_owned().err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_token_mint / assert_token_owner / assert_token_delegate
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "token")]
+const DELEGATE: Address = Address::new_from_array([5u8; 32]);
+
+/// An initialized token account of `MINT` held by `WALLET`, with `DELEGATE`
+/// approved when `delegated` is set.
+#[cfg(feature = "token")]
+fn token_account_data(delegated: bool) -> [u8; 165] {
+	let mut data = [0u8; 165];
+	data[..32].copy_from_slice(MINT.as_ref());
+	data[32..64].copy_from_slice(WALLET.as_ref());
+
+	if delegated {
+		data[72] = 1;
+		data[76..108].copy_from_slice(DELEGATE.as_ref());
+	}
+
+	data[108] = 1;
+	data
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn token_relationship_assertions_accept_matching_fields() {
+	for owner in [token::ID, token_2022::ID] {
+		let mut account = TestAccount::new(DELEGATE, token_account_data(true)).with_owner(owner);
+		let view = account.view();
+
+		let result = view
+			.assert_token_mint(&MINT)
+			.and_then(|view| view.assert_token_owner(&WALLET))
+			.and_then(|view| view.assert_token_delegate(&DELEGATE));
+		assert!(result.is_ok());
+	}
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn token_relationship_assertions_reject_other_fields() {
+	let mut account = TestAccount::new(DELEGATE, token_account_data(false)).with_owner(token::ID);
+	let view = account.view();
+
+	assert_eq!(
+		view.assert_token_mint(&WALLET).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		view.assert_token_owner(&MINT).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	// No delegate is approved.
+	assert_eq!(
+		view.assert_token_delegate(&DELEGATE).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn token_relationship_assertions_reject_other_programs() {
+	let mut account = TestAccount::new(DELEGATE, token_account_data(true));
+	let view = account.view();
+
+	assert_eq!(
+		view.assert_token_mint(&MINT).err(),
+		Some(ProgramError::InvalidAccountOwner)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// assert_mint_authority / assert_freeze_authority
+// ---------------------------------------------------------------------------
+
+/// An initialized mint with `WALLET` as mint authority and no freeze
+/// authority.
+#[cfg(feature = "token")]
+fn mint_data() -> [u8; 82] {
+	let mut data = [0u8; 82];
+	data[0] = 1;
+	data[4..36].copy_from_slice(WALLET.as_ref());
+	data[45] = 1;
+	data
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_mint_authority_accepts_the_authority() {
+	for owner in [token::ID, token_2022::ID] {
+		let mut account = TestAccount::new(MINT, mint_data()).with_owner(owner);
+		let view = account.view();
+
+		assert!(view.assert_mint_authority(&WALLET).is_ok());
+	}
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_mint_authority_rejects_other_accounts() {
+	let mut account = TestAccount::new(MINT, mint_data()).with_owner(token::ID);
+	let view = account.view();
+
+	assert_eq!(
+		view.assert_mint_authority(&DELEGATE).err(),
+		Some(ProgramError::IncorrectAuthority)
+	);
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_freeze_authority_rejects_unset_authority() {
+	let mut unset = TestAccount::new(MINT, mint_data()).with_owner(token::ID);
+	let mut data = mint_data();
+	data[46] = 1;
+	data[50..82].copy_from_slice(DELEGATE.as_ref());
+	let mut set = TestAccount::new(MINT, data).with_owner(token_2022::ID);
+	let (unset, set) = (unset.view(), set.view());
+
+	assert_eq!(
+		unset.assert_freeze_authority(&DELEGATE).err(),
+		Some(PinaProgramError::AuthorityNotSet.into())
+	);
+	assert!(set.assert_freeze_authority(&DELEGATE).is_ok());
+	assert_eq!(
+		set.assert_freeze_authority(&WALLET).err(),
+		Some(ProgramError::IncorrectAuthority)
+	);
+}
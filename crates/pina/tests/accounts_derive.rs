@@ -48,6 +48,38 @@ struct ParentAccounts<'a> {
 	pub nested: NestedAccounts<'a>,
 }
 
+#[derive(Accounts)]
+#[pina(crate = pina)]
+struct TestAccountsRemainingCursor<'a> {
+	pub one: &'a AccountView,
+	pub remaining: RemainingAccounts<'a>,
+}
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina)]
+struct SystemWalletAccounts<'a> {
+	#[pina(owner = system::ID)]
+	pub wallet: &'a AccountView,
+	#[pina(program = system)]
+	pub system_program: &'a AccountView,
+}
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina)]
+struct TransferBetweenAccounts<'a> {
+	pub source: SystemWalletAccounts<'a>,
+	pub authority: &'a AccountView,
+	pub destination: SystemWalletAccounts<'a>,
+}
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina)]
+struct TestAccountsProgram<'a> {
+	#[pina(program = system)]
+	pub system_program: &'a AccountView,
+	pub two: &'a AccountView,
+}
+
 #[derive(Accounts)]
 #[pina(crate = pina)]
 struct TestAccountsRemainingMut<'a> {
@@ -56,6 +88,75 @@ struct TestAccountsRemainingMut<'a> {
 	pub remaining: &'a mut [AccountView],
 }
 
+#[derive(Accounts, Debug)]
+#[pina(crate = pina)]
+struct ValidatedAccounts<'a> {
+	pub authority: &'a AccountView,
+	pub wallet: &'a AccountView,
+}
+
+validation_processor!(ValidatedAccounts, {
+	authority: [assert_signer()],
+	wallet: [assert_owner(&system::ID), assert_data_len(1)],
+});
+
+#[discriminator]
+enum ValidationInstruction {
+	Check = 0,
+}
+
+#[instruction(discriminator = ValidationInstruction, variant = Check)]
+struct CheckInstruction {}
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina)]
+struct ValidatedWithDataAccounts<'a> {
+	pub authority: &'a AccountView,
+	pub wallet: &'a AccountView,
+}
+
+validation_processor!(ValidatedWithDataAccounts, CheckInstruction, {
+	authority: [assert_signer()],
+	wallet: [assert_owner(&system::ID)],
+});
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina)]
+struct ConstrainedAccounts<'a> {
+	#[pina(signer, writable)]
+	pub payer: &'a AccountView,
+	#[pina(owner = system::ID, address = system::ID)]
+	pub wallet: &'a AccountView,
+}
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina, deny_duplicates)]
+struct DistinctAccounts<'a> {
+	#[pina(writable)]
+	pub source: &'a AccountView,
+	pub authority: &'a AccountView,
+	#[pina(writable)]
+	pub destination: &'a AccountView,
+}
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina, program_id = MOCK_PROGRAM_ID)]
+struct DistinctFromAccounts<'a> {
+	pub source: Option<&'a AccountView>,
+	pub authority: &'a AccountView,
+	#[pina(distinct_from = source)]
+	pub destination: &'a AccountView,
+}
+
+#[derive(Accounts, Debug)]
+#[pina(crate = pina, program_id = MOCK_PROGRAM_ID)]
+struct OptionalAccounts<'a> {
+	pub payer: &'a AccountView,
+	#[pina(owner = system::ID)]
+	pub rent_payer: Option<&'a AccountView>,
+	pub memo_program: Option<&'a mut AccountView>,
+}
+
 #[test]
 fn test_accounts_derive_exact() {
 	let ix_data = [3u8; 100];
@@ -193,6 +294,365 @@ fn test_accounts_derive_nested_loader_order() {
 	assert_eq!(test_accounts.nested.three as *mut AccountView, three_ptr);
 }
 
+#[test]
+fn test_accounts_derive_remaining_cursor() {
+	let mut input = unsafe { create_input(5, &[]) };
+	let mut accounts = [UNINIT; 5];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let mut parsed = TestAccountsRemainingCursor::try_from_account_infos(accounts).unwrap();
+	assert_eq!(parsed.one.data_len(), 0);
+	assert_eq!(parsed.remaining.len(), 4);
+
+	let nested = parsed.remaining.next_as::<NestedAccounts>().unwrap();
+	assert_eq!(nested.two.data_len(), 1);
+	assert_eq!(nested.three.data_len(), 2);
+	assert_eq!(parsed.remaining.try_next().unwrap().data_len(), 3);
+	assert_eq!(parsed.remaining.as_slice().len(), 1);
+	assert_eq!(
+		parsed.remaining.finish(),
+		Err(PinaProgramError::TooManyAccountKeys.into())
+	);
+
+	assert_eq!(parsed.remaining.next_mut().unwrap().data_len(), 4);
+	assert!(parsed.remaining.is_empty());
+	assert_eq!(parsed.remaining.finish(), Ok(()));
+	assert_eq!(
+		parsed.remaining.try_next().unwrap_err(),
+		ProgramError::NotEnoughAccountKeys
+	);
+	assert!(parsed.remaining.next().is_none());
+}
+
+#[test]
+fn test_accounts_derive_remaining_iterator() {
+	let mut input = unsafe { create_input(5, &[]) };
+	let mut accounts = [UNINIT; 5];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let parsed = TestAccountsRemainingCursor::try_from_account_infos(accounts).unwrap();
+	assert_eq!(parsed.remaining.size_hint(), (4, Some(4)));
+
+	let lengths: Vec<usize> = parsed.remaining.map(AccountView::data_len).collect();
+	assert_eq!(lengths, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_accounts_derive_repeated_nested_groups() {
+	let mut input = unsafe { create_input(5, &[]) };
+	let mut accounts = [UNINIT; 5];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let parsed = TransferBetweenAccounts::try_from_account_infos(accounts).unwrap();
+	assert_eq!(parsed.source.wallet.data_len(), 0);
+	assert_eq!(parsed.authority.data_len(), 2);
+	assert_eq!(parsed.destination.wallet.data_len(), 3);
+	assert_eq!(parsed.destination.system_program.data_len(), 4);
+
+	let mut input = unsafe { create_input(5, &[]) };
+	unsafe {
+		input.write(&[7u8; 32], account_offset(3) + 8 + 32);
+	}
+	let mut accounts = [UNINIT; 5];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	assert_eq!(
+		TransferBetweenAccounts::try_from_account_infos(accounts).unwrap_err(),
+		ProgramError::InvalidAccountOwner
+	);
+}
+
+#[test]
+fn test_accounts_derive_program_accepts_known_address() {
+	let ix_data = [3u8; 100];
+	// `create_input` leaves every address zeroed, which is the system program.
+	let mut input = unsafe { create_input(2, &ix_data) };
+	let mut accounts = [UNINIT; 2];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let test_accounts = TestAccountsProgram::try_from_account_infos(accounts).unwrap();
+	assert_eq!(test_accounts.system_program.address(), &system::ID);
+	// Fields without the attribute are not checked.
+	assert_eq!(test_accounts.two.data_len(), 1);
+}
+
+#[test]
+fn test_accounts_derive_program_rejects_wrong_address() {
+	let ix_data = [3u8; 100];
+	let mut input = unsafe { create_input(2, &ix_data) };
+	// Overwrite the first account's address, which follows the account count
+	// and the four flag bytes plus padding of its header.
+	unsafe {
+		input.write(&[7u8; 32], size_of::<u64>() + 8);
+	}
+	let mut accounts = [UNINIT; 2];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let result = TestAccountsProgram::try_from_account_infos(accounts);
+	assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+}
+
+#[test]
+fn test_accounts_derive_constraints_accept_valid_accounts() {
+	let mut input = unsafe { create_input(2, &[]) };
+	// Mark the first account as a writable signer; the flags follow the
+	// duplicate marker.
+	unsafe {
+		input.write(&[1, 1], size_of::<u64>() + 1);
+	}
+	let mut accounts = [UNINIT; 2];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let constrained = ConstrainedAccounts::try_from_account_infos(accounts).unwrap();
+	assert!(constrained.payer.is_signer());
+	assert_eq!(constrained.wallet.owner(), &system::ID);
+}
+
+/// Parse [`ConstrainedAccounts`] from two zeroed accounts after applying
+/// `writes` of `(offset, bytes)` to the input buffer.
+fn parse_constrained(writes: &[(usize, &[u8])]) -> Result<(), ProgramError> {
+	let mut input = unsafe { create_input(2, &[]) };
+	for (offset, bytes) in writes {
+		unsafe {
+			input.write(bytes, *offset);
+		}
+	}
+	let mut accounts = [UNINIT; 2];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	ConstrainedAccounts::try_from_account_infos(accounts).map(|_| ())
+}
+
+#[test]
+fn test_accounts_derive_constraints_reject_invalid_accounts() {
+	let payer_flags = size_of::<u64>() + 1;
+	// The second account's owner follows the first account's full record and
+	// its own 8 header bytes and address.
+	let wallet_owner = size_of::<u64>() + STATIC_ACCOUNT_DATA + size_of::<u64>() + 8 + 32;
+
+	assert_eq!(
+		parse_constrained(&[]),
+		Err(ProgramError::MissingRequiredSignature)
+	);
+	assert_eq!(
+		parse_constrained(&[(payer_flags, &[1])]),
+		Err(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		parse_constrained(&[(payer_flags, &[1, 1]), (wallet_owner, &[7u8; 32])]),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(parse_constrained(&[(payer_flags, &[1, 1])]), Ok(()));
+}
+
+/// The offset of account `index` in a buffer built by `create_input`.
+fn account_offset(index: usize) -> usize {
+	(0..index).fold(size_of::<u64>(), |offset, previous| {
+		offset
+			+ STATIC_ACCOUNT_DATA
+			+ size_of::<u64>()
+			+ ((previous + (BPF_ALIGN_OF_U128 - 1)) & !(BPF_ALIGN_OF_U128 - 1))
+	})
+}
+
+#[test]
+fn test_accounts_derive_optional_accounts_missing_at_end() {
+	let mut input = unsafe { create_input(1, &[]) };
+	let mut accounts = [UNINIT; 1];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let parsed = OptionalAccounts::try_from_account_infos(accounts).unwrap();
+	assert_eq!(parsed.payer.data_len(), 0);
+	assert!(parsed.rent_payer.is_none());
+	assert!(parsed.memo_program.is_none());
+}
+
+#[test]
+fn test_accounts_derive_optional_accounts_program_id_placeholder() {
+	let mut input = unsafe { create_input(3, &[]) };
+	unsafe {
+		input.write(MOCK_PROGRAM_ID.as_ref(), account_offset(1) + 8);
+	}
+	let mut accounts = [UNINIT; 3];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let parsed = OptionalAccounts::try_from_account_infos(accounts).unwrap();
+	assert!(parsed.rent_payer.is_none());
+	assert_eq!(parsed.memo_program.map(|memo| memo.data_len()), Some(2));
+}
+
+#[test]
+fn test_accounts_derive_optional_accounts_run_checks_when_present() {
+	let mut input = unsafe { create_input(3, &[]) };
+	let mut accounts = [UNINIT; 3];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let parsed = OptionalAccounts::try_from_account_infos(accounts).unwrap();
+	assert_eq!(parsed.rent_payer.map(AccountView::data_len), Some(1));
+	assert!(parsed.memo_program.is_some());
+
+	let mut input = unsafe { create_input(3, &[]) };
+	unsafe {
+		input.write(&[7u8; 32], account_offset(1) + 8 + 32);
+	}
+	let mut accounts = [UNINIT; 3];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	assert_eq!(
+		OptionalAccounts::try_from_account_infos(accounts).unwrap_err(),
+		ProgramError::InvalidAccountOwner
+	);
+}
+
+/// Build `N` accounts, apply `writes` to the input buffer and pass the parsed
+/// account slice to `parse`.
+fn with_accounts<const N: usize, R>(
+	writes: &[(usize, &[u8])],
+	parse: impl FnOnce(&mut [AccountView]) -> R,
+) -> R {
+	let mut input = unsafe { create_input(N, &[]) };
+	for (offset, bytes) in writes {
+		unsafe {
+			input.write(bytes, *offset);
+		}
+	}
+	let mut accounts = [UNINIT; N];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	parse(accounts)
+}
+
+#[test]
+fn test_accounts_derive_deny_duplicates() {
+	let writable = |index: usize| (account_offset(index) + 2, &[1u8][..]);
+	let other_address = (account_offset(2) + 8, &[9u8; 32][..]);
+	let parse = |accounts: &mut [AccountView]| {
+		DistinctAccounts::try_from_account_infos(accounts).map(|parsed| {
+			(
+				parsed.source.data_len(),
+				parsed.authority.data_len(),
+				parsed.destination.data_len(),
+			)
+		})
+	};
+
+	// Every account starts at the zero address.
+	assert_eq!(
+		with_accounts::<3, _>(&[writable(0), writable(2)], parse),
+		Err(PinaProgramError::DuplicateMutableAccount.into())
+	);
+	assert_eq!(
+		with_accounts::<3, _>(&[writable(0), writable(2), other_address], parse),
+		Ok((0, 1, 2))
+	);
+}
+
+#[test]
+fn test_accounts_derive_distinct_from() {
+	let writable = |index: usize| (account_offset(index) + 2, &[1u8][..]);
+	let parse = |accounts: &mut [AccountView]| {
+		DistinctFromAccounts::try_from_account_infos(accounts).map(|parsed| {
+			(
+				parsed.source.map(AccountView::data_len),
+				parsed.authority.data_len(),
+				parsed.destination.data_len(),
+			)
+		})
+	};
+
+	assert_eq!(
+		with_accounts::<3, _>(&[writable(0), writable(2)], parse),
+		Err(PinaProgramError::DuplicateMutableAccount.into())
+	);
+	// The read-only source and the authority may share the address.
+	assert_eq!(
+		with_accounts::<3, _>(&[writable(1), writable(2)], parse),
+		Ok((Some(0), 1, 2))
+	);
+}
+
+#[test]
+fn validation_processor_runs_field_assertions() {
+	let ix_data = [3u8; 100];
+	let mut input = unsafe { create_input(2, &ix_data) };
+	let mut accounts = [UNINIT; 2];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	// `create_input` never marks accounts as signers.
+	let result = ValidatedAccounts::try_from_account_infos(accounts)
+		.and_then(|validated| validated.process(&[]));
+	assert_eq!(result, Err(ProgramError::MissingRequiredSignature));
+}
+
+#[test]
+fn validation_processor_accepts_valid_accounts() {
+	let mut input = unsafe { create_input(2, &[]) };
+	// Mark the first account as a signer; its flag follows the duplicate
+	// marker.
+	unsafe {
+		input.write(&[1], size_of::<u64>() + 1);
+	}
+	let mut accounts = [UNINIT; 2];
+
+	let count = unsafe { deserialize(input.as_mut_ptr(), &mut accounts) }.1;
+	let accounts: &mut [AccountView] =
+		unsafe { core::slice::from_raw_parts_mut(accounts.as_mut_ptr().cast(), count) };
+
+	let validated = ValidatedAccounts::try_from_account_infos(accounts).unwrap();
+	assert_eq!(validated.process(&[]), Ok(()));
+
+	let validated = ValidatedWithDataAccounts::try_from_account_infos(accounts).unwrap();
+	assert_eq!(
+		validated.process(&[ValidationInstruction::Check as u8]),
+		Ok(())
+	);
+
+	let validated = ValidatedWithDataAccounts::try_from_account_infos(accounts).unwrap();
+	assert!(validated.process(&[9]).is_err());
+}
+
 /// The mock program ID used for testing.
 const MOCK_PROGRAM_ID: Address = Address::new_from_array([5u8; 32]);
 /// `assert_eq(core::mem::align_of::<u128>(), 8)` is true for BPF but not
@@ -304,3 +764,122 @@ unsafe fn create_input(accounts: usize, instruction_data: &[u8]) -> AlignedMemor
 
 	input
 }
+
+#[cfg(feature = "client")]
+fn key(byte: u8) -> Address {
+	Address::new_from_array([byte; 32])
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_accounts_keys_follow_field_flags() {
+	use pina::transaction::AccountMeta;
+	use pina::transaction::ToAccountMetas;
+
+	let metas = ConstrainedAccountsKeys {
+		payer: key(1),
+		wallet: key(2),
+	}
+	.to_account_metas();
+
+	assert_eq!(
+		metas,
+		vec![
+			AccountMeta::new(key(1), true),
+			AccountMeta::new_readonly(key(2), false)
+		]
+	);
+
+	let metas = TestAccountsMutKeys {
+		one: key(1),
+		two: key(2),
+	}
+	.to_account_metas();
+
+	assert_eq!(
+		metas,
+		vec![
+			AccountMeta::new(key(1), false),
+			AccountMeta::new(key(2), false)
+		]
+	);
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_accounts_keys_fill_in_program_ids() {
+	use pina::transaction::AccountMeta;
+	use pina::transaction::ToAccountMetas;
+
+	let metas = TestAccountsProgramKeys { two: key(2) }.to_account_metas();
+
+	assert_eq!(
+		metas,
+		vec![
+			AccountMeta::new_readonly(system::ID, false),
+			AccountMeta::new_readonly(key(2), false),
+		]
+	);
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_accounts_keys_flatten_nested_and_remaining_accounts() {
+	use pina::transaction::AccountMeta;
+	use pina::transaction::ToAccountMetas;
+
+	let metas = ParentAccountsKeys {
+		one: key(1),
+		nested: NestedAccountsKeys {
+			two: key(2),
+			three: key(3),
+		},
+	}
+	.to_account_metas();
+
+	assert_eq!(
+		metas,
+		vec![
+			AccountMeta::new_readonly(key(1), false),
+			AccountMeta::new_readonly(key(2), false),
+			AccountMeta::new(key(3), false),
+		]
+	);
+
+	let metas = TestAccountsRemainingKeys {
+		one: key(1),
+		remaining: vec![AccountMeta::new(key(4), true)],
+	}
+	.to_account_metas();
+
+	assert_eq!(
+		metas,
+		vec![
+			AccountMeta::new_readonly(key(1), false),
+			AccountMeta::new(key(4), true)
+		]
+	);
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_accounts_keys_use_placeholders_for_omitted_optionals() {
+	use pina::transaction::AccountMeta;
+	use pina::transaction::ToAccountMetas;
+
+	let metas = OptionalAccountsKeys {
+		payer: key(1),
+		rent_payer: None,
+		memo_program: Some(key(3)),
+	}
+	.to_account_metas();
+
+	assert_eq!(
+		metas,
+		vec![
+			AccountMeta::new_readonly(key(1), false),
+			AccountMeta::new_readonly(MOCK_PROGRAM_ID, false),
+			AccountMeta::new(key(3), false),
+		]
+	);
+}
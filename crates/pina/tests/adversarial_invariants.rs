@@ -486,6 +486,87 @@ fn close_with_recipient_conserves_lamports_and_zeroes_source() {
 	assert_eq!(total_after, total_before);
 }
 
+#[test]
+fn lamport_conservation_accepts_transfers_within_the_set() {
+	let unique_accounts = [
+		AccountBuilder::new()
+			.address(fake_address(40))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(700)
+			.data(&build_balance_state_bytes(5))
+			.is_writable(true),
+		AccountBuilder::new()
+			.address(fake_address(41))
+			.owner(system::ID)
+			.lamports(300)
+			.is_writable(true),
+		AccountBuilder::new()
+			.address(fake_address(42))
+			.owner(system::ID)
+			.lamports(50)
+			.is_writable(true),
+	];
+
+	let (_input, mut accounts, count) = load_accounts!(&unique_accounts, 0, 4);
+	let account_views = initialized_account_views(&mut accounts, count);
+	let conservation =
+		LamportConservation::new([&account_views[0], &account_views[1], &account_views[2]]);
+
+	let (escrow, rest) = account_views.split_at_mut(1);
+	let (maker, taker) = rest.split_at_mut(1);
+	escrow[0]
+		.send(200, &mut taker[0])
+		.unwrap_or_else(|error| panic!("send should succeed: {error:?}"));
+	escrow[0]
+		.close_with_recipient(&mut maker[0])
+		.unwrap_or_else(|error| panic!("close should succeed: {error:?}"));
+
+	assert_eq!(conservation.total(), 1_050);
+	assert_eq!(conservation.verify(0), Ok(()));
+	assert_eq!(
+		conservation.verify(1),
+		Err(PinaProgramError::LamportsNotConserved.into())
+	);
+}
+
+#[test]
+fn lamport_conservation_detects_skimmed_lamports() {
+	let unique_accounts = [
+		AccountBuilder::new()
+			.address(fake_address(43))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(700)
+			.is_writable(true),
+		AccountBuilder::new()
+			.address(fake_address(44))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(300)
+			.is_writable(true),
+		AccountBuilder::new()
+			.address(fake_address(45))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(0)
+			.is_writable(true),
+	];
+
+	let (_input, mut accounts, count) = load_accounts!(&unique_accounts, 0, 4);
+	let account_views = initialized_account_views(&mut accounts, count);
+	let conservation = LamportConservation::new([&account_views[0], &account_views[1]]);
+
+	// The third account is outside the guarded set, so lamports sent to it
+	// look like a skim unless they are declared as the expected outflow.
+	let (escrow, rest) = account_views.split_at_mut(1);
+	escrow[0]
+		.send(25, &mut rest[1])
+		.unwrap_or_else(|error| panic!("send should succeed: {error:?}"));
+
+	assert_eq!(
+		conservation.verify(0),
+		Err(PinaProgramError::LamportsNotConserved.into())
+	);
+	assert_eq!(conservation.verify(25), Ok(()));
+}
+
 #[test]
 fn close_with_recipient_overflow_preserves_balances_and_data() {
 	let state_bytes = build_balance_state_bytes(44);
@@ -578,6 +659,31 @@ fn assert_program_rejects_wrong_identity_and_non_executable_targets() {
 	);
 }
 
+#[test]
+fn assert_not_executable_rejects_program_accounts_in_data_slots() {
+	let unique_accounts = [
+		AccountBuilder::new()
+			.address(fake_address(25))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1)
+			.executable(false),
+		AccountBuilder::new()
+			.address(system::ID)
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1)
+			.executable(true),
+	];
+
+	let (_input, mut accounts, count) = load_accounts!(&unique_accounts, 0, 4);
+	let account_views = initialized_account_views(&mut accounts, count);
+
+	assert!(account_views[0].assert_not_executable().is_ok());
+	assert_eq!(
+		account_views[1].assert_not_executable(),
+		Err(ProgramError::InvalidAccountData)
+	);
+}
+
 #[test]
 fn non_canonical_pda_requires_explicit_bump_verification() {
 	let (seed_bytes, _canonical_address, canonical_bump, non_canonical_address, non_canonical_bump) =
@@ -0,0 +1,54 @@
+#![cfg(feature = "test-utils")]
+
+use pina::compute_units::ComputeUnitReport;
+use pina::compute_units::parse_cu_usage;
+
+#[test]
+fn parse_cu_usage_reads_log_cu_usage_lines() {
+	assert_eq!(
+		parse_cu_usage("Program log: Function process consumed 312 compute units"),
+		Some(("process", 312))
+	);
+	assert_eq!(
+		parse_cu_usage("Function increment consumed 7 compute units"),
+		Some(("increment", 7))
+	);
+}
+
+#[test]
+fn parse_cu_usage_ignores_other_lines() {
+	assert_eq!(parse_cu_usage("Program log: counter incremented"), None);
+	assert_eq!(
+		parse_cu_usage("Program log: Function process consumed many compute units"),
+		None
+	);
+	assert_eq!(parse_cu_usage("Program data: AQoAAAA="), None);
+}
+
+#[test]
+fn report_records_logs_by_function_name() {
+	let mut report = ComputeUnitReport::new();
+	report.record_logs([
+		"Program log: Function initialize consumed 1200 compute units",
+		"Program log: counter created",
+		"Program log: Function increment consumed 300 compute units",
+	]);
+
+	assert_eq!(report.get("initialize"), Some(1200));
+	assert_eq!(report.get("increment"), Some(300));
+	assert_eq!(report.iter().count(), 2);
+}
+
+#[test]
+fn report_serializes_sorted_json() {
+	let mut report = ComputeUnitReport::new();
+	report.record("Increment", 300);
+	report.record("Initialize", 1200);
+	report.record("Increment", 310);
+
+	assert_eq!(
+		report.to_json(),
+		"{\n\t\"Increment\": 310,\n\t\"Initialize\": 1200\n}\n"
+	);
+	assert_eq!(ComputeUnitReport::new().to_json(), "{}\n");
+}
@@ -1,15 +1,57 @@
 #![allow(unsafe_code)]
 
+#[cfg(feature = "account-resize")]
+use pina::AccountDeserialize;
 use pina::Address;
+use pina::AsAccount;
 use pina::CpiContext;
 use pina::CpiHandle;
+use pina::DynamicSpace;
+use pina::EVENT_IX_TAG;
+use pina::IntoDiscriminator;
+use pina::PinaProgramError;
+use pina::PodU64;
 use pina::ProgramError;
 use pina::ToCpiAccounts;
+#[cfg(feature = "token")]
+use pina::TokenCpi;
+use pina::account;
 use pina::combine_seeds_with_bump;
+use pina::create_and_init;
+#[cfg(feature = "token")]
+use pina::create_associated_token_account_if_needed;
+use pina::create_dynamic_account;
+#[cfg(feature = "token")]
+use pina::create_or_get_associated_token_account;
+use pina::create_program_account_verified;
+use pina::create_rent_exempt_account;
+use pina::discriminator;
+use pina::find_event_authority;
+use pina::is_emit_event_instruction;
+#[cfg(feature = "account-resize")]
+use pina::migrate_account;
+#[cfg(feature = "account-resize")]
+use pina::migrate_account_using;
+use pina::process_emit_event;
 #[cfg(feature = "account-resize")]
 use pina::realloc_account;
 #[cfg(feature = "account-resize")]
 use pina::realloc_account_zero;
+#[cfg(feature = "account-resize")]
+use pina::resize_with_rent;
+#[cfg(feature = "account-resize")]
+use pina::resize_with_rent_using;
+#[cfg(feature = "account-resize")]
+use pina::sysvars::rent::Rent;
+#[cfg(feature = "token")]
+use pina::transfer_checked_tokens;
+#[cfg(feature = "token")]
+use pina::transfer_checked_verified;
+#[cfg(feature = "token")]
+use pina::try_get_associated_token_address;
+use pina::verify_created_account;
+#[cfg(feature = "token")]
+use pina::verify_token_transfer;
 use pinocchio::AccountView;
 #[cfg(feature = "account-resize")]
 use pinocchio::account::MAX_PERMITTED_DATA_INCREASE;
@@ -190,3 +232,879 @@ fn cpi_context_accepts_typed_account_structs() {
 	assert_eq!(ordered[1].address(), second_view.address());
 	assert!(!ordered[1].is_writable());
 }
+
+/// A 16 byte header followed by `element_count` 32 byte entries.
+struct Registry;
+
+impl DynamicSpace for Registry {
+	fn space(element_count: usize) -> usize {
+		16 + element_count * 32
+	}
+}
+
+#[test]
+fn dynamic_space_counts_header_and_elements() {
+	assert_eq!(Registry::space(0), 16);
+	assert_eq!(Registry::space(4), 16 + 4 * 32);
+}
+
+#[test]
+fn create_dynamic_account_rejects_space_above_cpi_limit() {
+	let mut target = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), false, true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (target, payer) = (target.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+	// 16 + 320 * 32 exceeds the 10 KiB limit for a single CPI allocation.
+	let element_count = 320;
+
+	let result =
+		create_dynamic_account::<Registry>(&target, &payer, &owner, &[b"registry"], element_count);
+
+	assert_eq!(result, Err(PinaProgramError::InvalidAccountSize.into()));
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn resize_with_rent_rejects_growth_above_the_limit() {
+	let mut account = TestAccount::<16>::new(Address::new_from_array([1u8; 32]), false, true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut account, mut payer) = (account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	let result = resize_with_rent(
+		&mut account,
+		16 + MAX_PERMITTED_DATA_INCREASE + 1,
+		&mut payer,
+		None,
+		&owner,
+	);
+
+	assert_eq!(result, Err(ProgramError::InvalidRealloc));
+	assert_eq!(account.data_len(), 16);
+	assert_eq!(payer.lamports(), 1);
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn resize_with_rent_checks_the_account_before_resizing() {
+	let mut readonly = TestAccount::<16>::new(Address::new_from_array([1u8; 32]), false, false);
+	let mut account = TestAccount::<16>::new(Address::new_from_array([3u8; 32]), false, true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut readonly, mut account, mut payer) = (readonly.view(), account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	assert_eq!(
+		resize_with_rent(&mut readonly, 32, &mut payer, None, &owner),
+		Err(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		resize_with_rent(
+			&mut account,
+			32,
+			&mut payer,
+			None,
+			&Address::new_from_array([8u8; 32])
+		),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+	// Keeping the size moves no lamports and needs no rent sysvar.
+	assert_eq!(
+		resize_with_rent(&mut account, 16, &mut payer, None, &owner),
+		Ok(())
+	);
+}
+
+#[cfg(feature = "account-resize")]
+fn default_rent() -> Rent {
+	Rent::from_bytes(&6_960u64.to_le_bytes())
+		.unwrap_or_else(|e| panic!("rent should decode: {e:?}"))
+}
+
+#[cfg(feature = "account-resize")]
+fn minimum_balance(len: usize) -> u64 {
+	default_rent()
+		.try_minimum_balance(len)
+		.unwrap_or_else(|e| panic!("minimum balance: {e:?}"))
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn resize_with_rent_using_grows_the_account() {
+	let mut account = TestAccount::<64>::new(Address::new_from_array([1u8; 32]), false, true);
+	account.header.data_len = 16;
+	account.data[16..].fill(7);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let rent = default_rent();
+	let (mut account_view, mut payer) = (account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	resize_with_rent_using(&mut account_view, 48, &mut payer, None, &rent, &owner)
+		.unwrap_or_else(|e| panic!("grow: {e:?}"));
+
+	assert_eq!(account_view.data_len(), 48);
+	// The rent for the growth is paid with a system transfer CPI, which does
+	// nothing on the host, so neither balance is observed to change here.
+	assert_eq!(payer.lamports(), 1);
+	assert_eq!(account_view.lamports(), 1);
+	assert!(account.data[16..48].iter().all(|byte| *byte == 0));
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn resize_with_rent_using_refunds_the_excess_when_shrinking() {
+	let rent = default_rent();
+	let mut account = TestAccount::<64>::new(Address::new_from_array([1u8; 32]), false, true);
+	account.header.lamports = minimum_balance(64);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let mut recipient = TestAccount::<0>::new(Address::new_from_array([3u8; 32]), false, true);
+	let (mut account, mut payer, mut recipient) = (account.view(), payer.view(), recipient.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	resize_with_rent_using(
+		&mut account,
+		16,
+		&mut payer,
+		Some(&mut recipient),
+		&rent,
+		&owner,
+	)
+	.unwrap_or_else(|e| panic!("shrink: {e:?}"));
+
+	assert_eq!(account.data_len(), 16);
+	assert_eq!(account.lamports(), minimum_balance(16));
+	assert_eq!(
+		recipient.lamports(),
+		1 + minimum_balance(64) - minimum_balance(16)
+	);
+	assert_eq!(payer.lamports(), 1);
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn resize_with_rent_using_keeps_the_excess_without_a_recipient() {
+	let rent = default_rent();
+	let mut account = TestAccount::<64>::new(Address::new_from_array([1u8; 32]), false, true);
+	account.header.lamports = minimum_balance(64);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut account, mut payer) = (account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	resize_with_rent_using(&mut account, 16, &mut payer, None, &rent, &owner)
+		.unwrap_or_else(|e| panic!("shrink: {e:?}"));
+
+	assert_eq!(account.data_len(), 16);
+	assert_eq!(account.lamports(), minimum_balance(64));
+}
+
+#[cfg(feature = "account-resize")]
+#[discriminator]
+pub enum MigrationAccount {
+	LedgerV1 = 1,
+	Ledger = 2,
+	ArchiveV1 = 3,
+	Archive = 4,
+}
+
+#[cfg(feature = "account-resize")]
+#[account(discriminator = MigrationAccount)]
+pub struct LedgerV1 {
+	pub count: PodU64,
+	pub flags: u8,
+}
+
+/// Same size as `LedgerV1`, so migrating needs no rent.
+#[cfg(feature = "account-resize")]
+#[account(discriminator = MigrationAccount, version = 2, migrate_from = LedgerV1)]
+pub struct Ledger {
+	pub version: u8,
+	pub count: PodU64,
+}
+
+#[cfg(feature = "account-resize")]
+impl From<&LedgerV1> for Ledger {
+	fn from(previous: &LedgerV1) -> Self {
+		Self::builder().count(previous.count).build()
+	}
+}
+
+#[cfg(feature = "account-resize")]
+#[account(discriminator = MigrationAccount)]
+pub struct ArchiveV1 {
+	pub count: PodU64,
+	pub history: [PodU64; 4],
+}
+
+/// Drops the history, so migrating shrinks the account.
+#[cfg(feature = "account-resize")]
+#[account(discriminator = MigrationAccount, version = 2, migrate_from = ArchiveV1)]
+pub struct Archive {
+	pub version: u8,
+	pub count: PodU64,
+}
+
+#[cfg(feature = "account-resize")]
+impl From<&ArchiveV1> for Archive {
+	fn from(previous: &ArchiveV1) -> Self {
+		Self::builder().count(previous.count).build()
+	}
+}
+
+#[cfg(feature = "account-resize")]
+fn ledger_v1_account(is_writable: bool) -> TestAccount<{ size_of::<LedgerV1>() }> {
+	let mut account = TestAccount::new(Address::new_from_array([1u8; 32]), false, is_writable);
+	let previous = LedgerV1::builder()
+		.count(PodU64::from_primitive(7))
+		.flags(3)
+		.build();
+	account.data.copy_from_slice(previous.to_bytes());
+	account
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn migrate_account_rewrites_same_size_layouts() {
+	let mut account = ledger_v1_account(true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut account_view, mut payer) = (account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	// Equal sizes skip the resize, so no rent sysvar is needed.
+	migrate_account::<Ledger>(&mut account_view, &mut payer, &owner)
+		.unwrap_or_else(|e| panic!("migrate: {e:?}"));
+
+	let ledger = Ledger::try_from_bytes(&account.data)
+		.unwrap_or_else(|e| panic!("migrated ledger should decode: {e:?}"));
+	assert_eq!(account.data[0], MigrationAccount::Ledger as u8);
+	assert_eq!(ledger.version, 2);
+	assert_eq!(ledger.count, PodU64::from_primitive(7));
+	assert_eq!(payer.lamports(), 1);
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn migrate_account_using_shrinks_and_refunds_the_payer() {
+	let rent = default_rent();
+	let mut account = TestAccount::<{ size_of::<ArchiveV1>() }>::new(
+		Address::new_from_array([1u8; 32]),
+		false,
+		true,
+	);
+	account.header.lamports = minimum_balance(size_of::<ArchiveV1>());
+	let previous = ArchiveV1::builder()
+		.count(PodU64::from_primitive(11))
+		.history([PodU64::from_primitive(5); 4])
+		.build();
+	account.data.copy_from_slice(previous.to_bytes());
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut account_view, mut payer) = (account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	migrate_account_using::<Archive>(&mut account_view, &mut payer, &rent, &owner)
+		.unwrap_or_else(|e| panic!("migrate: {e:?}"));
+
+	assert_eq!(account_view.data_len(), size_of::<Archive>());
+	assert_eq!(
+		account_view.lamports(),
+		minimum_balance(size_of::<Archive>())
+	);
+	assert_eq!(
+		payer.lamports(),
+		1 + minimum_balance(size_of::<ArchiveV1>()) - minimum_balance(size_of::<Archive>())
+	);
+
+	let archive = Archive::try_from_bytes(&account.data[..size_of::<Archive>()])
+		.unwrap_or_else(|e| panic!("migrated archive should decode: {e:?}"));
+	assert_eq!(account.data[0], MigrationAccount::Archive as u8);
+	assert_eq!(archive.version, 2);
+	assert_eq!(archive.count, PodU64::from_primitive(11));
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn migrate_account_rejects_the_wrong_previous_layout_or_owner() {
+	let mut account = ledger_v1_account(true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut account_view, mut payer) = (account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	assert_eq!(
+		migrate_account::<Ledger>(
+			&mut account_view,
+			&mut payer,
+			&Address::new_from_array([8u8; 32])
+		),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+
+	// A second migration finds the new discriminator and converts nothing.
+	migrate_account::<Ledger>(&mut account_view, &mut payer, &owner)
+		.unwrap_or_else(|e| panic!("migrate: {e:?}"));
+	let migrated = account.data;
+	assert_eq!(
+		migrate_account::<Ledger>(&mut account_view, &mut payer, &owner),
+		Err(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(account.data, migrated);
+}
+
+#[cfg(feature = "account-resize")]
+#[test]
+fn migrate_account_rejects_readonly_accounts() {
+	let mut account = ledger_v1_account(false);
+	let original = account.data;
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut account_view, mut payer) = (account.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	assert_eq!(
+		migrate_account::<Ledger>(&mut account_view, &mut payer, &owner),
+		Err(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(account.data, original);
+}
+
+#[discriminator]
+pub enum HelperAccount {
+	Counter = 1,
+}
+
+#[account(discriminator = HelperAccount)]
+pub struct Counter {
+	pub bump: u8,
+	pub count: PodU64,
+}
+
+#[test]
+fn create_and_init_skips_init_when_creation_fails() {
+	let mut target = TestAccount::<{ size_of::<Counter>() }>::new(
+		Address::new_from_array([1u8; 32]),
+		false,
+		true,
+	);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (mut target, payer) = (target.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+	let mut initialized = false;
+
+	// The rent sysvar is unavailable off-chain, so account creation fails
+	// before the initializer gets a chance to run.
+	let result = create_and_init(&mut target, &payer, &owner, &[b"counter"], 255, || {
+		initialized = true;
+		Counter::builder().bump(255).count_u64(0).build()
+	});
+
+	assert!(result.is_err());
+	assert!(!initialized);
+	assert!(
+		target
+			.try_borrow()
+			.unwrap_or_else(|e| panic!("{e:?}"))
+			.iter()
+			.all(|byte| *byte == 0)
+	);
+}
+
+#[test]
+fn verify_created_account_checks_owner_and_size() {
+	let owner = Address::new_from_array([9u8; 32]);
+	let mut created = TestAccount::<{ size_of::<Counter>() }>::new(
+		Address::new_from_array([1u8; 32]),
+		false,
+		true,
+	);
+	let mut foreign = TestAccount::<{ size_of::<Counter>() }>::new(
+		Address::new_from_array([2u8; 32]),
+		false,
+		true,
+	);
+	foreign.header.owner = Address::new_from_array([3u8; 32]);
+	let mut short = TestAccount::<8>::new(Address::new_from_array([4u8; 32]), false, true);
+	let (created, foreign, short) = (created.view(), foreign.view(), short.view());
+
+	assert_eq!(verify_created_account::<Counter>(&created, &owner), Ok(()));
+	assert_eq!(
+		verify_created_account::<Counter>(&foreign, &owner),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(
+		verify_created_account::<Counter>(&short, &owner),
+		Err(PinaProgramError::InvalidAccountSize.into())
+	);
+}
+
+#[test]
+fn create_program_account_verified_propagates_creation_errors() {
+	let mut target = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), false, true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (target, payer) = (target.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	// The rent sysvar is unavailable off-chain, so creation fails before the
+	// post-create check runs.
+	assert!(
+		create_program_account_verified::<Counter>(&target, &payer, &owner, &[b"counter"]).is_err()
+	);
+}
+
+#[test]
+fn create_rent_exempt_account_requires_the_new_account_to_sign() {
+	let mut target = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), false, true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (target, payer) = (target.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	assert_eq!(
+		create_rent_exempt_account::<Counter>(&target, &payer, &owner),
+		Err(ProgramError::MissingRequiredSignature)
+	);
+}
+
+#[test]
+fn create_rent_exempt_account_propagates_rent_errors() {
+	let mut target = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), true, true);
+	let mut payer = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), true, true);
+	let (target, payer) = (target.view(), payer.view());
+	let owner = Address::new_from_array([9u8; 32]);
+
+	// Off-chain there is no rent sysvar to price the account with.
+	assert!(create_rent_exempt_account::<Counter>(&target, &payer, &owner).is_err());
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn token_cpi_reuses_accounts_across_cpis() {
+	let mut token_program = TestAccount::<0>::new(pina::token_2022::ID, false, false);
+	let mut authority = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), true, false);
+	let mut mint = TestAccount::<8>::new(Address::new_from_array([2u8; 32]), false, false);
+	let mut vault = TestAccount::<8>::new(Address::new_from_array([3u8; 32]), false, true);
+	let mut taker = TestAccount::<8>::new(Address::new_from_array([4u8; 32]), false, true);
+	let mut maker = TestAccount::<0>::new(Address::new_from_array([5u8; 32]), false, true);
+	let (token_program, authority, mint) = (token_program.view(), authority.view(), mint.view());
+	let (vault, taker, maker) = (vault.view(), taker.view(), maker.view());
+	let context = TokenCpi::new(&token_program, &authority, &mint);
+
+	// Off-chain the CPI itself is a no-op, so this checks the account checks
+	// pinocchio runs before invoking.
+	assert_eq!(context.transfer_checked(&vault, &taker, 500, 6), Ok(()));
+	assert_eq!(context.close(&vault, &maker), Ok(()));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn token_cpi_rejects_borrowed_writable_account() {
+	let mut token_program = TestAccount::<0>::new(pina::token_2022::ID, false, false);
+	let mut authority = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), true, false);
+	let mut mint = TestAccount::<8>::new(Address::new_from_array([2u8; 32]), false, false);
+	let mut vault = TestAccount::<8>::new(Address::new_from_array([3u8; 32]), false, true);
+	let mut maker = TestAccount::<0>::new(Address::new_from_array([5u8; 32]), false, true);
+	let (token_program, authority, mint) = (token_program.view(), authority.view(), mint.view());
+	let (vault, maker) = (vault.view(), maker.view());
+	let context = TokenCpi::new(&token_program, &authority, &mint);
+	let data = vault
+		.try_borrow()
+		.unwrap_or_else(|e| panic!("vault borrow: {e:?}"));
+
+	assert_eq!(context.close(&vault, &maker), Err(borrowed_during_cpi()));
+	drop(data);
+}
+
+#[cfg(feature = "token")]
+fn token_account(address: u8, amount: u64) -> TestAccount<165> {
+	let mut account = TestAccount::<165>::new(Address::new_from_array([address; 32]), false, true);
+	account.header.owner = pina::token_2022::ID;
+	account.data[64..72].copy_from_slice(&amount.to_le_bytes());
+	account
+}
+
+#[cfg(feature = "token")]
+fn set_token_amount(mut account: AccountView, amount: u64) {
+	account
+		.try_borrow_mut()
+		.unwrap_or_else(|e| panic!("token account borrow: {e:?}"))[64..72]
+		.copy_from_slice(&amount.to_le_bytes());
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn verify_token_transfer_accepts_exact_deltas() {
+	let mut from = token_account(3, 1_000);
+	let mut to = token_account(4, 50);
+	let (from_view, to_view) = (from.view(), to.view());
+	let (from_writer, to_writer) = (from.view(), to.view());
+
+	let result = verify_token_transfer(&from_view, &to_view, 400, || {
+		set_token_amount(from_writer, 600);
+		set_token_amount(to_writer, 450);
+		Ok(())
+	});
+
+	assert_eq!(result, Ok(()));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn verify_token_transfer_rejects_withheld_fee() {
+	let mut from = token_account(3, 1_000);
+	let mut to = token_account(4, 50);
+	let (from_view, to_view) = (from.view(), to.view());
+	let (from_writer, to_writer) = (from.view(), to.view());
+
+	// A 1% transfer fee is withheld from the recipient.
+	let result = verify_token_transfer(&from_view, &to_view, 400, || {
+		set_token_amount(from_writer, 600);
+		set_token_amount(to_writer, 446);
+		Ok(())
+	});
+
+	assert_eq!(result, Err(PinaProgramError::TokenTransferMismatch.into()));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn transfer_checked_verified_rejects_unmoved_balances() {
+	let mut token_program = TestAccount::<0>::new(pina::token_2022::ID, false, false);
+	let mut authority = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), true, false);
+	let mut mint = TestAccount::<8>::new(Address::new_from_array([2u8; 32]), false, false);
+	let mut from = token_account(3, 1_000);
+	let mut to = token_account(4, 50);
+	let (token_program, authority, mint) = (token_program.view(), authority.view(), mint.view());
+	let (from, to) = (from.view(), to.view());
+
+	// Off-chain the CPI is a no-op, so neither balance moves.
+	assert_eq!(
+		transfer_checked_verified(&from, &to, &mint, &authority, 400, 6, &token_program),
+		Err(PinaProgramError::TokenTransferMismatch.into())
+	);
+}
+
+/// A mint with 6 decimals owned by `token_program`.
+#[cfg(feature = "token")]
+fn mint_account(token_program: Address) -> TestAccount<82> {
+	let mut account = TestAccount::<82>::new(Address::new_from_array([2u8; 32]), false, false);
+	account.header.owner = token_program;
+	account.data[44] = 6;
+	account.data[45] = 1;
+	account
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn transfer_checked_tokens_accepts_either_token_program() {
+	for program_id in [pina::token::ID, pina::token_2022::ID] {
+		let mut token_program = TestAccount::<0>::new(program_id, false, false);
+		let mut authority = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), true, false);
+		let mut mint = mint_account(program_id);
+		let (mut from, mut to) = (token_account(3, 1_000), token_account(4, 0));
+		from.header.owner = program_id;
+		to.header.owner = program_id;
+		let (token_program, authority, mint) =
+			(token_program.view(), authority.view(), mint.view());
+
+		assert_eq!(
+			transfer_checked_tokens(
+				&from.view(),
+				&to.view(),
+				&mint,
+				&authority,
+				400,
+				&token_program
+			),
+			Ok(())
+		);
+	}
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn transfer_checked_tokens_rejects_mixed_programs() {
+	let mut token_program = TestAccount::<0>::new(pina::token_2022::ID, false, false);
+	let mut not_a_token_program = TestAccount::<0>::new(pina::system::ID, false, false);
+	let mut authority = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), true, false);
+	let mut mint = mint_account(pina::token_2022::ID);
+	let mut classic_mint = mint_account(pina::token::ID);
+	let (mut from, mut to) = (token_account(3, 1_000), token_account(4, 0));
+	let mut classic = token_account(5, 0);
+	classic.header.owner = pina::token::ID;
+	let (token_program, not_a_token_program) = (token_program.view(), not_a_token_program.view());
+	let (authority, mint, classic_mint) = (authority.view(), mint.view(), classic_mint.view());
+	let (from, to, classic) = (from.view(), to.view(), classic.view());
+
+	assert_eq!(
+		transfer_checked_tokens(&from, &to, &mint, &authority, 1, &not_a_token_program),
+		Err(ProgramError::InvalidAccountData)
+	);
+	assert_eq!(
+		transfer_checked_tokens(&from, &to, &classic_mint, &authority, 1, &token_program),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+	assert_eq!(
+		transfer_checked_tokens(&from, &classic, &mint, &authority, 1, &token_program),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+}
+
+/// An initialized Token-2022 ATA of wallet `[1; 32]` and mint `[2; 32]`
+/// holding `amount`, with `owner` stored as its token owner.
+#[cfg(feature = "token")]
+fn existing_ata(owner: Address, amount: u64) -> TestAccount<165> {
+	let wallet = Address::new_from_array([1u8; 32]);
+	let mint = Address::new_from_array([2u8; 32]);
+	let (address, _) = try_get_associated_token_address(&wallet, &mint, &pina::token_2022::ID)
+		.unwrap_or_else(|| panic!("ATA should derive"));
+	let mut account = token_account(0, amount);
+	account.header.address = address;
+	account.data[..32].copy_from_slice(mint.as_ref());
+	account.data[32..64].copy_from_slice(owner.as_ref());
+	account.data[108] = 1;
+	account
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn create_or_get_associated_token_account_returns_existing_accounts() {
+	let mut ata = existing_ata(Address::new_from_array([1u8; 32]), 700);
+	let mut reassigned = existing_ata(Address::new_from_array([6u8; 32]), 700);
+	let mut accounts = [
+		TestAccount::<0>::new(Address::new_from_array([5u8; 32]), true, true),
+		TestAccount::<0>::new(Address::new_from_array([1u8; 32]), false, false),
+		TestAccount::<0>::new(Address::new_from_array([2u8; 32]), false, false),
+		TestAccount::<0>::new(pina::system::ID, false, false),
+		TestAccount::<0>::new(pina::token_2022::ID, false, false),
+	];
+	let (ata, reassigned) = (ata.view(), reassigned.view());
+	let [payer, wallet, mint, system_program, token_program] =
+		accounts.each_mut().map(TestAccount::view);
+
+	let state = create_or_get_associated_token_account(
+		&ata,
+		&payer,
+		&wallet,
+		&mint,
+		&system_program,
+		&token_program,
+	)
+	.unwrap_or_else(|e| panic!("existing ATA should load: {e:?}"));
+	assert_eq!(state.amount(), 700);
+	drop(state);
+
+	assert!(matches!(
+		create_or_get_associated_token_account(
+			&reassigned,
+			&payer,
+			&wallet,
+			&mint,
+			&system_program,
+			&token_program,
+		),
+		Err(ProgramError::InvalidAccountData)
+	));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn create_or_get_associated_token_account_checks_program_and_address() {
+	let mut ata = existing_ata(Address::new_from_array([1u8; 32]), 0);
+	let mut other = token_account(3, 0);
+	let mut accounts = [
+		TestAccount::<0>::new(Address::new_from_array([5u8; 32]), true, true),
+		TestAccount::<0>::new(Address::new_from_array([1u8; 32]), false, false),
+		TestAccount::<0>::new(Address::new_from_array([2u8; 32]), false, false),
+		TestAccount::<0>::new(pina::system::ID, false, false),
+		TestAccount::<0>::new(pina::token_2022::ID, false, false),
+	];
+	let (ata, other) = (ata.view(), other.view());
+	let [payer, wallet, mint, system_program, token_program] =
+		accounts.each_mut().map(TestAccount::view);
+
+	assert!(matches!(
+		create_or_get_associated_token_account(
+			&ata,
+			&payer,
+			&wallet,
+			&mint,
+			&system_program,
+			&system_program,
+		),
+		Err(ProgramError::InvalidAccountData)
+	));
+	assert!(matches!(
+		create_or_get_associated_token_account(
+			&other,
+			&payer,
+			&wallet,
+			&mint,
+			&system_program,
+			&token_program,
+		),
+		Err(ProgramError::InvalidSeeds)
+	));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn create_associated_token_account_if_needed_releases_the_borrow() {
+	let mut ata = existing_ata(Address::new_from_array([1u8; 32]), 700);
+	let mut reassigned = existing_ata(Address::new_from_array([6u8; 32]), 700);
+	let mut accounts = [
+		TestAccount::<0>::new(Address::new_from_array([5u8; 32]), true, true),
+		TestAccount::<0>::new(Address::new_from_array([1u8; 32]), false, false),
+		TestAccount::<0>::new(Address::new_from_array([2u8; 32]), false, false),
+		TestAccount::<0>::new(pina::system::ID, false, false),
+		TestAccount::<0>::new(pina::token_2022::ID, false, false),
+	];
+	let (mut ata, reassigned) = (ata.view(), reassigned.view());
+	let [payer, wallet, mint, system_program, token_program] =
+		accounts.each_mut().map(TestAccount::view);
+
+	assert_eq!(
+		create_associated_token_account_if_needed(
+			&ata,
+			&payer,
+			&wallet,
+			&mint,
+			&system_program,
+			&token_program,
+		),
+		Ok(())
+	);
+	assert!(ata.try_borrow_mut().is_ok());
+	assert_eq!(
+		create_associated_token_account_if_needed(
+			&reassigned,
+			&payer,
+			&wallet,
+			&mint,
+			&system_program,
+			&token_program,
+		),
+		Err(ProgramError::InvalidAccountData)
+	);
+}
+
+/// The error a CPI returns for an account the caller still borrows. Debug
+/// builds name the problem; release builds fall back to pinocchio's check.
+fn borrowed_during_cpi() -> ProgramError {
+	if cfg!(debug_assertions) {
+		PinaProgramError::AccountBorrowedDuringCpi.into()
+	} else {
+		ProgramError::AccountBorrowFailed
+	}
+}
+
+fn counter_account() -> TestAccount<{ size_of::<Counter>() }> {
+	let mut account = TestAccount::<{ size_of::<Counter>() }>::new(
+		Address::new_from_array([1u8; 32]),
+		false,
+		true,
+	);
+	account
+		.data
+		.copy_from_slice(Counter::builder().bump(255).count_u64(0).build().to_bytes());
+	account
+}
+
+#[test]
+fn cpi_context_reports_guard_held_across_invoke() {
+	let mut counter = counter_account();
+	let mut other = TestAccount::<8>::new(Address::new_from_array([2u8; 32]), false, false);
+	let (mut counter_view, other_view) = (counter.view(), other.view());
+	let owner = Address::new_from_array([9u8; 32]);
+	let program = Address::new_from_array([6u8; 32]);
+
+	// The CPI reaches the counter through a second view of the same runtime
+	// account, as it would when an instruction lists the account twice.
+	let cpi_view = counter.view();
+
+	let mut state = counter_view
+		.as_account_mut::<Counter>(&owner)
+		.unwrap_or_else(|e| panic!("counter should load: {e:?}"));
+	state.count = PodU64::from_primitive(1);
+
+	let accounts = ExampleAccounts {
+		first: CpiHandle::writable(&cpi_view).unwrap_or_else(|e| panic!("first handle: {e:?}")),
+		second: CpiHandle::readonly(&other_view),
+	};
+
+	assert_eq!(
+		CpiContext::new(&program, accounts).invoke(&[], &[]),
+		Err(borrowed_during_cpi())
+	);
+	drop(state);
+}
+
+#[test]
+fn with_account_mut_releases_borrow_before_invoke() {
+	let mut counter = counter_account();
+	let mut other = TestAccount::<8>::new(Address::new_from_array([2u8; 32]), false, false);
+	let (mut counter_view, other_view) = (counter.view(), other.view());
+	let owner = Address::new_from_array([9u8; 32]);
+	let program = Address::new_from_array([6u8; 32]);
+
+	let count = counter_view
+		.with_account_mut::<Counter, _>(&owner, |state| {
+			state.count = PodU64::from_primitive(1);
+			state.count.get()
+		})
+		.unwrap_or_else(|e| panic!("counter should update: {e:?}"));
+
+	let accounts = ExampleAccounts {
+		first: CpiHandle::writable(&counter_view).unwrap_or_else(|e| panic!("first handle: {e:?}")),
+		second: CpiHandle::readonly(&other_view),
+	};
+
+	assert_eq!(count, 1);
+	assert_eq!(CpiContext::new(&program, accounts).invoke(&[], &[]), Ok(()));
+}
+
+#[test]
+fn event_ix_tag_matches_anchor() {
+	assert_eq!(
+		EVENT_IX_TAG,
+		[0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]
+	);
+	assert!(is_emit_event_instruction(
+		&[EVENT_IX_TAG.as_slice(), &[1, 2]].concat()
+	));
+	assert!(!is_emit_event_instruction(&EVENT_IX_TAG[..7]));
+}
+
+#[test]
+fn process_emit_event_requires_the_signing_event_authority() {
+	let program_id = Address::new_from_array([5u8; 32]);
+	let (address, bump) =
+		find_event_authority(&program_id).unwrap_or_else(|| panic!("no event authority"));
+	let mut signed = TestAccount::<0>::new(address, true, false);
+	let mut unsigned = TestAccount::<0>::new(address, false, false);
+	let mut impostor = TestAccount::<0>::new(Address::new_from_array([6u8; 32]), true, false);
+
+	assert_eq!(
+		process_emit_event(&program_id, &[signed.view()], bump),
+		Ok(())
+	);
+	assert_eq!(
+		process_emit_event(&program_id, &[unsigned.view()], bump),
+		Err(ProgramError::MissingRequiredSignature)
+	);
+	assert_eq!(
+		process_emit_event(&program_id, &[impostor.view()], bump),
+		Err(ProgramError::InvalidSeeds)
+	);
+	assert_eq!(
+		process_emit_event(&program_id, &[], bump),
+		Err(ProgramError::NotEnoughAccountKeys)
+	);
+}
+
+#[test]
+fn process_emit_event_checks_the_address_against_the_bump() {
+	let program_id = Address::new_from_array([5u8; 32]);
+	let (address, bump) =
+		find_event_authority(&program_id).unwrap_or_else(|| panic!("no event authority"));
+	let mut signed = TestAccount::<0>::new(address, true, false);
+
+	// Any other bump derives a different address, or none at all.
+	assert_eq!(
+		process_emit_event(&program_id, &[signed.view()], bump.wrapping_sub(1)),
+		Err(ProgramError::InvalidSeeds)
+	);
+}
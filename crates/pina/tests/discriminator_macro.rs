@@ -35,3 +35,277 @@ pub enum MyAccount {
 	GameState = 1,
 	SectionState = 2,
 }
+
+#[discriminator(crate = ::pina, primitive = u32)]
+pub enum SparseDiscriminator {
+	Low = 3,
+	High = 40_000,
+	Middle = 7,
+}
+
+#[test]
+fn test_discriminator_max() {
+	assert_eq!(MyDiscriminator::MAX, 1);
+	assert_eq!(MyAccount::MAX, 2);
+	// The maximum doesn't depend on declaration order.
+	assert_eq!(SparseDiscriminator::MAX, 40_000);
+}
+
+#[discriminator(primitive = u16, endian = "big")]
+#[derive(Debug, PartialEq)]
+pub enum BridgeDiscriminator {
+	Deposit = 0x0102,
+	Withdraw = 0x0304,
+}
+
+#[discriminator(primitive = u32, endian = big)]
+pub enum BareEndianDiscriminator {
+	Relay = 0x0102_0304,
+}
+
+#[test]
+fn test_discriminator_big_endian_roundtrip() {
+	let mut bytes = [0u8; 2];
+	BridgeDiscriminator::Withdraw.write_discriminator(&mut bytes);
+	assert_eq!(bytes, [0x03, 0x04]); // big-endian for u16
+
+	let decoded = BridgeDiscriminator::discriminator_from_bytes(&bytes).unwrap();
+	assert_eq!(decoded, BridgeDiscriminator::Withdraw);
+	assert!(BridgeDiscriminator::Withdraw.matches_discriminator(&bytes));
+	assert!(!BridgeDiscriminator::Deposit.matches_discriminator(&bytes));
+
+	// The explicit big-endian codec agrees with the default one.
+	let mut be_bytes = [0u8; 2];
+	BridgeDiscriminator::Withdraw.write_discriminator_be(&mut be_bytes);
+	assert_eq!(be_bytes, bytes);
+	assert_eq!(
+		BridgeDiscriminator::discriminator_from_bytes_be(&be_bytes).unwrap(),
+		BridgeDiscriminator::Withdraw
+	);
+
+	// `endian` also accepts a bare identifier.
+	let mut bare = [0u8; 4];
+	BareEndianDiscriminator::Relay.write_discriminator(&mut bare);
+	assert_eq!(bare, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_discriminator_bytes_match_patterns() {
+	let dispatch = |data: &[u8]| {
+		match data.get(..2) {
+			Some(MyAccount::CONFIG_STATE_BYTES) => Some(MyAccount::ConfigState as u16),
+			Some(MyAccount::GAME_STATE_BYTES) => Some(MyAccount::GameState as u16),
+			_ => None,
+		}
+	};
+
+	assert_eq!(dispatch(&[1, 0, 9]), Some(1));
+	assert_eq!(dispatch(&[0, 0]), Some(0));
+	assert_eq!(dispatch(&[0, 1]), None);
+	assert_eq!(dispatch(&[1]), None);
+
+	assert_eq!(BridgeDiscriminator::WITHDRAW_BYTES, &[0x03, 0x04]);
+}
+
+test_discriminator_exhaustive!(SparseDiscriminator, u32);
+
+/// A hand-written discriminator whose `TryFrom` wrongly accepts an unassigned
+/// value, as a forgotten match arm cleanup might.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GappyDiscriminator {
+	Open = 0,
+	Close = 1,
+}
+
+impl GappyDiscriminator {
+	pub const VARIANTS: &'static [Self] = &[Self::Open, Self::Close];
+}
+
+impl TryFrom<u8> for GappyDiscriminator {
+	type Error = ProgramError;
+
+	fn try_from(value: u8) -> Result<Self, ProgramError> {
+		match value {
+			0 | 7 => Ok(Self::Open),
+			1 => Ok(Self::Close),
+			_ => Err(PinaProgramError::InvalidDiscriminator.into()),
+		}
+	}
+}
+
+test_discriminator_exhaustive!(
+	GappyDiscriminator,
+	u8,
+	#[should_panic(expected = "unassigned discriminator value 7 was not rejected")]
+	test_discriminator_exhaustive_catches_gaps
+);
+
+#[discriminator(format = anchor)]
+#[derive(Debug)]
+pub enum AnchorInstruction {
+	Initialize,
+	SetValue,
+}
+
+#[discriminator(format = anchor, namespace = account)]
+#[derive(Debug)]
+pub enum AnchorAccount {
+	Vault,
+}
+
+#[instruction(discriminator = AnchorInstruction, variant = Initialize, format = anchor)]
+pub struct InitializeInstruction {
+	pub amount: PodU64,
+}
+
+#[account(discriminator = AnchorAccount, format = anchor)]
+pub struct Vault {
+	pub amount: PodU64,
+}
+
+#[test]
+fn test_discriminator_anchor_format() {
+	assert_eq!(AnchorInstruction::BYTES, 8);
+	assert_eq!(
+		AnchorInstruction::INITIALIZE_BYTES,
+		[175, 175, 109, 31, 13, 152, 155, 237]
+	);
+	assert_eq!(
+		AnchorInstruction::SET_VALUE_BYTES,
+		anchor_instruction_discriminator("set_value")
+	);
+
+	let data = anchor_instruction_discriminator("set_value");
+	assert_eq!(
+		AnchorInstruction::discriminator_from_bytes(&data),
+		Ok(AnchorInstruction::SetValue)
+	);
+
+	let vault = Vault::builder().amount(PodU64::from_primitive(5)).build();
+	assert_eq!(
+		&vault.to_bytes()[..8],
+		anchor_account_discriminator("Vault")
+	);
+	assert!(Vault::matches_discriminator(vault.to_bytes()));
+
+	let initialize = InitializeInstruction::builder()
+		.amount(PodU64::from_primitive(1))
+		.build();
+	assert_eq!(
+		&initialize.to_bytes()[..8],
+		anchor_instruction_discriminator("initialize")
+	);
+}
+
+#[discriminator(crate = ::pina, primitive = u16)]
+#[derive(Debug)]
+pub enum ImplicitDiscriminator {
+	First,
+	Second,
+	Tenth = 10,
+	Eleventh,
+}
+
+#[test]
+fn test_discriminator_implicit_values_count_up() {
+	assert_eq!(u16::from(ImplicitDiscriminator::First), 0);
+	assert_eq!(u16::from(ImplicitDiscriminator::Second), 1);
+	assert_eq!(u16::from(ImplicitDiscriminator::Eleventh), 11);
+	assert_eq!(ImplicitDiscriminator::MAX, 11);
+	assert_eq!(
+		ImplicitDiscriminator::try_from(11u16),
+		Ok(ImplicitDiscriminator::Eleventh)
+	);
+	assert!(ImplicitDiscriminator::try_from(2u16).is_err());
+}
+
+#[discriminator(crate = ::pina, lock = "tests/fixtures/discriminators.toml")]
+#[derive(Debug)]
+pub enum LockedInstruction {
+	Initialize,
+	Deposit,
+	Withdraw = 5,
+	// Added after the lock was written, so it is not pinned yet.
+	Close,
+}
+
+#[test]
+fn test_discriminator_lock_allows_new_variants() {
+	assert_eq!(LockedInstruction::Close as u8, 6);
+	assert_eq!(
+		LockedInstruction::try_from(1u8),
+		Ok(LockedInstruction::Deposit)
+	);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashedInstruction {
+	Deposit,
+	Withdraw,
+}
+
+into_discriminator!(HashedInstruction, [u8; 8], {
+	Deposit => anchor_instruction_discriminator("deposit"),
+	Withdraw => anchor_instruction_discriminator("withdraw"),
+});
+
+#[instruction(crate = ::pina, discriminator = HashedInstruction, variant = Withdraw)]
+pub struct WithdrawInstruction {
+	pub amount: PodU64,
+}
+
+#[test]
+fn test_array_discriminators_round_trip() {
+	let tag = anchor_instruction_discriminator("withdraw");
+	let mut data = [0u8; 9];
+	HashedInstruction::Withdraw.write_discriminator(&mut data);
+
+	assert_eq!(data[..8], tag);
+	assert!(HashedInstruction::Withdraw.matches_discriminator(&data));
+	assert!(!HashedInstruction::Deposit.matches_discriminator(&data));
+	assert_eq!(
+		HashedInstruction::discriminator_from_bytes(&data),
+		Ok(HashedInstruction::Withdraw)
+	);
+	assert_eq!(
+		HashedInstruction::discriminator_from_bytes(&[0u8; 8]),
+		Err(PinaProgramError::InvalidDiscriminator.into())
+	);
+	assert_eq!(
+		HashedInstruction::discriminator_from_bytes(&tag[..7]),
+		Err(ProgramError::InvalidInstructionData)
+	);
+
+	// Byte tags have no byte order.
+	let mut be = [0u8; 8];
+	HashedInstruction::Withdraw.write_discriminator_be(&mut be);
+	assert_eq!(be, tag);
+}
+
+#[test]
+fn test_array_discriminators_in_instruction_layouts() {
+	let withdraw = WithdrawInstruction::builder().amount_u64(5).build();
+	let bytes = withdraw.to_bytes();
+
+	assert_eq!(bytes.len(), 16);
+	assert_eq!(bytes[..8], anchor_instruction_discriminator("withdraw"));
+	assert!(WithdrawInstruction::matches_discriminator(bytes));
+	assert_eq!(
+		WithdrawInstruction::try_from_bytes_with_rest(bytes).map(|(ix, _)| ix.amount),
+		Ok(PodU64::from_primitive(5))
+	);
+}
+
+#[test]
+fn test_raw_byte_array_discriminators() {
+	let tag = *b"tag!";
+	let mut data = [0u8; 6];
+	tag.write_discriminator(&mut data);
+
+	assert_eq!(<[u8; 4]>::BYTES, 4);
+	assert_eq!(&data, b"tag!\0\0");
+	assert!(tag.matches_discriminator(&data));
+	assert_eq!(<[u8; 4]>::discriminator_from_bytes(&data), Ok(tag));
+	assert!(!tag.matches_discriminator(&data[..3]));
+}
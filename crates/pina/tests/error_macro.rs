@@ -23,3 +23,24 @@ fn test_error_macro() {
 		_ => panic!("Wrong error type"),
 	}
 }
+
+#[error(crate = ::pina, offset = 6000)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowError {
+	#[msg("The escrow has already been settled")]
+	Settled = 0,
+	Expired = 1,
+}
+
+#[test]
+fn test_error_offset_rebases_codes() {
+	assert_eq!(EscrowError::Settled.code(), 6000);
+	assert_eq!(
+		ProgramError::from(EscrowError::Settled),
+		ProgramError::Custom(6000)
+	);
+	assert_eq!(
+		ProgramError::from(EscrowError::Expired),
+		ProgramError::Custom(6001)
+	);
+}
@@ -0,0 +1,24 @@
+use pina::*;
+
+#[error(crate = ::pina)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultError {
+	#[msg("The vault is locked until the unlock slot")]
+	Locked = 0,
+	Empty = 1,
+}
+
+#[test]
+fn test_error_messages() {
+	assert_eq!(
+		VaultError::Locked.message(),
+		"The vault is locked until the unlock slot"
+	);
+	assert_eq!(VaultError::Empty.message(), "Empty");
+
+	// Converting still produces the plain custom code after logging.
+	assert_eq!(
+		ProgramError::from(VaultError::Empty),
+		ProgramError::Custom(1)
+	);
+}
@@ -30,3 +30,56 @@ fn test_event_bytes() {
 	assert_eq!(event.discriminator, from_bytes.discriminator);
 	assert_eq!(event.choice, from_bytes.choice);
 }
+
+/// Format an event the way the runtime records `sol_log_data` output.
+#[cfg(feature = "client")]
+fn program_data_log(event: Initialize) -> String {
+	use base64::Engine;
+
+	let encoded = base64::engine::general_purpose::STANDARD.encode(event.to_bytes());
+	format!("{}{encoded}", events::PROGRAM_DATA_PREFIX)
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_event_from_log() {
+	use pina::events::EventLog;
+
+	let event = Initialize::builder().choice(42).build();
+	let line = program_data_log(event);
+
+	assert_eq!(Initialize::from_log(&line), Some(event));
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_decode_event_splits_discriminator() {
+	let event = Initialize::builder().choice(7).build();
+	let line = program_data_log(event);
+
+	let (discriminator, payload) = events::decode_event::<Event>(&line).unwrap();
+	assert_eq!(discriminator, Event::Initialize);
+	assert_eq!(payload, vec![7]);
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_event_from_log_rejects_other_events() {
+	use pina::events::EventLog;
+
+	assert_eq!(Initialize::from_log("Program data: AQc="), None);
+	assert_eq!(Initialize::from_log("Program data: not base64"), None);
+	assert_eq!(Initialize::from_log("Program data: "), None);
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_event_from_cpi_data() {
+	use pina::events::EventLog;
+
+	let event = Initialize::builder().choice(3).build();
+	let data = [EVENT_IX_TAG.as_slice(), event.to_bytes()].concat();
+
+	assert_eq!(Initialize::from_cpi_data(&data), Some(event));
+	assert_eq!(Initialize::from_cpi_data(event.to_bytes()), None);
+}
@@ -45,3 +45,73 @@ fn test_instruction_macro() {
 
 	assert_eq!(flip_bit, *flip_bit_from_bytes);
 }
+
+#[test]
+fn test_instruction_try_from_bytes_with_rest() {
+	let flip_bit = FlipBit::builder()
+		.section_index(1)
+		.array_index(2)
+		.offset(3)
+		.value(1)
+		.build();
+	let mut data = flip_bit.to_bytes().to_vec();
+	data.extend_from_slice(b"memo");
+
+	let (parsed, rest) = FlipBit::try_from_bytes_with_rest(&data)
+		.unwrap_or_else(|e| panic!("instruction with payload should parse: {e:?}"));
+	assert_eq!(parsed, &flip_bit);
+	assert_eq!(rest, b"memo");
+
+	let (_, empty) = FlipBit::try_from_bytes_with_rest(flip_bit.to_bytes())
+		.unwrap_or_else(|e| panic!("instruction without payload should parse: {e:?}"));
+	assert!(empty.is_empty());
+
+	assert_eq!(
+		FlipBit::try_from_bytes_with_rest(&data[..3]),
+		Err(ProgramError::InvalidInstructionData)
+	);
+
+	// Another instruction's discriminator is rejected even though the bytes
+	// are long enough.
+	data[0] = MyInstruction::Another as u8;
+	assert_eq!(
+		FlipBit::try_from_bytes_with_rest(&data),
+		Err(ProgramError::InvalidInstructionData)
+	);
+}
+
+#[cfg(feature = "client")]
+#[derive(Accounts)]
+pub struct FlipBitAccounts<'a> {
+	#[pina(signer)]
+	pub authority: &'a AccountView,
+	pub state: &'a mut AccountView,
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_instruction_build_instruction() {
+	use pina::transaction::AccountMeta;
+
+	let program_id = Address::new_from_array([7u8; 32]);
+	let authority = Address::new_from_array([1u8; 32]);
+	let state = Address::new_from_array([2u8; 32]);
+	let flip_bit = FlipBit::builder()
+		.section_index(1)
+		.array_index(2)
+		.offset(3)
+		.value(1)
+		.build();
+
+	let ix = flip_bit.build_instruction(&program_id, &FlipBitAccountsKeys { authority, state });
+
+	assert_eq!(ix.program_id, program_id);
+	assert_eq!(
+		ix.accounts,
+		vec![
+			AccountMeta::new_readonly(authority, true),
+			AccountMeta::new(state, false)
+		]
+	);
+	assert_eq!(ix.data, flip_bit.to_bytes());
+}
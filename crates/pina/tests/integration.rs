@@ -44,6 +44,8 @@ pub enum TestInstruction {
 #[discriminator(crate = ::pina)]
 pub enum TestAccountType {
 	TestState = 1,
+	BalanceState = 2,
+	SequenceState = 3,
 }
 
 /// On-chain state for the test program.
@@ -63,6 +65,26 @@ pub struct TestState {
 	pub value: PodU64,
 }
 
+/// Ledger-style state used to exercise `transfer_between` and
+/// `authorize_and_load_mut`.
+#[account(crate = ::pina, discriminator = TestAccountType)]
+#[derive(HasBalance, HasAuthority)]
+pub struct BalanceState {
+	#[pina(authority)]
+	pub owner: Address,
+	#[pina(balance)]
+	pub amount: PodU64,
+}
+
+/// Order-book style state used to exercise `next_sequence`.
+#[account(crate = ::pina, discriminator = TestAccountType)]
+#[derive(HasSequence)]
+pub struct SequenceState {
+	pub authority: Address,
+	#[pina(sequence)]
+	pub next_id: PodU64,
+}
+
 /// Instruction data for Initialize.
 #[instruction(crate = ::pina, discriminator = TestInstruction, variant = Initialize)]
 pub struct InitializeInstr {
@@ -189,6 +211,39 @@ impl<'a> ProcessAccountInfos<'a> for CloseAccounts<'a> {
 	}
 }
 
+/// The same program's `Update` flow written as `#[program]` handlers.
+#[program(crate = ::pina, program_id = TEST_PROGRAM_ID, no_entrypoint)]
+pub mod macro_program {
+	use super::*;
+
+	pub fn check_authority(accounts: UpdateAccounts<'_>) -> ProgramResult {
+		accounts.authority.assert_signer()?;
+
+		Ok(())
+	}
+
+	#[pina(discriminator = 5)]
+	pub fn set_value(accounts: UpdateAccounts<'_>, data: &[u8]) -> ProgramResult {
+		accounts.authority.assert_signer()?;
+
+		let mut state = accounts
+			.state_account
+			.as_account_mut::<TestState>(&TEST_PROGRAM_ID)?;
+		state.value = PodU64::from_primitive(read_value(data)?);
+
+		Ok(())
+	}
+
+	fn read_value(data: &[u8]) -> Result<u64, ProgramError> {
+		let bytes = data
+			.get(1..9)
+			.and_then(|bytes| <[u8; 8]>::try_from(bytes).ok())
+			.ok_or(ProgramError::InvalidInstructionData)?;
+
+		Ok(u64::from_le_bytes(bytes))
+	}
+}
+
 /// Top-level instruction dispatch.
 fn process_instruction(
 	program_id: &Address,
@@ -642,6 +697,67 @@ fn full_account_lifecycle() {
 	);
 }
 
+// ---------------------------------------------------------------------------
+// Test: #[program] dispatch
+// ---------------------------------------------------------------------------
+
+/// Tests that the `#[program]` dispatcher routes each discriminator to its
+/// handler and rejects unknown instructions.
+#[test]
+fn program_macro_dispatches_to_handlers() {
+	let authority_key: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let state_key: Address = address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki");
+	let state_bytes = build_test_state_bytes(3, 10);
+	let accounts = [
+		AccountBuilder::new()
+			.address(authority_key)
+			.owner(system::ID)
+			.lamports(1_000_000)
+			.is_signer(true),
+		AccountBuilder::new()
+			.address(state_key)
+			.owner(TEST_PROGRAM_ID)
+			.lamports(890_880)
+			.data(&state_bytes)
+			.is_writable(true),
+	];
+
+	let mut data = [5u8; 9];
+	data[1..].copy_from_slice(&77u64.to_le_bytes());
+
+	let mut input = unsafe { create_test_input(&accounts, &data) };
+	let mut accts = [UNINIT; 10];
+	let (program_id, account_views, ix_data, _) =
+		unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+
+	assert_eq!(
+		macro_program::MacroProgramInstruction::CheckAuthority as u8,
+		0
+	);
+	assert_eq!(
+		macro_program::process_instruction(program_id, account_views, ix_data),
+		Ok(())
+	);
+	let state = account_views[1]
+		.as_account::<TestState>(&TEST_PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("read failed: {e:?}"));
+	assert_eq!(u64::from(state.value), 77);
+	drop(state);
+
+	assert_eq!(
+		macro_program::process_instruction(program_id, account_views, &[0]),
+		Ok(())
+	);
+	assert_eq!(
+		macro_program::process_instruction(program_id, account_views, &[1]),
+		Err(ProgramError::InvalidInstructionData)
+	);
+	assert_eq!(
+		macro_program::process_instruction(&system::ID, account_views, &[0]),
+		Err(ProgramError::IncorrectProgramId)
+	);
+}
+
 // ---------------------------------------------------------------------------
 // Test: Multi-instruction flow
 // ---------------------------------------------------------------------------
@@ -1379,6 +1495,369 @@ fn close_account_zeroed_clears_source_bytes_before_close() {
 	assert_eq!(recipient.lamports(), 1_000_000);
 }
 
+#[test]
+fn close_account_secure_marks_revived_accounts_as_closed() {
+	let account_key: Address = address!("2Eg4H7V2Cd9uBSXreMKe1KjEo9e4NMpM4GZpsLtkj6pp");
+	let recipient_key: Address = address!("9Z6iYoJ1E9nQ7h6nC3ieUzYDRKsAqPPYUajF2iGaGLjm");
+	let state_data = build_test_state_bytes(9, 99);
+
+	let accounts = [
+		AccountBuilder::new()
+			.address(account_key)
+			.owner(TEST_PROGRAM_ID)
+			.lamports(600_000)
+			.data(&state_data)
+			.is_writable(true),
+		AccountBuilder::new()
+			.address(recipient_key)
+			.owner(system::ID)
+			.lamports(400_000)
+			.is_writable(true),
+	];
+
+	let dummy_data: &[u8] = &[0u8];
+	let mut input = unsafe { create_test_input(&accounts, dummy_data) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (closed_accounts, recipient_accounts) = account_views.split_at_mut(1);
+	let closed_account = &mut closed_accounts[0];
+	let recipient = &mut recipient_accounts[0];
+
+	assert!(closed_account.assert_not_closed().is_ok());
+
+	let result = closed_account.close_account_secure(recipient);
+	assert!(result.is_ok(), "close should succeed: {result:?}");
+	assert_eq!(closed_account.lamports(), 0);
+	assert_eq!(recipient.lamports(), 1_000_000);
+
+	// A later instruction sends lamports back to revive the account.
+	closed_account.set_lamports(600_000);
+
+	{
+		let data = closed_account
+			.try_borrow()
+			.unwrap_or_else(|e| panic!("closed account should be readable: {e:?}"));
+		assert_eq!(data.len(), state_data.len());
+		assert_eq!(data[..8], CLOSED_ACCOUNT_DISCRIMINATOR);
+		assert!(data[8..].iter().all(|byte| *byte == 0));
+	}
+
+	assert_eq!(
+		closed_account.assert_not_closed().err(),
+		Some(PinaProgramError::AccountClosed.into())
+	);
+	assert!(
+		closed_account
+			.assert_type::<TestState>(&TEST_PROGRAM_ID)
+			.is_err()
+	);
+}
+
+// ---------------------------------------------------------------------------
+// Test: transfer_between
+// ---------------------------------------------------------------------------
+
+fn build_balance_state_bytes(owner: &Address, amount: u64) -> Vec<u8> {
+	let state = BalanceState::builder()
+		.owner(*owner)
+		.amount(PodU64::from_primitive(amount))
+		.build();
+	bytemuck::bytes_of(&state).to_vec()
+}
+
+fn balance_accounts(source_amount: u64, dest_amount: u64) -> [AccountBuilder; 2] {
+	let owner: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+
+	[
+		AccountBuilder::new()
+			.address(address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki"))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1_000_000)
+			.data(&build_balance_state_bytes(&owner, source_amount))
+			.is_writable(true),
+		AccountBuilder::new()
+			.address(address!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS"))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1_000_000)
+			.data(&build_balance_state_bytes(&owner, dest_amount))
+			.is_writable(true),
+	]
+}
+
+fn read_balance(account: AccountView) -> u64 {
+	account
+		.as_account::<BalanceState>(&TEST_PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("balance state should load: {e:?}"))
+		.balance()
+}
+
+#[test]
+fn transfer_between_moves_balance() {
+	let accounts = balance_accounts(100, 5);
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (source_accounts, dest_accounts) = account_views.split_at_mut(1);
+	let source = &mut source_accounts[0];
+	let dest = &mut dest_accounts[0];
+
+	let result = transfer_between::<BalanceState>(source, dest, 40, &TEST_PROGRAM_ID);
+	assert!(result.is_ok(), "transfer should succeed: {result:?}");
+
+	assert_eq!(read_balance(*source), 60);
+	assert_eq!(read_balance(*dest), 45);
+}
+
+#[test]
+fn transfer_between_rejects_insufficient_funds() {
+	let accounts = balance_accounts(10, 0);
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (source_accounts, dest_accounts) = account_views.split_at_mut(1);
+	let source = &mut source_accounts[0];
+	let dest = &mut dest_accounts[0];
+
+	let result = transfer_between::<BalanceState>(source, dest, 11, &TEST_PROGRAM_ID);
+	assert_eq!(result, Err(ProgramError::InsufficientFunds));
+
+	assert_eq!(read_balance(*source), 10);
+	assert_eq!(read_balance(*dest), 0);
+}
+
+#[test]
+fn transfer_between_rejects_destination_overflow() {
+	let accounts = balance_accounts(10, u64::MAX);
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (source_accounts, dest_accounts) = account_views.split_at_mut(1);
+	let source = &mut source_accounts[0];
+	let dest = &mut dest_accounts[0];
+
+	let result = transfer_between::<BalanceState>(source, dest, 1, &TEST_PROGRAM_ID);
+	assert_eq!(result, Err(ProgramError::ArithmeticOverflow));
+
+	assert_eq!(read_balance(*source), 10);
+	assert_eq!(read_balance(*dest), u64::MAX);
+}
+
+/// Passing the same account as source and destination must be rejected
+/// before either balance is touched.
+#[test]
+fn transfer_between_rejects_same_account() {
+	let accounts = balance_accounts(100, 0);
+	let mut input = unsafe { create_test_input(&accounts[..1], &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let mut source = account_views[0];
+	let mut dest = account_views[0];
+
+	let result = transfer_between::<BalanceState>(&mut source, &mut dest, 50, &TEST_PROGRAM_ID);
+	assert_eq!(result, Err(ProgramError::InvalidArgument));
+
+	assert_eq!(read_balance(source), 100);
+}
+
+// ---------------------------------------------------------------------------
+// Test: authorize_and_load_mut
+// ---------------------------------------------------------------------------
+
+#[test]
+fn authorize_and_load_mut_returns_state_for_authority() {
+	let owner: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let accounts = [
+		AccountBuilder::new().address(owner).is_signer(true),
+		AccountBuilder::new()
+			.address(address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki"))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1_000_000)
+			.data(&build_balance_state_bytes(&owner, 10))
+			.is_writable(true),
+	];
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (signer_accounts, state_accounts) = account_views.split_at_mut(1);
+
+	let mut state = authorize_and_load_mut::<BalanceState>(
+		&mut state_accounts[0],
+		&signer_accounts[0],
+		&TEST_PROGRAM_ID,
+	)
+	.unwrap_or_else(|e| panic!("authority should load the state: {e:?}"));
+	state.amount = PodU64::from_primitive(99);
+	drop(state);
+
+	assert_eq!(read_balance(state_accounts[0]), 99);
+}
+
+#[test]
+fn authorize_and_load_mut_rejects_other_signer() {
+	let owner: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let intruder: Address = address!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS");
+	let accounts = [
+		AccountBuilder::new().address(intruder).is_signer(true),
+		AccountBuilder::new()
+			.address(address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki"))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1_000_000)
+			.data(&build_balance_state_bytes(&owner, 10))
+			.is_writable(true),
+	];
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (signer_accounts, state_accounts) = account_views.split_at_mut(1);
+
+	let result = authorize_and_load_mut::<BalanceState>(
+		&mut state_accounts[0],
+		&signer_accounts[0],
+		&TEST_PROGRAM_ID,
+	);
+
+	assert_eq!(result.err(), Some(ProgramError::IncorrectAuthority));
+}
+
+#[test]
+fn authorize_and_load_mut_requires_signature() {
+	let owner: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let accounts = [
+		AccountBuilder::new().address(owner),
+		AccountBuilder::new()
+			.address(address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki"))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1_000_000)
+			.data(&build_balance_state_bytes(&owner, 10))
+			.is_writable(true),
+	];
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (signer_accounts, state_accounts) = account_views.split_at_mut(1);
+
+	let result = authorize_and_load_mut::<BalanceState>(
+		&mut state_accounts[0],
+		&signer_accounts[0],
+		&TEST_PROGRAM_ID,
+	);
+
+	assert_eq!(result.err(), Some(ProgramError::MissingRequiredSignature));
+}
+
+#[test]
+fn authorize_and_load_mut_requires_a_writable_account() {
+	let owner: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let accounts = [
+		AccountBuilder::new().address(owner).is_signer(true),
+		AccountBuilder::new()
+			.address(address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki"))
+			.owner(TEST_PROGRAM_ID)
+			.lamports(1_000_000)
+			.data(&build_balance_state_bytes(&owner, 10)),
+	];
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+	let (signer_accounts, state_accounts) = account_views.split_at_mut(1);
+
+	let result = authorize_and_load_mut::<BalanceState>(
+		&mut state_accounts[0],
+		&signer_accounts[0],
+		&TEST_PROGRAM_ID,
+	);
+
+	assert_eq!(result.err(), Some(ProgramError::InvalidAccountData));
+}
+
+// ---------------------------------------------------------------------------
+// Test: update_validated
+// ---------------------------------------------------------------------------
+
+fn update_balance(amount: u64, limit: u64) -> (ProgramResult, u64) {
+	let owner: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let accounts = [AccountBuilder::new()
+		.address(address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki"))
+		.owner(TEST_PROGRAM_ID)
+		.lamports(1_000_000)
+		.data(&build_balance_state_bytes(&owner, 10))
+		.is_writable(true)];
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+
+	let result = update_validated::<BalanceState>(
+		&mut account_views[0],
+		&TEST_PROGRAM_ID,
+		|state| state.amount = PodU64::from_primitive(amount),
+		|state| u64::from(state.amount) <= limit,
+	);
+
+	(result, read_balance(account_views[0]))
+}
+
+#[test]
+fn update_validated_applies_update_within_invariant() {
+	let (result, balance) = update_balance(40, 50);
+
+	assert_eq!(result, Ok(()));
+	assert_eq!(balance, 40);
+}
+
+#[test]
+fn update_validated_rejects_update_violating_invariant() {
+	let (result, balance) = update_balance(60, 50);
+
+	assert_eq!(result, Err(ProgramError::InvalidAccountData));
+	assert_eq!(balance, 10);
+}
+
+// ---------------------------------------------------------------------------
+// Test: next_sequence
+// ---------------------------------------------------------------------------
+
+fn next_sequence_twice(start: u64) -> (Result<u64, ProgramError>, Result<u64, ProgramError>, u64) {
+	let state = SequenceState::builder()
+		.authority(address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY"))
+		.next_id(PodU64::from_primitive(start))
+		.build();
+	let accounts = [AccountBuilder::new()
+		.address(address!("3Jiy8N6ZGv3ueH9k3svLRaHscmQbE6v7W9FHJaGH2mki"))
+		.owner(TEST_PROGRAM_ID)
+		.lamports(1_000_000)
+		.data(bytemuck::bytes_of(&state))
+		.is_writable(true)];
+	let mut input = unsafe { create_test_input(&accounts, &[0u8]) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+
+	let first = next_sequence::<SequenceState>(&mut account_views[0], &TEST_PROGRAM_ID);
+	let second = next_sequence::<SequenceState>(&mut account_views[0], &TEST_PROGRAM_ID);
+	let stored = account_views[0]
+		.as_account::<SequenceState>(&TEST_PROGRAM_ID)
+		.unwrap_or_else(|e| panic!("sequence state should load: {e:?}"))
+		.sequence();
+
+	(first, second, stored)
+}
+
+#[test]
+fn next_sequence_returns_current_and_increments() {
+	assert_eq!(next_sequence_twice(7), (Ok(7), Ok(8), 9));
+}
+
+#[test]
+fn next_sequence_rejects_overflow() {
+	assert_eq!(
+		next_sequence_twice(u64::MAX - 1),
+		(
+			Ok(u64::MAX - 1),
+			Err(ProgramError::ArithmeticOverflow),
+			u64::MAX
+		)
+	);
+}
+
 // ---------------------------------------------------------------------------
 // Test: AccountView validation chain
 // ---------------------------------------------------------------------------
@@ -1838,6 +2317,123 @@ fn as_associated_token_account_checked_accepts_token_2022_owner() {
 	assert!(shadow.try_borrow_mut().is_ok());
 }
 
+#[cfg(feature = "token")]
+fn mint_info_result(owner: &Address) -> Result<(u64, u8), ProgramError> {
+	let mint_key: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+	let mint_data = build_token_mint_bytes(9, 5_000_000);
+
+	let accounts = [AccountBuilder::new()
+		.address(mint_key)
+		.owner(*owner)
+		.lamports(1_000_000)
+		.data(&mint_data)];
+
+	let dummy_data: &[u8] = &[0u8];
+	let mut input = unsafe { create_test_input(&accounts, dummy_data) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+
+	mint_info(&account_views[0], &token::ID)
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn mint_info_reads_supply_and_decimals() {
+	assert_eq!(mint_info_result(&token::ID), Ok((5_000_000, 9)));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn mint_info_rejects_other_token_program() {
+	assert_eq!(
+		mint_info_result(&token_2022::ID),
+		Err(ProgramError::InvalidAccountOwner)
+	);
+}
+
+#[cfg(feature = "token")]
+fn owned_ata_result(stored_owner: &Address) -> ProgramResult {
+	let wallet: Address = address!("4Nd1mL5g7dUvNbKQjnYQgQki71RJKVQ1BM8DT6vKrrf5");
+	let mint: Address = address!("CktRuQ2mttxyPjdvVSxGJySLjeRGna43E77gzHu6HotE");
+	let (ata_address, _bump) = try_get_associated_token_address(&wallet, &mint, &token::ID)
+		.unwrap_or_else(|| panic!("failed to derive ata"));
+	let token_account_data = build_token_account_bytes(&mint, stored_owner, 10);
+
+	let accounts = [AccountBuilder::new()
+		.address(ata_address)
+		.owner(token::ID)
+		.lamports(1_000_000)
+		.data(&token_account_data)
+		.is_writable(true)];
+
+	let dummy_data: &[u8] = &[0u8];
+	let mut input = unsafe { create_test_input(&accounts, dummy_data) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+
+	assert_owned_ata(&account_views[0], &wallet, &mint, &token::ID)
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_owned_ata_accepts_wallet_owned_ata() {
+	let wallet: Address = address!("4Nd1mL5g7dUvNbKQjnYQgQki71RJKVQ1BM8DT6vKrrf5");
+
+	assert_eq!(owned_ata_result(&wallet), Ok(()));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_owned_ata_rejects_mismatched_stored_owner() {
+	let other: Address = address!("BHvLHF6mJpWxywWY5S2tsHdDtHirHyeRxoS6uF6T5FoY");
+
+	assert_eq!(
+		owned_ata_result(&other),
+		Err(ProgramError::InvalidAccountData)
+	);
+}
+
+#[cfg(feature = "token")]
+fn initialized_ata_result(data: &[u8]) -> ProgramResult {
+	let wallet: Address = address!("4Nd1mL5g7dUvNbKQjnYQgQki71RJKVQ1BM8DT6vKrrf5");
+	let mint: Address = address!("CktRuQ2mttxyPjdvVSxGJySLjeRGna43E77gzHu6HotE");
+	let (ata_address, _bump) = try_get_associated_token_address(&wallet, &mint, &token::ID)
+		.unwrap_or_else(|| panic!("failed to derive ata"));
+
+	let accounts = [AccountBuilder::new()
+		.address(ata_address)
+		.owner(token::ID)
+		.lamports(1_000_000)
+		.data(data)
+		.is_writable(true)];
+
+	let dummy_data: &[u8] = &[0u8];
+	let mut input = unsafe { create_test_input(&accounts, dummy_data) };
+	let mut accts = [UNINIT; 10];
+	let (_, account_views, ..) = unsafe { deserialize_test_input::<10>(&mut input, &mut accts) };
+
+	assert_initialized_ata(&account_views[0], &wallet, &mint, &token::ID)
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_initialized_ata_accepts_initialized_ata() {
+	let wallet: Address = address!("4Nd1mL5g7dUvNbKQjnYQgQki71RJKVQ1BM8DT6vKrrf5");
+	let mint: Address = address!("CktRuQ2mttxyPjdvVSxGJySLjeRGna43E77gzHu6HotE");
+	let token_account_data = build_token_account_bytes(&mint, &wallet, 10);
+
+	assert_eq!(initialized_ata_result(&token_account_data), Ok(()));
+}
+
+#[cfg(feature = "token")]
+#[test]
+fn assert_initialized_ata_rejects_empty_ata() {
+	assert_eq!(
+		initialized_ata_result(&[]),
+		Err(ProgramError::UninitializedAccount)
+	);
+}
+
 // ---------------------------------------------------------------------------
 // Test: TryFromAccountInfos derive
 // ---------------------------------------------------------------------------
@@ -30,7 +30,9 @@ use std::alloc::alloc;
 use std::alloc::dealloc;
 
 use pina::Address;
+use pina::introspection::assert_ed25519_verified;
 use pina::introspection::assert_no_cpi;
+use pina::introspection::assert_top_level_caller;
 use pina::introspection::get_current_instruction_index;
 use pina::introspection::get_instruction_count;
 use pina::introspection::has_instruction_after;
@@ -59,6 +61,9 @@ const PROGRAM_A: Address = pina::address!("6UkV1fMrN1Fuf5kJCbSEkmy5Tapenny9CLYq3
 const PROGRAM_B: Address = pina::address!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS");
 const PROGRAM_C: Address = pina::address!("11111111111111111111111111111111");
 
+/// The ed25519 signature verification precompile.
+const ED25519_PROGRAM: Address = pina::address!("Ed25519SigVerify111111111111111111111111111");
+
 // ---------------------------------------------------------------------------
 // Sysvar data builder
 // ---------------------------------------------------------------------------
@@ -384,6 +389,40 @@ fn assert_no_cpi_checks_correct_index() {
 	assert_eq!(result, Err(ProgramError::InvalidAccountData));
 }
 
+#[test]
+fn assert_top_level_caller_accepts_trusted_parent() {
+	// [A, B], current=1 → B's instruction is executing and CPIs into us
+	let instructions = vec![
+		FakeInstruction::simple(PROGRAM_A),
+		FakeInstruction::simple(PROGRAM_B),
+	];
+	let sysvar_data = build_sysvar_data(&instructions, 1);
+	let builder = AccountBuilder::sysvar(sysvar_data);
+	let mut input = unsafe { create_sysvar_input(&builder, &[]) };
+	let mut accounts = [UNINIT];
+	let account = unsafe { deserialize_input(&mut input, &mut accounts) };
+
+	assert_top_level_caller(account, &PROGRAM_B)
+		.unwrap_or_else(|e| panic!("assert_top_level_caller should pass for the parent: {e:?}"));
+}
+
+#[test]
+fn assert_top_level_caller_rejects_other_caller() {
+	// A earlier in the transaction does not count as the caller
+	let instructions = vec![
+		FakeInstruction::simple(PROGRAM_A),
+		FakeInstruction::simple(PROGRAM_B),
+	];
+	let sysvar_data = build_sysvar_data(&instructions, 1);
+	let builder = AccountBuilder::sysvar(sysvar_data);
+	let mut input = unsafe { create_sysvar_input(&builder, &[]) };
+	let mut accounts = [UNINIT];
+	let account = unsafe { deserialize_input(&mut input, &mut accounts) };
+
+	let result = assert_top_level_caller(account, &PROGRAM_A);
+	assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+}
+
 #[test]
 fn has_instruction_before_finds_earlier_program() {
 	// [A, B, C], current=2 → both A and B are before C
@@ -514,3 +553,85 @@ fn wrong_sysvar_address_rejected() {
 	let result = get_instruction_count(account);
 	assert_eq!(result, Err(ProgramError::UnsupportedSysvar));
 }
+
+/// Build ed25519 verify instruction data for a single signature, laid out the
+/// way the client SDKs serialize it: header, offsets, pubkey, signature and
+/// finally the message.
+fn ed25519_verify_data(pubkey: &Address, message: &[u8]) -> Vec<u8> {
+	let pubkey_offset: u16 = 16;
+	let signature_offset: u16 = pubkey_offset + 32;
+	let message_offset: u16 = signature_offset + 64;
+	let offsets = [
+		signature_offset,
+		u16::MAX,
+		pubkey_offset,
+		u16::MAX,
+		message_offset,
+		message.len() as u16,
+		u16::MAX,
+	];
+
+	let mut data = vec![1u8, 0];
+	for value in offsets {
+		data.extend_from_slice(&value.to_le_bytes());
+	}
+	data.extend_from_slice(pubkey.as_ref());
+	data.extend_from_slice(&[7u8; 64]);
+	data.extend_from_slice(message);
+	data
+}
+
+#[test]
+fn assert_ed25519_verified_finds_matching_signature() {
+	let signer = PROGRAM_B;
+	let instructions = vec![
+		FakeInstruction::with_data(
+			ED25519_PROGRAM,
+			&ed25519_verify_data(&signer, b"withdraw:100"),
+		),
+		FakeInstruction::simple(PROGRAM_A),
+	];
+	let (_input, _accounts, account) = sysvar_account!(&instructions, 1);
+
+	assert_ed25519_verified(account, &signer, b"withdraw:100")
+		.unwrap_or_else(|e| panic!("signature should be found: {e:?}"));
+}
+
+#[test]
+fn assert_ed25519_verified_rejects_other_pubkey_or_message() {
+	let signer = PROGRAM_B;
+	let instructions = vec![
+		FakeInstruction::with_data(
+			ED25519_PROGRAM,
+			&ed25519_verify_data(&signer, b"withdraw:100"),
+		),
+		FakeInstruction::simple(PROGRAM_A),
+	];
+	let (_input, _accounts, account) = sysvar_account!(&instructions, 1);
+
+	assert_eq!(
+		assert_ed25519_verified(account, &PROGRAM_C, b"withdraw:100"),
+		Err(ProgramError::MissingRequiredSignature)
+	);
+	assert_eq!(
+		assert_ed25519_verified(account, &signer, b"withdraw:999"),
+		Err(ProgramError::MissingRequiredSignature)
+	);
+}
+
+#[test]
+fn assert_ed25519_verified_ignores_other_programs() {
+	// Identical data sent to a program other than the precompile proves
+	// nothing about the signature.
+	let signer = PROGRAM_B;
+	let instructions = vec![
+		FakeInstruction::with_data(PROGRAM_C, &ed25519_verify_data(&signer, b"withdraw:100")),
+		FakeInstruction::simple(PROGRAM_A),
+	];
+	let (_input, _accounts, account) = sysvar_account!(&instructions, 1);
+
+	assert_eq!(
+		assert_ed25519_verified(account, &signer, b"withdraw:100"),
+		Err(ProgramError::MissingRequiredSignature)
+	);
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "test-utils")]
+
+use pina::Address;
+use pina::HasLayout;
+use pina::IntoDiscriminator;
+use pina::PodU64;
+use pina::account;
+use pina::discriminator;
+use pina::layout::LayoutSnapshot;
+
+#[discriminator]
+pub enum SnapshotAccount {
+	Config = 1,
+	Position = 2,
+}
+
+#[account(discriminator = SnapshotAccount)]
+pub struct Config {
+	pub authority: Address,
+	pub fee_bps: u8,
+}
+
+#[account(discriminator = SnapshotAccount)]
+pub struct Position {
+	pub owner: Address,
+	pub amount: PodU64,
+}
+
+const GOLDEN: &str = "\
+Config size 34
+Config.discriminator 0
+Config.authority 1
+Config.fee_bps 33
+Position size 41
+Position.discriminator 0
+Position.owner 1
+Position.amount 33
+";
+
+fn snapshot() -> LayoutSnapshot {
+	let mut snapshot = LayoutSnapshot::new();
+	snapshot.record::<Position>();
+	snapshot.record::<Config>();
+	snapshot
+}
+
+#[test]
+fn field_offsets_follow_declaration_order() {
+	assert_eq!(
+		Position::FIELD_OFFSETS,
+		&[("discriminator", 0), ("owner", 1), ("amount", 33)]
+	);
+}
+
+#[test]
+fn snapshot_renders_types_in_name_order() {
+	assert_eq!(snapshot().to_text(), GOLDEN);
+	assert_eq!(snapshot().compare(GOLDEN), Ok(()));
+}
+
+#[test]
+fn compare_ignores_line_endings_and_blank_lines() {
+	let golden = GOLDEN.replace('\n', "\r\n") + "\r\n";
+
+	assert_eq!(snapshot().compare(&golden), Ok(()));
+}
+
+#[test]
+fn compare_reports_moved_fields() {
+	// A golden file recorded while `fee_bps` was declared before `authority`.
+	let golden = GOLDEN
+		.replace("Config.authority 1", "Config.authority 2")
+		.replace("Config.fee_bps 33", "Config.fee_bps 1");
+
+	let diff = snapshot()
+		.compare(&golden)
+		.err()
+		.unwrap_or_else(|| panic!("layout change should be detected"));
+
+	assert!(diff.contains("- Config.authority 2"), "{diff}");
+	assert!(diff.contains("- Config.fee_bps 1"), "{diff}");
+	assert!(diff.contains("+ Config.authority 1"), "{diff}");
+	assert!(diff.contains("+ Config.fee_bps 33"), "{diff}");
+	assert!(!diff.contains("Position"), "{diff}");
+}
+
+#[test]
+fn compare_reports_added_types() {
+	let golden = GOLDEN
+		.lines()
+		.filter(|line| !line.starts_with("Position"))
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	let diff = snapshot()
+		.compare(&golden)
+		.err()
+		.unwrap_or_else(|| panic!("new account type should be detected"));
+
+	assert!(diff.contains("+ Position size 41"), "{diff}");
+}
@@ -0,0 +1,276 @@
+#![allow(unsafe_code)]
+
+use pina::Address;
+use pina::ProgramError;
+use pina::sysvars::Sysvar;
+use pina::sysvars::clock::Clock;
+use pina::sysvars::epoch_schedule;
+use pina::sysvars::epoch_schedule::EPOCH_SCHEDULE_LEN;
+use pina::sysvars::epoch_schedule::EpochSchedule;
+use pina::sysvars::epoch_schedule::load_epoch_schedule;
+use pina::sysvars::last_restart_slot;
+use pina::sysvars::last_restart_slot::LastRestartSlot;
+use pina::sysvars::last_restart_slot::load_last_restart_slot;
+use pina::sysvars::load_sysvar;
+use pina::sysvars::rent::Rent;
+use pinocchio::AccountView;
+use pinocchio::account::NOT_BORROWED;
+use pinocchio::account::RuntimeAccount;
+
+const SYSVAR_OWNER: Address = pina::address!("Sysvar1111111111111111111111111111111111111");
+
+#[repr(C)]
+struct SysvarAccount<const N: usize> {
+	header: RuntimeAccount,
+	data: [u8; N],
+}
+
+impl<const N: usize> SysvarAccount<N> {
+	fn new(address: Address, data: [u8; N]) -> Self {
+		Self {
+			header: RuntimeAccount {
+				borrow_state: NOT_BORROWED,
+				is_signer: 0,
+				is_writable: 0,
+				executable: 0,
+				padding: [0; 4],
+				address,
+				owner: SYSVAR_OWNER,
+				lamports: 1_000_000,
+				data_len: N as u64,
+			},
+			data,
+		}
+	}
+
+	fn view(&mut self) -> AccountView {
+		unsafe { AccountView::new_unchecked(core::ptr::addr_of_mut!(self.header)) }
+	}
+}
+
+fn epoch_schedule_bytes(
+	slots_per_epoch: u64,
+	warmup: bool,
+	first_normal_epoch: u64,
+	first_normal_slot: u64,
+) -> [u8; EPOCH_SCHEDULE_LEN] {
+	let mut data = [0u8; EPOCH_SCHEDULE_LEN];
+	data[0..8].copy_from_slice(&slots_per_epoch.to_le_bytes());
+	data[8..16].copy_from_slice(&slots_per_epoch.to_le_bytes());
+	data[16] = u8::from(warmup);
+	data[17..25].copy_from_slice(&first_normal_epoch.to_le_bytes());
+	data[25..33].copy_from_slice(&first_normal_slot.to_le_bytes());
+	data
+}
+
+// ---------------------------------------------------------------------------
+// EpochSchedule
+// ---------------------------------------------------------------------------
+
+#[test]
+fn load_epoch_schedule_computes_epoch_for_slot() {
+	let mut account = SysvarAccount::new(
+		epoch_schedule::ID,
+		epoch_schedule_bytes(432_000, false, 0, 0),
+	);
+	let view = account.view();
+
+	let schedule = load_epoch_schedule(&view).unwrap_or_else(|e| panic!("load failed: {e:?}"));
+
+	assert_eq!(schedule.slots_per_epoch(), 432_000);
+	assert_eq!(schedule.get_epoch(1_000_000), 2);
+	assert_eq!(schedule.get_epoch_and_slot_index(1_000_000), (2, 136_000));
+}
+
+#[test]
+fn load_epoch_schedule_handles_warmup_epochs() {
+	// Warmup epochs are 32, 64, 128, ... slots long until epoch 8, which is the
+	// first epoch with the full 8192 slots.
+	let mut account = SysvarAccount::new(
+		epoch_schedule::ID,
+		epoch_schedule_bytes(8192, true, 8, 8160),
+	);
+	let view = account.view();
+
+	let schedule = load_epoch_schedule(&view).unwrap_or_else(|e| panic!("load failed: {e:?}"));
+
+	assert_eq!(schedule.get_epoch_and_slot_index(0), (0, 0));
+	assert_eq!(schedule.get_epoch_and_slot_index(40), (1, 8));
+	assert_eq!(schedule.get_epoch_and_slot_index(8159), (7, 4095));
+	assert_eq!(schedule.get_epoch_and_slot_index(8160), (8, 0));
+	assert_eq!(schedule.get_epoch(8160 + 8192), 9);
+}
+
+#[test]
+fn load_epoch_schedule_rejects_other_sysvars() {
+	let clock = pina::address!("SysvarC1ock11111111111111111111111111111111");
+	let mut account = SysvarAccount::new(clock, epoch_schedule_bytes(432_000, false, 0, 0));
+	let view = account.view();
+
+	assert!(load_epoch_schedule(&view).is_err());
+}
+
+#[test]
+fn load_epoch_schedule_rejects_short_data() {
+	let mut account = SysvarAccount::new(epoch_schedule::ID, [0u8; 16]);
+	let view = account.view();
+
+	assert_eq!(
+		load_epoch_schedule(&view).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn epoch_schedule_from_account_view_checks_address() {
+	let mut account = SysvarAccount::new(
+		epoch_schedule::ID,
+		epoch_schedule_bytes(432_000, false, 0, 0),
+	);
+	let mut other = SysvarAccount::new(
+		last_restart_slot::ID,
+		epoch_schedule_bytes(432_000, false, 0, 0),
+	);
+	let (view, other) = (account.view(), other.view());
+
+	assert_eq!(
+		EpochSchedule::from_account_view(&view),
+		load_epoch_schedule(&view)
+	);
+	assert_eq!(
+		EpochSchedule::from_account_view(&other).err(),
+		Some(ProgramError::InvalidArgument)
+	);
+}
+
+#[test]
+fn epoch_schedule_get_rejects_missing_syscall_data() {
+	// Off-chain the syscall leaves the buffer zeroed, which is no valid
+	// schedule.
+	assert_eq!(
+		EpochSchedule::get().err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// LastRestartSlot
+// ---------------------------------------------------------------------------
+
+#[test]
+fn load_last_restart_slot_reads_slot() {
+	let mut account = SysvarAccount::new(last_restart_slot::ID, 285_431_017u64.to_le_bytes());
+	let view = account.view();
+
+	assert_eq!(load_last_restart_slot(&view), Ok(285_431_017));
+}
+
+#[test]
+fn load_last_restart_slot_rejects_other_sysvars() {
+	let mut account = SysvarAccount::new(epoch_schedule::ID, 285_431_017u64.to_le_bytes());
+	let view = account.view();
+
+	assert_eq!(
+		load_last_restart_slot(&view).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn load_last_restart_slot_rejects_short_data() {
+	let mut account = SysvarAccount::new(last_restart_slot::ID, [0u8; 4]);
+	let view = account.view();
+
+	assert_eq!(
+		load_last_restart_slot(&view).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn last_restart_slot_from_account_view_checks_address() {
+	let mut account = SysvarAccount::new(last_restart_slot::ID, 285_431_017u64.to_le_bytes());
+	let mut other = SysvarAccount::new(epoch_schedule::ID, 285_431_017u64.to_le_bytes());
+	let (view, other) = (account.view(), other.view());
+
+	assert_eq!(
+		LastRestartSlot::from_account_view(&view),
+		Ok(LastRestartSlot(285_431_017))
+	);
+	assert_eq!(
+		LastRestartSlot::from_account_view(&other).err(),
+		Some(ProgramError::InvalidArgument)
+	);
+}
+
+// ---------------------------------------------------------------------------
+// load_sysvar
+// ---------------------------------------------------------------------------
+
+#[test]
+fn load_sysvar_decodes_clock() {
+	let mut data = [0u8; Clock::LEN];
+	data[0..8].copy_from_slice(&250_000_000u64.to_le_bytes());
+	data[16..24].copy_from_slice(&578u64.to_le_bytes());
+	data[32..40].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+	let mut account = SysvarAccount::new(pina::sysvars::clock::CLOCK_ID, data);
+	let view = account.view();
+
+	let clock = load_sysvar::<Clock>(&view).unwrap_or_else(|e| panic!("load failed: {e:?}"));
+
+	assert_eq!(clock.slot, 250_000_000);
+	assert_eq!(clock.epoch, 578);
+	assert_eq!(clock.unix_timestamp, 1_700_000_000);
+}
+
+#[test]
+fn load_sysvar_decodes_rent() {
+	let mut account = SysvarAccount::new(pina::sysvars::rent::RENT_ID, 6_960u64.to_le_bytes());
+	let view = account.view();
+
+	let rent = load_sysvar::<Rent>(&view).unwrap_or_else(|e| panic!("load failed: {e:?}"));
+
+	assert_eq!(rent.try_minimum_balance(0), Ok(128 * 6_960));
+}
+
+#[test]
+fn load_sysvar_matches_dedicated_readers() {
+	let mut schedule = SysvarAccount::new(
+		epoch_schedule::ID,
+		epoch_schedule_bytes(432_000, false, 0, 0),
+	);
+	let mut restart = SysvarAccount::new(last_restart_slot::ID, 285_431_017u64.to_le_bytes());
+	let schedule_view = schedule.view();
+	let restart_view = restart.view();
+
+	assert_eq!(
+		load_sysvar::<EpochSchedule>(&schedule_view),
+		load_epoch_schedule(&schedule_view)
+	);
+	assert_eq!(
+		load_sysvar::<LastRestartSlot>(&restart_view),
+		Ok(LastRestartSlot(285_431_017))
+	);
+}
+
+#[test]
+fn load_sysvar_rejects_other_sysvars() {
+	let mut account = SysvarAccount::new(pina::sysvars::rent::RENT_ID, [0u8; Clock::LEN]);
+	let view = account.view();
+
+	assert_eq!(
+		load_sysvar::<Clock>(&view).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn load_sysvar_rejects_short_data() {
+	let mut account = SysvarAccount::new(pina::sysvars::clock::CLOCK_ID, [0u8; 16]);
+	let view = account.view();
+
+	assert_eq!(
+		load_sysvar::<Clock>(&view).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
@@ -0,0 +1,268 @@
+#![allow(unsafe_code)]
+
+use pina::accounts::Program;
+use pina::accounts::Readonly;
+use pina::accounts::SignerAccount;
+use pina::accounts::System;
+use pina::accounts::Sysvar;
+use pina::accounts::Writable;
+use pina::sysvars::clock::CLOCK_ID;
+use pina::sysvars::clock::Clock;
+use pina::sysvars::rent::Rent;
+use pina::*;
+use pinocchio::account::NOT_BORROWED;
+use pinocchio::account::RuntimeAccount;
+
+const SYSVAR_OWNER: Address = address!("Sysvar1111111111111111111111111111111111111");
+
+#[repr(C)]
+struct TestAccount<const N: usize> {
+	header: RuntimeAccount,
+	data: [u8; N],
+}
+
+impl<const N: usize> TestAccount<N> {
+	fn new(address: Address, owner: Address) -> Self {
+		Self {
+			header: RuntimeAccount {
+				borrow_state: NOT_BORROWED,
+				is_signer: 0,
+				is_writable: 0,
+				executable: 0,
+				padding: [0; 4],
+				address,
+				owner,
+				lamports: 1,
+				data_len: N as u64,
+			},
+			data: [0u8; N],
+		}
+	}
+
+	fn program(address: Address) -> Self {
+		let mut account = Self::new(address, Address::new_from_array([3u8; 32]));
+		account.header.executable = 1;
+		account
+	}
+
+	fn view(&mut self) -> AccountView {
+		unsafe { AccountView::new_unchecked(core::ptr::addr_of_mut!(self.header)) }
+	}
+}
+
+#[derive(Accounts)]
+#[pina(crate = pina)]
+struct AdvanceAccounts<'a> {
+	pub authority: SignerAccount<'a>,
+	pub clock: Sysvar<'a, Clock>,
+	pub system_program: Program<'a, System>,
+}
+
+/// Only callable once the authority's signature was checked.
+fn authority_key(authority: SignerAccount<'_>) -> Address {
+	*authority.address()
+}
+
+#[test]
+fn flag_wrappers_check_the_account_flags() {
+	let mut signer = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), system::ID);
+	signer.header.is_signer = 1;
+	let mut writable = TestAccount::<0>::new(Address::new_from_array([2u8; 32]), system::ID);
+	writable.header.is_writable = 1;
+	let (signer, writable) = (signer.view(), writable.view());
+
+	let authority =
+		SignerAccount::try_from(&signer).unwrap_or_else(|e| panic!("signer should parse: {e:?}"));
+	assert_eq!(authority_key(authority), Address::new_from_array([1u8; 32]));
+	assert_eq!(
+		SignerAccount::try_from(&writable).err(),
+		Some(ProgramError::MissingRequiredSignature)
+	);
+
+	assert!(Writable::try_from(&writable).is_ok());
+	assert_eq!(
+		Writable::try_from(&signer).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+
+	assert!(Readonly::try_from(&signer).is_ok());
+	assert_eq!(
+		Readonly::try_from(&writable).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[test]
+fn program_checks_address_and_executable() {
+	let mut system = TestAccount::<0>::program(system::ID);
+	let mut other = TestAccount::<0>::program(Address::new_from_array([4u8; 32]));
+	let mut data = TestAccount::<0>::new(system::ID, system::ID);
+	let (system, other, data) = (system.view(), other.view(), data.view());
+
+	let program = Program::<System>::try_from(&system)
+		.unwrap_or_else(|e| panic!("system program should parse: {e:?}"));
+
+	assert_eq!(program.address(), &system::ID);
+	assert_eq!(
+		Program::<System>::try_from(&other).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+	assert!(Program::<System>::try_from(&data).is_err());
+}
+
+#[test]
+fn sysvar_checks_address_and_decodes() {
+	let mut clock = TestAccount::<{ size_of::<Clock>() }>::new(CLOCK_ID, SYSVAR_OWNER);
+	clock.data[..8].copy_from_slice(&42u64.to_le_bytes());
+	let clock = clock.view();
+
+	let sysvar =
+		Sysvar::<Clock>::try_from(&clock).unwrap_or_else(|e| panic!("clock should parse: {e:?}"));
+	let state = sysvar
+		.get()
+		.unwrap_or_else(|e| panic!("clock should decode: {e:?}"));
+
+	assert_eq!(state.slot, 42);
+	assert!(Sysvar::<Rent>::try_from(&clock).is_err());
+}
+
+#[test]
+fn derive_accounts_runs_typed_field_checks() {
+	let mut authority = TestAccount::<0>::new(Address::new_from_array([1u8; 32]), system::ID);
+	authority.header.is_signer = 1;
+	let mut clock = TestAccount::<{ size_of::<Clock>() }>::new(CLOCK_ID, SYSVAR_OWNER);
+	let mut system = TestAccount::<0>::program(system::ID);
+	let mut wrong = TestAccount::<0>::program(Address::new_from_array([4u8; 32]));
+
+	let mut accounts = [authority.view(), clock.view(), system.view()];
+	let parsed = AdvanceAccounts::try_from_account_infos(&mut accounts)
+		.unwrap_or_else(|e| panic!("accounts should parse: {e:?}"));
+	assert!(parsed.authority.is_signer());
+	assert_eq!(parsed.clock.get().map(|clock| clock.slot), Ok(0));
+	assert_eq!(parsed.system_program.address(), &system::ID);
+
+	let mut accounts = [authority.view(), clock.view(), wrong.view()];
+	assert_eq!(
+		AdvanceAccounts::try_from_account_infos(&mut accounts).err(),
+		Some(ProgramError::InvalidAccountData)
+	);
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn derive_accounts_keys_fill_in_typed_addresses() {
+	use pina::transaction::AccountMeta;
+	use pina::transaction::ToAccountMetas;
+
+	let authority = Address::new_from_array([1u8; 32]);
+	let metas = AdvanceAccountsKeys { authority }.to_account_metas();
+
+	assert_eq!(
+		metas,
+		vec![
+			AccountMeta::new_readonly(authority, true),
+			AccountMeta::new_readonly(CLOCK_ID, false),
+			AccountMeta::new_readonly(system::ID, false),
+		]
+	);
+}
+
+#[cfg(feature = "token")]
+mod token_accounts {
+	use pina::accounts::Mint;
+	use pina::accounts::TokenAccount;
+
+	use super::*;
+
+	const MINT_LEN: usize = 82;
+	const ACCOUNT_LEN: usize = 165;
+
+	fn mint<const N: usize>(owner: Address) -> TestAccount<N> {
+		let mut account = TestAccount::<N>::new(Address::new_from_array([5u8; 32]), owner);
+		// `is_initialized` follows the authority option, supply and decimals.
+		account.data[45] = 1;
+		account
+	}
+
+	fn token_account<const N: usize>(owner: Address) -> TestAccount<N> {
+		let mut account = TestAccount::<N>::new(Address::new_from_array([6u8; 32]), owner);
+		// `state` follows the mint, owner, amount and delegate option.
+		account.data[108] = 1;
+		account
+	}
+
+	#[test]
+	fn mint_accepts_either_token_program() {
+		let mut spl = mint::<MINT_LEN>(token::ID);
+		let mut extended = mint::<{ ACCOUNT_LEN + 4 }>(token_2022::ID);
+		extended.data[ACCOUNT_LEN] = 1;
+		let (spl, extended) = (spl.view(), extended.view());
+
+		let mint = Mint::try_from(&spl).unwrap_or_else(|e| panic!("mint should parse: {e:?}"));
+		assert!(
+			mint.state()
+				.unwrap_or_else(|e| panic!("mint state: {e:?}"))
+				.is_initialized()
+		);
+		assert!(Mint::try_from(&extended).is_ok());
+	}
+
+	#[test]
+	fn mint_rejects_foreign_owners_and_other_layouts() {
+		let mut foreign = mint::<MINT_LEN>(system::ID);
+		let mut uninitialized =
+			TestAccount::<MINT_LEN>::new(Address::new_from_array([5u8; 32]), token::ID);
+		let mut account = token_account::<ACCOUNT_LEN>(token::ID);
+		let mut extended_account = mint::<{ ACCOUNT_LEN + 4 }>(token_2022::ID);
+		extended_account.data[ACCOUNT_LEN] = 2;
+		let mut padded = mint::<{ ACCOUNT_LEN + 4 }>(token::ID);
+		padded.data[ACCOUNT_LEN] = 1;
+
+		assert_eq!(
+			Mint::try_from(&foreign.view()).err(),
+			Some(ProgramError::InvalidAccountOwner)
+		);
+		assert_eq!(
+			Mint::try_from(&uninitialized.view()).err(),
+			Some(ProgramError::UninitializedAccount)
+		);
+
+		// A token account, a Token-2022 extended token account, and mint data
+		// padded past the base length under SPL Token.
+		for account in [account.view(), extended_account.view(), padded.view()] {
+			assert_eq!(
+				Mint::try_from(&account).err(),
+				Some(ProgramError::InvalidAccountData)
+			);
+		}
+	}
+
+	#[test]
+	fn token_account_checks_layout_and_state() {
+		let mut spl = token_account::<ACCOUNT_LEN>(token::ID);
+		let mut extended = token_account::<{ ACCOUNT_LEN + 4 }>(token_2022::ID);
+		extended.data[ACCOUNT_LEN] = 2;
+		let mut mint = mint::<MINT_LEN>(token::ID);
+		let mut uninitialized =
+			TestAccount::<ACCOUNT_LEN>::new(Address::new_from_array([6u8; 32]), token::ID);
+
+		let account = spl.view();
+		let parsed = TokenAccount::try_from(&account)
+			.unwrap_or_else(|e| panic!("token account should parse: {e:?}"));
+		assert!(
+			parsed
+				.state()
+				.unwrap_or_else(|e| panic!("token account state: {e:?}"))
+				.is_initialized()
+		);
+		assert!(TokenAccount::try_from(&extended.view()).is_ok());
+		assert_eq!(
+			TokenAccount::try_from(&mint.view()).err(),
+			Some(ProgramError::InvalidAccountData)
+		);
+		assert_eq!(
+			TokenAccount::try_from(&uninitialized.view()).err(),
+			Some(ProgramError::UninitializedAccount)
+		);
+	}
+}
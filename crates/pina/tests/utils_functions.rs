@@ -1,5 +1,6 @@
 use pina::ProgramError;
 use pina::parse_instruction;
+use pina::parse_instruction_checked;
 
 // Use the pina discriminator macro to create a proper discriminator enum.
 #[pina::discriminator(crate = ::pina)]
@@ -72,6 +73,38 @@ fn parse_instruction_extra_data_is_ok() {
 	assert_eq!(result, TestInstruction::Update);
 }
 
+// ---- parse_instruction_checked tests ----
+
+#[pina::discriminator(crate = ::pina, primitive = u16)]
+#[derive(Debug, PartialEq)]
+pub enum WideInstruction {
+	Initialize = 0,
+	Update = 258,
+}
+
+#[test]
+fn parse_instruction_checked_empty_data() {
+	let err =
+		parse_instruction_checked::<WideInstruction>(&PROGRAM_ID, &PROGRAM_ID, &[]).unwrap_err();
+	assert_eq!(err, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn parse_instruction_checked_too_short_data() {
+	let data = [2u8];
+	let err =
+		parse_instruction_checked::<WideInstruction>(&PROGRAM_ID, &PROGRAM_ID, &data).unwrap_err();
+	assert_eq!(err, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn parse_instruction_checked_valid_data() {
+	let data = [2u8, 1, 0xFF];
+	let result: WideInstruction = parse_instruction_checked(&PROGRAM_ID, &PROGRAM_ID, &data)
+		.unwrap_or_else(|e| panic!("expected valid parse: {e:?}"));
+	assert_eq!(result, WideInstruction::Update);
+}
+
 // ---- assert function tests ----
 
 #[test]
@@ -99,3 +132,53 @@ fn assert_custom_error_type() {
 		other => panic!("expected Custom error, got: {other:?}"),
 	}
 }
+
+// ---- derive_atas tests ----
+
+#[cfg(feature = "token")]
+#[test]
+fn derive_atas_matches_individual_derivations() {
+	let wallet = pina::Address::new_from_array([3u8; 32]);
+	let mints = [
+		pina::Address::new_from_array([4u8; 32]),
+		pina::Address::new_from_array([5u8; 32]),
+	];
+
+	let atas = pina::derive_atas(&wallet, &mints, &pina::token::ID).unwrap();
+
+	for (ata, mint) in atas.iter().zip(&mints) {
+		let expected = pina::try_get_associated_token_address(&wallet, mint, &pina::token::ID);
+		assert_eq!(Some(*ata), expected);
+	}
+	assert_ne!(atas[0].0, atas[1].0);
+}
+
+// ---- assert_relation tests ----
+
+#[test]
+fn assert_relation_checks_each_relation() {
+	use pina::Relation;
+
+	let cases = [
+		(Relation::BothNonZero, (1, 2), (0, 2)),
+		(Relation::Equal, (5, 5), (5, 6)),
+		(Relation::NotEqual, (5, 6), (5, 5)),
+		(Relation::ALessThanB, (1, 2), (2, 2)),
+		(Relation::ALessThanOrEqualB, (2, 2), (3, 2)),
+		(Relation::AGreaterThanB, (3, 2), (2, 2)),
+		(Relation::AGreaterThanOrEqualB, (2, 2), (1, 2)),
+	];
+
+	for (relation, (pass_a, pass_b), (fail_a, fail_b)) in cases {
+		assert_eq!(
+			pina::assert_relation(pass_a, pass_b, relation),
+			Ok(()),
+			"{relation:?} should hold for ({pass_a}, {pass_b})"
+		);
+		assert_eq!(
+			pina::assert_relation(fail_a, fail_b, relation),
+			Err(ProgramError::InvalidInstructionData),
+			"{relation:?} should fail for ({fail_a}, {fail_b})"
+		);
+	}
+}
@@ -201,9 +201,14 @@ fn build_discriminator_node(disc: &DiscriminatorIr) -> DiscriminatorNode {
 		8 => NumberFormat::U64,
 		_ => NumberFormat::U8,
 	};
+	let number_type = if disc.big_endian {
+		NumberTypeNode::be(format)
+	} else {
+		NumberTypeNode::le(format)
+	};
 
 	DiscriminatorNode::Constant(ConstantDiscriminatorNode::new(
-		ConstantValueNode::new(NumberTypeNode::le(format), NumberValueNode::new(disc.value)),
+		ConstantValueNode::new(number_type, NumberValueNode::new(disc.value)),
 		0,
 	))
 }
@@ -248,7 +253,11 @@ fn build_pda_node(pda: &PdaIr) -> PdaNode {
 }
 
 fn build_error_node(error: &ErrorIr) -> ErrorNode {
-	let message = error.docs.first().cloned().unwrap_or_default();
+	let message = error
+		.message
+		.clone()
+		.or_else(|| error.docs.first().cloned())
+		.unwrap_or_default();
 
 	let mut node = ErrorNode::new(error.name.as_str(), error.code as usize, message);
 
@@ -301,6 +310,7 @@ mod tests {
 				discriminator: DiscriminatorIr {
 					value: 1,
 					repr_size: 1,
+					big_endian: false,
 				},
 				docs: vec![],
 			}],
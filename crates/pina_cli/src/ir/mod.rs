@@ -57,11 +57,12 @@ pub struct FieldIr {
 	pub docs: Vec<String>,
 }
 
-/// A discriminator value and its byte width.
+/// A discriminator value, its byte width and byte order.
 #[derive(Debug, Clone)]
 pub struct DiscriminatorIr {
 	pub value: u64,
 	pub repr_size: usize,
+	pub big_endian: bool,
 }
 
 /// A program error variant from `#[error]`.
@@ -69,6 +70,8 @@ pub struct DiscriminatorIr {
 pub struct ErrorIr {
 	pub name: String,
 	pub code: u32,
+	/// The `#[msg("...")]` text, if any.
+	pub message: Option<String>,
 	pub docs: Vec<String>,
 }
 
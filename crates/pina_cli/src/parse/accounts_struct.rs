@@ -2,6 +2,8 @@ use syn::File;
 use syn::Item;
 
 use super::doc_comments::extract_docs;
+use super::validation::known_address;
+use super::validation::known_program_address;
 
 /// A parsed `#[derive(Accounts)]` struct.
 #[derive(Debug, Clone)]
@@ -13,10 +15,24 @@ pub struct AccountsStruct {
 
 /// A single field inside an `#[derive(Accounts)]` struct.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct AccountsField {
 	pub name: String,
 	pub docs: Vec<String>,
 	pub is_mutable: bool,
+	/// Whether a `#[pina(signer)]` attribute requires the account to sign.
+	pub is_signer: bool,
+	/// Whether a `#[pina(writable)]` attribute requires the account to be
+	/// writable.
+	pub is_writable: bool,
+	/// Whether the field is an `Option<&AccountView>` that callers may omit.
+	pub is_optional: bool,
+	/// The type name of a nested `#[derive(Accounts)]` struct, whose accounts
+	/// are parsed in place of this field.
+	pub nested: Option<String>,
+	/// The address asserted by a `#[pina(program = ...)]` or
+	/// `#[pina(address = ...)]` attribute.
+	pub program_address: Option<String>,
 }
 
 /// Extract all `#[derive(Accounts)]` structs from a file.
@@ -77,17 +93,95 @@ fn extract_account_fields(fields: &syn::Fields) -> Vec<AccountsField> {
 				.as_ref()
 				.map_or_else(|| "unknown".to_owned(), ToString::to_string);
 			let docs = extract_docs(&field.attrs);
-			let is_mutable = type_is_mutable_account(&field.ty);
+			let is_optional = optional_inner_type(&field.ty).is_some();
+			let is_mutable =
+				type_is_mutable_account(optional_inner_type(&field.ty).unwrap_or(&field.ty));
+			let constraints = field_constraints(&field.attrs);
+			let nested = nested_struct_name(&field.ty);
 
 			AccountsField {
 				name,
 				docs,
 				is_mutable,
+				is_signer: constraints.is_signer,
+				is_writable: constraints.is_writable,
+				is_optional,
+				nested,
+				program_address: constraints.program_address,
 			}
 		})
 		.collect()
 }
 
+/// The account constraints declared with `#[pina(...)]` field attributes.
+#[derive(Default)]
+struct FieldConstraints {
+	is_signer: bool,
+	is_writable: bool,
+	program_address: Option<String>,
+}
+
+fn field_constraints(attrs: &[syn::Attribute]) -> FieldConstraints {
+	let mut constraints = FieldConstraints::default();
+
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("pina")) {
+		let _ = attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("program") {
+				let program: syn::Ident = meta.value()?.parse()?;
+				constraints.program_address = known_program_address(&program.to_string());
+			} else if meta.path.is_ident("address") {
+				let address: syn::Expr = meta.value()?.parse()?;
+				constraints.program_address = known_address(&address);
+			} else if meta.path.is_ident("signer") {
+				constraints.is_signer = true;
+			} else if meta.path.is_ident("writable") {
+				constraints.is_writable = true;
+			} else if meta.input.peek(syn::Token![=]) {
+				// Skip the values of constraints that don't affect the IDL.
+				let _: syn::Expr = meta.value()?.parse()?;
+			}
+
+			Ok(())
+		});
+	}
+
+	constraints
+}
+
+/// The `T` of an `Option<T>` field type.
+fn optional_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+	let syn::Type::Path(type_path) = ty else {
+		return None;
+	};
+	let segment = type_path.path.segments.last()?;
+	if segment.ident != "Option" {
+		return None;
+	}
+	let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+		return None;
+	};
+
+	match arguments.args.first()? {
+		syn::GenericArgument::Type(inner) => Some(inner),
+		_ => None,
+	}
+}
+
+/// The struct name of a field typed as another accounts struct, such as
+/// `TokenPairAccounts<'a>`.
+fn nested_struct_name(ty: &syn::Type) -> Option<String> {
+	let syn::Type::Path(type_path) = ty else {
+		return None;
+	};
+	let segment = type_path.path.segments.last()?;
+
+	if segment.ident == "Option" || segment.ident == "RemainingAccounts" {
+		return None;
+	}
+
+	Some(segment.ident.to_string())
+}
+
 fn type_is_mutable_account(ty: &syn::Type) -> bool {
 	let syn::Type::Reference(reference) = ty else {
 		return false;
@@ -144,4 +238,67 @@ mod tests {
 		assert!(structs[0].fields[1].is_mutable);
 		assert!(structs[0].fields[2].is_mutable);
 	}
+
+	#[test]
+	fn extracts_constraint_attributes() {
+		let source = r"
+			#[derive(Accounts, Debug)]
+			pub struct WithdrawAccounts<'a> {
+				#[pina(signer, writable)]
+				pub authority: &'a AccountView,
+				#[pina(owner = crate::ID, writable)]
+				pub vault: &'a AccountView,
+				#[pina(address = system::ID)]
+				pub system_program: &'a AccountView,
+			}
+		";
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let fields = &extract_accounts_structs(&file)[0].fields;
+
+		assert!(fields[0].is_signer && fields[0].is_writable);
+		assert!(!fields[1].is_signer && fields[1].is_writable);
+		assert_eq!(fields[1].program_address, None);
+		assert_eq!(
+			fields[2].program_address.as_deref(),
+			Some("11111111111111111111111111111111")
+		);
+	}
+
+	#[test]
+	fn extracts_optional_account_fields() {
+		let source = r"
+			#[derive(Accounts, Debug)]
+			pub struct CloseAccounts<'a> {
+				pub authority: &'a AccountView,
+				pub rent_recipient: Option<&'a mut AccountView>,
+				pub event_authority: Option<&'a AccountView>,
+			}
+		";
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let fields = &extract_accounts_structs(&file)[0].fields;
+
+		assert!(!fields[0].is_optional);
+		assert!(fields[1].is_optional && fields[1].is_mutable);
+		assert!(fields[2].is_optional && !fields[2].is_mutable);
+	}
+
+	#[test]
+	fn extracts_known_program_fields() {
+		let source = r#"
+			#[derive(Accounts, Debug)]
+			pub struct CreateAccounts<'a> {
+				pub payer: &'a mut AccountView,
+				#[pina(program = system)]
+				pub system_program: &'a AccountView,
+			}
+		"#;
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let structs = extract_accounts_structs(&file);
+
+		assert_eq!(structs[0].fields[0].program_address, None);
+		assert_eq!(
+			structs[0].fields[1].program_address.as_deref(),
+			Some("11111111111111111111111111111111")
+		);
+	}
 }
@@ -172,6 +172,7 @@ mod tests {
 			discriminator: DiscriminatorIr {
 				value: disc_value,
 				repr_size,
+				big_endian: false,
 			},
 			docs: vec![],
 		}
@@ -213,6 +214,7 @@ mod tests {
 			discriminator: DiscriminatorIr {
 				value: disc_value,
 				repr_size: 1,
+				big_endian: false,
 			},
 			docs: vec![],
 		}
@@ -1,3 +1,4 @@
+use heck::ToSnakeCase;
 use syn::File;
 use syn::Item;
 
@@ -8,6 +9,8 @@ pub struct DiscriminatorEnum {
 	pub variants: Vec<DiscriminatorVariant>,
 	/// The repr size in bytes (1 for u8, 2 for u16, etc.). Defaults to 1.
 	pub repr_size: usize,
+	/// Whether the discriminator is stored big-endian (`endian = big`).
+	pub big_endian: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -28,16 +31,47 @@ pub fn extract_discriminator_enums(file: &File) -> Vec<DiscriminatorEnum> {
 			continue;
 		}
 
-		let repr_size = detect_repr_size(&item_enum.attrs);
+		let args = parse_discriminator_args(&item_enum.attrs);
+
+		if let Some(namespace) = args.anchor_namespace {
+			result.push(DiscriminatorEnum {
+				name: item_enum.ident.to_string(),
+				variants: item_enum
+					.variants
+					.iter()
+					.map(|variant| {
+						DiscriminatorVariant {
+							name: variant.ident.to_string(),
+							value: anchor_sighash(namespace, &variant.ident.to_string()),
+						}
+					})
+					.collect(),
+				repr_size: 8,
+				big_endian: false,
+			});
+			continue;
+		}
+
+		let repr_size = args
+			.repr_size
+			.unwrap_or_else(|| detect_repr_size(&item_enum.attrs));
 		let mut variants = Vec::new();
+		// Variants without a value count up from the previous one. After a
+		// value that is not a literal the count is unknown until the next
+		// literal.
+		let mut next = Some(0);
 		for variant in &item_enum.variants {
-			if let Some((_, expr)) = &variant.discriminant {
-				if let Some(val) = expr_to_u64(expr) {
-					variants.push(DiscriminatorVariant {
-						name: variant.ident.to_string(),
-						value: val,
-					});
-				}
+			let value = match &variant.discriminant {
+				Some((_, expr)) => expr_to_u64(expr),
+				None => next,
+			};
+			next = value.and_then(|value| value.checked_add(1));
+
+			if let Some(value) = value {
+				variants.push(DiscriminatorVariant {
+					name: variant.ident.to_string(),
+					value,
+				});
 			}
 		}
 
@@ -45,12 +79,117 @@ pub fn extract_discriminator_enums(file: &File) -> Vec<DiscriminatorEnum> {
 			name: item_enum.ident.to_string(),
 			variants,
 			repr_size,
+			big_endian: args.big_endian && repr_size > 1,
 		});
 	}
 
 	result
 }
 
+/// The layout arguments of a `#[discriminator(...)]` attribute.
+#[derive(Debug, Default)]
+struct DiscriminatorArgs {
+	/// The byte width set by `primitive = ...`, if any.
+	repr_size: Option<usize>,
+	big_endian: bool,
+	/// The sighash namespace of a `format = anchor` enum.
+	anchor_namespace: Option<AnchorNamespace>,
+}
+
+/// The sighash namespace of an Anchor format discriminator.
+#[derive(Debug, Clone, Copy)]
+enum AnchorNamespace {
+	/// `global:<variant_in_snake_case>`, for instructions.
+	Global,
+	/// `account:<Variant>`, for account types.
+	Account,
+}
+
+/// Read `primitive`, `endian`, `format` and `namespace` from the
+/// `#[discriminator(...)]` attribute.
+///
+/// The macro accepts the values as bare identifiers or string literals, so
+/// both are read here. Other arguments are skipped.
+fn parse_discriminator_args(attrs: &[syn::Attribute]) -> DiscriminatorArgs {
+	let mut args = DiscriminatorArgs::default();
+	let mut anchor = false;
+	let mut account_namespace = false;
+
+	for attr in attrs {
+		if !attr.path().is_ident("discriminator") || !matches!(attr.meta, syn::Meta::List(_)) {
+			continue;
+		}
+
+		let _ = attr.parse_nested_meta(|meta| {
+			if !meta.input.peek(syn::Token![=]) {
+				return Ok(());
+			}
+
+			let value = meta_value_name(&meta.value()?.parse::<syn::Expr>()?);
+
+			if meta.path.is_ident("primitive") {
+				args.repr_size = value.as_deref().map(primitive_size);
+			} else if meta.path.is_ident("endian") {
+				args.big_endian = value.as_deref() == Some("big");
+			} else if meta.path.is_ident("format") {
+				anchor = value.as_deref() == Some("anchor");
+			} else if meta.path.is_ident("namespace") {
+				account_namespace = value.as_deref() == Some("account");
+			}
+
+			Ok(())
+		});
+	}
+
+	if anchor {
+		args.anchor_namespace = Some(if account_namespace {
+			AnchorNamespace::Account
+		} else {
+			AnchorNamespace::Global
+		});
+	}
+
+	args
+}
+
+/// The `u64` value the macro assigns to a variant of an Anchor format enum:
+/// the first eight bytes of `sha256("<namespace>:<name>")`, read
+/// little-endian.
+fn anchor_sighash(namespace: AnchorNamespace, variant: &str) -> u64 {
+	let preimage = match namespace {
+		AnchorNamespace::Global => format!("global:{}", variant.to_snake_case()),
+		AnchorNamespace::Account => format!("account:{variant}"),
+	};
+	let hash = sha2_const_stable::Sha256::new()
+		.update(preimage.as_bytes())
+		.finalize();
+	let mut bytes = [0u8; 8];
+	bytes.copy_from_slice(&hash[..8]);
+
+	u64::from_le_bytes(bytes)
+}
+
+/// The name in an argument value written as `name` or `"name"`.
+fn meta_value_name(expr: &syn::Expr) -> Option<String> {
+	match expr {
+		syn::Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+		syn::Expr::Lit(syn::ExprLit {
+			lit: syn::Lit::Str(lit),
+			..
+		}) => Some(lit.value()),
+		_ => None,
+	}
+}
+
+fn primitive_size(primitive: &str) -> usize {
+	match primitive {
+		"u16" => 2,
+		"u32" => 4,
+		"u64" => 8,
+		_ => 1,
+	}
+}
+
 /// Detect `#[repr(u8)]`, `#[repr(u16)]`, etc. Default to 1 byte.
 fn detect_repr_size(attrs: &[syn::Attribute]) -> usize {
 	for attr in attrs {
@@ -60,12 +199,7 @@ fn detect_repr_size(attrs: &[syn::Attribute]) -> usize {
 		let Ok(inner) = attr.parse_args::<syn::Ident>() else {
 			continue;
 		};
-		return match inner.to_string().as_str() {
-			"u16" => 2,
-			"u32" => 4,
-			"u64" => 8,
-			_ => 1,
-		};
+		return primitive_size(&inner.to_string());
 	}
 	// The #[discriminator] macro defaults to u8 repr.
 	1
@@ -108,5 +242,89 @@ mod tests {
 		assert_eq!(enums[0].variants[1].name, "Bar");
 		assert_eq!(enums[0].variants[1].value, 1);
 		assert_eq!(enums[0].repr_size, 1);
+		assert!(!enums[0].big_endian);
+	}
+
+	#[test]
+	fn reads_primitive_and_endian_arguments() {
+		let source = r#"
+			#[discriminator(crate = ::pina, primitive = u16, endian = big, final)]
+			pub enum Wide {
+				Foo = 1,
+			}
+
+			#[discriminator(primitive = "u32", endian = "little")]
+			pub enum Quoted {
+				Foo = 1,
+			}
+
+			#[discriminator(endian = big)]
+			pub enum Narrow {
+				Foo = 1,
+			}
+		"#;
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let layouts: Vec<_> = extract_discriminator_enums(&file)
+			.iter()
+			.map(|disc| (disc.repr_size, disc.big_endian))
+			.collect();
+
+		// `endian` has no effect on single-byte discriminators.
+		assert_eq!(layouts, [(2, true), (4, false), (1, false)]);
+	}
+
+	#[test]
+	fn anchor_format_variants_get_their_sighash() {
+		let source = r"
+			#[discriminator(crate = ::pina, format = anchor)]
+			pub enum LegacyInstruction {
+				Initialize,
+			}
+
+			#[discriminator(format = anchor, namespace = account)]
+			pub enum LegacyAccount {
+				Counter,
+			}
+		";
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let enums = extract_discriminator_enums(&file);
+
+		// The bytes `pina::anchor_instruction_discriminator("initialize")`
+		// returns.
+		assert_eq!(
+			enums[0].variants[0].value.to_le_bytes(),
+			[175, 175, 109, 31, 13, 152, 155, 237]
+		);
+		assert_eq!(enums[0].repr_size, 8);
+		assert!(!enums[0].big_endian);
+		assert_eq!(
+			enums[1].variants[0].value,
+			anchor_sighash(AnchorNamespace::Account, "Counter")
+		);
+		assert_ne!(
+			enums[1].variants[0].value,
+			anchor_sighash(AnchorNamespace::Global, "Counter")
+		);
+	}
+
+	#[test]
+	fn implicit_values_count_up_from_the_previous_variant() {
+		let source = r"
+			#[discriminator]
+			pub enum MyInstruction {
+				Foo,
+				Bar,
+				Baz = 7,
+				Qux,
+			}
+		";
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let enums = extract_discriminator_enums(&file);
+		let values: Vec<_> = enums[0]
+			.variants
+			.iter()
+			.map(|variant| (variant.name.as_str(), variant.value))
+			.collect();
+		assert_eq!(values, [("Foo", 0), ("Bar", 1), ("Baz", 7), ("Qux", 8)]);
 	}
 }
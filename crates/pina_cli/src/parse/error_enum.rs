@@ -12,22 +12,35 @@ pub fn extract_error_enums(file: &File) -> Vec<ErrorIr> {
 		let Item::Enum(item_enum) = item else {
 			continue;
 		};
-		if !has_attr(&item_enum.attrs, "error") {
+		let Some(error_attr) = item_enum
+			.attrs
+			.iter()
+			.find(|attr| attr.path().is_ident("error"))
+		else {
 			continue;
-		}
+		};
+		let offset = error_offset(error_attr);
 
 		for variant in &item_enum.variants {
 			let code = variant
 				.discriminant
 				.as_ref()
 				.and_then(|(_, expr)| expr_to_u32(expr))
-				.unwrap_or(0);
+				.unwrap_or(0)
+				.saturating_add(offset);
 
 			let docs = extract_docs(&variant.attrs);
+			let message = variant
+				.attrs
+				.iter()
+				.find(|attr| attr.path().is_ident("msg"))
+				.and_then(|attr| attr.parse_args::<syn::LitStr>().ok())
+				.map(|message| message.value());
 
 			result.push(ErrorIr {
 				name: variant.ident.to_string(),
 				code,
+				message,
 				docs,
 			});
 		}
@@ -36,8 +49,24 @@ pub fn extract_error_enums(file: &File) -> Vec<ErrorIr> {
 	result
 }
 
-fn has_attr(attrs: &[syn::Attribute], name: &str) -> bool {
-	attrs.iter().any(|a| a.path().is_ident(name))
+/// The `offset = N` argument of `#[error(...)]`, or zero.
+fn error_offset(attr: &syn::Attribute) -> u32 {
+	let mut offset = 0;
+
+	if let syn::Meta::List(_) = &attr.meta {
+		let _ = attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("offset") {
+				let value: syn::Expr = meta.value()?.parse()?;
+				offset = expr_to_u32(&value).unwrap_or_default();
+			} else if meta.input.peek(syn::Token![=]) {
+				let _: syn::Expr = meta.value()?.parse()?;
+			}
+
+			Ok(())
+		});
+	}
+
+	offset
 }
 
 fn expr_to_u32(expr: &syn::Expr) -> Option<u32> {
@@ -74,4 +103,37 @@ mod tests {
 			vec!["The sender does not have enough lamports."]
 		);
 	}
+
+	#[test]
+	fn extracts_error_codes_with_offset() {
+		let source = r"
+			#[error(crate = ::pina, offset = 6000, final)]
+			pub enum VaultError {
+				Locked = 0,
+				Empty = 123,
+			}
+		";
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let errors = extract_error_enums(&file);
+		assert_eq!(errors[0].code, 6000);
+		assert_eq!(errors[1].code, 6123);
+	}
+
+	#[test]
+	fn extracts_error_messages() {
+		let source = r#"
+			#[error]
+			pub enum VaultError {
+				/// Raised before the unlock slot.
+				#[msg("The vault is locked")]
+				Locked = 0,
+				Empty = 1,
+			}
+		"#;
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let errors = extract_error_enums(&file);
+		assert_eq!(errors[0].message.as_deref(), Some("The vault is locked"));
+		assert_eq!(errors[0].docs, vec!["Raised before the unlock slot."]);
+		assert_eq!(errors[1].message, None);
+	}
 }
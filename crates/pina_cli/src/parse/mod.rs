@@ -8,6 +8,7 @@ pub mod error_enum;
 pub mod instruction_data;
 pub mod module_resolver;
 pub mod program_id;
+pub mod program_module;
 pub mod seeds;
 pub mod types;
 pub mod validation;
@@ -19,6 +20,7 @@ use heck::ToSnakeCase;
 
 use crate::error::IdlError;
 use crate::ir::AccountIr;
+use crate::ir::DefaultValueIr;
 use crate::ir::DiscriminatorIr;
 use crate::ir::ErrorIr;
 use crate::ir::InstructionAccountIr;
@@ -66,6 +68,8 @@ pub fn assemble_program_ir_multi(
 	let mut all_errors = Vec::new();
 	let mut all_seed_constants = Vec::new();
 	let mut dispatch = Vec::new();
+	let mut program_dispatch = Vec::new();
+	let mut program_instructions = Vec::new();
 	let mut all_validation_props = HashMap::new();
 	let mut public_key = None;
 	let mut pdas_ir = Vec::new();
@@ -81,6 +85,12 @@ pub fn assemble_program_ir_multi(
 		all_ix_accounts_structs.extend(accounts_struct::extract_accounts_structs(file));
 		all_errors.extend(error_enum::extract_error_enums(file));
 
+		for module in program_module::extract_program_modules(file) {
+			all_disc_enums.push(module.discriminator);
+			program_dispatch.extend(module.dispatch);
+			program_instructions.extend(module.instructions);
+		}
+
 		let file_dispatch = entrypoint::extract_dispatch_map(file);
 		if !file_dispatch.is_empty() {
 			dispatch = file_dispatch;
@@ -90,7 +100,12 @@ pub fn assemble_program_ir_multi(
 		all_validation_props.extend(file_validation_props);
 
 		let file_seed_constants = seeds::extract_seed_constants(file);
-		let file_pdas = seeds::extract_pda_from_seed_macros(file, &file_seed_constants);
+		let mut file_pdas = seeds::extract_pda_from_seed_macros(file, &file_seed_constants);
+		for pda in seeds::extract_pda_from_account_seeds(file, &file_seed_constants) {
+			if !file_pdas.iter().any(|existing| existing.name == pda.name) {
+				file_pdas.push(pda);
+			}
+		}
 
 		all_seed_constants.extend(file_seed_constants);
 		pdas_ir.extend(file_pdas);
@@ -98,6 +113,17 @@ pub fn assemble_program_ir_multi(
 
 	let public_key = public_key.ok_or(IdlError::NoProgramId)?;
 
+	// `#[program]` handlers without an `#[instruction]` struct take no
+	// arguments beyond the discriminator.
+	program_instructions.retain(|program_ix: &instruction_data::InstructionStruct| {
+		!all_instruction_structs.iter().any(|ix| {
+			ix.discriminator_enum == program_ix.discriminator_enum
+				&& ix.variant == program_ix.variant
+		})
+	});
+	all_instruction_structs.extend(program_instructions);
+	dispatch.extend(program_dispatch);
+
 	assemble_from_extracted(
 		program_name,
 		public_key,
@@ -273,6 +299,7 @@ pub fn build_discriminator_map(
 				DiscriminatorIr {
 					value: variant.value,
 					repr_size: disc.repr_size,
+					big_endian: disc.big_endian,
 				},
 			);
 		}
@@ -337,8 +364,13 @@ fn build_instructions_from_dispatch(
 					}
 				})?;
 
-			let val_props = validation_props.get(accounts_struct_name);
-			build_instruction_accounts(accts_struct, val_props, pdas_ir)
+			build_instruction_accounts(
+				accts_struct,
+				ix_accounts_structs,
+				validation_props,
+				pdas_ir,
+				&mut Vec::new(),
+			)
 		} else {
 			Vec::new()
 		};
@@ -362,38 +394,68 @@ fn build_instructions_from_dispatch(
 	Ok(instructions)
 }
 
-fn build_instruction_accounts(
-	accts_struct: &accounts_struct::AccountsStruct,
-	val_props: Option<&HashMap<String, validation::AccountProperties>>,
+/// Build the IDL accounts of an instruction, flattening fields that embed
+/// another `#[derive(Accounts)]` struct in the order the loader consumes them.
+///
+/// `expanding` holds the structs currently being flattened so that a
+/// malformed, self-referencing source cannot recurse forever.
+fn build_instruction_accounts<'s>(
+	accts_struct: &'s accounts_struct::AccountsStruct,
+	ix_accounts_structs: &'s [accounts_struct::AccountsStruct],
+	validation_props: &HashMap<String, HashMap<String, validation::AccountProperties>>,
 	pdas_ir: &[PdaIr],
+	expanding: &mut Vec<&'s str>,
 ) -> Vec<InstructionAccountIr> {
-	accts_struct
-		.fields
-		.iter()
-		.map(|field| {
-			let props = val_props
-				.and_then(|m| m.get(&field.name))
-				.cloned()
-				.unwrap_or_default();
-
-			let pda_name = if props.is_pda {
-				infer_pda_name_for_field(&field.name, pdas_ir)
-			} else {
-				None
-			};
-
-			InstructionAccountIr {
-				name: field.name.clone(),
-				is_writable: field.is_mutable || props.is_writable,
-				is_signer: props.is_signer,
-				is_optional: false,
-				default_value: props.default_value,
-				is_pda: props.is_pda,
-				pda_name,
-				docs: field.docs.clone(),
-			}
-		})
-		.collect()
+	let val_props = validation_props.get(&accts_struct.name);
+	let mut accounts = Vec::with_capacity(accts_struct.fields.len());
+	expanding.push(&accts_struct.name);
+
+	for field in &accts_struct.fields {
+		let nested = field.nested.as_deref().and_then(|name| {
+			ix_accounts_structs
+				.iter()
+				.find(|nested| nested.name == name && !expanding.contains(&name))
+		});
+
+		if let Some(nested) = nested {
+			accounts.extend(build_instruction_accounts(
+				nested,
+				ix_accounts_structs,
+				validation_props,
+				pdas_ir,
+				expanding,
+			));
+			continue;
+		}
+
+		let props = val_props
+			.and_then(|m| m.get(&field.name))
+			.cloned()
+			.unwrap_or_default();
+
+		let pda_name = if props.is_pda {
+			infer_pda_name_for_field(&field.name, pdas_ir)
+		} else {
+			None
+		};
+
+		accounts.push(InstructionAccountIr {
+			name: field.name.clone(),
+			is_writable: field.is_mutable || field.is_writable || props.is_writable,
+			is_signer: field.is_signer || props.is_signer,
+			is_optional: field.is_optional,
+			default_value: props
+				.default_value
+				.or_else(|| field.program_address.clone().map(DefaultValueIr::PublicKey)),
+			is_pda: props.is_pda,
+			pda_name,
+			docs: field.docs.clone(),
+		});
+	}
+
+	expanding.pop();
+
+	accounts
 }
 
 fn infer_pda_name_for_field(field_name: &str, pdas: &[PdaIr]) -> Option<String> {
@@ -592,6 +654,71 @@ mod tests {
 		assert!(ir.instructions[0].accounts[1].is_writable);
 	}
 
+	#[test]
+	fn assemble_program_ir_flattens_nested_accounts_structs() {
+		let source = r#"
+			declare_id!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS");
+
+			#[discriminator]
+			pub enum SwapInstruction {
+				Swap = 0,
+			}
+
+			#[instruction(discriminator = SwapInstruction, variant = Swap)]
+			pub struct SwapIx {}
+
+			#[derive(Accounts, Debug)]
+			pub struct TokenPairAccounts<'a> {
+				pub mint: &'a AccountView,
+				pub vault: &'a mut AccountView,
+			}
+
+			#[derive(Accounts, Debug)]
+			pub struct SwapAccounts<'a> {
+				#[pina(signer)]
+				pub authority: &'a AccountView,
+				pub token_pair: TokenPairAccounts<'a>,
+				#[pina(program = system)]
+				pub system_program: &'a AccountView,
+			}
+
+			impl<'a> ProcessAccountInfos<'a> for SwapAccounts<'a> {
+				fn process(self, data: &[u8]) -> ProgramResult {
+					let _ = SwapIx::try_from_bytes(data)?;
+					Ok(())
+				}
+			}
+
+			pub mod entrypoint {
+				use super::*;
+
+				pub fn process_instruction(
+					program_id: &Address,
+					accounts: &mut [AccountView],
+					data: &[u8],
+				) -> ProgramResult {
+					let instruction: SwapInstruction = parse_instruction(program_id, &ID, data)?;
+
+					match instruction {
+						SwapInstruction::Swap => SwapAccounts::try_from(accounts)?.process(data),
+					}
+				}
+			}
+		"#;
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let ir = assemble_program_ir(&file, "swap").unwrap_or_else(|e| panic!("assemble: {e}"));
+		let accounts = &ir.instructions[0].accounts;
+		let names: Vec<&str> = accounts
+			.iter()
+			.map(|account| account.name.as_str())
+			.collect();
+
+		assert_eq!(names, ["authority", "mint", "vault", "system_program"]);
+		assert!(accounts[0].is_signer);
+		assert!(!accounts[1].is_writable);
+		assert!(accounts[2].is_writable);
+	}
+
 	#[test]
 	fn assemble_program_ir_rejects_missing_instruction_discriminator_variant() {
 		let source = r#"
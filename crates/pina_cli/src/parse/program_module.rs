@@ -0,0 +1,247 @@
+use heck::ToUpperCamelCase;
+use syn::File;
+use syn::Item;
+
+use super::discriminator::DiscriminatorEnum;
+use super::discriminator::DiscriminatorVariant;
+use super::doc_comments::extract_docs;
+use super::entrypoint::DispatchEntry;
+use super::instruction_data::InstructionStruct;
+
+/// A parsed `#[program]` module.
+///
+/// The macro generates the discriminator enum and the `process_instruction`
+/// dispatch, so neither appears in the source. Both are rebuilt here from the
+/// handlers, following the same naming and numbering rules as the macro.
+#[derive(Debug, Clone)]
+pub struct ProgramModule {
+	/// The generated discriminator enum.
+	pub discriminator: DiscriminatorEnum,
+	/// One dispatch entry per handler, in declaration order.
+	pub dispatch: Vec<DispatchEntry>,
+	/// An argument-less instruction per handler, used for handlers that have
+	/// no `#[instruction]` struct describing their data.
+	pub instructions: Vec<InstructionStruct>,
+}
+
+/// Extract all `#[program]` modules from a file.
+pub fn extract_program_modules(file: &File) -> Vec<ProgramModule> {
+	file.items
+		.iter()
+		.filter_map(|item| {
+			let Item::Mod(item_mod) = item else {
+				return None;
+			};
+			let attr = item_mod
+				.attrs
+				.iter()
+				.find(|attr| attr.path().is_ident("program"))?;
+			let (_, items) = item_mod.content.as_ref()?;
+
+			Some(parse_program_module(&item_mod.ident, attr, items))
+		})
+		.collect()
+}
+
+fn parse_program_module(
+	module_name: &syn::Ident,
+	attr: &syn::Attribute,
+	items: &[Item],
+) -> ProgramModule {
+	let mut enum_name = format!(
+		"{}Instruction",
+		module_name.to_string().to_upper_camel_case()
+	);
+	let mut repr_size = 1;
+
+	if matches!(attr.meta, syn::Meta::List(_)) {
+		let _ = attr.parse_nested_meta(|meta| {
+			if !meta.input.peek(syn::Token![=]) {
+				return Ok(());
+			}
+
+			let value = meta.value()?.parse::<syn::Expr>()?;
+			let syn::Expr::Path(path) = value else {
+				return Ok(());
+			};
+			let Some(ident) = path.path.get_ident().map(ToString::to_string) else {
+				return Ok(());
+			};
+
+			if meta.path.is_ident("instruction") {
+				enum_name = ident;
+			} else if meta.path.is_ident("primitive") {
+				repr_size = match ident.as_str() {
+					"u16" => 2,
+					"u32" => 4,
+					"u64" => 8,
+					_ => 1,
+				};
+			}
+
+			Ok(())
+		});
+	}
+
+	let mut variants = Vec::new();
+	let mut dispatch = Vec::new();
+	let mut instructions = Vec::new();
+	let mut next_value: u64 = 0;
+
+	for item in items {
+		let Item::Fn(handler) = item else {
+			continue;
+		};
+
+		if !matches!(handler.vis, syn::Visibility::Public(_)) {
+			continue;
+		}
+
+		let value = handler_discriminator(&handler.attrs).unwrap_or(next_value);
+		next_value = value.saturating_add(1);
+
+		let variant = handler.sig.ident.to_string().to_upper_camel_case();
+
+		variants.push(DiscriminatorVariant {
+			name: variant.clone(),
+			value,
+		});
+		dispatch.push(DispatchEntry {
+			variant: variant.clone(),
+			accounts_struct: handler.sig.inputs.first().and_then(accounts_type_name),
+		});
+		instructions.push(InstructionStruct {
+			name: format!("{variant}Instruction"),
+			discriminator_enum: enum_name.clone(),
+			variant,
+			fields: Vec::new(),
+			docs: extract_docs(&handler.attrs),
+		});
+	}
+
+	ProgramModule {
+		discriminator: DiscriminatorEnum {
+			name: enum_name,
+			variants,
+			repr_size,
+			big_endian: false,
+		},
+		dispatch,
+		instructions,
+	}
+}
+
+/// The value set by `#[pina(discriminator = N)]` on a handler.
+fn handler_discriminator(attrs: &[syn::Attribute]) -> Option<u64> {
+	let mut value = None;
+
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("pina")) {
+		let _ = attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("discriminator") {
+				value = meta.value()?.parse::<syn::LitInt>()?.base10_parse().ok();
+			}
+
+			Ok(())
+		});
+	}
+
+	value
+}
+
+/// The accounts struct named by a handler's first parameter, such as
+/// `PingAccounts` for `accounts: PingAccounts<'_>`.
+fn accounts_type_name(input: &syn::FnArg) -> Option<String> {
+	let syn::FnArg::Typed(pat_type) = input else {
+		return None;
+	};
+	let syn::Type::Path(type_path) = pat_type.ty.as_ref() else {
+		return None;
+	};
+
+	type_path
+		.path
+		.segments
+		.last()
+		.map(|segment| segment.ident.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rebuilds_the_generated_enum_and_dispatch() {
+		let source = r"
+			#[program(crate = ::pina, primitive = u16, no_entrypoint)]
+			pub mod ping {
+				use super::*;
+
+				/// Check the authority.
+				pub fn ping(accounts: PingAccounts<'_>, _data: &[u8]) -> ProgramResult {
+					Ok(())
+				}
+
+				#[pina(discriminator = 4)]
+				pub fn pong_back(accounts: PongAccounts<'_>) -> ProgramResult {
+					Ok(())
+				}
+
+				pub fn after(accounts: PingAccounts<'_>) -> ProgramResult {
+					Ok(())
+				}
+
+				fn helper() {}
+			}
+		";
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let modules = extract_program_modules(&file);
+
+		assert_eq!(modules.len(), 1);
+		let module = &modules[0];
+		assert_eq!(module.discriminator.name, "PingInstruction");
+		assert_eq!(module.discriminator.repr_size, 2);
+
+		let values: Vec<_> = module
+			.discriminator
+			.variants
+			.iter()
+			.map(|variant| (variant.name.as_str(), variant.value))
+			.collect();
+		assert_eq!(values, [("Ping", 0), ("PongBack", 4), ("After", 5)]);
+
+		let accounts: Vec<_> = module
+			.dispatch
+			.iter()
+			.map(|entry| entry.accounts_struct.as_deref())
+			.collect();
+		assert_eq!(
+			accounts,
+			[
+				Some("PingAccounts"),
+				Some("PongAccounts"),
+				Some("PingAccounts")
+			]
+		);
+		assert_eq!(module.instructions[0].docs, ["Check the authority."]);
+	}
+
+	#[test]
+	fn reads_a_custom_enum_name() {
+		let source = r"
+			#[program(instruction = GameInstruction)]
+			pub mod game {
+				pub fn start(accounts: StartAccounts<'_>) -> ProgramResult {
+					Ok(())
+				}
+			}
+		";
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let modules = extract_program_modules(&file);
+
+		assert_eq!(modules[0].discriminator.name, "GameInstruction");
+		assert_eq!(
+			modules[0].instructions[0].discriminator_enum,
+			"GameInstruction"
+		);
+	}
+}
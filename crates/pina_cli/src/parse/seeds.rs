@@ -1,3 +1,4 @@
+use heck::ToSnakeCase;
 use syn::File;
 use syn::Item;
 
@@ -87,6 +88,104 @@ pub fn extract_pda_from_seed_macros(file: &File, seed_constants: &[SeedConstant]
 	pdas
 }
 
+/// Extract PDAs declared with `#[account(seeds = [...])]`.
+///
+/// The PDA is named after the account struct without a `State` or `Account`
+/// suffix, so `CounterState` declares the `counter` PDA. String literals and
+/// known seed constants become constant seeds and lowercase identifiers
+/// become `Address` variables, matching the parameters the macro generates.
+pub fn extract_pda_from_account_seeds(file: &File, seed_constants: &[SeedConstant]) -> Vec<PdaIr> {
+	let mut pdas = Vec::new();
+
+	for item in &file.items {
+		let Item::Struct(item_struct) = item else {
+			continue;
+		};
+		let Some(seeds) = account_seeds_attr(&item_struct.attrs) else {
+			continue;
+		};
+
+		let struct_name = item_struct.ident.to_string();
+		let pda_name = struct_name
+			.strip_suffix("State")
+			.or_else(|| struct_name.strip_suffix("Account"))
+			.filter(|name| !name.is_empty())
+			.unwrap_or(&struct_name)
+			.to_snake_case();
+		let seeds = seeds
+			.elems
+			.iter()
+			.filter_map(|seed| account_seed_to_ir(seed, seed_constants))
+			.collect::<Vec<_>>();
+
+		if !seeds.is_empty() {
+			pdas.push(PdaIr {
+				name: pda_name,
+				seeds,
+			});
+		}
+	}
+
+	pdas
+}
+
+/// The `seeds = [...]` array of an `#[account(...)]` attribute.
+fn account_seeds_attr(attrs: &[syn::Attribute]) -> Option<syn::ExprArray> {
+	for attr in attrs {
+		if !attr.path().is_ident("account") {
+			continue;
+		}
+
+		let Ok(meta_list) = attr.parse_args_with(
+			syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+		) else {
+			continue;
+		};
+
+		for meta in meta_list {
+			if let syn::Meta::NameValue(nv) = meta
+				&& nv.path.is_ident("seeds")
+				&& let syn::Expr::Array(array) = nv.value
+			{
+				return Some(array);
+			}
+		}
+	}
+
+	None
+}
+
+fn account_seed_to_ir(seed: &syn::Expr, seed_constants: &[SeedConstant]) -> Option<PdaSeedIr> {
+	match seed {
+		syn::Expr::Lit(syn::ExprLit {
+			lit: syn::Lit::Str(lit),
+			..
+		}) => {
+			Some(PdaSeedIr::Constant {
+				value: lit.value().into_bytes(),
+			})
+		}
+		syn::Expr::Path(path) => {
+			let name = path.path.get_ident()?.to_string();
+
+			if let Some(constant) = seed_constants.iter().find(|c| c.name == name) {
+				return Some(PdaSeedIr::Constant {
+					value: constant.value.clone(),
+				});
+			}
+
+			name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+				.then(|| {
+					PdaSeedIr::Variable {
+						name,
+						rust_type: "Address".to_owned(),
+					}
+				})
+		}
+		_ => extract_byte_string_value(seed).map(|value| PdaSeedIr::Constant { value }),
+	}
+}
+
 /// Parse the token stream of a seed macro to extract seeds.
 ///
 /// The heuristic looks for the first arm with the fewest macro params (the
@@ -233,4 +332,28 @@ mod tests {
 			matches!(&pdas[0].seeds[1], PdaSeedIr::Variable { name, .. } if name == "authority")
 		);
 	}
+
+	#[test]
+	fn extracts_pda_from_account_seeds() {
+		let source = r#"
+			const COUNTER_SEED: &[u8] = b"counter";
+
+			#[account(discriminator = CounterAccount, seeds = [COUNTER_SEED, "v1", authority])]
+			pub struct CounterState {
+				#[bump]
+				pub bump: u8,
+			}
+		"#;
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let constants = extract_seed_constants(&file);
+		let pdas = extract_pda_from_account_seeds(&file, &constants);
+		assert_eq!(pdas.len(), 1);
+		assert_eq!(pdas[0].name, "counter");
+		assert_eq!(pdas[0].seeds.len(), 3);
+		assert!(matches!(&pdas[0].seeds[0], PdaSeedIr::Constant { value } if value == b"counter"));
+		assert!(matches!(&pdas[0].seeds[1], PdaSeedIr::Constant { value } if value == b"v1"));
+		assert!(
+			matches!(&pdas[0].seeds[2], PdaSeedIr::Variable { name, .. } if name == "authority")
+		);
+	}
 }
@@ -21,6 +21,23 @@ const KNOWN_ADDRESSES: &[(&str, &str)] = &[
 	),
 ];
 
+/// Resolve the program named in a `#[pina(program = ...)]` field attribute to
+/// its base58 address.
+pub fn known_program_address(program: &str) -> Option<String> {
+	let known_path = match program {
+		"system" => "system::ID",
+		"token" => "token::ID",
+		"token_2022" => "token_2022::ID",
+		"associated_token" => "associated_token_account::ID",
+		_ => return None,
+	};
+
+	KNOWN_ADDRESSES
+		.iter()
+		.find(|(path, _)| *path == known_path)
+		.map(|(_, address)| (*address).to_owned())
+}
+
 /// Properties inferred from validation chain analysis for a single account
 /// field.
 #[derive(Debug, Clone, Default)]
@@ -122,6 +139,14 @@ fn collect_assertions_from_expr(expr: &Expr, props: &mut HashMap<String, Account
 				apply_assertion(&method, &mc.args, entry);
 			}
 
+			// `HasBump::assert_pda` is called on the loaded state and takes the
+			// account view as its first argument.
+			if method == "assert_pda"
+				&& let Some(field_name) = mc.args.first().and_then(resolve_self_field)
+			{
+				props.entry(field_name).or_default().is_pda = true;
+			}
+
 			// Also recurse into the receiver (for chained calls).
 			collect_assertions_from_expr(&mc.receiver, props);
 
@@ -233,8 +258,12 @@ fn apply_assertion(
 fn first_arg_to_known_address(
 	args: &syn::punctuated::Punctuated<Expr, syn::Token![,]>,
 ) -> Option<String> {
-	let first = args.first()?;
-	let path_str = expr_to_path_string(first)?;
+	known_address(args.first()?)
+}
+
+/// If `expr` refers to a known program ID, return its base58 address.
+pub fn known_address(expr: &Expr) -> Option<String> {
+	let path_str = expr_to_path_string(expr)?;
 	for &(known_path, known_addr) in KNOWN_ADDRESSES {
 		if path_str.contains(known_path) {
 			return Some(known_addr.to_owned());
@@ -348,6 +377,24 @@ mod tests {
 		assert!(props["counter"].is_writable);
 	}
 
+	#[test]
+	fn extracts_pda_from_stored_bump() {
+		let source = r#"
+			impl<'a> ProcessAccountInfos<'a> for MyAccounts<'a> {
+				fn process(self, data: &[u8]) -> ProgramResult {
+					self.vault
+						.as_account::<VaultState>(&ID)?
+						.assert_pda(self.vault, &[b"vault"], &ID)?;
+					Ok(())
+				}
+			}
+		"#;
+		let file = syn::parse_file(source).unwrap_or_else(|e| panic!("parse failed: {e}"));
+		let all = extract_validation_properties(&file);
+		let props = &all["MyAccounts"];
+		assert!(props["vault"].is_pda);
+	}
+
 	#[test]
 	fn extracts_known_address() {
 		let source = r#"
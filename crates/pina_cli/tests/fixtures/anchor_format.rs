@@ -0,0 +1,25 @@
+declare_id!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS");
+
+#[discriminator(format = anchor)]
+pub enum LegacyInstruction {
+	Initialize,
+	SetValue,
+}
+
+#[discriminator(format = anchor, namespace = account)]
+pub enum LegacyAccount {
+	ValueAccount,
+}
+
+#[account(discriminator = LegacyAccount)]
+pub struct ValueAccount {
+	pub value: PodU64,
+}
+
+#[instruction(discriminator = LegacyInstruction, variant = Initialize)]
+pub struct InitializeInstruction {}
+
+#[instruction(discriminator = LegacyInstruction, variant = SetValue)]
+pub struct SetValueInstruction {
+	pub value: PodU64,
+}
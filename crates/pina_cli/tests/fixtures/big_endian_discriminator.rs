@@ -0,0 +1,11 @@
+declare_id!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS");
+
+#[discriminator(primitive = u16, endian = big)]
+pub enum BridgeInstruction {
+	Relay = 0x0102,
+}
+
+#[instruction(discriminator = BridgeInstruction, variant = Relay)]
+pub struct RelayInstruction {
+	pub nonce: PodU64,
+}
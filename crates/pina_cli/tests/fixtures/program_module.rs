@@ -0,0 +1,32 @@
+declare_id!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS");
+
+#[derive(Accounts, Debug)]
+pub struct PingAccounts<'a> {
+	#[pina(signer)]
+	pub authority: &'a AccountView,
+	pub target: &'a mut AccountView,
+}
+
+#[instruction(discriminator = PingProgramInstruction, variant = SetValue)]
+pub struct SetValueInstruction {
+	pub value: PodU64,
+}
+
+#[program]
+pub mod ping_program {
+	use super::*;
+
+	/// Check the authority signed.
+	pub fn ping(accounts: PingAccounts<'_>) -> ProgramResult {
+		accounts.authority.assert_signer()?;
+
+		Ok(())
+	}
+
+	#[pina(discriminator = 5)]
+	pub fn set_value(accounts: PingAccounts<'_>, data: &[u8]) -> ProgramResult {
+		let _ = SetValueInstruction::try_from_bytes(data)?;
+
+		Ok(())
+	}
+}
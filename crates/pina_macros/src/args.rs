@@ -4,6 +4,7 @@
 use darling::FromDeriveInput;
 use darling::FromField;
 use darling::FromMeta;
+use darling::FromVariant;
 use quote::ToTokens;
 use syn::Expr;
 
@@ -13,10 +14,30 @@ pub(crate) struct AccountArgs {
 	/// Set the path to the crate
 	#[darling(default = "default_crate_path", rename = "crate")]
 	pub(crate) crate_path: syn::Path,
-	/// Set the discriminator enum for this account.
-	pub(crate) discriminator: syn::Path,
+	/// Set the discriminator enum for this account. Required unless the
+	/// account is `external`.
+	pub(crate) discriminator: Option<syn::Path>,
 	/// Set the variant of the discriminator enum.
 	pub(crate) variant: Option<syn::Ident>,
+	/// Declare the PDA seeds (without the bump) as an array of literals,
+	/// constants and seed parameters.
+	pub(crate) seeds: Option<Expr>,
+	/// Set `anchor` to assert that the discriminator is Anchor's
+	/// `sha256("account:<Name>")` sighash.
+	#[darling(default)]
+	pub(crate) format: DiscriminatorFormat,
+	/// Set the layout version stored in the struct's `version: u8` field.
+	pub(crate) version: Option<u8>,
+	/// Set the account layout this one replaces, implementing `Migrate`.
+	pub(crate) migrate_from: Option<syn::Path>,
+	/// Leave out the by-value builder so the account can exceed the stack.
+	#[darling(default)]
+	pub(crate) zero_copy: bool,
+	/// Declare the layout of an account owned by another program.
+	#[darling(default)]
+	pub(crate) external: bool,
+	/// Set the program that owns an `external` account.
+	pub(crate) owner: Option<Expr>,
 }
 
 /// Arguments for the `#[instruction(...)]` attribute macro.
@@ -29,6 +50,10 @@ pub(crate) struct InstructionArgs {
 	pub(crate) discriminator: syn::Path,
 	/// Set the variant of the discriminator enum.
 	pub(crate) variant: Option<syn::Ident>,
+	/// Set `anchor` to assert that the discriminator is Anchor's
+	/// `sha256("global:<variant_in_snake_case>")` sighash.
+	#[darling(default)]
+	pub(crate) format: DiscriminatorFormat,
 }
 
 /// Arguments for the `#[event(...)]` attribute macro.
@@ -43,6 +68,33 @@ pub(crate) struct EventArgs {
 	pub(crate) variant: Option<syn::Ident>,
 }
 
+/// Arguments for the `#[program(...)]` attribute macro.
+#[derive(Debug, FromMeta)]
+pub(crate) struct ProgramArgs {
+	/// Set the path to the crate
+	#[darling(default = "default_crate_path", rename = "crate")]
+	pub(crate) crate_path: syn::Path,
+	/// Set the name of the generated instruction discriminator enum. Defaults
+	/// to the module name in `PascalCase` followed by `Instruction`.
+	pub(crate) instruction: Option<syn::Ident>,
+	/// Set the primitive type of the generated discriminator enum.
+	#[darling(default = "Primitive::default")]
+	pub(crate) primitive: Primitive,
+	/// Set the program ID that instructions must be addressed to. Defaults to
+	/// `crate::ID`.
+	pub(crate) program_id: Option<Expr>,
+	/// Skip the `nostd_entrypoint!` wiring.
+	pub(crate) no_entrypoint: darling::util::Flag,
+}
+
+/// Arguments for a `#[pina(...)]` attribute on a `#[program]` handler.
+#[derive(Debug, Default, FromMeta)]
+pub(crate) struct ProgramHandlerArgs {
+	/// Set the discriminator value of the handler's instruction. Defaults to
+	/// one more than the previous handler, starting at zero.
+	pub(crate) discriminator: Option<syn::LitInt>,
+}
+
 /// Arguments for the `#[error(...)]` attribute macro.
 #[derive(Debug, FromMeta)]
 pub(crate) struct ErrorArgs {
@@ -52,6 +104,9 @@ pub(crate) struct ErrorArgs {
 	/// Set whether the error enum is in it's final form.
 	#[darling(rename = "final")]
 	pub(crate) is_final: darling::util::Flag,
+	/// Add this value to every variant when converting into
+	/// `ProgramError::Custom`.
+	pub(crate) offset: Option<u32>,
 }
 
 fn default_crate_path() -> syn::Path {
@@ -67,14 +122,124 @@ pub(crate) struct DiscriminatorArgs {
 	/// - `u16`
 	/// - `u32`
 	/// - `u64`
-	#[darling(default = "Primitive::default")]
-	pub(crate) primitive: Primitive,
+	///
+	/// Enums using the `anchor` format are always `u64`.
+	pub(crate) primitive: Option<Primitive>,
 	/// Set the path to the crate
 	#[darling(default = "default_crate_path", rename = "crate")]
 	pub(crate) crate_path: syn::Path,
 	/// Set whether the error enum is in it's final form.
 	#[darling(rename = "final")]
 	pub(crate) is_final: darling::util::Flag,
+	/// Set the byte order used to store the discriminator. Can be one of:
+	/// - `"little"` (default)
+	/// - `"big"`
+	#[darling(default)]
+	pub(crate) endian: Endian,
+	/// Set how variant values are assigned. Can be one of:
+	/// - `integer` (default): the value of each variant, implicit values
+	///   counting up from the previous one.
+	/// - `anchor`: Anchor's 8-byte sighash of each variant name.
+	#[darling(default)]
+	pub(crate) format: DiscriminatorFormat,
+	/// Set the Anchor sighash namespace of an `anchor` format enum. Can be one
+	/// of:
+	/// - `global` (default): instructions, hashed as `global:<snake_case>`.
+	/// - `account`: accounts, hashed as `account:<Name>`.
+	pub(crate) namespace: Option<AnchorNamespace>,
+	/// Set a TOML file, relative to the manifest directory, that pins the
+	/// values of published variants.
+	pub(crate) lock: Option<syn::LitStr>,
+}
+
+/// How the values of a discriminator are assigned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum DiscriminatorFormat {
+	#[default]
+	Integer,
+	Anchor,
+}
+
+impl FromMeta for DiscriminatorFormat {
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"integer" => Ok(DiscriminatorFormat::Integer),
+			"anchor" => Ok(DiscriminatorFormat::Anchor),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+
+	fn from_expr(expr: &Expr) -> darling::Result<Self> {
+		match expr {
+			Expr::Path(path) if path.path.get_ident().is_some() => {
+				Self::from_string(&path.path.get_ident().unwrap().to_string())
+					.map_err(|error| error.with_span(expr))
+			}
+			Expr::Lit(lit) => Self::from_value(&lit.lit),
+			Expr::Group(group) => Self::from_expr(&group.expr),
+			_ => Err(darling::Error::unexpected_expr_type(expr)),
+		}
+	}
+}
+
+/// The Anchor sighash namespace of an `anchor` format discriminator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum AnchorNamespace {
+	#[default]
+	Global,
+	Account,
+}
+
+impl FromMeta for AnchorNamespace {
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"global" => Ok(AnchorNamespace::Global),
+			"account" => Ok(AnchorNamespace::Account),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+
+	fn from_expr(expr: &Expr) -> darling::Result<Self> {
+		match expr {
+			Expr::Path(path) if path.path.get_ident().is_some() => {
+				Self::from_string(&path.path.get_ident().unwrap().to_string())
+					.map_err(|error| error.with_span(expr))
+			}
+			Expr::Lit(lit) => Self::from_value(&lit.lit),
+			Expr::Group(group) => Self::from_expr(&group.expr),
+			_ => Err(darling::Error::unexpected_expr_type(expr)),
+		}
+	}
+}
+
+/// Byte order of a multi-byte discriminator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Endian {
+	#[default]
+	Little,
+	Big,
+}
+
+impl FromMeta for Endian {
+	fn from_string(value: &str) -> darling::Result<Self> {
+		match value {
+			"little" => Ok(Endian::Little),
+			"big" => Ok(Endian::Big),
+			_ => Err(darling::Error::unknown_value(value)),
+		}
+	}
+
+	fn from_expr(expr: &Expr) -> darling::Result<Self> {
+		match expr {
+			Expr::Path(path) if path.path.get_ident().is_some() => {
+				Self::from_string(&path.path.get_ident().unwrap().to_string())
+					.map_err(|error| error.with_span(expr))
+			}
+			Expr::Lit(lit) => Self::from_value(&lit.lit),
+			Expr::Group(group) => Self::from_expr(&group.expr),
+			_ => Err(darling::Error::unexpected_expr_type(expr)),
+		}
+	}
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -138,10 +303,17 @@ impl FromMeta for Primitive {
 #[darling(attributes(pina), supports(struct_named))]
 pub(crate) struct AccountsInput {
 	pub(crate) ident: syn::Ident,
+	pub(crate) vis: syn::Visibility,
 	pub(crate) generics: syn::Generics,
 	pub(crate) data: darling::ast::Data<darling::util::Ignored, AccountsField>,
 	#[darling(default = "default_crate_path", rename = "crate")]
 	pub(crate) crate_path: syn::Path,
+	/// The program ID that marks an omitted optional account. Defaults to
+	/// `crate::ID`.
+	pub(crate) program_id: Option<Expr>,
+	/// Reject two writable account fields with the same address.
+	#[darling(default)]
+	pub(crate) deny_duplicates: darling::util::Flag,
 }
 
 #[derive(Debug, FromField)]
@@ -151,4 +323,123 @@ pub(crate) struct AccountsField {
 	pub(crate) ty: syn::Type,
 	#[darling(default)]
 	pub(crate) remaining: darling::util::Flag,
+	/// Assert that this account is a well-known program while parsing.
+	pub(crate) program: Option<KnownProgram>,
+	/// Assert that this account signed the transaction while parsing.
+	#[darling(default)]
+	pub(crate) signer: darling::util::Flag,
+	/// Assert that this account is writable while parsing.
+	#[darling(default)]
+	pub(crate) writable: darling::util::Flag,
+	/// Assert that this account is owned by the given program while parsing.
+	pub(crate) owner: Option<Expr>,
+	/// Assert that this account has the given address while parsing.
+	pub(crate) address: Option<Expr>,
+	/// Reject this account when it is writable and shares its address with an
+	/// earlier field.
+	pub(crate) distinct_from: Option<syn::Ident>,
+}
+
+/// A well-known program accepted by `#[pina(program = ...)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KnownProgram {
+	System,
+	Token,
+	Token2022,
+	AssociatedToken,
+}
+
+impl KnownProgram {
+	/// The path of the program's ID, relative to the pina crate root.
+	pub(crate) fn id_path(self) -> proc_macro2::TokenStream {
+		match self {
+			KnownProgram::System => quote::quote!(system::ID),
+			KnownProgram::Token => quote::quote!(token::ID),
+			KnownProgram::Token2022 => quote::quote!(token_2022::ID),
+			KnownProgram::AssociatedToken => quote::quote!(associated_token_account::ID),
+		}
+	}
+}
+
+impl FromMeta for KnownProgram {
+	fn from_expr(expr: &Expr) -> darling::Result<Self> {
+		let error = darling::Error::custom(
+			"Unsupported program. Must be one of: `system`, `token`, `token_2022`, \
+			 `associated_token`.",
+		)
+		.with_span(expr);
+		match expr {
+			Expr::Path(path) => {
+				let Some(ident) = path.path.get_ident() else {
+					return Err(error);
+				};
+
+				match ident.to_string().as_str() {
+					"system" => Ok(KnownProgram::System),
+					"token" => Ok(KnownProgram::Token),
+					"token_2022" => Ok(KnownProgram::Token2022),
+					"associated_token" => Ok(KnownProgram::AssociatedToken),
+					_ => Err(error),
+				}
+			}
+			Expr::Group(group) => Self::from_expr(&group.expr),
+			_ => Err(error),
+		}
+	}
+}
+
+/// Parsed input for the account state derives (`HasBalance`,
+/// `HasAuthority`).
+///
+/// The derives share the `pina` helper attribute, so every field flag is
+/// accepted by each of them and a struct can derive several at once.
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(pina), supports(struct_named))]
+pub(crate) struct StateDeriveInput {
+	pub(crate) ident: syn::Ident,
+	pub(crate) generics: syn::Generics,
+	pub(crate) data: darling::ast::Data<darling::util::Ignored, StateDeriveField>,
+	#[darling(default = "default_crate_path", rename = "crate")]
+	pub(crate) crate_path: syn::Path,
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(pina))]
+pub(crate) struct StateDeriveField {
+	pub(crate) ident: Option<syn::Ident>,
+	/// Use this field as the balance instead of the field named `balance`.
+	#[darling(default)]
+	pub(crate) balance: darling::util::Flag,
+	/// Use this field as the authority instead of the field named
+	/// `authority`.
+	#[darling(default)]
+	pub(crate) authority: darling::util::Flag,
+	/// Use this field as the sequence number instead of the field named
+	/// `sequence`.
+	#[darling(default)]
+	pub(crate) sequence: darling::util::Flag,
+}
+
+/// Parsed input for `#[derive(AccountUnion)]`.
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(pina), supports(enum_newtype))]
+pub(crate) struct AccountUnionInput {
+	pub(crate) ident: syn::Ident,
+	pub(crate) generics: syn::Generics,
+	pub(crate) data: darling::ast::Data<AccountUnionVariant, darling::util::Ignored>,
+	#[darling(default = "default_crate_path", rename = "crate")]
+	pub(crate) crate_path: syn::Path,
+}
+
+/// A variant of an `AccountUnion` enum, holding the guard of one account
+/// type.
+#[derive(Debug, FromVariant)]
+pub(crate) struct AccountUnionVariant {
+	pub(crate) ident: syn::Ident,
+	pub(crate) fields: darling::ast::Fields<AccountUnionField>,
+}
+
+#[derive(Debug, FromField)]
+pub(crate) struct AccountUnionField {
+	pub(crate) ty: syn::Type,
 }
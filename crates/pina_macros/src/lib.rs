@@ -1,12 +1,24 @@
 use args::AccountArgs;
+use args::AccountUnionInput;
+use args::AccountsField;
 use args::AccountsInput;
+use args::AnchorNamespace;
 use args::DiscriminatorArgs;
+use args::DiscriminatorFormat;
+use args::Endian;
 use args::ErrorArgs;
 use args::EventArgs;
+use args::Primitive;
+use args::ProgramArgs;
+use args::ProgramHandlerArgs;
+use args::StateDeriveField;
+use args::StateDeriveInput;
 use darling::FromDeriveInput;
 use darling::FromMeta;
 use darling::ast::NestedMeta;
 use heck::ToShoutySnakeCase;
+use heck::ToSnakeCase;
+use heck::ToUpperCamelCase;
 use proc_macro::TokenStream;
 use quote::format_ident;
 use quote::quote;
@@ -30,7 +42,64 @@ mod tests;
 ///
 /// Fields may be `&'a AccountView`, `&'a mut AccountView`, `&'a [AccountView]`,
 /// or `&'a mut [AccountView]`. One field may be annotated with
-/// `#[pina(remaining)]` to capture all trailing accounts as a slice.
+/// `#[pina(remaining)]` to capture all trailing accounts as a slice. A last
+/// field typed `RemainingAccounts<'a>` captures them as a cursor instead,
+/// which reads the tail one account, or one nested accounts struct, at a time.
+///
+/// A field whose type is another `#[derive(Accounts)]` struct, such as
+/// `pub token_pair: TokenPairAccounts<'a>`, consumes that struct's accounts in
+/// place and runs its checks, so a cluster of accounts shared by several
+/// instructions is declared once.
+///
+/// Annotate a program account with `#[pina(program = system)]` to assert its
+/// address while parsing instead of calling `assert_address` in every
+/// processor. `token`, `token_2022` and `associated_token` are also accepted
+/// and require pina's `token` feature.
+///
+/// The typed fields in `pina::accounts` carry their checks in the type and
+/// run them while parsing. `Program<'a, System>` asserts the program address,
+/// `Sysvar<'a, Clock>` the sysvar address, and `Mint<'a>` and
+/// `TokenAccount<'a>` the token program owner, the layout and that the state
+/// is initialized, so the processor can read them without further checks.
+/// `SignerAccount<'a>`, `Writable<'a>` and `Readonly<'a>` do the same for the
+/// account flags, and can be passed on to helpers as proof of the check.
+///
+/// The common per-account checks can be declared the same way and run, in
+/// this order, as each account is parsed:
+///
+/// - `#[pina(signer)]` asserts that the account signed the transaction.
+/// - `#[pina(writable)]` asserts that the account is writable.
+/// - `#[pina(owner = expr)]` asserts that the account is owned by `expr`.
+/// - `#[pina(address = expr)]` asserts that the account is `expr`.
+///
+/// Flags can be combined, as in `#[pina(signer, writable)]`. Checks that
+/// depend on instruction data, such as PDA seeds, stay in the processor.
+///
+/// The cursor already rejects a `&'a mut AccountView` field whose address is
+/// passed again as a later writable account. Set `#[pina(deny_duplicates)]`
+/// on the struct to reject any two writable account fields with the same
+/// address, including `&'a AccountView` fields that are only asserted
+/// writable, or mark a single field with `#[pina(distinct_from = other)]` to
+/// compare it against one earlier field. Both return
+/// `PinaProgramError::DuplicateMutableAccount`. Accounts inside nested
+/// structs and remaining accounts are not compared.
+///
+/// Fields typed `Option<&'a AccountView>` or `Option<&'a mut AccountView>` are
+/// optional accounts. They are `None` when the instruction ran out of
+/// accounts, or when the client passed the program ID in that position as a
+/// placeholder, and their checks only run when the account is present. The
+/// placeholder defaults to `crate::ID`; set `#[pina(program_id = expr)]` on
+/// the struct to use another value.
+///
+/// With pina's `client` feature the derive also emits a `<Name>Keys` struct
+/// for clients. It holds an `Address` per account field, an `Option<Address>`
+/// per optional account, the nested struct's `Keys` per nested field and a
+/// `Vec<AccountMeta>` for remaining accounts, while `program`, `Program` and
+/// `Sysvar` fields are filled in from their known address.
+/// `ToAccountMetas::to_account_metas()` lists the metas in field order, with
+/// `signer` and `Signer` fields as signers, `writable`, `Writable` and
+/// `&'a mut` fields as writable, and the placeholder for omitted optional
+/// accounts.
 #[proc_macro_derive(Accounts, attributes(pina))]
 pub fn accounts_derive(input: TokenStream) -> TokenStream {
 	accounts_derive_impl(input.into()).into()
@@ -70,11 +139,13 @@ fn accounts_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenSt
 	let mut field_idents = Vec::new();
 	let mut parse_fields = Vec::new();
 	let mut remaining_field = None;
+	// Account reference fields parsed so far, and whether each is optional.
+	let mut account_fields: Vec<(&syn::Ident, bool)> = Vec::new();
 	let field_count = fields.len();
 	let mut seen_remaining = false;
 
 	for field in fields.iter() {
-		if !field.remaining.is_present() {
+		if !field.remaining.is_present() && !is_remaining_accounts(&field.ty) {
 			continue;
 		}
 
@@ -92,7 +163,7 @@ fn accounts_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenSt
 	for (index, field) in fields.iter().enumerate() {
 		let ident = field.ident.as_ref().unwrap();
 
-		if field.remaining.is_present() {
+		if field.remaining.is_present() || is_remaining_accounts(&field.ty) {
 			if index + 1 != field_count {
 				return syn::Error::new_spanned(
 					&field.ident,
@@ -101,12 +172,28 @@ fn accounts_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenSt
 				.to_compile_error();
 			}
 
-			remaining_field = field.ident.as_ref();
+			remaining_field = Some(field);
 			continue;
 		}
 
 		field_idents.push(ident);
-		let parse_field = if is_mut_reference(&field.ty) {
+		let optional = optional_account_reference(&field.ty);
+		let is_account_field = is_account_reference(&field.ty)
+			|| optional.is_some()
+			|| account_wrapper(&field.ty).is_some();
+		let parse_field = if let Some(reference) = optional {
+			let next = if reference.mutability.is_some() {
+				quote!(next_optional_mut)
+			} else {
+				quote!(next_optional)
+			};
+			let program_id = args
+				.program_id
+				.as_ref()
+				.map_or_else(|| quote!(crate::ID), |program_id| quote!(#program_id));
+
+			quote! { let #ident = cursor.#next(&(#program_id))?; }
+		} else if is_mut_reference(&field.ty) {
 			quote! { let #ident = cursor.next_mut()?; }
 		} else if is_reference(&field.ty) {
 			quote! { let #ident = cursor.next()?; }
@@ -115,6 +202,128 @@ fn accounts_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenSt
 			quote! { let #ident = <#ty as #crate_path::ParseAccounts>::parse_accounts(cursor)?; }
 		};
 		parse_fields.push(parse_field);
+
+		// Checks on an optional account only run when it was provided.
+		let checks_start = parse_fields.len();
+
+		if let Some(program) = field.program {
+			if !is_account_reference(&field.ty) && optional.is_none() {
+				return syn::Error::new_spanned(
+					&field.ident,
+					"`#[pina(program = ...)]` can only be used on account reference fields",
+				)
+				.to_compile_error();
+			}
+
+			let id_path = program.id_path();
+			parse_fields.push(quote! {
+				#crate_path::AccountInfoValidation::assert_address(&*#ident, &#crate_path::#id_path)?;
+			});
+		}
+
+		let has_constraints = field.signer.is_present()
+			|| field.writable.is_present()
+			|| field.owner.is_some()
+			|| field.address.is_some();
+
+		if has_constraints && !is_account_field {
+			return syn::Error::new_spanned(
+				&field.ident,
+				"`signer`, `writable`, `owner` and `address` can only be used on account \
+				 reference fields",
+			)
+			.to_compile_error();
+		}
+
+		if field.signer.is_present() {
+			parse_fields.push(quote! {
+				#crate_path::AccountInfoValidation::assert_signer(&*#ident)?;
+			});
+		}
+
+		if field.writable.is_present() {
+			parse_fields.push(quote! {
+				#crate_path::AccountInfoValidation::assert_writable(&*#ident)?;
+			});
+		}
+
+		if let Some(owner) = &field.owner {
+			parse_fields.push(quote! {
+				#crate_path::AccountInfoValidation::assert_owner(&*#ident, &(#owner))?;
+			});
+		}
+
+		if let Some(address) = &field.address {
+			parse_fields.push(quote! {
+				#crate_path::AccountInfoValidation::assert_address(&*#ident, &(#address))?;
+			});
+		}
+
+		if let Some(other) = &field.distinct_from {
+			if !is_account_field {
+				return syn::Error::new_spanned(
+					&field.ident,
+					"`#[pina(distinct_from = ...)]` can only be used on account reference fields",
+				)
+				.to_compile_error();
+			}
+
+			let Some(&(_, other_optional)) = account_fields.iter().find(|(name, _)| *name == other)
+			else {
+				return syn::Error::new_spanned(
+					other,
+					"`distinct_from` must name an earlier account field",
+				)
+				.to_compile_error();
+			};
+
+			parse_fields.push(if other_optional {
+				quote! {
+					#crate_path::assert_distinct_writable(
+						[::core::option::Option::Some(&*#ident), #other.as_deref()]
+							.into_iter()
+							.flatten(),
+					)?;
+				}
+			} else {
+				quote! { #crate_path::assert_distinct_writable([&*#ident, &*#other])?; }
+			});
+		}
+
+		if is_account_field {
+			account_fields.push((ident, optional.is_some()));
+		}
+
+		if optional.is_some() && parse_fields.len() > checks_start {
+			let checks = parse_fields.split_off(checks_start);
+			parse_fields.push(quote! {
+				if let ::core::option::Option::Some(#ident) = &#ident {
+					let #ident: &#crate_path::AccountView = #ident;
+					#(#checks)*
+				}
+			});
+		}
+	}
+
+	if args.deny_duplicates.is_present() && account_fields.len() > 1 {
+		let has_optional = account_fields.iter().any(|(_, optional)| *optional);
+		let accounts = account_fields.iter().map(|(ident, optional)| {
+			if *optional {
+				quote!(#ident.as_deref())
+			} else if has_optional {
+				quote!(::core::option::Option::Some(&*#ident))
+			} else {
+				quote!(&*#ident)
+			}
+		});
+
+		parse_fields.push(if has_optional {
+			quote! {
+				#crate_path::assert_distinct_writable([#(#accounts),*].into_iter().flatten())?;
+			}
+		} else {
+			quote! { #crate_path::assert_distinct_writable([#(#accounts),*])?; }
+		});
 	}
 
 	let finish_exact = remaining_field.is_none().then(|| {
@@ -122,8 +331,26 @@ fn accounts_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenSt
 			cursor.finish_exact()?;
 		}
 	});
-	let remaining_binding = remaining_field.map(|f| quote! { let #f = cursor.remaining_mut(); });
-	let remaining_field_ident = remaining_field.map(|f| quote!(#f,));
+	let remaining_binding = remaining_field.map(|field| {
+		let ident = &field.ident;
+
+		if is_remaining_accounts(&field.ty) {
+			quote! { let #ident = #crate_path::RemainingAccounts::new(cursor.remaining_mut()); }
+		} else {
+			quote! { let #ident = cursor.remaining_mut(); }
+		}
+	});
+	let remaining_field_ident = remaining_field.map(|field| {
+		let ident = &field.ident;
+		quote!(#ident,)
+	});
+	let client_keys = accounts_client_keys(
+		&args.ident,
+		&args.vis,
+		crate_path,
+		args.program_id.as_ref(),
+		&fields,
+	);
 
 	quote! {
 		impl #impl_generics #crate_path::ParseAccounts #ty_generics for #struct_name #ty_generics #where_clause {
@@ -159,13 +386,545 @@ fn accounts_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenSt
 				<Self as #crate_path::TryFromAccountInfos>::try_from_account_infos(accounts)
 			}
 		}
+
+		#client_keys
+	}
+}
+
+/// The off-chain `<Name>Keys` struct for an accounts struct, with one address
+/// per account field and a `ToAccountMetas` impl that lists them in order.
+///
+/// Program and sysvar fields are filled in from their known address, optional
+/// accounts fall back to the same placeholder the parser accepts, and nested
+/// accounts structs and remaining accounts contribute their metas in place.
+fn accounts_client_keys(
+	struct_name: &syn::Ident,
+	vis: &syn::Visibility,
+	crate_path: &syn::Path,
+	program_id: Option<&syn::Expr>,
+	fields: &darling::ast::Fields<AccountsField>,
+) -> proc_macro2::TokenStream {
+	let keys_name = format_ident!("{}Keys", struct_name);
+	let keys_doc = format!("Account addresses for [`{struct_name}`], in instruction order.");
+	let account_meta = quote!(#crate_path::transaction::AccountMeta);
+	let metas_vec = quote!(#crate_path::alloc::vec::Vec<#account_meta>);
+	let mut key_fields = Vec::new();
+	let mut appends = Vec::new();
+
+	for field in fields.iter() {
+		let ident = field.ident.as_ref().unwrap();
+
+		if field.remaining.is_present() || is_remaining_accounts(&field.ty) {
+			key_fields.push(quote! { pub #ident: #metas_vec });
+			appends.push(quote! { metas.extend_from_slice(&self.#ident); });
+			continue;
+		}
+
+		let optional = optional_account_reference(&field.ty);
+		let wrapper = account_wrapper(&field.ty);
+
+		if !is_account_reference(&field.ty) && optional.is_none() && wrapper.is_none() {
+			let nested_keys = nested_accounts_keys(&field.ty);
+			key_fields.push(quote! { pub #ident: #nested_keys });
+			appends.push(quote! {
+				#crate_path::transaction::ToAccountMetas::append_account_metas(&self.#ident, metas);
+			});
+			continue;
+		}
+
+		let known_address = match (field.program, wrapper) {
+			(Some(program), _) => {
+				let id_path = program.id_path();
+				Some(quote!(#crate_path::#id_path))
+			}
+			(None, Some(AccountWrapper::Program(program))) => {
+				Some(quote!(<#program as #crate_path::accounts::ProgramId>::ID))
+			}
+			(None, Some(AccountWrapper::Sysvar(sysvar))) => {
+				Some(quote!(<#sysvar as #crate_path::sysvars::SysvarAccount>::ADDRESS))
+			}
+			(None, Some(AccountWrapper::Account { .. }) | None) => None,
+		};
+		let (wrapper_signer, wrapper_writable) = match wrapper {
+			Some(AccountWrapper::Account { signer, writable }) => (signer, writable),
+			_ => (false, false),
+		};
+		let is_signer = field.signer.is_present() || wrapper_signer;
+		let is_writable = field.writable.is_present()
+			|| wrapper_writable
+			|| is_mut_reference(&field.ty)
+			|| optional.is_some_and(|reference| reference.mutability.is_some());
+
+		if optional.is_some() {
+			let program_id =
+				program_id.map_or_else(|| quote!(crate::ID), |program_id| quote!(#program_id));
+
+			key_fields.push(quote! { pub #ident: ::core::option::Option<#crate_path::Address> });
+			appends.push(quote! {
+				metas.push(match self.#ident {
+					::core::option::Option::Some(pubkey) => #account_meta {
+						pubkey,
+						is_signer: #is_signer,
+						is_writable: #is_writable,
+					},
+					::core::option::Option::None => #account_meta::new_readonly(#program_id, false),
+				});
+			});
+		} else if let Some(pubkey) = known_address {
+			appends.push(quote! {
+				metas.push(#account_meta {
+					pubkey: #pubkey,
+					is_signer: #is_signer,
+					is_writable: #is_writable,
+				});
+			});
+		} else {
+			key_fields.push(quote! { pub #ident: #crate_path::Address });
+			appends.push(quote! {
+				metas.push(#account_meta {
+					pubkey: self.#ident,
+					is_signer: #is_signer,
+					is_writable: #is_writable,
+				});
+			});
+		}
+	}
+
+	quote! {
+		#crate_path::__client! {
+			#[doc = #keys_doc]
+			#[derive(Clone, Debug, PartialEq, Eq)]
+			#vis struct #keys_name {
+				#(#key_fields,)*
+			}
+
+			impl #crate_path::transaction::ToAccountMetas for #keys_name {
+				fn append_account_metas(&self, metas: &mut #metas_vec) {
+					#(#appends)*
+				}
+			}
+		}
+	}
+}
+
+/// The `<Name>Keys` path for a nested accounts struct type, dropping its
+/// lifetime.
+fn nested_accounts_keys(ty: &Type) -> proc_macro2::TokenStream {
+	let Type::Path(path) = ty else {
+		return syn::Error::new_spanned(ty, "expected an accounts struct type").to_compile_error();
+	};
+	let mut path = path.path.clone();
+
+	if let Some(last) = path.segments.last_mut() {
+		last.ident = format_ident!("{}Keys", last.ident);
+		last.arguments = syn::PathArguments::None;
+	}
+
+	quote!(#path)
+}
+
+/// Derives the `HasBalance` trait for a named-field account struct.
+///
+/// The balance is read from the field named `balance`, or from the single
+/// field annotated with `#[pina(balance)]`. The field type must convert to and
+/// from `u64`, which holds for `u64` and `PodU64`.
+#[proc_macro_derive(HasBalance, attributes(pina))]
+pub fn has_balance_derive(input: TokenStream) -> TokenStream {
+	has_balance_derive_impl(input.into()).into()
+}
+
+fn has_balance_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	let input: DeriveInput = match syn::parse2(input) {
+		Ok(v) => v,
+		Err(e) => return e.to_compile_error(),
+	};
+
+	let args = match StateDeriveInput::from_derive_input(&input) {
+		Ok(v) => v,
+		Err(e) => return e.write_errors(),
+	};
+
+	let struct_name = &args.ident;
+	let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
+	let crate_path = &args.crate_path;
+	let fields = args.data.as_ref().take_struct().unwrap();
+
+	let balance_field = match find_state_field(
+		struct_name,
+		&fields.fields,
+		"balance",
+		|field| field.balance.is_present(),
+		"HasBalance",
+	) {
+		Ok(ident) => ident,
+		Err(error) => return error.to_compile_error(),
+	};
+
+	quote! {
+		impl #impl_generics #crate_path::HasBalance for #struct_name #ty_generics #where_clause {
+			#[inline]
+			fn balance(&self) -> u64 {
+				::core::convert::Into::<u64>::into(self.#balance_field)
+			}
+
+			#[inline]
+			fn set_balance(&mut self, balance: u64) {
+				self.#balance_field = ::core::convert::Into::into(balance);
+			}
+		}
+	}
+}
+
+/// Derives the `HasSequence` trait for a named-field account struct.
+///
+/// The sequence number is read from the field named `sequence`, or from the
+/// single field annotated with `#[pina(sequence)]`. The field type must
+/// convert to and from `u64`, which holds for `u64` and `PodU64`.
+#[proc_macro_derive(HasSequence, attributes(pina))]
+pub fn has_sequence_derive(input: TokenStream) -> TokenStream {
+	has_sequence_derive_impl(input.into()).into()
+}
+
+fn has_sequence_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	let input: DeriveInput = match syn::parse2(input) {
+		Ok(v) => v,
+		Err(e) => return e.to_compile_error(),
+	};
+
+	let args = match StateDeriveInput::from_derive_input(&input) {
+		Ok(v) => v,
+		Err(e) => return e.write_errors(),
+	};
+
+	let struct_name = &args.ident;
+	let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
+	let crate_path = &args.crate_path;
+	let fields = args.data.as_ref().take_struct().unwrap();
+
+	let sequence_field = match find_state_field(
+		struct_name,
+		&fields.fields,
+		"sequence",
+		|field| field.sequence.is_present(),
+		"HasSequence",
+	) {
+		Ok(ident) => ident,
+		Err(error) => return error.to_compile_error(),
+	};
+
+	quote! {
+		impl #impl_generics #crate_path::HasSequence for #struct_name #ty_generics #where_clause {
+			#[inline]
+			fn sequence(&self) -> u64 {
+				::core::convert::Into::<u64>::into(self.#sequence_field)
+			}
+
+			#[inline]
+			fn set_sequence(&mut self, sequence: u64) {
+				self.#sequence_field = ::core::convert::Into::into(sequence);
+			}
+		}
+	}
+}
+
+/// Derives the `HasAuthority` trait for a named-field account struct.
+///
+/// The authority is read from the field named `authority`, or from the single
+/// field annotated with `#[pina(authority)]`. The field must be an `Address`.
+#[proc_macro_derive(HasAuthority, attributes(pina))]
+pub fn has_authority_derive(input: TokenStream) -> TokenStream {
+	has_authority_derive_impl(input.into()).into()
+}
+
+fn has_authority_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	let input: DeriveInput = match syn::parse2(input) {
+		Ok(v) => v,
+		Err(e) => return e.to_compile_error(),
+	};
+
+	let args = match StateDeriveInput::from_derive_input(&input) {
+		Ok(v) => v,
+		Err(e) => return e.write_errors(),
+	};
+
+	let struct_name = &args.ident;
+	let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
+	let crate_path = &args.crate_path;
+	let fields = args.data.as_ref().take_struct().unwrap();
+
+	let authority_field = match find_state_field(
+		struct_name,
+		&fields.fields,
+		"authority",
+		|field| field.authority.is_present(),
+		"HasAuthority",
+	) {
+		Ok(ident) => ident,
+		Err(error) => return error.to_compile_error(),
+	};
+
+	quote! {
+		impl #impl_generics #crate_path::HasAuthority for #struct_name #ty_generics #where_clause {
+			#[inline]
+			fn authority(&self) -> &#crate_path::Address {
+				&self.#authority_field
+			}
+		}
+	}
+}
+
+/// Pick the field a state derive reads from: the single field marked with the
+/// `pina` flag, or else the field called `name`.
+fn find_state_field<'a>(
+	struct_name: &syn::Ident,
+	fields: &[&'a StateDeriveField],
+	name: &str,
+	is_marked: impl Fn(&StateDeriveField) -> bool,
+	derive_name: &str,
+) -> syn::Result<&'a syn::Ident> {
+	let mut marked = fields.iter().filter(|field| is_marked(field));
+
+	match (marked.next(), marked.next()) {
+		(Some(_), Some(second)) => {
+			Err(syn::Error::new_spanned(
+				&second.ident,
+				format!("Only one field can be marked as `{name}`"),
+			))
+		}
+		(Some(field), None) => Ok(field.ident.as_ref().unwrap()),
+		(None, _) => {
+			fields
+				.iter()
+				.filter_map(|field| field.ident.as_ref())
+				.find(|ident| *ident == name)
+				.ok_or_else(|| {
+					syn::Error::new_spanned(
+						struct_name,
+						format!(
+							"`{derive_name}` requires a field named `{name}` or a field marked \
+							 `#[pina({name})]`"
+						),
+					)
+				})
+		}
+	}
+}
+
+/// Derives a `load` constructor for an enum with one variant per account
+/// type an instruction accepts.
+///
+/// Every variant holds the `Ref` guard of one account type. `load(account,
+/// program_id)` checks the account's owner and returns the first variant whose
+/// discriminator the account holds, trying each type in order with
+/// `AsAccount::try_as_account` so the size is checked like `assert_type`. An
+/// account holding none of them is rejected with `InvalidAccountData`, the
+/// same error and log as `load_one_of!`, which does this without declaring an
+/// enum.
+///
+/// ```ignore
+/// #[derive(AccountUnion)]
+/// pub enum AppState<'a> {
+/// 	UserProfile(Ref<'a, UserProfile>),
+/// 	AdminConfig(Ref<'a, AdminConfig>),
+/// }
+///
+/// match AppState::load(self.target, &ID)? {
+/// 	AppState::UserProfile(profile) => self.update_profile(&profile),
+/// 	AppState::AdminConfig(config) => self.update_config(&config),
+/// }
+/// ```
+#[proc_macro_derive(AccountUnion, attributes(pina))]
+pub fn account_union_derive(input: TokenStream) -> TokenStream {
+	account_union_derive_impl(input.into()).into()
+}
+
+fn account_union_derive_impl(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	let input: DeriveInput = match syn::parse2(input) {
+		Ok(v) => v,
+		Err(e) => return e.to_compile_error(),
+	};
+
+	let args = match AccountUnionInput::from_derive_input(&input) {
+		Ok(v) => v,
+		Err(e) => return e.write_errors(),
+	};
+
+	let enum_name = &args.ident;
+	let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
+	let crate_path = &args.crate_path;
+	let variants = args.data.as_ref().take_enum().unwrap();
+
+	let Some(lifetime) = args.generics.lifetimes().next().map(|lt| &lt.lifetime) else {
+		return syn::Error::new_spanned(
+			enum_name,
+			"AccountUnion enum must have **ONE** lifetime parameter",
+		)
+		.to_compile_error();
+	};
+
+	let mut loads = Vec::new();
+
+	for variant in variants {
+		let variant_name = &variant.ident;
+		let ty = &variant.fields.fields[0].ty;
+
+		let Some(account_type) = ref_target(ty) else {
+			return syn::Error::new_spanned(
+				ty,
+				"AccountUnion variants must hold a `Ref<'a, T>` of an account type",
+			)
+			.to_compile_error();
+		};
+
+		loads.push(quote! {
+			if let ::core::option::Option::Some(state) =
+				#crate_path::AsAccount::try_as_account::<#account_type>(account, program_id)?
+			{
+				return ::core::result::Result::Ok(Self::#variant_name(state));
+			}
+		});
+	}
+
+	quote! {
+		impl #impl_generics #enum_name #ty_generics #where_clause {
+			/// Load the account as whichever variant's account type it holds.
+			#[track_caller]
+			pub fn load(
+				account: &#lifetime #crate_path::AccountView,
+				program_id: &#crate_path::Address,
+			) -> ::core::result::Result<Self, #crate_path::ProgramError> {
+				#(#loads)*
+
+				::core::result::Result::Err(#crate_path::account_type_mismatch(account))
+			}
+		}
 	}
 }
 
+/// The `T` of a `Ref<'a, T>` variant type.
+fn ref_target(ty: &Type) -> Option<&Type> {
+	let Type::Path(type_path) = ty else {
+		return None;
+	};
+	let segment = type_path.path.segments.last()?;
+
+	if segment.ident != "Ref" {
+		return None;
+	}
+
+	let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+		return None;
+	};
+
+	arguments.args.iter().find_map(|argument| {
+		match argument {
+			syn::GenericArgument::Type(ty) => Some(ty),
+			_ => None,
+		}
+	})
+}
+
 fn is_reference(ty: &Type) -> bool {
 	matches!(ty, Type::Reference(_))
 }
 
+fn is_account_reference(ty: &Type) -> bool {
+	matches!(ty, Type::Reference(reference) if !matches!(*reference.elem, Type::Slice(_)))
+}
+
+/// The account reference inside an `Option<&'a AccountView>` or
+/// `Option<&'a mut AccountView>` field type.
+fn optional_account_reference(ty: &Type) -> Option<&syn::TypeReference> {
+	let Type::Path(path) = ty else {
+		return None;
+	};
+	let segment = path.path.segments.last()?;
+
+	if segment.ident != "Option" {
+		return None;
+	}
+
+	let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+		return None;
+	};
+
+	match arguments.args.first()? {
+		syn::GenericArgument::Type(Type::Reference(reference))
+			if !matches!(*reference.elem, Type::Slice(_)) =>
+		{
+			Some(reference)
+		}
+		_ => None,
+	}
+}
+
+/// A typed account field from `pina::accounts`.
+#[derive(Clone, Copy)]
+enum AccountWrapper<'a> {
+	/// `Program<'a, P>`, holding `P`.
+	Program(&'a Type),
+	/// `Sysvar<'a, S>`, holding `S`.
+	Sysvar(&'a Type),
+	/// Any other wrapper, with the flags its type implies for clients.
+	Account { signer: bool, writable: bool },
+}
+
+/// The typed account wrapper a field uses, recognized by its type name.
+fn account_wrapper(ty: &Type) -> Option<AccountWrapper<'_>> {
+	let Type::Path(path) = ty else {
+		return None;
+	};
+	let segment = path.path.segments.last()?;
+	let type_argument = || {
+		let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+			return None;
+		};
+
+		arguments.args.iter().find_map(|argument| {
+			match argument {
+				syn::GenericArgument::Type(ty) => Some(ty),
+				_ => None,
+			}
+		})
+	};
+
+	match segment.ident.to_string().as_str() {
+		"Program" => type_argument().map(AccountWrapper::Program),
+		"Sysvar" => type_argument().map(AccountWrapper::Sysvar),
+		"SignerAccount" => {
+			Some(AccountWrapper::Account {
+				signer: true,
+				writable: false,
+			})
+		}
+		"Writable" => {
+			Some(AccountWrapper::Account {
+				signer: false,
+				writable: true,
+			})
+		}
+		"Readonly" | "Mint" | "TokenAccount" => {
+			Some(AccountWrapper::Account {
+				signer: false,
+				writable: false,
+			})
+		}
+		_ => None,
+	}
+}
+
+/// Whether the field is a `RemainingAccounts<'a>` tail cursor.
+fn is_remaining_accounts(ty: &Type) -> bool {
+	matches!(
+		ty,
+		Type::Path(path) if path
+			.path
+			.segments
+			.last()
+			.is_some_and(|segment| segment.ident == "RemainingAccounts")
+	)
+}
+
 fn is_mut_reference(ty: &Type) -> bool {
 	matches!(ty, Type::Reference(reference) if reference.mutability.is_some())
 }
@@ -223,6 +982,46 @@ fn is_mut_reference(ty: &Type) -> bool {
 ///
 /// - `final` - By default all error enums are marked as `non_exhaustive`. The
 ///   `final` flag will remove this.
+///
+/// - `offset` - added to every variant value when converting into
+///   `ProgramError::Custom`, so `#[error(offset = 6000)]` maps `Invalid = 0`
+///   to `Custom(6000)`. The rebased value is also returned by `code()`. This
+///   keeps variant values small while reserving a distinct range per enum,
+///   such as Anchor's `6000` user error range.
+///
+/// Every `Custom` code is reserved with a hidden symbol named after the crate
+/// and the code, so two `#[error]` enums anywhere in the same crate that
+/// produce the same code, with or without an `offset`, fail to build with
+/// "symbol is already defined". The symbol is only emitted during codegen, so
+/// the duplicate is reported by `cargo build` and `cargo test` but not by
+/// `cargo check`. Variant values must be integer literals so that every code
+/// is known when the enum is expanded.
+///
+/// #### Messages
+///
+/// Annotate a variant with `#[msg("...")]` to give it a human-readable message.
+/// The enum gets a `message(self) -> &'static str` method, and converting an
+/// error into a `ProgramError` logs its message (when the `logs` feature is
+/// enabled) so explorers show more than a bare `Custom(n)` code. Variants
+/// without `#[msg]` use their name as the message.
+///
+/// ```
+/// use pina::*;
+///
+/// #[error(crate = ::pina)]
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// pub enum VaultError {
+/// 	#[msg("The vault is locked until the unlock slot")]
+/// 	Locked = 0,
+/// 	Empty = 1,
+/// }
+///
+/// assert_eq!(
+/// 	VaultError::Locked.message(),
+/// 	"The vault is locked until the unlock slot"
+/// );
+/// assert_eq!(VaultError::Empty.message(), "Empty");
+/// ```
 #[proc_macro_attribute]
 pub fn error(args: TokenStream, input: TokenStream) -> TokenStream {
 	error_impl(args.into(), input.into()).into()
@@ -250,6 +1049,7 @@ fn error_impl(
 	let ErrorArgs {
 		crate_path,
 		is_final,
+		offset,
 	} = args;
 
 	// Add #[repr(u32)]
@@ -262,13 +1062,163 @@ fn error_impl(
 		item_enum.attrs.push(non_exhaustive_attr);
 	}
 
+	// Strip `#[msg("...")]` from each variant, falling back to the variant
+	// name so every error has something to log.
+	let mut messages = Vec::new();
+	for variant in &mut item_enum.variants {
+		let mut msg_attrs = variant
+			.attrs
+			.iter()
+			.filter(|attr| attr.path().is_ident("msg"));
+
+		let message = match (msg_attrs.next(), msg_attrs.next()) {
+			(Some(_), Some(duplicate)) => {
+				return syn::Error::new_spanned(
+					duplicate,
+					"A variant can only have one `#[msg(...)]` attribute.",
+				)
+				.to_compile_error();
+			}
+			(Some(attr), None) => {
+				match attr.parse_args::<syn::LitStr>() {
+					Ok(message) => message,
+					Err(error) => return error.to_compile_error(),
+				}
+			}
+			(None, _) => syn::LitStr::new(&variant.ident.to_string(), variant.ident.span()),
+		};
+
+		variant.attrs.retain(|attr| !attr.path().is_ident("msg"));
+		messages.push((variant.ident.clone(), message));
+	}
+
+	// Reserve every `Custom` code with an exported marker symbol named after
+	// the crate and the code, so two `#[error]` enums anywhere in the crate
+	// that produce the same code fail to build instead of sharing it.
+	let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+	let mut code_markers = Vec::new();
+	let mut next_value = Some(0u32);
+	for variant in &item_enum.variants {
+		let value = match &variant.discriminant {
+			Some((
+				_,
+				syn::Expr::Lit(syn::ExprLit {
+					lit: syn::Lit::Int(value),
+					..
+				}),
+			)) => {
+				match value.base10_parse::<u32>() {
+					Ok(value) => value,
+					Err(_) => {
+						return syn::Error::new_spanned(
+							value,
+							"The error code must fit in a `u32`.",
+						)
+						.to_compile_error();
+					}
+				}
+			}
+			Some((_, discriminant)) => {
+				return syn::Error::new_spanned(
+					discriminant,
+					"`#[error]` variants must use integer literals so their codes can be reserved.",
+				)
+				.to_compile_error();
+			}
+			None => {
+				let Some(value) = next_value else {
+					return syn::Error::new_spanned(
+						&variant.ident,
+						"The error code must fit in a `u32`.",
+					)
+					.to_compile_error();
+				};
+				value
+			}
+		};
+		next_value = value.checked_add(1);
+
+		let code = match offset {
+			Some(offset) => {
+				let Some(code) = value.checked_add(offset) else {
+					return syn::Error::new_spanned(
+						&variant.ident,
+						"The error code overflows `u32` once `offset` is added.",
+					)
+					.to_compile_error();
+				};
+				code
+			}
+			None => value,
+		};
+
+		let symbol = format!("__pina_error_code_{crate_name}_{code}");
+		let marker = quote::quote_spanned! {variant.ident.span()=>
+			const _: () = {
+				#[allow(unsafe_code)]
+				#[unsafe(export_name = #symbol)]
+				static CODE: u8 = 0;
+			};
+		};
+		code_markers.push(marker);
+	}
+
 	let enum_name = &item_enum.ident;
+	let code = if let Some(offset) = offset {
+		let overflow_checks = item_enum.variants.iter().map(|variant| {
+			let variant = &variant.ident;
+			quote! {
+				::core::assert!(
+					(#enum_name::#variant as u32).checked_add(#offset).is_some(),
+					"the error code overflows `u32` once `offset` is added",
+				);
+			}
+		});
+		code_markers.push(quote! {
+			const _: () = {
+				#(#overflow_checks)*
+			};
+		});
+		quote!(#offset + self as u32)
+	} else {
+		quote!(self as u32)
+	};
+	let message_arms = messages.iter().map(|(variant, message)| {
+		quote! { Self::#variant => #message, }
+	});
+	let log_arms = messages.iter().map(|(variant, message)| {
+		quote! {
+			#enum_name::#variant => {
+				#crate_path::log!(#message);
+			}
+		}
+	});
 	let impls = quote! {
+		impl #enum_name {
+			/// The `ProgramError::Custom` code of this error.
+			pub const fn code(self) -> u32 {
+				#code
+			}
+
+			/// The message logged when this error is converted into a `ProgramError`.
+			pub const fn message(self) -> &'static str {
+				match self {
+					#(#message_arms)*
+				}
+			}
+		}
+
 		impl ::core::convert::From<#enum_name> for #crate_path::ProgramError {
 			fn from(e: #enum_name) -> Self {
-				#crate_path::ProgramError::Custom(e as u32)
+				match e {
+					#(#log_arms)*
+				}
+
+				#crate_path::ProgramError::Custom(e.code())
 			}
 		}
+
+		#(#code_markers)*
 	};
 
 	quote! {
@@ -293,6 +1243,76 @@ fn error_impl(
 ///   access to the `pina` crate in the dependencies.
 /// - `final` - By default all discriminator enums are marked as
 ///   `non_exhaustive`. The `final` flag will remove this annotation.
+/// - `endian` - Either `little` (the default) or `big`, written bare or as a
+///   string. Multi-byte
+///   discriminators are normally stored little-endian like every other Solana
+///   integer. Use `endian = "big"` when the account or instruction layout is
+///   shared with a big-endian external system, for example a bridge or
+///   cross-chain message format, so the leading bytes match what that system
+///   expects. The flag has no effect on `u8` discriminators.
+/// - `format` - Either `integer` (the default), where variants without an
+///   explicit value take one more than the previous variant, starting at
+///   zero, just like a plain Rust enum, or `anchor`. Anchor enums are always
+///   little-endian `u64` and their variants must not have values: each one is
+///   assigned the first eight bytes of `sha256("<namespace>:<name>")`,
+///   matching what Anchor programs and clients expect.
+/// - `namespace` - The Anchor sighash namespace, only used with `format =
+///   anchor`. `global` (the default) hashes the snake case variant name for
+///   instructions, while `account` hashes the variant name as written for
+///   account types.
+/// - `lock` - A TOML file, relative to the crate's manifest directory, that
+///   pins the values of published variants. The build fails if a pinned
+///   variant is removed or its value changes, for example because variants
+///   with implicit values were reordered. New variants can be added freely
+///   and pinned once they ship.
+///
+/// ```toml
+/// # discriminators.toml
+/// [MyInstruction]
+/// Initialize = 0
+/// Increment = 1
+/// ```
+///
+/// ```rust
+/// use pina::*;
+///
+/// #[discriminator(crate = ::pina, format = anchor)]
+/// pub enum LegacyInstruction {
+/// 	Initialize,
+/// }
+///
+/// assert_eq!(
+/// 	LegacyInstruction::INITIALIZE_BYTES,
+/// 	[175, 175, 109, 31, 13, 152, 155, 237]
+/// );
+/// ```
+///
+/// The enum also gets a `MAX` associated constant holding the largest declared
+/// variant value, which is useful for table-driven dispatch and bounds checks,
+/// and a `VARIANTS` constant listing every variant, which
+/// `test_discriminator_exhaustive!` uses to check the parsing surface.
+///
+/// Each variant also gets a `<VARIANT>_BYTES` constant holding its encoded
+/// bytes in the configured endianness. These are usable as match patterns, so
+/// dispatch code can branch on raw instruction data without converting it:
+///
+/// ```rust
+/// use pina::*;
+///
+/// #[discriminator(crate = ::pina, primitive = u16)]
+/// pub enum Command {
+/// 	Open = 1,
+/// 	Close = 2,
+/// }
+///
+/// let data = [2u8, 0, 42];
+/// let handled = match &data[..2] {
+/// 	Command::OPEN_BYTES => "open",
+/// 	Command::CLOSE_BYTES => "close",
+/// 	_ => "unknown",
+/// };
+/// assert_eq!(handled, "close");
+/// ```
 ///
 /// #### Codegen
 ///
@@ -325,6 +1345,38 @@ fn error_impl(
 /// 	SectionState = 2,
 /// }
 ///
+/// impl MyAccount {
+/// 	/// The encoded discriminator bytes of [`Self::ConfigState`].
+/// 	pub const CONFIG_STATE_BYTES: &'static [u8] = &(Self::ConfigState as u8).to_le_bytes();
+/// 	/// The encoded discriminator bytes of [`Self::GameState`].
+/// 	pub const GAME_STATE_BYTES: &'static [u8] = &(Self::GameState as u8).to_le_bytes();
+/// 	/// The encoded discriminator bytes of [`Self::SectionState`].
+/// 	pub const SECTION_STATE_BYTES: &'static [u8] = &(Self::SectionState as u8).to_le_bytes();
+/// 	/// Every variant declared by this enum, in declaration order.
+/// 	pub const VARIANTS: &'static [Self] =
+/// 		&[Self::ConfigState, Self::GameState, Self::SectionState];
+/// }
+///
+/// impl MyAccount {
+/// 	/// The largest discriminator value declared by this enum.
+/// 	pub const MAX: u8 = {
+/// 		let values = [
+/// 			Self::ConfigState as u8,
+/// 			Self::GameState as u8,
+/// 			Self::SectionState as u8,
+/// 		];
+/// 		let mut max = values[0];
+/// 		let mut index = 1;
+/// 		while index < values.len() {
+/// 			if values[index] > max {
+/// 				max = values[index];
+/// 			}
+/// 			index += 1;
+/// 		}
+/// 		max
+/// 	};
+/// }
+///
 /// impl ::core::convert::From<MyAccount> for u8 {
 /// 	#[inline]
 /// 	fn from(enum_value: MyAccount) -> Self {
@@ -384,14 +1436,51 @@ fn discriminator_impl(
 		Err(e) => return e.to_compile_error(),
 	};
 
-	let enum_name = &item_enum.ident;
-
 	let DiscriminatorArgs {
 		primitive,
 		crate_path,
 		is_final,
+		endian,
+		format,
+		namespace,
+		lock,
 	} = args;
 
+	let primitive = match format {
+		DiscriminatorFormat::Integer => {
+			if namespace.is_some() {
+				return syn::Error::new_spanned(
+					&item_enum.ident,
+					"`namespace` is only supported with `format = anchor`.",
+				)
+				.to_compile_error();
+			}
+
+			primitive.unwrap_or_default()
+		}
+		DiscriminatorFormat::Anchor => {
+			if !matches!(primitive, None | Some(Primitive::U64)) || endian == Endian::Big {
+				return syn::Error::new_spanned(
+					&item_enum.ident,
+					"Anchor discriminators are always little-endian `u64` values.",
+				)
+				.to_compile_error();
+			}
+
+			if let Err(error) = assign_anchor_discriminants(
+				&mut item_enum,
+				&crate_path,
+				namespace.unwrap_or_default(),
+			) {
+				return error.to_compile_error();
+			}
+
+			Primitive::U64
+		}
+	};
+
+	let enum_name = &item_enum.ident;
+
 	// Add #[repr(primitive)]
 	let repr_attr: Attribute = syn::parse_quote!(#[repr(#primitive)]);
 	item_enum.attrs.push(repr_attr);
@@ -477,17 +1566,95 @@ fn discriminator_impl(
 				#const_ident => ::core::result::Result::Ok(Self::#variant_name),
 			});
 		} else {
-			return syn::Error::new_spanned(
-				variant,
-				"Enum variant for discriminator must have an explicit value.",
-			)
-			.to_compile_error();
+			// The enum is `#[repr(#primitive)]`, so the compiler assigns the
+			// implicit value and the cast reads it back.
+			let variant_name = &variant.ident;
+			let const_ident =
+				format_ident!("__{}", variant_name.to_string().to_shouty_snake_case());
+
+			consts.push(quote! {
+				const #const_ident: #primitive = #enum_name::#variant_name as #primitive;
+			});
+
+			match_arms.push(quote! {
+				#const_ident => ::core::result::Result::Ok(Self::#variant_name),
+			});
 		}
 	}
 
+	let lock_assertions = match lock {
+		Some(lock) => {
+			match discriminator_lock_assertions(&item_enum, primitive, &lock) {
+				Ok(assertions) => assertions,
+				Err(error) => return error.to_compile_error(),
+			}
+		}
+		None => quote! {},
+	};
+
+	let to_bytes = match endian {
+		Endian::Little => quote!(to_le_bytes),
+		Endian::Big => quote!(to_be_bytes),
+	};
+	let variant_bytes_consts = item_enum.variants.iter().map(|variant| {
+		let variant_name = &variant.ident;
+		let bytes_ident =
+			format_ident!("{}_BYTES", variant_name.to_string().to_shouty_snake_case());
+		let doc = format!(" The encoded discriminator bytes of [`Self::{variant_name}`].");
+
+		quote! {
+			#[doc = #doc]
+			pub const #bytes_ident: &'static [u8] = &(Self::#variant_name as #primitive).#to_bytes();
+		}
+	});
+
+	let all_variant_names = item_enum.variants.iter().map(|variant| &variant.ident);
+	let variants_const = quote! {
+		impl #enum_name {
+			/// Every variant declared by this enum, in declaration order.
+			pub const VARIANTS: &'static [Self] = &[#(Self::#all_variant_names),*];
+
+			#(#variant_bytes_consts)*
+		}
+	};
+
+	// Empty enums have no maximum, so the constant is only emitted when at
+	// least one variant exists.
+	let variant_names = item_enum.variants.iter().map(|variant| &variant.ident);
+	let max_const = (!item_enum.variants.is_empty()).then(|| {
+		quote! {
+			impl #enum_name {
+				/// The largest discriminator value declared by this enum.
+				pub const MAX: #primitive = {
+					let values = [#(Self::#variant_names as #primitive),*];
+					let mut max = values[0];
+					let mut index = 1;
+					while index < values.len() {
+						if values[index] > max {
+							max = values[index];
+						}
+						index += 1;
+					}
+					max
+				};
+			}
+		}
+	});
+
+	let into_discriminator = match endian {
+		Endian::Little => quote!(#crate_path::into_discriminator!(#enum_name, #primitive);),
+		Endian::Big => quote!(#crate_path::into_discriminator!(#enum_name, #primitive, big);),
+	};
+
 	let implementations = quote! {
 		#primitive_width_assertion
 
+		#lock_assertions
+
+		#variants_const
+
+		#max_const
+
 		impl ::core::convert::From<#enum_name> for #primitive {
 			#[inline]
 			fn from(enum_value: #enum_name) -> Self {
@@ -511,14 +1678,185 @@ fn discriminator_impl(
 			}
 		}
 
-		unsafe impl #crate_path::Zeroable for #enum_name {}
-		unsafe impl #crate_path::Pod for #enum_name {}
-		#crate_path::into_discriminator!(#enum_name, #primitive);
-	};
+		unsafe impl #crate_path::Zeroable for #enum_name {}
+		unsafe impl #crate_path::Pod for #enum_name {}
+		#into_discriminator
+	};
+
+	quote! {
+		#item_enum
+		#implementations
+	}
+}
+
+/// Set every variant of an `anchor` format enum to its sighash, read as a
+/// little-endian `u64` so the stored bytes match what Anchor writes.
+fn assign_anchor_discriminants(
+	item_enum: &mut ItemEnum,
+	crate_path: &syn::Path,
+	namespace: AnchorNamespace,
+) -> syn::Result<()> {
+	for variant in &mut item_enum.variants {
+		if let Some((_, discriminant)) = &variant.discriminant {
+			return Err(syn::Error::new_spanned(
+				discriminant,
+				"Variants of an `anchor` format discriminator are assigned their sighash and \
+				 cannot have an explicit value.",
+			));
+		}
+
+		let sighash = match namespace {
+			AnchorNamespace::Global => {
+				let method = variant.ident.to_string().to_snake_case();
+				quote!(#crate_path::anchor_instruction_discriminator(#method))
+			}
+			AnchorNamespace::Account => {
+				let account = variant.ident.to_string();
+				quote!(#crate_path::anchor_account_discriminator(#account))
+			}
+		};
+
+		variant.discriminant = Some((
+			syn::parse_quote!(=),
+			syn::parse_quote!(u64::from_le_bytes(#sighash)),
+		));
+	}
+
+	Ok(())
+}
+
+/// Assert that every variant pinned in the `lock` file of a discriminator
+/// keeps its published value.
+///
+/// The file maps enum names to tables of `Variant = value`. A pinned variant
+/// that no longer exists is reported here, while changed values are checked by
+/// the emitted const assertions since explicit values can be any constant
+/// expression. The file is also passed to `include_bytes!` so editing it
+/// triggers a rebuild.
+fn discriminator_lock_assertions(
+	item_enum: &ItemEnum,
+	primitive: Primitive,
+	lock: &syn::LitStr,
+) -> syn::Result<proc_macro2::TokenStream> {
+	let enum_name = &item_enum.ident;
+	let relative_path = lock.value();
+	let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+	let contents = std::fs::read_to_string(
+		std::path::Path::new(&manifest_dir).join(&relative_path),
+	)
+	.map_err(|error| {
+		syn::Error::new_spanned(lock, format!("Cannot read `{relative_path}`: {error}."))
+	})?;
+	let table = contents.parse::<toml::Table>().map_err(|error| {
+		syn::Error::new_spanned(
+			lock,
+			format!("`{relative_path}` is not valid TOML: {error}"),
+		)
+	})?;
+	let Some(pinned) = table
+		.get(&enum_name.to_string())
+		.and_then(toml::Value::as_table)
+	else {
+		return Err(syn::Error::new_spanned(
+			lock,
+			format!(
+				"`{relative_path}` has no `[{enum_name}]` table pinning its published variants."
+			),
+		));
+	};
+
+	let mut assertions = Vec::new();
+
+	for (name, value) in pinned {
+		let Some(value) = value
+			.as_integer()
+			.and_then(|value| u64::try_from(value).ok())
+		else {
+			return Err(syn::Error::new_spanned(
+				lock,
+				format!(
+					"`{enum_name}.{name}` in `{relative_path}` must be a non-negative integer."
+				),
+			));
+		};
+		let Some(variant) = item_enum
+			.variants
+			.iter()
+			.find(|variant| variant.ident == name)
+		else {
+			return Err(syn::Error::new_spanned(
+				enum_name,
+				format!(
+					"`{enum_name}::{name}` is pinned to {value} in `{relative_path}` but no \
+					 longer exists. Published discriminators cannot be removed."
+				),
+			));
+		};
+
+		let variant_name = &variant.ident;
+		let value = proc_macro2::Literal::u64_unsuffixed(value);
+		let message = format!(
+			"`{enum_name}::{name}` no longer has the discriminator {value} pinned in \
+			 `{relative_path}`. Published values must not change; give the variant an explicit \
+			 value."
+		);
+
+		assertions.push(quote! {
+			::core::assert!(#enum_name::#variant_name as #primitive == #value, #message);
+		});
+	}
+
+	Ok(quote! {
+		const _: () = {
+			const _: &[u8] = ::core::include_bytes!(::core::concat!(
+				::core::env!("CARGO_MANIFEST_DIR"),
+				"/",
+				#relative_path
+			));
+
+			#(#assertions)*
+		};
+	})
+}
+
+/// The `discriminator::variant` value of a `HasDiscriminator` impl.
+///
+/// The variant is written as a braced struct expression, which only accepts
+/// enum variants, and spanned on the variant ident. A missing variant or an
+/// associated item such as `BYTES` then fails with "no variant named ..."
+/// pointing at the attribute, or at the struct name when the variant
+/// defaults to it, instead of a type mismatch on the whole attribute.
+fn discriminator_variant(
+	discriminator: &syn::Path,
+	variant: &syn::Ident,
+) -> proc_macro2::TokenStream {
+	quote::quote_spanned!(variant.span()=> #discriminator::#variant {})
+}
+
+/// Assert at compile time that `discriminator::variant` holds the Anchor
+/// sighash of `preimage`, which `sighash` computes.
+fn anchor_discriminator_assertion(
+	discriminator: &syn::Path,
+	variant: &syn::Ident,
+	sighash: &proc_macro2::TokenStream,
+	preimage: &str,
+) -> proc_macro2::TokenStream {
+	let message = format!(
+		"`{}::{variant}` is not the Anchor discriminator `sha256(\"{preimage}\")`. Declare the \
+		 enum with `#[discriminator(format = anchor, namespace = {})]`.",
+		quote!(#discriminator).to_string().replace(' ', ""),
+		preimage.split(':').next().unwrap_or_default(),
+	);
+
+	let value = discriminator_variant(discriminator, variant);
 
 	quote! {
-		#item_enum
-		#implementations
+		const _: () = {
+			::core::assert!(
+				#discriminator::BYTES == 8 && #value as u64 == u64::from_le_bytes(#sighash),
+				#message
+			);
+		};
 	}
 }
 
@@ -532,7 +1870,194 @@ fn discriminator_impl(
 ///   defaults to `::pina` assuming that `pina` is installed in the consuming
 ///   crate.
 /// - `discriminator` - the discriminator enum to use for this account. The
-///   variant should match the name of the account struct.
+///   variant should match the name of the account struct. Required unless
+///   the account is `external`.
+/// - `format` - set to `anchor` to fail compilation unless the variant holds
+///   Anchor's `sha256("account:<StructName>")` sighash, as assigned by
+///   `#[discriminator(format = anchor, namespace = account)]`. Accounts
+///   written this way can be read by Anchor clients.
+/// - `version` - the layout version, stored in a `version: u8` field that the
+///   struct must declare. The builder defaults the field to this value and
+///   the struct gets a matching `VERSION` constant.
+/// - `migrate_from` - the account struct this layout replaces. Requires
+///   `version` and a `From<&Previous>` conversion, and implements
+///   `pina::Migrate` so `migrate_account` can upgrade deployed accounts in
+///   place.
+/// - `zero_copy` - leave out the builder, which constructs the account by
+///   value, for accounts too large for the stack. Fields cannot take
+///   `#[default(...)]` and `migrate_from` is not supported.
+/// - `external` - declare the layout of an account owned by another program,
+///   set with `owner`. See [External accounts](#external-accounts).
+///
+/// #### Layout
+///
+/// The struct is `#[repr(C)]` and is checked at compile time: every field must
+/// be `Pod` and have an alignment of one, and the struct must have no implicit
+/// padding. Use the exported `Pod*` wrappers instead of native integers. The
+/// exact size of the account data, discriminator included, is available as
+/// the associated `LEN` constant, and `minimum_balance(&rent)` returns the
+/// lamports needed to make an account of that size rent exempt.
+///
+/// #### Field attributes
+///
+/// - `#[validate(expr)]` - generates a `set_<field>(&mut self, value) ->
+///   ProgramResult` method. The expression is evaluated with the new value
+///   bound to `value` and the setter returns
+///   `ProgramError::InvalidAccountData` when it is `false`. The field may be
+///   annotated multiple times and every expression must hold.
+///
+/// ```rust
+/// use pina::*;
+///
+/// const MAX_FEE_BPS: u16 = 1_000;
+///
+/// #[discriminator(crate = ::pina, primitive = u8, final)]
+/// pub enum MyAccount {
+/// 	PoolState = 0,
+/// }
+///
+/// #[account(crate = ::pina, discriminator = MyAccount)]
+/// pub struct PoolState {
+/// 	#[validate(u16::from(value) <= MAX_FEE_BPS)]
+/// 	pub fee_bps: PodU16,
+/// }
+///
+/// let mut pool = PoolState::builder().fee_bps(PodU16::from(30)).build();
+/// assert!(pool.set_fee_bps(PodU16::from(200)).is_ok());
+/// assert!(pool.set_fee_bps(PodU16::from(2_000)).is_err());
+/// ```
+///
+/// #### Builder
+///
+/// `builder()` returns a `TypedBuilder` with the discriminator already set.
+/// Every field using a `Pod*` integer or `PodBool` wrapper also gets a
+/// `<field>_<primitive>` builder method that takes the plain value, so
+/// `.count_u64(5)` is equivalent to `.count(PodU64::from(5))`.
+///
+/// Annotate a field with `#[default(expr)]` to let the builder fall back to
+/// `expr` when the field is not set, or with a bare `#[default]` to use the
+/// type's `Default` value. Accounts whose counters and flags always start at
+/// zero then only need their remaining fields set:
+///
+/// ```rust
+/// use pina::*;
+///
+/// #[discriminator(crate = ::pina, primitive = u8, final)]
+/// pub enum MyAccount {
+/// 	CounterState = 0,
+/// }
+///
+/// #[account(crate = ::pina, discriminator = MyAccount)]
+/// pub struct CounterState {
+/// 	pub authority: Address,
+/// 	#[default(PodU64::from_primitive(0))]
+/// 	pub count: PodU64,
+/// }
+///
+/// let counter = CounterState::builder()
+/// 	.authority(Address::new_from_array([1; 32]))
+/// 	.build();
+/// assert_eq!(counter.count, PodU64::from_primitive(0));
+/// ```
+///
+/// #### PDA seeds
+///
+/// `seeds = [...]` declares the account's PDA seed schema, without the bump.
+/// String literals are byte seeds, lowercase identifiers become `&[u8]`
+/// parameters and any other expression, such as a constant, is used as
+/// written. The macro then generates `seeds(..)`, `seeds_with_bump(.., &[bump])`,
+/// `find_address(.., program_id)` and
+/// `assert_canonical_pda(account, .., program_id)`, which checks the canonical
+/// bump and returns it:
+///
+/// ```rust
+/// use pina::*;
+///
+/// #[discriminator(crate = ::pina, primitive = u8, final)]
+/// pub enum MyAccount {
+/// 	CounterState = 0,
+/// }
+///
+/// #[account(crate = ::pina, discriminator = MyAccount, seeds = ["counter", authority])]
+/// pub struct CounterState {
+/// 	pub count: PodU64,
+/// }
+///
+/// let authority = Address::new_from_array([1; 32]);
+/// assert_eq!(
+/// 	CounterState::seeds(authority.as_ref()),
+/// 	[b"counter".as_slice(), authority.as_ref()]
+/// );
+/// ```
+///
+/// #### Stored bumps
+///
+/// Mark the `u8` field holding the account's PDA bump with `#[bump]` to
+/// implement `HasBump`, whose `assert_pda` method re-derives the PDA from the
+/// seeds and the stored bump. At most one field can carry the attribute.
+///
+/// #### Layout
+///
+/// `HasLayout::FIELD_OFFSETS` lists every field, including the injected
+/// discriminator, with its byte offset. `pina::layout::LayoutSnapshot` uses it
+/// to catch layout drift against a golden file in tests.
+///
+/// #### Zero-copy accounts
+///
+/// An account of tens of kilobytes, such as an order book, cannot be built in
+/// a 4 KiB SBF stack frame. Mark it `zero_copy` and access it only through
+/// `pina::AccountLoader`, whose `load()` and `load_mut()` return guards over
+/// the account data and whose `load_init()` writes the discriminator into a
+/// freshly created, zeroed account instead of copying in a built value:
+///
+/// ```rust
+/// use pina::*;
+///
+/// #[discriminator(crate = ::pina, primitive = u8, final)]
+/// pub enum GameAccount {
+/// 	Board = 0,
+/// }
+///
+/// #[account(crate = ::pina, discriminator = GameAccount, zero_copy)]
+/// pub struct Board {
+/// 	pub turn: PodU64,
+/// 	pub cells: [u8; 16_384],
+/// }
+///
+/// assert_eq!(Board::LEN, 1 + 8 + 16_384);
+/// ```
+///
+/// #### External accounts
+///
+/// Programs that read oracle or partner-program state describe its layout
+/// with `#[account(external, owner = OTHER_ID)]`. The struct keeps the
+/// foreign layout as declared: no discriminator is injected, no builder is
+/// generated, and it gets the same alignment and padding checks, a `LEN`
+/// constant and a `pina::ExternalAccount` impl recording the owner.
+/// `AsAccount::as_external_account::<T>()` then checks the owner and the size
+/// before mapping the data:
+///
+/// ```rust
+/// use pina::*;
+///
+/// const ORACLE_ID: Address = Address::new_from_array([7u8; 32]);
+///
+/// #[account(crate = ::pina, external, owner = ORACLE_ID)]
+/// pub struct PriceFeed {
+/// 	pub price: PodI64,
+/// 	pub exponent: PodI32,
+/// 	pub published_slot: PodU64,
+/// }
+///
+/// assert_eq!(PriceFeed::LEN, 20);
+/// assert_eq!(<PriceFeed as ExternalAccount>::OWNER, ORACLE_ID);
+/// ```
+///
+/// #### State comparison
+///
+/// The derived `PartialEq` also compares the discriminator bytes. The generated
+/// `eq_state(&self, other)` method compares only the declared fields, which
+/// keeps state-change checks and test diffs focused on the data itself.
 ///
 /// #### Codegen
 ///
@@ -758,65 +2283,110 @@ fn account_impl(
 		crate_path,
 		discriminator,
 		variant,
+		seeds,
+		format,
+		version,
+		migrate_from,
+		zero_copy,
+		external,
+		owner,
 	} = args;
-	let variant = variant.unwrap_or(struct_name.clone());
-
-	// Add #[repr(C)]
-	let repr_attr: Attribute = syn::parse_quote!(#[repr(C)]);
-	item_struct.attrs.push(repr_attr);
 
-	// Add derive macros
-	let derives_to_add: [syn::Path; 7] = [
-		syn::parse_quote!(#crate_path::TypedBuilder),
-		syn::parse_quote!(#crate_path::Pod),
-		syn::parse_quote!(#crate_path::Zeroable),
-		syn::parse_quote!(::core::clone::Clone),
-		syn::parse_quote!(::core::marker::Copy),
-		syn::parse_quote!(::core::cmp::PartialEq),
-		syn::parse_quote!(::core::cmp::Eq),
-	];
+	if external {
+		let conflict = [
+			("discriminator", discriminator.is_some()),
+			("variant", variant.is_some()),
+			("seeds", seeds.is_some()),
+			("format", format != DiscriminatorFormat::Integer),
+			("version", version.is_some()),
+			("migrate_from", migrate_from.is_some()),
+			("zero_copy", zero_copy),
+		]
+		.into_iter()
+		.find_map(|(name, set)| set.then_some(name));
+
+		if let Some(name) = conflict {
+			return syn::Error::new_spanned(
+				struct_name,
+				format!(
+					"`external` accounts keep another program's layout, so `{name}` does not \
+					 apply."
+				),
+			)
+			.to_compile_error();
+		}
 
-	let derive_attr = item_struct
-		.attrs
-		.iter_mut()
-		.find(|attr| attr.path().is_ident("derive"));
+		let Some(owner) = owner else {
+			return syn::Error::new_spanned(
+				struct_name,
+				"`external` accounts must set the `owner` program.",
+			)
+			.to_compile_error();
+		};
 
-	if let Some(derive_attr) = derive_attr {
-		let existing_derives_result =
-			derive_attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated);
+		return external_account_impl(&crate_path, &owner, item_struct);
+	}
 
-		match existing_derives_result {
-			Ok(mut existing_derives) => {
-				let existing_derive_names: std::collections::HashSet<String> = existing_derives
-					.iter()
-					.map(|p| p.segments.last().unwrap().ident.to_string())
-					.collect();
+	if let Some(owner) = &owner {
+		return syn::Error::new_spanned(owner, "`owner` only applies to `external` accounts.")
+			.to_compile_error();
+	}
 
-				for derive_to_add in &derives_to_add {
-					let to_add_name = derive_to_add.segments.last().unwrap().ident.to_string();
-					if !existing_derive_names.contains(&to_add_name) {
-						existing_derives.push(derive_to_add.clone());
-					}
-				}
+	let Some(discriminator) = discriminator else {
+		return syn::Error::new_spanned(
+			struct_name,
+			"Accounts must set a `discriminator` unless they are `external`.",
+		)
+		.to_compile_error();
+	};
+	let variant = variant.unwrap_or(struct_name.clone());
+	let variant_value = discriminator_variant(&discriminator, &variant);
+	let anchor_assertion = (format == DiscriminatorFormat::Anchor).then(|| {
+		let account_name = struct_name.to_string();
+		let sighash = quote!(#crate_path::anchor_account_discriminator(#account_name));
+
+		anchor_discriminator_assertion(
+			&discriminator,
+			&variant,
+			&sighash,
+			&format!("account:{account_name}"),
+		)
+	});
+	let seed_helpers = match seeds.map(|seeds| account_seed_helpers(&crate_path, &seeds)) {
+		Some(Ok(helpers)) => Some(helpers),
+		Some(Err(error)) => return error.to_compile_error(),
+		None => None,
+	};
 
-				let new_derive_attr: Attribute = syn::parse_quote! {
-					#[derive(#existing_derives)]
-				};
+	// Add #[repr(C)]
+	let repr_attr: Attribute = syn::parse_quote!(#[repr(C)]);
+	item_struct.attrs.push(repr_attr);
 
-				*derive_attr = new_derive_attr;
-			}
-			Err(error) => return error.to_compile_error(),
-		}
-	} else {
-		// No derive attribute exists, so create one
-		let new_derive_attr: Attribute = syn::parse_quote!(#[derive(#(#derives_to_add),*)]);
-		item_struct.attrs.push(new_derive_attr);
+	// Add derive macros. Zero-copy accounts are never built by value, so they
+	// skip the builder.
+	let builder_derive = (!zero_copy).then(|| syn::parse_quote!(#crate_path::TypedBuilder));
+	let derives_to_add: Vec<syn::Path> = builder_derive
+		.into_iter()
+		.chain([
+			syn::parse_quote!(#crate_path::Pod),
+			syn::parse_quote!(#crate_path::Zeroable),
+			syn::parse_quote!(::core::clone::Clone),
+			syn::parse_quote!(::core::marker::Copy),
+			syn::parse_quote!(::core::cmp::PartialEq),
+			syn::parse_quote!(::core::cmp::Eq),
+		])
+		.collect();
+
+	if let Err(error) = merge_derives(&mut item_struct.attrs, &derives_to_add) {
+		return error.to_compile_error();
 	}
 
 	// Add builder attribute
-	let builder_attr: Attribute =
-		syn::parse_quote!(#[builder(builder_method(vis = "", name = __builder))]);
-	item_struct.attrs.push(builder_attr);
+	if !zero_copy {
+		let builder_attr: Attribute =
+			syn::parse_quote!(#[builder(builder_method(vis = "", name = __builder))]);
+		item_struct.attrs.push(builder_attr);
+	}
 
 	let bytemuck_crate_str = format!(
 		"{}::bytemuck",
@@ -831,6 +2401,182 @@ fn account_impl(
 			.to_compile_error();
 	};
 
+	if let Some(version) = version {
+		let version_field = named_fields
+			.named
+			.iter_mut()
+			.find(|field| field.ident.as_ref().is_some_and(|ident| ident == "version"));
+
+		let Some(version_field) = version_field else {
+			return syn::Error::new_spanned(
+				struct_name,
+				"Versioned account structs must declare a `version: u8` field.",
+			)
+			.to_compile_error();
+		};
+
+		if !matches!(&version_field.ty, Type::Path(path) if path.path.is_ident("u8")) {
+			return syn::Error::new_spanned(
+				&version_field.ty,
+				"The `version` field of a versioned account must be a `u8`.",
+			)
+			.to_compile_error();
+		}
+
+		// New accounts are built at the current version unless the field
+		// already has its own default.
+		if !zero_copy
+			&& !version_field
+				.attrs
+				.iter()
+				.any(|attr| attr.path().is_ident("default"))
+		{
+			version_field
+				.attrs
+				.push(syn::parse_quote!(#[default(#version)]));
+		}
+	} else if let Some(migrate_from) = &migrate_from {
+		return syn::Error::new_spanned(
+			migrate_from,
+			"`migrate_from` requires the account to set a `version`.",
+		)
+		.to_compile_error();
+	}
+
+	if let Some(migrate_from) = migrate_from.as_ref().filter(|_| zero_copy) {
+		return syn::Error::new_spanned(
+			migrate_from,
+			"`migrate_from` builds the new layout by value, which `zero_copy` accounts do not \
+			 support.",
+		)
+		.to_compile_error();
+	}
+
+	// Extract `#[validate(...)]` field attributes into checked setters. The
+	// attribute is removed so it never reaches the emitted struct.
+	let mut validated_setters = Vec::new();
+	let mut bump_field = None;
+	let mut has_defaults = false;
+	for field in &mut named_fields.named {
+		let bump_attrs = field.attrs.len();
+		field.attrs.retain(|attr| !attr.path().is_ident("bump"));
+
+		if field.attrs.len() != bump_attrs {
+			if bump_field.is_some() {
+				return syn::Error::new_spanned(
+					&field.ident,
+					"Only one field can be marked with `#[bump]`.",
+				)
+				.to_compile_error();
+			}
+
+			bump_field.clone_from(&field.ident);
+		}
+
+		// Rewrite `#[default(expr)]` into the `TypedBuilder` field default so
+		// `builder()` callers can skip the field.
+		let mut default_attrs = field
+			.attrs
+			.iter()
+			.filter(|attr| attr.path().is_ident("default"));
+
+		if let Some(default_attr) = default_attrs.next() {
+			if zero_copy {
+				return syn::Error::new_spanned(
+					default_attr,
+					"`zero_copy` accounts have no builder, so `#[default(...)]` does not apply.",
+				)
+				.to_compile_error();
+			}
+
+			if let Some(duplicate) = default_attrs.next() {
+				return syn::Error::new_spanned(
+					duplicate,
+					"A field can only have one `#[default(...)]` attribute.",
+				)
+				.to_compile_error();
+			}
+
+			let builder_default: Attribute = match &default_attr.meta {
+				syn::Meta::Path(_) => syn::parse_quote!(#[builder(default)]),
+				_ => {
+					match default_attr.parse_args::<syn::Expr>() {
+						Ok(expr) => syn::parse_quote!(#[builder(default = #expr)]),
+						Err(error) => return error.to_compile_error(),
+					}
+				}
+			};
+
+			field.attrs.retain(|attr| !attr.path().is_ident("default"));
+			field.attrs.push(builder_default);
+			has_defaults = true;
+		}
+
+		let mut validations = Vec::new();
+		let mut parse_error = None;
+		field.attrs.retain(|attr| {
+			if !attr.path().is_ident("validate") {
+				return true;
+			}
+
+			match attr.parse_args::<syn::Expr>() {
+				Ok(expr) => validations.push(expr),
+				Err(error) => parse_error = Some(error),
+			}
+
+			false
+		});
+
+		if let Some(error) = parse_error {
+			return error.to_compile_error();
+		}
+
+		if validations.is_empty() {
+			continue;
+		}
+
+		let field_name = field.ident.as_ref().unwrap();
+		let field_type = &field.ty;
+		let setter_name = format_ident!("set_{}", field_name);
+		let field_name_str = field_name.to_string();
+		let invalid_msg = format!("Invalid value for field `{field_name_str}`");
+		let setter_doc =
+			format!("Set `{field_name_str}` after checking its `#[validate(...)]` invariants.");
+
+		validated_setters.push(quote! {
+			#[doc = #setter_doc]
+			#[track_caller]
+			pub fn #setter_name(&mut self, value: #field_type) -> #crate_path::ProgramResult {
+				#(
+					if !(#validations) {
+						#crate_path::log!(#invalid_msg);
+						#crate_path::log_caller();
+
+						return Err(#crate_path::ProgramError::InvalidAccountData);
+					}
+				)*
+
+				self.#field_name = value;
+
+				Ok(())
+			}
+		});
+	}
+
+	// Field defaults expand to paths into `typed_builder`, which the calling
+	// crate may only reach through the pina re-export.
+	if has_defaults {
+		item_struct.attrs.push(syn::parse_quote!(
+			#[builder(crate_module_path = #crate_path::typed_builder)]
+		));
+	}
+
+	let state_field_names = named_fields
+		.named
+		.iter()
+		.filter_map(|field| field.ident.clone())
+		.collect::<Vec<_>>();
+
 	let discriminator_field = syn::parse_quote! {
 		discriminator: [u8; #discriminator::BYTES]
 	};
@@ -896,18 +2642,101 @@ fn account_impl(
 		.map(|_| quote! { () })
 		.collect::<Vec<_>>();
 
+	let version_const = version.map(|version| {
+		quote! {
+			impl #struct_name {
+				/// The layout version stored in the `version` field.
+				pub const VERSION: u8 = #version;
+			}
+		}
+	});
+
+	let migrate = migrate_from.zip(version).map(|(previous, version)| {
+		quote! {
+			impl #crate_path::Migrate for #struct_name {
+				type Previous = #previous;
+
+				const VERSION: u8 = #version;
+
+				fn migrate(previous: &Self::Previous) -> Self {
+					let mut next = <Self as ::core::convert::From<&#previous>>::from(previous);
+					<Self as #crate_path::HasDiscriminator>::VALUE
+						.write_discriminator(&mut next.discriminator);
+					next.version = #version;
+
+					next
+				}
+			}
+		}
+	});
+
+	let has_bump = bump_field.map(|field| {
+		quote! {
+			impl #crate_path::HasBump for #struct_name {
+				fn bump(&self) -> u8 {
+					self.#field
+				}
+			}
+		}
+	});
+
+	let layout_field_names = item_struct
+		.fields
+		.iter()
+		.filter_map(|field| field.ident.clone())
+		.collect::<Vec<_>>();
+	let layout_field_strs = layout_field_names
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>();
+
 	let builder_type_alias = format_ident!("{}BuilderType", struct_name);
+	let builder_alias = (!zero_copy).then(|| {
+		quote! {
+			#[allow(dead_code)]
+			type #builder_type_alias = #builder_name<(
+				([u8; #discriminator::BYTES],),
+				#(#builder_generics,)*
+			)>;
+		}
+	});
+	let primitive_setters =
+		(!zero_copy).then(|| primitive_builder_setters(&builder_name, &item_struct.fields));
+	let builder_fn = (!zero_copy).then(|| {
+		quote! {
+			pub fn builder() -> #builder_type_alias {
+				let mut bytes = [0u8; #discriminator::BYTES];
+				<Self as #crate_path::HasDiscriminator>::VALUE.write_discriminator(&mut bytes);
 
-	let implementations = quote! {
-		#[allow(dead_code)]
-		type #builder_type_alias = #builder_name<(
-			([u8; #discriminator::BYTES],),
-			#(#builder_generics,)*
-		)>;
+				Self::__builder().discriminator(bytes)
+			}
+		}
+	});
+
+	let implementations = quote! {
+		#builder_alias
 
 		#assertions
 
+		#anchor_assertion
+
+		#primitive_setters
+
 		impl #struct_name {
+			/// The exact size of the account data in bytes, including the discriminator.
+			pub const LEN: usize = ::core::mem::size_of::<Self>();
+
+			/// The lamports an account of [`Self::LEN`] bytes needs to be rent exempt.
+			///
+			/// # Errors
+			///
+			/// Returns an error when `rent` overflows computing the balance.
+			pub fn minimum_balance(
+				rent: &#crate_path::sysvars::rent::Rent,
+			) -> ::core::result::Result<u64, #crate_path::ProgramError> {
+				rent.try_minimum_balance(Self::LEN)
+			}
+
 			/// Zero out all bytes in the struct including padding bytes. This can be useful when closing an account.
 			pub fn zeroed(&mut self) {
 				#crate_path::bytemuck::write_zeroes(self);
@@ -917,18 +2746,34 @@ fn account_impl(
 				#crate_path::bytemuck::bytes_of(self)
 			}
 
-			pub fn builder() -> #builder_type_alias {
-				let mut bytes = [0u8; #discriminator::BYTES];
-				<Self as #crate_path::HasDiscriminator>::VALUE.write_discriminator(&mut bytes);
+			#builder_fn
 
-				Self::__builder().discriminator(bytes)
+			/// Compare every field except the discriminator.
+			pub fn eq_state(&self, other: &Self) -> bool {
+				true #(&& self.#state_field_names == other.#state_field_names)*
 			}
+
+			#(#validated_setters)*
+
+			#seed_helpers
 		}
 
 		impl #crate_path::HasDiscriminator for #struct_name {
 			type Type = #discriminator;
 
-			const VALUE: Self::Type = #discriminator::#variant;
+			const VALUE: Self::Type = #variant_value;
+		}
+
+		#has_bump
+
+		#version_const
+
+		#migrate
+
+		impl #crate_path::HasLayout for #struct_name {
+			const FIELD_OFFSETS: &'static [(&'static str, usize)] = &[
+				#((#layout_field_strs, ::core::mem::offset_of!(Self, #layout_field_names)),)*
+			];
 		}
 
 		impl #crate_path::AccountValidation for #struct_name {
@@ -1004,6 +2849,137 @@ fn account_impl(
 	}
 }
 
+/// Add `derives` to the item's `#[derive(...)]`, skipping any it already
+/// lists by name, or add the attribute when the item has none.
+fn merge_derives(attrs: &mut Vec<Attribute>, derives: &[syn::Path]) -> syn::Result<()> {
+	let Some(derive_attr) = attrs.iter_mut().find(|attr| attr.path().is_ident("derive")) else {
+		attrs.push(syn::parse_quote!(#[derive(#(#derives),*)]));
+		return Ok(());
+	};
+
+	let mut existing_derives =
+		derive_attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)?;
+	let existing_derive_names: std::collections::HashSet<String> = existing_derives
+		.iter()
+		.map(|p| p.segments.last().unwrap().ident.to_string())
+		.collect();
+
+	for derive_to_add in derives {
+		let to_add_name = derive_to_add.segments.last().unwrap().ident.to_string();
+		if !existing_derive_names.contains(&to_add_name) {
+			existing_derives.push(derive_to_add.clone());
+		}
+	}
+
+	*derive_attr = syn::parse_quote!(#[derive(#existing_derives)]);
+
+	Ok(())
+}
+
+/// The `#[account(external, owner = ...)]` expansion: the foreign layout as
+/// declared, checked to be tightly packed, with an `ExternalAccount` impl.
+fn external_account_impl(
+	crate_path: &syn::Path,
+	owner: &syn::Expr,
+	mut item_struct: ItemStruct,
+) -> proc_macro2::TokenStream {
+	let struct_name = item_struct.ident.clone();
+
+	let Fields::Named(named_fields) = &item_struct.fields else {
+		return syn::Error::new_spanned(item_struct, "Account structs must have named fields")
+			.to_compile_error();
+	};
+
+	let field_names = named_fields
+		.named
+		.iter()
+		.filter_map(|field| field.ident.clone())
+		.collect::<Vec<_>>();
+	let field_strs = field_names
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>();
+	let field_types = named_fields
+		.named
+		.iter()
+		.map(|field| field.ty.clone())
+		.collect::<Vec<_>>();
+
+	item_struct.attrs.push(syn::parse_quote!(#[repr(C)]));
+
+	let derives_to_add: [syn::Path; 6] = [
+		syn::parse_quote!(#crate_path::Pod),
+		syn::parse_quote!(#crate_path::Zeroable),
+		syn::parse_quote!(::core::clone::Clone),
+		syn::parse_quote!(::core::marker::Copy),
+		syn::parse_quote!(::core::cmp::PartialEq),
+		syn::parse_quote!(::core::cmp::Eq),
+	];
+
+	if let Err(error) = merge_derives(&mut item_struct.attrs, &derives_to_add) {
+		return error.to_compile_error();
+	}
+
+	let bytemuck_crate_str = format!(
+		"{}::bytemuck",
+		quote!(#crate_path).to_string().replace(' ', "")
+	);
+	item_struct
+		.attrs
+		.push(syn::parse_quote!(#[bytemuck(crate = #bytemuck_crate_str)]));
+
+	let assertion_const_name = format_ident!(
+		"__{}_ALIGNMENT_ASSERTIONS__",
+		struct_name.to_string().to_uppercase()
+	);
+
+	quote! {
+		#item_struct
+
+		const #assertion_const_name: () = {
+			#(
+				::core::assert!(
+					::core::mem::align_of::<#field_types>() == 1,
+					concat!(
+						"The alignment of field `",
+						#field_strs,
+						"` with type `",
+						stringify!(#field_types),
+						"` should be one. Consider using one of the exported `Pod*` types from the `pina` crate."
+					)
+				);
+			)*
+			::core::assert!(
+				::core::mem::size_of::<#struct_name>() == 0usize #(+ ::core::mem::size_of::<#field_types>())*,
+				concat!(
+					"`",
+					stringify!(#struct_name),
+					"` layout is padded. Declare the padding of the external layout as explicit fields."
+				)
+			);
+		};
+
+		impl #struct_name {
+			/// The size of the external layout in bytes.
+			pub const LEN: usize = ::core::mem::size_of::<Self>();
+
+			pub fn to_bytes(&self) -> &[u8] {
+				#crate_path::bytemuck::bytes_of(self)
+			}
+		}
+
+		impl #crate_path::ExternalAccount for #struct_name {
+			const OWNER: #crate_path::Address = #owner;
+		}
+
+		impl #crate_path::HasLayout for #struct_name {
+			const FIELD_OFFSETS: &'static [(&'static str, usize)] = &[
+				#((#field_strs, ::core::mem::offset_of!(Self, #field_names)),)*
+			];
+		}
+	}
+}
+
 /// The instruction macro is used to annotate instruction data that will exist
 /// within a solana instruction.
 ///
@@ -1011,6 +2987,45 @@ fn account_impl(
 ///
 /// - `discriminator` - the discriminator enum to use for this instruction. The
 ///   variant should match the name of the instruction struct.
+/// - `variant` - the variant of the discriminator enum, defaulting to the
+///   struct name. A name that is not a variant of the enum fails to compile
+///   with "no variant named ..." pointing at the attribute.
+/// - `format` - set to `anchor` to fail compilation unless the variant holds
+///   Anchor's `sha256("global:<variant_in_snake_case>")` sighash, as assigned
+///   by `#[discriminator(format = anchor)]`.
+///
+/// #### Trailing data
+///
+/// Instruction structs are fixed-size, so variable-length data such as a memo
+/// or metadata URI goes after them. `try_from_bytes_with_rest(data)` checks
+/// the discriminator and returns the instruction together with the remaining
+/// bytes:
+///
+/// ```ignore
+/// let (args, uri) = UpdateMetadataInstruction::try_from_bytes_with_rest(data)?;
+/// ```
+///
+/// #### Builder
+///
+/// As with `#[account]`, `Pod*` wrapper fields get `<field>_<primitive>`
+/// builder methods, such as `.amount_u64(10)`, alongside the regular setters.
+///
+/// #### Client
+///
+/// With pina's `client` feature the struct also gets
+/// `build_instruction(program_id, accounts)`, which pairs its bytes with the
+/// account metas of any `ToAccountMetas` value, usually the `<Name>Keys`
+/// generated by `#[derive(Accounts)]`:
+///
+/// ```ignore
+/// let ix = FlipBit::builder()
+/// 	.section_index(0)
+/// 	.array_index(1)
+/// 	.offset(2)
+/// 	.value(1)
+/// 	.build()
+/// 	.build_instruction(&ID, &FlipBitAccountsKeys { authority, state });
+/// ```
 ///
 /// #### Codegen
 ///
@@ -1146,8 +3161,21 @@ fn instruction_impl(
 		crate_path,
 		discriminator,
 		variant,
+		format,
 	} = args;
 	let variant = variant.unwrap_or(struct_name.clone());
+	let variant_value = discriminator_variant(&discriminator, &variant);
+	let anchor_assertion = (format == DiscriminatorFormat::Anchor).then(|| {
+		let method = variant.to_string().to_snake_case();
+		let sighash = quote!(#crate_path::anchor_instruction_discriminator(#method));
+
+		anchor_discriminator_assertion(
+			&discriminator,
+			&variant,
+			&sighash,
+			&format!("global:{method}"),
+		)
+	});
 
 	// Add #[repr(C)]
 	let repr_attr: Attribute = syn::parse_quote!(#[repr(C)]);
@@ -1286,6 +3314,7 @@ fn instruction_impl(
 		.collect::<Vec<_>>();
 
 	let builder_type_alias = format_ident!("{}BuilderType", struct_name);
+	let primitive_setters = primitive_builder_setters(&builder_name, &item_struct.fields);
 
 	let implementations = quote! {
 		#[allow(dead_code)]
@@ -1296,6 +3325,10 @@ fn instruction_impl(
 
 		#assertions
 
+		#anchor_assertion
+
+		#primitive_setters
+
 		impl #struct_name {
 			pub fn to_bytes(&self) -> &[u8] {
 				#crate_path::bytemuck::bytes_of(self)
@@ -1306,6 +3339,28 @@ fn instruction_impl(
 					.or(Err(#crate_path::ProgramError::InvalidInstructionData))
 			}
 
+			/// Split `data` into this instruction and the variable-length
+			/// payload that follows it.
+			///
+			/// # Errors
+			///
+			/// Returns `InvalidInstructionData` when `data` is shorter than the
+			/// instruction or does not start with its discriminator.
+			pub fn try_from_bytes_with_rest(
+				data: &[u8],
+			) -> Result<(&Self, &[u8]), #crate_path::ProgramError> {
+				let Some((instruction, rest)) = data.split_at_checked(::core::mem::size_of::<Self>())
+				else {
+					return Err(#crate_path::ProgramError::InvalidInstructionData);
+				};
+
+				if !<Self as #crate_path::HasDiscriminator>::VALUE.matches_discriminator(instruction) {
+					return Err(#crate_path::ProgramError::InvalidInstructionData);
+				}
+
+				Ok((Self::try_from_bytes(instruction)?, rest))
+			}
+
 			pub fn builder() -> #builder_type_alias {
 				let mut bytes = [0u8; #discriminator::BYTES];
 				<Self as #crate_path::HasDiscriminator>::VALUE.write_discriminator(&mut bytes);
@@ -1317,7 +3372,25 @@ fn instruction_impl(
 		impl #crate_path::HasDiscriminator for #struct_name {
 			type Type = #discriminator;
 
-			const VALUE: Self::Type = #discriminator::#variant;
+			const VALUE: Self::Type = #variant_value;
+		}
+
+		#crate_path::__client! {
+			impl #struct_name {
+				/// Build the instruction for `program_id`, with this struct as its
+				/// data and the account metas of `accounts`.
+				pub fn build_instruction(
+					&self,
+					program_id: &#crate_path::Address,
+					accounts: &impl #crate_path::transaction::ToAccountMetas,
+				) -> #crate_path::transaction::Instruction {
+					#crate_path::transaction::Instruction {
+						program_id: *program_id,
+						accounts: accounts.to_account_metas(),
+						data: #crate_path::alloc::vec::Vec::from(self.to_bytes()),
+					}
+				}
+			}
 		}
 	};
 
@@ -1337,6 +3410,11 @@ fn instruction_impl(
 /// - `discriminator` - the discriminator enum to use for this event.
 /// - `variant` - the variant of the discriminator enum to use for this event.
 ///
+/// #### Builder
+///
+/// As with `#[account]`, `Pod*` wrapper fields get `<field>_<primitive>`
+/// builder methods alongside the regular setters.
+///
 /// #### Codegen
 ///
 /// It will transform the following:
@@ -1445,6 +3523,7 @@ fn event_impl(
 		variant,
 	} = args;
 	let variant = variant.unwrap_or(struct_name.clone());
+	let variant_value = discriminator_variant(&discriminator, &variant);
 
 	// Add #[repr(C)]
 	let repr_attr: Attribute = syn::parse_quote!(#[repr(C)]);
@@ -1582,6 +3661,7 @@ fn event_impl(
 		.collect::<Vec<_>>();
 
 	let builder_type_alias = format_ident!("{}BuilderType", struct_name);
+	let primitive_setters = primitive_builder_setters(&builder_name, &item_struct.fields);
 
 	let implementations = quote! {
 		#[allow(dead_code)]
@@ -1592,6 +3672,8 @@ fn event_impl(
 
 		#assertions
 
+		#primitive_setters
+
 		impl #struct_name {
 			pub fn to_bytes(&self) -> &[u8] {
 				#crate_path::bytemuck::bytes_of(self)
@@ -1613,7 +3695,7 @@ fn event_impl(
 		impl #crate_path::HasDiscriminator for #struct_name {
 			type Type = #discriminator;
 
-			const VALUE: Self::Type = #discriminator::#variant;
+			const VALUE: Self::Type = #variant_value;
 		}
 	};
 
@@ -1622,3 +3704,432 @@ fn event_impl(
 		#implementations
 	}
 }
+
+/// `#[program]` turns an inline module of instruction handlers into a
+/// program.
+///
+/// Every `pub fn` in the module handles one instruction. Its first parameter
+/// is an accounts struct implementing `TryFromAccountInfos`, usually through
+/// `#[derive(Accounts)]`, and an optional second parameter receives the full
+/// instruction data, discriminator included. Other items, including private
+/// helper functions, are left as they are.
+///
+/// The macro adds to the module:
+///
+/// - A `#[discriminator]` enum with one variant per handler, named after the
+///   handler in `PascalCase`. Values count up from zero in declaration order;
+///   `#[pina(discriminator = N)]` on a handler sets its value, and later
+///   handlers continue from it.
+/// - A `process_instruction` function that checks the program ID, parses the
+///   discriminator and calls the matching handler, so the dispatch `match`
+///   cannot drift from the enum.
+/// - `nostd_entrypoint!(process_instruction)`, behind the `bpf-entrypoint`
+///   feature like the hand-written entrypoints in the examples.
+///
+/// The macro accepts the following arguments:
+///
+/// - `crate`: the path to the pina crate, defaults to `::pina`.
+/// - `instruction`: the name of the generated enum, defaults to the module
+///   name in `PascalCase` followed by `Instruction`.
+/// - `primitive`: the discriminator primitive, defaults to `u8`.
+/// - `program_id`: the program ID instructions must target, defaults to
+///   `crate::ID`.
+/// - `no_entrypoint`: skip the entrypoint, for example in tests or when the
+///   program is used as a library.
+///
+/// ```
+/// use pina::*;
+///
+/// declare_id!("GJQcuWrT2f3f4KNuJcXhhwUa1ZQTYbxzzJ1hotzKu8hS");
+///
+/// #[derive(Accounts)]
+/// pub struct PingAccounts<'a> {
+/// 	pub authority: &'a AccountView,
+/// }
+///
+/// #[program(no_entrypoint)]
+/// pub mod ping {
+/// 	use super::*;
+///
+/// 	pub fn ping(accounts: PingAccounts<'_>, _data: &[u8]) -> ProgramResult {
+/// 		accounts.authority.assert_signer()?;
+///
+/// 		Ok(())
+/// 	}
+///
+/// 	#[pina(discriminator = 4)]
+/// 	pub fn pong(_accounts: PingAccounts<'_>) -> ProgramResult {
+/// 		Ok(())
+/// 	}
+/// }
+///
+/// fn main() {
+/// 	assert_eq!(ping::PingInstruction::Ping as u8, 0);
+/// 	assert_eq!(ping::PingInstruction::Pong as u8, 4);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn program(args: TokenStream, input: TokenStream) -> TokenStream {
+	program_impl(args.into(), input.into()).into()
+}
+
+fn program_impl(
+	args: proc_macro2::TokenStream,
+	input: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+	let nested_metas = match NestedMeta::parse_meta_list(args) {
+		Ok(value) => value,
+		Err(e) => return e.into_compile_error(),
+	};
+
+	let args = match ProgramArgs::from_list(&nested_metas) {
+		Ok(v) => v,
+		Err(e) => return e.write_errors(),
+	};
+
+	let mut item_mod: syn::ItemMod = match syn::parse2(input) {
+		Ok(v) => v,
+		Err(e) => return e.to_compile_error(),
+	};
+
+	let ProgramArgs {
+		crate_path,
+		instruction,
+		primitive,
+		program_id,
+		no_entrypoint,
+	} = args;
+
+	let module_name = item_mod.ident.clone();
+	let enum_name = instruction.unwrap_or_else(|| {
+		format_ident!(
+			"{}Instruction",
+			module_name.to_string().to_upper_camel_case()
+		)
+	});
+	let program_id = program_id.map_or_else(|| quote!(crate::ID), |id| quote!(#id));
+
+	let Some((_, items)) = &mut item_mod.content else {
+		return syn::Error::new_spanned(
+			&item_mod,
+			"`#[program]` requires an inline module: `mod name { ... }`",
+		)
+		.to_compile_error();
+	};
+
+	let mut variants = Vec::new();
+	let mut match_arms = Vec::new();
+	let mut next_value: u64 = 0;
+
+	for item in items.iter_mut() {
+		let syn::Item::Fn(handler) = item else {
+			continue;
+		};
+
+		if !matches!(handler.vis, syn::Visibility::Public(_)) {
+			continue;
+		}
+
+		let mut handler_args = ProgramHandlerArgs::default();
+		let mut attrs = Vec::with_capacity(handler.attrs.len());
+
+		for attr in handler.attrs.drain(..) {
+			if !attr.path().is_ident("pina") {
+				attrs.push(attr);
+				continue;
+			}
+
+			handler_args = match ProgramHandlerArgs::from_meta(&attr.meta) {
+				Ok(v) => v,
+				Err(e) => return e.write_errors(),
+			};
+		}
+
+		// Handlers take the accounts struct by value because the dispatcher
+		// parses it for them.
+		attrs.push(syn::parse_quote!(#[allow(clippy::needless_pass_by_value)]));
+		handler.attrs = attrs;
+
+		let value = match &handler_args.discriminator {
+			Some(literal) => {
+				match literal.base10_parse::<u64>() {
+					Ok(value) => value,
+					Err(e) => return e.to_compile_error(),
+				}
+			}
+			None => next_value,
+		};
+		next_value = value.saturating_add(1);
+
+		let handler_name = &handler.sig.ident;
+		let variant = syn::Ident::new(
+			&handler_name.to_string().to_upper_camel_case(),
+			handler_name.span(),
+		);
+		let value = proc_macro2::Literal::u64_unsuffixed(value);
+		let accounts = quote! {
+			#crate_path::TryFromAccountInfos::try_from_account_infos(accounts)?
+		};
+
+		let call = match handler.sig.inputs.len() {
+			1 => quote!(#handler_name(#accounts)),
+			2 => quote!(#handler_name(#accounts, data)),
+			_ => {
+				return syn::Error::new_spanned(
+					&handler.sig,
+					"`#[program]` handlers take an accounts struct and optionally the instruction \
+					 data: `fn name(accounts: MyAccounts<'_>, data: &[u8])`",
+				)
+				.to_compile_error();
+			}
+		};
+
+		variants.push(quote!(#variant = #value));
+		match_arms.push(quote!(#enum_name::#variant => #call,));
+	}
+
+	if variants.is_empty() {
+		return syn::Error::new_spanned(
+			&module_name,
+			"`#[program]` modules need at least one `pub fn` instruction handler",
+		)
+		.to_compile_error();
+	}
+
+	let enum_doc = format!(" The instructions of the `{module_name}` program.");
+
+	items.push(syn::parse_quote! {
+		#[doc = #enum_doc]
+		#[#crate_path::discriminator(crate = #crate_path, primitive = #primitive)]
+		pub enum #enum_name {
+			#(#variants,)*
+		}
+	});
+	items.push(syn::parse_quote! {
+		/// Route an instruction to its handler.
+		///
+		/// # Errors
+		///
+		/// Returns `IncorrectProgramId` for another program's instruction,
+		/// `InvalidInstructionData` for an unknown discriminator and any error
+		/// from parsing the accounts or from the handler.
+		#[inline]
+		pub fn process_instruction(
+			program_id: &#crate_path::Address,
+			accounts: &mut [#crate_path::AccountView],
+			data: &[u8],
+		) -> #crate_path::ProgramResult {
+			let instruction: #enum_name =
+				#crate_path::parse_instruction(program_id, &(#program_id), data)?;
+
+			match instruction {
+				#(#match_arms)*
+			}
+		}
+	});
+
+	if !no_entrypoint.is_present() {
+		items.push(syn::parse_quote! {
+			#[cfg(feature = "bpf-entrypoint")]
+			#crate_path::nostd_entrypoint!(process_instruction);
+		});
+	}
+
+	quote! {
+		#item_mod
+	}
+}
+
+/// Returns the primitive a `Pod*` wrapper type converts from, if `ty` is one of
+/// the wrappers exported by `pina`.
+fn pod_primitive(ty: &Type) -> Option<syn::Ident> {
+	let Type::Path(type_path) = ty else {
+		return None;
+	};
+	let segment = type_path.path.segments.last()?;
+
+	if !segment.arguments.is_none() {
+		return None;
+	}
+
+	let primitive = match segment.ident.to_string().as_str() {
+		"PodBool" => "bool",
+		"PodU16" => "u16",
+		"PodI16" => "i16",
+		"PodU32" => "u32",
+		"PodI32" => "i32",
+		"PodU64" => "u64",
+		"PodI64" => "i64",
+		"PodU128" => "u128",
+		"PodI128" => "i128",
+		_ => return None,
+	};
+
+	Some(format_ident!("{}", primitive))
+}
+
+/// Generate the `seeds`, `seeds_with_bump`, `find_address` and
+/// `assert_canonical_pda` associated functions for `#[account(seeds = [...])]`.
+///
+/// String literals become byte string seeds and lowercase identifiers become
+/// `&[u8]` parameters, in order of first use. Any other expression, such as a
+/// `SEED` constant, is used as written and must coerce to `&[u8]`.
+fn account_seed_helpers(
+	crate_path: &syn::Path,
+	seeds: &syn::Expr,
+) -> syn::Result<proc_macro2::TokenStream> {
+	let syn::Expr::Array(seeds) = seeds else {
+		return Err(syn::Error::new_spanned(
+			seeds,
+			"`seeds` must be an array, such as `seeds = [\"counter\", authority]`.",
+		));
+	};
+
+	// One slot of `MAX_SEEDS` is reserved for the bump.
+	if seeds.elems.is_empty() || seeds.elems.len() >= 16 {
+		return Err(syn::Error::new_spanned(
+			seeds,
+			"`seeds` must declare between 1 and 15 seeds so the bump still fits.",
+		));
+	}
+
+	let mut params: Vec<syn::Ident> = Vec::new();
+	let elems = seeds
+		.elems
+		.iter()
+		.map(|seed| {
+			if let syn::Expr::Lit(syn::ExprLit {
+				lit: syn::Lit::Str(lit),
+				..
+			}) = seed
+			{
+				let bytes = syn::LitByteStr::new(lit.value().as_bytes(), lit.span());
+				return quote! { #bytes };
+			}
+
+			if let Some(ident) = seed_parameter(seed) {
+				if !params.contains(ident) {
+					params.push(ident.clone());
+				}
+				return quote! { #ident };
+			}
+
+			quote! { #seed }
+		})
+		.collect::<Vec<_>>();
+
+	let seed_count = elems.len();
+	let seed_count_with_bump = seed_count + 1;
+
+	Ok(quote! {
+		/// The PDA seeds declared in `#[account(seeds = [...])]`, without the
+		/// bump.
+		pub fn seeds<'seeds>(#(#params: &'seeds [u8]),*) -> [&'seeds [u8]; #seed_count] {
+			[#(#elems),*]
+		}
+
+		/// The PDA seeds declared in `#[account(seeds = [...])]` followed by
+		/// `bump`, ready for signing or `assert_seeds_with_bump`.
+		pub fn seeds_with_bump<'seeds>(
+			#(#params: &'seeds [u8],)*
+			bump: &'seeds [u8; 1],
+		) -> [&'seeds [u8]; #seed_count_with_bump] {
+			[#(#elems,)* bump]
+		}
+
+		/// Find the PDA address and canonical bump for these seeds.
+		pub fn find_address(
+			#(#params: &[u8],)*
+			program_id: &#crate_path::Address,
+		) -> ::core::option::Option<(#crate_path::Address, u8)> {
+			#crate_path::try_find_program_address(&Self::seeds(#(#params),*), program_id)
+		}
+
+		/// Assert that `account` is the PDA for these seeds with the canonical
+		/// bump, and return the bump.
+		///
+		/// Unlike `HasBump::assert_pda`, this needs no loaded state, so it
+		/// also checks accounts that are not created yet.
+		#[track_caller]
+		pub fn assert_canonical_pda(
+			account: &#crate_path::AccountView,
+			#(#params: &[u8],)*
+			program_id: &#crate_path::Address,
+		) -> ::core::result::Result<u8, #crate_path::ProgramError> {
+			#crate_path::AccountInfoValidation::assert_canonical_bump(
+				account,
+				&Self::seeds(#(#params),*),
+				program_id,
+			)
+		}
+	})
+}
+
+/// The parameter named by a seed written as a lowercase identifier.
+fn seed_parameter(seed: &syn::Expr) -> Option<&syn::Ident> {
+	let syn::Expr::Path(path) = seed else {
+		return None;
+	};
+	let ident = path.path.get_ident()?;
+	let is_parameter = path.qself.is_none()
+		&& ident
+			.to_string()
+			.starts_with(|c: char| c.is_ascii_lowercase() || c == '_');
+
+	is_parameter.then_some(ident)
+}
+
+/// Generates `<field>_<primitive>` builder methods for every `Pod*` wrapper
+/// field. Each method wraps the primitive and forwards to the `TypedBuilder`
+/// setter, so it carries the same builder state transition.
+fn primitive_builder_setters(
+	builder_name: &syn::Ident,
+	fields: &Fields,
+) -> proc_macro2::TokenStream {
+	let state_params = (0..fields.len())
+		.map(|index| format_ident!("__PinaField{}", index))
+		.collect::<Vec<_>>();
+
+	let setters = fields.iter().enumerate().filter_map(|(index, field)| {
+		let field_name = field.ident.as_ref()?;
+		let field_type = &field.ty;
+		let primitive = pod_primitive(field_type)?;
+		let method_name = format_ident!("{}_{}", field_name, primitive);
+		let doc = format!(
+			"Set `{field_name}` from a `{primitive}`, wrapping it in `{}`.",
+			quote!(#field_type)
+		);
+
+		let impl_params = state_params
+			.iter()
+			.enumerate()
+			.filter(|(other, _)| *other != index)
+			.map(|(_, param)| param);
+		let state = state_params.iter().enumerate().map(|(other, param)| {
+			if other == index {
+				quote! { () }
+			} else {
+				quote! { #param }
+			}
+		});
+		let target_state = state_params.iter().enumerate().map(|(other, param)| {
+			if other == index {
+				quote! { (#field_type,) }
+			} else {
+				quote! { #param }
+			}
+		});
+
+		Some(quote! {
+			#[allow(dead_code)]
+			impl<#(#impl_params),*> #builder_name<(#(#state,)*)> {
+				#[doc = #doc]
+				pub fn #method_name(self, value: #primitive) -> #builder_name<(#(#target_state,)*)> {
+					self.#field_name(<#field_type as ::core::convert::From<#primitive>>::from(value))
+				}
+			}
+		})
+	});
+
+	quote! { #(#setters)* }
+}
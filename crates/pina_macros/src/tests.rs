@@ -1,11 +1,16 @@
 use quote::quote;
 
 use crate::account_impl;
+use crate::account_union_derive_impl;
 use crate::accounts_derive_impl;
 use crate::discriminator_impl;
 use crate::error_impl;
 use crate::event_impl;
+use crate::has_authority_derive_impl;
+use crate::has_balance_derive_impl;
+use crate::has_sequence_derive_impl;
 use crate::instruction_impl;
+use crate::program_impl;
 
 /// Format a `proc_macro2::TokenStream` into a readable Rust string using
 /// `prettyplease`.
@@ -74,6 +79,19 @@ fn discriminator_u64_primitive() {
 	insta::assert_snapshot!("discriminator_u64_primitive", output);
 }
 
+#[test]
+fn discriminator_anchor_format() {
+	let args = quote! { crate = ::pina, format = anchor };
+	let input = quote! {
+		pub enum AnchorInstruction {
+			Initialize,
+			SetValue,
+		}
+	};
+	let output = pretty(discriminator_impl(args, input));
+	insta::assert_snapshot!("discriminator_anchor_format", output);
+}
+
 #[test]
 fn discriminator_final_attribute() {
 	let args = quote! { primitive = u8, crate = ::pina, final };
@@ -119,6 +137,60 @@ fn discriminator_many_variants() {
 	insta::assert_snapshot!("discriminator_many_variants", output);
 }
 
+#[test]
+fn discriminator_big_endian() {
+	let args = quote! { primitive = u16, crate = ::pina, endian = "big" };
+	let input = quote! {
+		pub enum BridgeDiscriminator {
+			Deposit = 0x0102,
+			Withdraw = 0x0304,
+		}
+	};
+	let output = pretty(discriminator_impl(args, input));
+	insta::assert_snapshot!("discriminator_big_endian", output);
+}
+
+#[test]
+fn discriminator_implicit_values() {
+	let args = quote! { crate = ::pina };
+	let input = quote! {
+		pub enum ImplicitDiscriminator {
+			First,
+			Second,
+			Tenth = 10,
+			Eleventh,
+		}
+	};
+	let output = pretty(discriminator_impl(args, input));
+	insta::assert_snapshot!("discriminator_implicit_values", output);
+}
+
+#[test]
+fn discriminator_with_lock() {
+	let args = quote! { crate = ::pina, lock = "tests/fixtures/discriminators.toml" };
+	let input = quote! {
+		pub enum LockedInstruction {
+			Initialize,
+			Increment,
+			Decrement,
+		}
+	};
+	let output = pretty(discriminator_impl(args, input));
+	insta::assert_snapshot!("discriminator_with_lock", output);
+}
+
+#[test]
+fn discriminator_lock_rejects_removed_variant() {
+	let args = quote! { crate = ::pina, lock = "tests/fixtures/discriminators.toml" };
+	let input = quote! {
+		pub enum RetiredInstruction {
+			Initialize,
+		}
+	};
+	let output = pretty(discriminator_impl(args, input));
+	insta::assert_snapshot!("discriminator_lock_rejects_removed_variant", output);
+}
+
 // ---------------------------------------------------------------------------
 // #[error] snapshots
 // ---------------------------------------------------------------------------
@@ -137,6 +209,35 @@ fn error_basic() {
 	insta::assert_snapshot!("error_basic", output);
 }
 
+#[test]
+fn error_with_messages() {
+	let args = quote! { crate = ::pina };
+	let input = quote! {
+		#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+		pub enum VaultError {
+			#[msg("The vault is locked")]
+			Locked = 0,
+			Empty = 1,
+		}
+	};
+	let output = pretty(error_impl(args, input));
+	insta::assert_snapshot!("error_with_messages", output);
+}
+
+#[test]
+fn error_with_offset() {
+	let args = quote! { crate = ::pina, offset = 6000 };
+	let input = quote! {
+		pub enum OffsetError {
+			First = 0,
+			Second,
+			Skipped = 10,
+		}
+	};
+	let output = pretty(error_impl(args, input));
+	insta::assert_snapshot!("error_with_offset", output);
+}
+
 #[test]
 fn error_final() {
 	let args = quote! { crate = ::pina, final };
@@ -256,6 +357,63 @@ fn account_with_custom_variant() {
 	insta::assert_snapshot!("account_with_custom_variant", output);
 }
 
+#[test]
+fn account_with_anchor_format() {
+	let args = quote! { crate = ::pina, discriminator = AnchorAccount, format = anchor };
+	let input = quote! {
+		pub struct Vault {
+			pub amount: PodU64,
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_with_anchor_format", output);
+}
+
+#[test]
+fn account_with_version_migration() {
+	let args = quote! {
+		crate = ::pina,
+		discriminator = CounterAccount,
+		version = 2,
+		migrate_from = CounterStateV1
+	};
+	let input = quote! {
+		pub struct CounterState {
+			pub version: u8,
+			pub count: PodU64,
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_with_version_migration", output);
+}
+
+#[test]
+fn account_zero_copy() {
+	let args = quote! { crate = ::pina, discriminator = GameAccount, zero_copy };
+	let input = quote! {
+		pub struct Board {
+			pub turn: PodU64,
+			pub cells: [u8; 16384],
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_zero_copy", output);
+}
+
+#[test]
+fn account_external() {
+	let args = quote! { crate = ::pina, external, owner = ORACLE_ID };
+	let input = quote! {
+		#[derive(Debug)]
+		pub struct PriceFeed {
+			pub price: PodI64,
+			pub published_slot: PodU64,
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_external", output);
+}
+
 #[test]
 fn account_many_fields() {
 	let args = quote! { crate = ::pina, discriminator = MyAccount };
@@ -275,6 +433,69 @@ fn account_many_fields() {
 	insta::assert_snapshot!("account_many_fields", output);
 }
 
+#[test]
+fn account_with_validated_fields() {
+	let args = quote! { crate = ::pina, discriminator = MyAccount };
+	let input = quote! {
+		pub struct FeeState {
+			#[validate(u16::from(value) <= MAX_FEE_BPS)]
+			pub fee_bps: PodU16,
+			#[validate(value != [0u8; 32])]
+			#[validate(value != [255u8; 32])]
+			pub authority: [u8; 32],
+			pub bump: u8,
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_with_validated_fields", output);
+}
+
+#[test]
+fn account_with_bump_field() {
+	let args = quote! { crate = ::pina, discriminator = MyAccount };
+	let input = quote! {
+		pub struct VaultState {
+			pub authority: [u8; 32],
+			#[bump]
+			pub vault_bump: u8,
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_with_bump_field", output);
+}
+
+#[test]
+fn account_with_default_fields() {
+	let args = quote! { crate = ::pina, discriminator = MyAccount };
+	let input = quote! {
+		pub struct TaskState {
+			pub owner: [u8; 32],
+			#[default(PodU64::from_primitive(0))]
+			pub count: PodU64,
+			#[default]
+			pub completed: PodBool,
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_with_default_fields", output);
+}
+
+#[test]
+fn account_with_seeds() {
+	let args = quote! {
+		crate = ::pina,
+		discriminator = MyAccount,
+		seeds = ["counter", SEED_VERSION, authority, mint]
+	};
+	let input = quote! {
+		pub struct CounterState {
+			pub count: PodU64,
+		}
+	};
+	let output = pretty(account_impl(args, input));
+	insta::assert_snapshot!("account_with_seeds", output);
+}
+
 // ---------------------------------------------------------------------------
 // #[instruction] snapshots
 // ---------------------------------------------------------------------------
@@ -433,6 +654,102 @@ fn accounts_derive_with_remaining() {
 	insta::assert_snapshot!("accounts_derive_with_remaining", output);
 }
 
+#[test]
+fn accounts_derive_with_known_program() {
+	let input = quote! {
+		#[pina(crate = ::pina)]
+		pub struct CreateAccounts<'a> {
+			pub payer: &'a mut AccountView,
+			#[pina(program = system)]
+			pub system_program: &'a AccountView,
+			#[pina(program = token_2022)]
+			pub token_program: &'a AccountView,
+		}
+	};
+	let output = pretty(accounts_derive_impl(input));
+	insta::assert_snapshot!("accounts_derive_with_known_program", output);
+}
+
+#[test]
+fn accounts_derive_with_typed_fields() {
+	let input = quote! {
+		#[pina(crate = ::pina)]
+		pub struct MintRewardsAccounts<'a> {
+			pub authority: SignerAccount<'a>,
+			#[pina(writable)]
+			pub mint: Mint<'a>,
+			pub vault: Writable<'a>,
+			pub config: Readonly<'a>,
+			pub rewards: TokenAccount<'a>,
+			pub clock: Sysvar<'a, Clock>,
+			pub token_program: Program<'a, Token>,
+		}
+	};
+	let output = pretty(accounts_derive_impl(input));
+	insta::assert_snapshot!("accounts_derive_with_typed_fields", output);
+}
+
+#[test]
+fn accounts_derive_with_constraints() {
+	let input = quote! {
+		#[pina(crate = ::pina)]
+		pub struct WithdrawAccounts<'a> {
+			#[pina(signer, writable)]
+			pub authority: &'a mut AccountView,
+			#[pina(owner = crate::ID)]
+			pub vault: &'a mut AccountView,
+			#[pina(address = system::ID)]
+			pub system_program: &'a AccountView,
+		}
+	};
+	let output = pretty(accounts_derive_impl(input));
+	insta::assert_snapshot!("accounts_derive_with_constraints", output);
+}
+
+#[test]
+fn accounts_derive_with_optional_accounts() {
+	let input = quote! {
+		#[pina(crate = ::pina)]
+		pub struct CloseAccounts<'a> {
+			pub authority: &'a AccountView,
+			#[pina(writable)]
+			pub rent_recipient: Option<&'a mut AccountView>,
+			pub event_authority: Option<&'a AccountView>,
+		}
+	};
+	let output = pretty(accounts_derive_impl(input));
+	insta::assert_snapshot!("accounts_derive_with_optional_accounts", output);
+}
+
+#[test]
+fn accounts_derive_with_remaining_accounts_cursor() {
+	let input = quote! {
+		#[pina(crate = ::pina)]
+		pub struct AirdropAccounts<'a> {
+			pub authority: &'a AccountView,
+			pub recipients: RemainingAccounts<'a>,
+		}
+	};
+	let output = pretty(accounts_derive_impl(input));
+	insta::assert_snapshot!("accounts_derive_with_remaining_accounts_cursor", output);
+}
+
+#[test]
+fn accounts_derive_with_duplicate_checks() {
+	let input = quote! {
+		#[pina(crate = ::pina, deny_duplicates)]
+		pub struct SwapAccounts<'a> {
+			#[pina(writable)]
+			pub source: &'a AccountView,
+			pub fee_vault: Option<&'a mut AccountView>,
+			#[pina(distinct_from = fee_vault)]
+			pub destination: &'a mut AccountView,
+		}
+	};
+	let output = pretty(accounts_derive_impl(input));
+	insta::assert_snapshot!("accounts_derive_with_duplicate_checks", output);
+}
+
 #[test]
 fn accounts_derive_single_field() {
 	let input = quote! {
@@ -476,3 +793,151 @@ fn accounts_derive_default_crate() {
 	let output = pretty(accounts_derive_impl(input));
 	insta::assert_snapshot!("accounts_derive_default_crate", output);
 }
+
+// ---------------------------------------------------------------------------
+// #[derive(HasBalance)] snapshots
+// ---------------------------------------------------------------------------
+
+#[test]
+fn has_balance_derive_named_field() {
+	let input = quote! {
+		pub struct Vault {
+			pub owner: [u8; 32],
+			pub balance: PodU64,
+		}
+	};
+	let output = pretty(has_balance_derive_impl(input));
+	insta::assert_snapshot!("has_balance_derive_named_field", output);
+}
+
+#[test]
+fn has_balance_derive_marked_field() {
+	let input = quote! {
+		#[pina(crate = pina)]
+		pub struct Balance {
+			pub owner: [u8; 32],
+			#[pina(balance)]
+			pub amount: PodU64,
+		}
+	};
+	let output = pretty(has_balance_derive_impl(input));
+	insta::assert_snapshot!("has_balance_derive_marked_field", output);
+}
+
+// ---------------------------------------------------------------------------
+// #[derive(HasSequence)] snapshots
+// ---------------------------------------------------------------------------
+
+#[test]
+fn has_sequence_derive_marked_field() {
+	let input = quote! {
+		#[pina(crate = pina)]
+		pub struct OrderBook {
+			pub authority: Address,
+			#[pina(sequence)]
+			pub next_order_id: PodU64,
+		}
+	};
+	let output = pretty(has_sequence_derive_impl(input));
+	insta::assert_snapshot!("has_sequence_derive_marked_field", output);
+}
+
+// ---------------------------------------------------------------------------
+// #[derive(HasAuthority)] snapshots
+// ---------------------------------------------------------------------------
+
+#[test]
+fn has_authority_derive_marked_field() {
+	let input = quote! {
+		pub struct Vault {
+			#[pina(authority)]
+			pub owner: Address,
+			#[pina(balance)]
+			pub amount: PodU64,
+		}
+	};
+	let output = pretty(has_authority_derive_impl(input));
+	insta::assert_snapshot!("has_authority_derive_marked_field", output);
+}
+
+// ---------------------------------------------------------------------------
+// #[derive(AccountUnion)] snapshots
+// ---------------------------------------------------------------------------
+
+#[test]
+fn account_union_derive_variants() {
+	let input = quote! {
+		#[pina(crate = pina)]
+		pub enum AppState<'a> {
+			UserProfile(Ref<'a, UserProfile>),
+			AdminConfig(pina::Ref<'a, AdminConfig>),
+		}
+	};
+	let output = pretty(account_union_derive_impl(input));
+	insta::assert_snapshot!("account_union_derive_variants", output);
+}
+
+#[test]
+fn account_union_derive_rejects_non_ref_variants() {
+	let input = quote! {
+		pub enum AppState<'a> {
+			UserProfile(&'a UserProfile),
+		}
+	};
+	let output = account_union_derive_impl(input).to_string();
+
+	assert!(output.contains("AccountUnion variants must hold a `Ref<'a, T>`"));
+}
+
+// ---------------------------------------------------------------------------
+// #[program] snapshots
+// ---------------------------------------------------------------------------
+
+#[test]
+fn program_module() {
+	let args = quote! {};
+	let input = quote! {
+		pub mod counter {
+			use super::*;
+
+			pub fn initialize(accounts: InitializeAccounts<'_>, data: &[u8]) -> ProgramResult {
+				Ok(())
+			}
+
+			#[pina(discriminator = 4)]
+			pub fn increment(accounts: IncrementAccounts<'_>) -> ProgramResult {
+				Ok(())
+			}
+
+			pub fn close(accounts: CloseAccounts<'_>, data: &[u8]) -> ProgramResult {
+				helper()
+			}
+
+			fn helper() -> ProgramResult {
+				Ok(())
+			}
+		}
+	};
+	let output = pretty(program_impl(args, input));
+	insta::assert_snapshot!("program_module", output);
+}
+
+#[test]
+fn program_module_with_arguments() {
+	let args = quote! {
+		crate = ::pina,
+		instruction = EscrowIx,
+		primitive = u16,
+		program_id = ESCROW_ID,
+		no_entrypoint,
+	};
+	let input = quote! {
+		mod escrow {
+			pub fn make(accounts: MakeAccounts<'_>, data: &[u8]) -> ProgramResult {
+				Ok(())
+			}
+		}
+	};
+	let output = pretty(program_impl(args, input));
+	insta::assert_snapshot!("program_module_with_arguments", output);
+}
@@ -0,0 +1,21 @@
+use pina::*;
+
+#[discriminator]
+pub enum AccountKind {
+	HugeState = 0,
+}
+
+#[account(discriminator = AccountKind)]
+pub struct HugeState {
+	pub first: [u8; 4096],
+	pub second: [u8; 4096],
+	pub third: [u8; 4096],
+}
+
+type CreateFn = fn(&AccountView, &AccountView, &Address, &[&[u8]], u8) -> ProgramResult;
+
+fn main() {
+	// Coercing to a function pointer instantiates the size check for `HugeState`.
+	let create: CreateFn = create_program_account_with_bump::<HugeState>;
+	core::hint::black_box(create);
+}
@@ -0,0 +1,15 @@
+use pina::*;
+
+const ORACLE_ID: Address = Address::new_from_array([7u8; 32]);
+
+#[discriminator]
+pub enum AccountKind {
+	PriceFeed = 0,
+}
+
+#[account(discriminator = AccountKind, external, owner = ORACLE_ID)]
+pub struct PriceFeed {
+	pub price: PodI64,
+}
+
+fn main() {}
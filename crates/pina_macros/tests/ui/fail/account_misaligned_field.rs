@@ -0,0 +1,13 @@
+use pina::*;
+
+#[discriminator]
+pub enum AccountKind {
+	CounterState = 0,
+}
+
+#[account(discriminator = AccountKind)]
+pub struct CounterState {
+	pub count: u64,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use pina::*;
+
+#[discriminator]
+pub enum AccountKind {
+	FlagState = 0,
+}
+
+#[account(discriminator = AccountKind)]
+pub struct FlagState {
+	pub enabled: bool,
+}
+
+fn main() {}
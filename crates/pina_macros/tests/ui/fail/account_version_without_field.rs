@@ -0,0 +1,13 @@
+use pina::*;
+
+#[discriminator]
+pub enum AccountKind {
+	CounterState = 0,
+}
+
+#[account(discriminator = AccountKind, version = 2)]
+pub struct CounterState {
+	pub count: PodU64,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use pina::*;
+
+#[discriminator]
+pub enum AccountKind {
+	Board = 0,
+}
+
+#[account(discriminator = AccountKind, zero_copy)]
+pub struct Board {
+	#[default(PodU64::from_primitive(1))]
+	pub turn: PodU64,
+	pub cells: [u8; 16384],
+}
+
+fn main() {}
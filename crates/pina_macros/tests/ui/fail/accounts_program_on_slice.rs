@@ -0,0 +1,10 @@
+use pina::*;
+
+#[derive(Accounts)]
+pub struct ProgramOnSlice<'a> {
+	pub payer: &'a AccountView,
+	#[pina(program = system)]
+	pub programs: &'a [AccountView],
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pina::*;
+
+// trybuild builds this from `target/tests/trybuild/pina_macros`, so the lock
+// path climbs back to this directory.
+#[discriminator(lock = "../../../../crates/pina_macros/tests/ui/fail/discriminator_lock_mismatch.toml")]
+pub enum LockedInstruction {
+	// Swapped since the lock was written.
+	Deposit,
+	Initialize,
+}
+
+fn main() {}
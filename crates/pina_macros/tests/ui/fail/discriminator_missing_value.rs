@@ -1,8 +0,0 @@
-use pina::*;
-
-#[discriminator]
-pub enum MissingValueDiscriminator {
-	Value,
-}
-
-fn main() {}
@@ -0,0 +1,10 @@
+use pina::*;
+
+const BASE: u32 = 6000;
+
+#[error]
+pub enum VaultError {
+	Locked = BASE as isize,
+}
+
+fn main() {}
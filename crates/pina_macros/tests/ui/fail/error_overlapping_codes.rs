@@ -0,0 +1,14 @@
+use pina::*;
+
+#[error(offset = 6000)]
+pub enum VaultError {
+	Locked = 0,
+	Empty = 1,
+}
+
+#[error(offset = 6001)]
+pub enum LegacyError {
+	Unauthorized = 0,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+
+mod vault {
+	use pina::*;
+
+	#[error(offset = 6000)]
+	pub enum VaultError {
+		Locked,
+		Empty,
+	}
+}
+
+mod legacy {
+	use pina::*;
+
+	#[error]
+	pub enum LegacyError {
+		Unauthorized = 6001,
+	}
+}
+
+fn main() {}
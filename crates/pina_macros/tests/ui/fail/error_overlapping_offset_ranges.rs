@@ -0,0 +1,24 @@
+use pina::*;
+
+#[error(offset = 6000)]
+pub enum EscrowError {
+	Settled,
+	Expired,
+	Cancelled,
+	Disputed,
+}
+
+#[error(offset = 6100)]
+pub enum TokenError {
+	Frozen,
+	Mismatch = 99,
+	Overdrawn,
+}
+
+#[error(offset = 6003)]
+pub enum StakeError {
+	Unbonding,
+	Slashed,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use pina::*;
+
+#[discriminator(primitive = u8)]
+pub enum MyInstruction {
+	Initialize = 0,
+}
+
+#[instruction(discriminator = MyInstruction)]
+pub struct Increment {
+	pub amount: u8,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use pina::*;
+
+#[discriminator(primitive = u8)]
+pub enum MyInstruction {
+	Initialize = 0,
+}
+
+#[instruction(discriminator = MyInstruction, variant = Increment)]
+pub struct IncrementInstruction {
+	pub amount: u8,
+}
+
+fn main() {}
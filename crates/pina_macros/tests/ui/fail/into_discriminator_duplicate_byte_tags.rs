@@ -0,0 +1,14 @@
+use pina::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleInstruction {
+	Publish,
+	Retire,
+}
+
+into_discriminator!(OracleInstruction, [u8; 4], {
+	Publish => *b"publ",
+	Retire => *b"publ",
+});
+
+fn main() {}
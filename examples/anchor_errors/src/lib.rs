@@ -17,17 +17,18 @@ use pina::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
-#[error]
+#[error(offset = 6000)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MyError {
-	Hello = 6000,
-	HelloNoMsg = 6123,
-	HelloNext = 6124,
-	HelloCustom = 6125,
-	ValueMismatch = 6126,
-	ValueMatch = 6127,
-	ValueLess = 6128,
-	ValueLessOrEqual = 6129,
+	#[msg("This is an error message clients will automatically display")]
+	Hello = 0,
+	HelloNoMsg = 123,
+	HelloNext = 124,
+	HelloCustom = 125,
+	ValueMismatch = 126,
+	ValueMatch = 127,
+	ValueLess = 128,
+	ValueLessOrEqual = 129,
 }
 
 #[discriminator]
@@ -153,15 +154,15 @@ mod tests {
 	fn hello_variants_return_expected_errors() {
 		assert!(matches!(
 			process_instruction_variant(ErrorsInstruction::Hello),
-			Err(ProgramError::Custom(code)) if code == MyError::Hello as u32
+			Err(ProgramError::Custom(code)) if code == MyError::Hello.code()
 		));
 		assert!(matches!(
 			process_instruction_variant(ErrorsInstruction::HelloNoMsg),
-			Err(ProgramError::Custom(code)) if code == MyError::HelloNoMsg as u32
+			Err(ProgramError::Custom(code)) if code == MyError::HelloNoMsg.code()
 		));
 		assert!(matches!(
 			process_instruction_variant(ErrorsInstruction::HelloNext),
-			Err(ProgramError::Custom(code)) if code == MyError::HelloNext as u32
+			Err(ProgramError::Custom(code)) if code == MyError::HelloNext.code()
 		));
 	}
 
@@ -169,19 +170,19 @@ mod tests {
 	fn require_helpers_return_expected_errors() {
 		assert!(matches!(
 			process_instruction_variant(ErrorsInstruction::RequireEq),
-			Err(ProgramError::Custom(code)) if code == MyError::ValueMismatch as u32
+			Err(ProgramError::Custom(code)) if code == MyError::ValueMismatch.code()
 		));
 		assert!(matches!(
 			process_instruction_variant(ErrorsInstruction::RequireNeq),
-			Err(ProgramError::Custom(code)) if code == MyError::ValueMatch as u32
+			Err(ProgramError::Custom(code)) if code == MyError::ValueMatch.code()
 		));
 		assert!(matches!(
 			process_instruction_variant(ErrorsInstruction::RequireGt),
-			Err(ProgramError::Custom(code)) if code == MyError::ValueLessOrEqual as u32
+			Err(ProgramError::Custom(code)) if code == MyError::ValueLessOrEqual.code()
 		));
 		assert!(matches!(
 			process_instruction_variant(ErrorsInstruction::RequireGte),
-			Err(ProgramError::Custom(code)) if code == MyError::ValueLess as u32
+			Err(ProgramError::Custom(code)) if code == MyError::ValueLess.code()
 		));
 	}
 
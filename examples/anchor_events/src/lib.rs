@@ -1,8 +1,10 @@
 //! Anchor `events` parity example ported to pina.
 //!
-//! Anchor's event transport (`emit!`, `emit_cpi!`) is framework-specific. This
-//! parity port focuses on the event type definitions and deterministic
-//! serialization/discriminator behavior in pina.
+//! Events are emitted with pina's `emit!`, which logs the discriminator-prefixed
+//! event bytes through `sol_log_data` like Anchor's `emit!`. `TestEventCpi`
+//! uses `emit_cpi!` instead, which invokes the program itself with the event
+//! bytes, signed by the `__event_authority` PDA passed as the first account.
+//! The program's own account follows it, like Anchor's `#[event_cpi]`.
 
 #![allow(clippy::inline_always)]
 #![no_std]
@@ -18,6 +20,10 @@ use pina::*;
 
 declare_id!("2dhGsWUzy5YKUsjZdLHLmkNpUDAXkNa9MYWsPc4Ziqzy");
 
+/// Bump of the `__event_authority` PDA of `ID`, so `emit_cpi!` does not
+/// derive it on every call.
+pub const EVENT_AUTHORITY_BUMP: u8 = 254;
+
 #[discriminator]
 pub enum EventsInstruction {
 	Initialize = 0,
@@ -67,6 +73,30 @@ pub enum EmittedEvent {
 	MyOtherEvent(MyOtherEvent),
 }
 
+impl EmittedEvent {
+	/// Log the event for indexers and clients.
+	#[allow(dead_code)]
+	fn emit(&self) {
+		match self {
+			Self::MyEvent(event) => emit!(*event),
+			Self::MyOtherEvent(event) => emit!(*event),
+		}
+	}
+
+	/// Send the event as a self-invocation signed by the event authority.
+	#[allow(dead_code)]
+	fn emit_cpi(&self, program: AccountView, event_authority: AccountView) -> ProgramResult {
+		match self {
+			Self::MyEvent(event) => {
+				emit_cpi!(*event, &program, &event_authority, EVENT_AUTHORITY_BUMP)
+			}
+			Self::MyOtherEvent(event) => {
+				emit_cpi!(*event, &program, &event_authority, EVENT_AUTHORITY_BUMP)
+			}
+		}
+	}
+}
+
 #[allow(dead_code)]
 fn build_event(instruction: EventsInstruction) -> EmittedEvent {
 	match instruction {
@@ -108,12 +138,26 @@ pub mod entrypoint {
 	#[inline(always)]
 	pub fn process_instruction(
 		program_id: &Address,
-		_accounts: &mut [AccountView],
+		accounts: &mut [AccountView],
 		data: &[u8],
 	) -> ProgramResult {
+		if is_emit_event_instruction(data) {
+			return process_emit_event(program_id, accounts, EVENT_AUTHORITY_BUMP);
+		}
+
 		let instruction: EventsInstruction = parse_instruction(program_id, &ID, data)?;
-		let _ = build_event(instruction);
-		Ok(())
+		let event = build_event(instruction);
+
+		if !matches!(instruction, EventsInstruction::TestEventCpi) {
+			event.emit();
+			return Ok(());
+		}
+
+		let [event_authority, program, ..] = accounts else {
+			return Err(ProgramError::NotEnoughAccountKeys);
+		};
+
+		event.emit_cpi(*program, *event_authority)
 	}
 }
 
@@ -154,6 +198,25 @@ mod tests {
 		assert_eq!(event.label, LABEL_CPI);
 	}
 
+	#[test]
+	fn event_authority_bump_matches_the_derived_pda() {
+		let (_, bump) =
+			find_event_authority(&ID).unwrap_or_else(|| panic!("no event authority for ID"));
+
+		assert_eq!(bump, EVENT_AUTHORITY_BUMP);
+	}
+
+	#[test]
+	fn emitting_events_off_chain_is_a_noop() {
+		for instruction in [
+			EventsInstruction::Initialize,
+			EventsInstruction::TestEvent,
+			EventsInstruction::TestEventCpi,
+		] {
+			build_event(instruction).emit();
+		}
+	}
+
 	#[test]
 	fn my_event_roundtrip_serialization() {
 		let event = MyEvent::builder()
@@ -0,0 +1,164 @@
+//! Self-invocation tests for `emit_cpi!`.
+//!
+//! These run `TestEventCpi` through `mollusk-svm`, so the event
+//! self-invocation and the `process_emit_event` handler it reaches execute
+//! on the SBF runtime.
+//!
+//! ## Prerequisites
+//!
+//! The program must be compiled to an SBF binary before running these tests:
+//!
+//! ```sh
+//! cargo build --release --target bpfel-unknown-none -p anchor_events \
+//!     -Z build-std=core,alloc -F bpf-entrypoint
+//! ```
+//!
+//! Then set `SBF_OUT_DIR` to the directory containing the `.so` file, or place
+//! it in `tests/fixtures/`.
+//!
+//! ## Running
+//!
+//! ```sh
+//! SBF_OUT_DIR=target/bpfel-unknown-none/release \
+//!     cargo test -p anchor_events --test emit_cpi -- --nocapture
+//! ```
+
+use anchor_events::EVENT_AUTHORITY_BUMP;
+use anchor_events::EventsInstruction;
+use mollusk_svm::Mollusk;
+use mollusk_svm::program::create_program_account_loader_v3;
+use mollusk_svm::result::Check;
+use pina::EVENT_AUTHORITY_SEED;
+use pina::EVENT_IX_TAG;
+use pina::ProgramError;
+use solana_account::Account;
+use solana_instruction::AccountMeta;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+fn program_id() -> Pubkey {
+	let id = anchor_events::ID;
+	let bytes: &[u8] = id.as_ref();
+	let array: [u8; 32] = bytes
+		.try_into()
+		.unwrap_or_else(|_| panic!("address must be 32 bytes"));
+	Pubkey::new_from_array(array)
+}
+
+/// Try to create a mollusk instance for the events program.
+///
+/// Returns `None` if the BPF binary cannot be found. This allows the tests to
+/// be skipped gracefully without triggering a panic-abort from the `no_std`
+/// panic handler.
+fn try_create_mollusk() -> Option<Mollusk> {
+	let so_name = "anchor_events.so";
+	let search_dirs: Vec<std::path::PathBuf> = [
+		std::env::var("SBF_OUT_DIR").ok(),
+		std::env::var("BPF_OUT_DIR").ok(),
+		Some("tests/fixtures".to_owned()),
+	]
+	.into_iter()
+	.flatten()
+	.map(std::path::PathBuf::from)
+	.collect();
+
+	let found = search_dirs.iter().any(|dir| dir.join(so_name).is_file());
+	if !found {
+		return None;
+	}
+
+	Some(Mollusk::new(&program_id(), "anchor_events"))
+}
+
+macro_rules! mollusk_or_skip {
+	() => {
+		match try_create_mollusk() {
+			Some(mollusk) => mollusk,
+			None => {
+				eprintln!(
+					"[SKIP] anchor_events SBF binary not found. Build it first with `cargo build \
+					 --release --target bpfel-unknown-none -p anchor_events -Z \
+					 build-std=core,alloc -F bpf-entrypoint`."
+				);
+				return;
+			}
+		}
+	};
+}
+
+/// The event authority PDA, derived from the bump the program hardcodes.
+fn event_authority() -> Pubkey {
+	Pubkey::create_program_address(
+		&[EVENT_AUTHORITY_SEED, &[EVENT_AUTHORITY_BUMP]],
+		&program_id(),
+	)
+	.unwrap_or_else(|e| panic!("invalid event authority bump: {e}"))
+}
+
+fn accounts() -> Vec<(Pubkey, Account)> {
+	vec![
+		(event_authority(), Account::default()),
+		(
+			program_id(),
+			create_program_account_loader_v3(&program_id()),
+		),
+	]
+}
+
+#[test]
+fn emit_cpi_invokes_the_program_signed_by_the_event_authority() {
+	let mollusk = mollusk_or_skip!();
+
+	let instruction = Instruction::new_with_bytes(
+		program_id(),
+		&[EventsInstruction::TestEventCpi as u8],
+		vec![
+			AccountMeta::new_readonly(event_authority(), false),
+			AccountMeta::new_readonly(program_id(), false),
+		],
+	);
+
+	let result =
+		mollusk.process_and_validate_instruction(&instruction, &accounts(), &[Check::success()]);
+
+	eprintln!(
+		"[CU BENCHMARK] TestEventCpi: {} compute units consumed",
+		result.compute_units_consumed
+	);
+}
+
+#[test]
+fn emit_cpi_requires_the_program_account() {
+	let mollusk = mollusk_or_skip!();
+
+	let instruction = Instruction::new_with_bytes(
+		program_id(),
+		&[EventsInstruction::TestEventCpi as u8],
+		vec![AccountMeta::new_readonly(event_authority(), false)],
+	);
+
+	mollusk.process_and_validate_instruction(
+		&instruction,
+		&accounts()[..1],
+		&[Check::err(ProgramError::NotEnoughAccountKeys)],
+	);
+}
+
+#[test]
+fn unsigned_event_instructions_are_rejected() {
+	let mollusk = mollusk_or_skip!();
+
+	let mut data = EVENT_IX_TAG.to_vec();
+	data.extend_from_slice(&[EventsInstruction::TestEventCpi as u8; 17]);
+	let instruction = Instruction::new_with_bytes(
+		program_id(),
+		&data,
+		vec![AccountMeta::new_readonly(event_authority(), false)],
+	);
+
+	mollusk.process_and_validate_instruction(
+		&instruction,
+		&accounts()[..1],
+		&[Check::err(ProgramError::MissingRequiredSignature)],
+	);
+}
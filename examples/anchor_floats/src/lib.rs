@@ -97,12 +97,7 @@ impl<'a> ProcessAccountInfos<'a> for CreateAccounts<'a> {
 		self.account.assert_empty()?.assert_writable()?;
 		self.system_program.assert_address(&system::ID)?;
 
-		create_account(
-			self.authority,
-			self.account,
-			size_of::<FloatDataAccount>(),
-			&ID,
-		)?;
+		create_rent_exempt_account::<FloatDataAccount>(self.account, self.authority, &ID)?;
 
 		let mut account = self.account.as_account_mut::<FloatDataAccount>(&ID)?;
 		apply_create(&mut account, self.authority.address(), data_f32, data_f64);
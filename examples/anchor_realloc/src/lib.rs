@@ -2,6 +2,8 @@
 //!
 //! This adaptation focuses on the key safety checks from Anchor's realloc
 //! tests: maximum permitted growth and duplicate realloc target detection.
+//! Resizing goes through `resize_with_rent`, which also has the authority
+//! fund the rent for any growth.
 
 #![allow(clippy::inline_always)]
 #![no_std]
@@ -13,13 +15,10 @@
 ))]
 extern crate std;
 
-use pina::pinocchio::Resize;
 use pina::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
-const MAX_PERMITTED_DATA_INCREASE: usize = 10_240;
-
 #[error]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReallocError {
@@ -45,19 +44,27 @@ pub struct Realloc2Ix {
 
 #[derive(Accounts, Debug)]
 pub struct ReallocAccounts<'a> {
-	pub authority: &'a AccountView,
+	pub authority: &'a mut AccountView,
 	pub sample: &'a mut AccountView,
 	pub system_program: &'a AccountView,
 }
 
 #[derive(Accounts, Debug)]
 pub struct Realloc2Accounts<'a> {
-	pub authority: &'a AccountView,
+	pub authority: &'a mut AccountView,
 	pub sample1: &'a mut AccountView,
 	pub sample2: &'a mut AccountView,
 	pub system_program: &'a AccountView,
 }
 
+/// Resize `account` to `len` bytes with `payer` funding any growth, reporting
+/// growth past `MAX_PERMITTED_DATA_INCREASE` with Anchor's error code.
+fn resize_sample(account: &mut AccountView, len: usize, payer: &mut AccountView) -> ProgramResult {
+	validate_realloc_delta(account.data_len(), len)?;
+
+	resize_with_rent(account, len, payer, None, &ID)
+}
+
 fn validate_realloc_delta(current_len: usize, new_len: usize) -> ProgramResult {
 	if new_len > current_len {
 		let delta = new_len - current_len;
@@ -84,12 +91,9 @@ impl<'a> ProcessAccountInfos<'a> for ReallocAccounts<'a> {
 		let target_len = usize::from(u16::from(args.len));
 
 		self.authority.assert_signer()?;
-		self.sample.assert_writable()?;
 		self.system_program.assert_address(&system::ID)?;
 
-		validate_realloc_delta(self.sample.data_len(), target_len)?;
-
-		self.sample.resize(target_len)
+		resize_sample(self.sample, target_len, self.authority)
 	}
 }
 
@@ -102,17 +106,13 @@ impl<'a> ProcessAccountInfos<'a> for Realloc2Accounts<'a> {
 			.ok_or(ProgramError::ArithmeticOverflow)?;
 
 		self.authority.assert_signer()?;
-		self.sample1.assert_writable()?;
-		self.sample2.assert_writable()?;
 		self.system_program.assert_address(&system::ID)?;
 
 		validate_distinct_realloc_targets(self.sample1.address(), self.sample2.address())?;
-		validate_realloc_delta(self.sample1.data_len(), base_len)?;
-		validate_realloc_delta(self.sample2.data_len(), second_target_len)?;
 
-		self.sample1.resize(base_len)?;
+		resize_sample(self.sample1, base_len, self.authority)?;
 
-		self.sample2.resize(second_target_len)
+		resize_sample(self.sample2, second_target_len, self.authority)
 	}
 }
 
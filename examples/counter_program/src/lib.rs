@@ -12,7 +12,8 @@
 //!   `parse_instruction` for type-safe routing.
 //! - **Account validation chains** — `.assert_signer()?.assert_writable()?` for
 //!   concise, composable checks.
-//! - **`create_program_account`** — pina's CPI helper for PDA account creation.
+//! - **`create_and_init`** — pina's CPI helper for creating and initializing a
+//!   PDA account in one call.
 //!
 //! ## Instructions
 //!
@@ -88,9 +89,10 @@ pub enum CounterAccountType {
 /// | 1      | 1    | bump          |
 /// | 2      | 8    | count (PodU64)|
 /// ```
-#[account(discriminator = CounterAccountType)]
+#[account(discriminator = CounterAccountType, seeds = [COUNTER_SEED, authority])]
 pub struct CounterState {
 	/// The PDA bump seed, stored on-chain so we don't need to re-derive it.
+	#[bump]
 	pub bump: u8,
 	/// The current counter value. Uses `PodU64` (a little-endian `u64`
 	/// wrapper) for safe alignment in `#[repr(C)]` structs.
@@ -120,24 +122,11 @@ pub struct IncrementInstruction {}
 // PDA seeds
 // ---------------------------------------------------------------------------
 
-/// Seed prefix for counter PDAs.
+/// Seed prefix for counter PDAs. The full seeds, `["counter",
+/// <authority_address>]`, are declared on `CounterState`, which generates
+/// `CounterState::seeds` and `CounterState::seeds_with_bump`.
 const COUNTER_SEED: &[u8] = b"counter";
 
-/// Build the PDA seeds for a counter account.
-///
-/// Seeds: `["counter", <authority_address>]`
-///
-/// With bump: `["counter", <authority_address>, &[bump]]`
-#[macro_export]
-macro_rules! counter_seeds {
-	($authority:expr) => {
-		&[COUNTER_SEED, $authority]
-	};
-	($authority:expr, $bump:expr) => {
-		&[COUNTER_SEED, $authority, &[$bump]]
-	};
-}
-
 // ---------------------------------------------------------------------------
 // Accounts structs
 // ---------------------------------------------------------------------------
@@ -154,6 +143,7 @@ pub struct InitializeAccounts<'a> {
 	/// The counter PDA account (must be empty — not yet created).
 	pub counter: &'a mut AccountView,
 	/// The system program, required for `CreateAccount` CPI.
+	#[pina(program = system)]
 	pub system_program: &'a AccountView,
 }
 
@@ -175,32 +165,24 @@ impl<'a> ProcessAccountInfos<'a> for InitializeAccounts<'a> {
 		// Parse instruction and prepare PDA seeds
 		let args = InitializeInstruction::try_from_bytes(data)?;
 		let authority_key = self.authority.address();
-		let seeds = counter_seeds!(authority_key.as_ref());
-		let seeds_with_bump = counter_seeds!(authority_key.as_ref(), args.bump);
+		let seeds = CounterState::seeds(authority_key.as_ref());
+		let bump = [args.bump];
+		let seeds_with_bump = CounterState::seeds_with_bump(authority_key.as_ref(), &bump);
 
 		// Validate accounts
 		self.authority.assert_signer()?;
 		self.counter
 			.assert_empty()?
 			.assert_writable()?
-			.assert_seeds_with_bump(seeds_with_bump, &ID)?;
-		self.system_program.assert_address(&system::ID)?;
+			.assert_seeds_with_bump(&seeds_with_bump, &ID)?;
 
-		// Create the PDA account
-		create_program_account_with_bump::<CounterState>(
-			self.counter,
-			self.authority,
-			&ID,
-			seeds,
-			args.bump,
-		)?;
-
-		// Initialize account data
-		let mut counter = self.counter.as_account_mut::<CounterState>(&ID)?;
-		*counter = CounterState::builder()
-			.bump(args.bump)
-			.count(PodU64::from_primitive(0))
-			.build();
+		// Create the PDA account and write its initial state
+		create_and_init(self.counter, self.authority, &ID, &seeds, args.bump, || {
+			CounterState::builder()
+				.bump(args.bump)
+				.count(PodU64::from_primitive(0))
+				.build()
+		})?;
 
 		log!("Counter initialized");
 
@@ -217,17 +199,14 @@ impl<'a> ProcessAccountInfos<'a> for IncrementAccounts<'a> {
 		self.authority.assert_signer()?;
 
 		let authority_key = self.authority.address();
-		self.counter
-			.assert_not_empty()?
-			.assert_writable()?
-			.assert_type::<CounterState>(&ID)?;
+		self.counter.assert_not_empty()?.assert_writable()?;
 
-		let bump = {
-			let counter = self.counter.as_account::<CounterState>(&ID)?;
-			counter.bump
-		};
-		let seeds_with_bump = counter_seeds!(authority_key.as_ref(), bump);
-		self.counter.assert_seeds_with_bump(seeds_with_bump, &ID)?;
+		// Checks owner, discriminator and size while borrowing the data once.
+		self.counter.load_account::<CounterState>(&ID)?.assert_pda(
+			self.counter,
+			&CounterState::seeds(authority_key.as_ref()),
+			&ID,
+		)?;
 
 		// Mutate state
 		let mut counter = self.counter.as_account_mut::<CounterState>(&ID)?;
@@ -285,6 +264,19 @@ mod tests {
 		assert_eq!(CounterInstruction::Increment as u8, 1);
 	}
 
+	#[test]
+	fn discriminator_max() {
+		assert_eq!(CounterInstruction::MAX, 1);
+		assert_eq!(CounterAccountType::MAX, 1);
+	}
+
+	test_discriminator_exhaustive!(
+		CounterInstruction,
+		u8,
+		instruction_discriminator_is_exhaustive
+	);
+	test_discriminator_exhaustive!(CounterAccountType, u8, account_discriminator_is_exhaustive);
+
 	#[test]
 	fn discriminator_roundtrip() {
 		assert!(CounterInstruction::try_from(0u8).is_ok());
@@ -316,6 +308,28 @@ mod tests {
 		assert_eq!(u64::from(state.count), 100);
 	}
 
+	#[test]
+	fn counter_state_builder_accepts_primitives() {
+		let state = CounterState::builder().bump(42).count_u64(100).build();
+		assert_eq!(state.bump, 42);
+		assert_eq!(u64::from(state.count), 100);
+		assert_eq!(
+			state.to_bytes(),
+			&[
+				CounterAccountType::CounterState as u8,
+				42,
+				100,
+				0,
+				0,
+				0,
+				0,
+				0,
+				0,
+				0
+			]
+		);
+	}
+
 	#[test]
 	fn counter_state_deserialize_roundtrip() {
 		let state = CounterState::builder()
@@ -368,24 +382,34 @@ mod tests {
 	}
 
 	#[test]
-	fn counter_seeds_macro() {
+	fn counter_seeds() {
 		let authority = [1u8; 32];
-		let seeds = counter_seeds!(&authority);
+		let seeds = CounterState::seeds(&authority);
 		assert_eq!(seeds.len(), 2);
 		assert_eq!(seeds[0], b"counter");
 		assert_eq!(seeds[1], &authority);
 	}
 
 	#[test]
-	fn counter_seeds_with_bump_macro() {
+	fn counter_seeds_with_bump() {
 		let authority = [1u8; 32];
-		let seeds = counter_seeds!(&authority, 42);
+		let seeds = CounterState::seeds_with_bump(&authority, &[42]);
 		assert_eq!(seeds.len(), 3);
 		assert_eq!(seeds[0], b"counter");
 		assert_eq!(seeds[1], &authority);
 		assert_eq!(seeds[2], &[42u8]);
 	}
 
+	#[test]
+	fn counter_find_address_matches_the_seeds() {
+		let authority = [1u8; 32];
+
+		assert_eq!(
+			CounterState::find_address(&authority, &ID),
+			try_find_program_address(&[b"counter", &authority], &ID)
+		);
+	}
+
 	#[test]
 	fn program_id_is_valid() {
 		assert_ne!(ID, Address::default());
@@ -23,6 +23,9 @@
 //! SBF_OUT_DIR=target/bpfel-unknown-none/release \
 //!     cargo test -p counter_program --test cu_benchmarks -- --nocapture
 //! ```
+//!
+//! Set `PINA_CU_REPORT_DIR` to also write a per-instruction JSON report to
+//! `counter_program.json` in that directory for CI comparison.
 
 use counter_program::CounterInstruction;
 use counter_program::CounterState;
@@ -31,6 +34,7 @@ use mollusk_svm::program::keyed_account_for_system_program;
 use mollusk_svm::result::Check;
 use pina::PodU64;
 use pina::bytemuck;
+use pina::compute_units::ComputeUnitReport;
 use solana_account::Account;
 use solana_instruction::AccountMeta;
 use solana_instruction::Instruction;
@@ -305,6 +309,53 @@ fn benchmark_cu_initialize_full_flow() {
 	);
 }
 
+/// A counter PDA that already holds lamports can't be created with
+/// `CreateAccount`, so initialization takes the transfer, allocate and assign
+/// path instead.
+#[test]
+fn initialize_prefunded_counter() {
+	let Some(mollusk) = try_create_mollusk() else {
+		eprintln!("[SKIP] counter_program SBF binary not found. Build it first.");
+		return;
+	};
+
+	let authority = Pubkey::new_unique();
+	let (counter_pda, bump) = derive_counter_pda(&authority);
+	let rent_exempt = mollusk
+		.sysvars
+		.rent
+		.minimum_balance(size_of::<CounterState>());
+
+	let instruction = Instruction::new_with_bytes(
+		program_id(),
+		&initialize_ix_data(bump),
+		vec![
+			AccountMeta::new(authority, true),
+			AccountMeta::new(counter_pda, true),
+			AccountMeta::new_readonly(solana_sdk_ids::system_program::id(), false),
+		],
+	);
+
+	let authority_account = Account::new(1_000_000_000, 0, &solana_sdk_ids::system_program::id());
+	let prefunded_counter = Account::new(1_000, 0, &solana_sdk_ids::system_program::id());
+
+	let accounts = vec![
+		(authority, authority_account),
+		(counter_pda, prefunded_counter),
+		keyed_account_for_system_program(),
+	];
+
+	let result =
+		mollusk.process_and_validate_instruction(&instruction, &accounts, &[Check::success()]);
+
+	let counter = result
+		.get_account(&counter_pda)
+		.unwrap_or_else(|| panic!("counter PDA not found in resulting accounts"));
+	assert_eq!(counter.owner, program_id());
+	assert_eq!(counter.lamports, rent_exempt);
+	assert_eq!(counter.data.len(), size_of::<CounterState>());
+}
+
 /// Print a summary of all CU measurements.
 #[test]
 fn benchmark_cu_summary() {
@@ -390,3 +441,76 @@ fn benchmark_cu_summary() {
 	eprintln!("  Note: Increment includes PDA seed verification and checked arithmetic.");
 	eprintln!();
 }
+
+/// Record the CU cost of each counter instruction into a JSON report keyed by
+/// instruction name.
+#[test]
+fn compute_unit_report() {
+	let Some(mollusk) = try_create_mollusk() else {
+		eprintln!("[SKIP] counter_program SBF binary not found. Build it first.");
+		return;
+	};
+
+	let authority = Pubkey::new_unique();
+	let (counter_pda, bump) = derive_counter_pda(&authority);
+	let lamports = mollusk
+		.sysvars
+		.rent
+		.minimum_balance(size_of::<CounterState>());
+	let authority_account = Account::new(1_000_000_000, 0, &solana_sdk_ids::system_program::id());
+
+	let init_ix = Instruction::new_with_bytes(
+		program_id(),
+		&initialize_ix_data(bump),
+		vec![
+			AccountMeta::new(authority, true),
+			AccountMeta::new(counter_pda, true),
+			AccountMeta::new_readonly(solana_sdk_ids::system_program::id(), false),
+		],
+	);
+	let init_result = mollusk.process_and_validate_instruction(
+		&init_ix,
+		&[
+			(authority, authority_account.clone()),
+			(counter_pda, Account::default()),
+			keyed_account_for_system_program(),
+		],
+		&[Check::success()],
+	);
+
+	let incr_ix = Instruction::new_with_bytes(
+		program_id(),
+		&increment_ix_data(),
+		vec![
+			AccountMeta::new_readonly(authority, true),
+			AccountMeta::new(counter_pda, false),
+		],
+	);
+	let incr_result = mollusk.process_and_validate_instruction(
+		&incr_ix,
+		&[
+			(authority, authority_account),
+			(counter_pda, counter_account(bump, 0, lamports)),
+		],
+		&[Check::success()],
+	);
+
+	let mut report = ComputeUnitReport::new();
+	report.record("Initialize", init_result.compute_units_consumed);
+	report.record("Increment", incr_result.compute_units_consumed);
+
+	assert!(report.get("Initialize").is_some_and(|units| units > 0));
+	assert!(report.get("Increment").is_some_and(|units| units > 0));
+
+	let json = report.to_json();
+	eprintln!("[CU REPORT] counter_program:\n{json}");
+
+	if let Ok(dir) = std::env::var("PINA_CU_REPORT_DIR") {
+		let dir = std::path::PathBuf::from(dir);
+		std::fs::create_dir_all(&dir)
+			.unwrap_or_else(|e| panic!("failed to create {}: {e}", dir.display()));
+		let path = dir.join("counter_program.json");
+		std::fs::write(&path, json)
+			.unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+	}
+}
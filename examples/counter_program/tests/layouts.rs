@@ -0,0 +1,23 @@
+//! Guards the on-chain layout of the counter account against accidental
+//! changes. Set `PINA_UPDATE_LAYOUTS=1` to re-record `layouts.golden` after an
+//! intentional change.
+
+use counter_program::CounterState;
+use pina::layout::LayoutSnapshot;
+
+#[test]
+fn account_layouts_match_golden() {
+	let mut snapshot = LayoutSnapshot::new();
+	snapshot.record::<CounterState>();
+
+	if std::env::var_os("PINA_UPDATE_LAYOUTS").is_some() {
+		let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/layouts.golden");
+		std::fs::write(path, snapshot.to_text())
+			.unwrap_or_else(|e| panic!("failed to write {path}: {e}"));
+		return;
+	}
+
+	if let Err(diff) = snapshot.compare(include_str!("layouts.golden")) {
+		panic!("{diff}");
+	}
+}
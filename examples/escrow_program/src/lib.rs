@@ -65,7 +65,7 @@ pub enum EscrowError {
 	TokenAccountMismatch = 1,
 }
 
-#[account(discriminator = EscrowAccount)]
+#[account(discriminator = EscrowAccount, seeds = [SEED_PREFIX, maker, seed])]
 pub struct EscrowState {
 	pub maker: Address,
 	pub mint_a: Address,
@@ -104,42 +104,28 @@ pub struct MakeAccounts<'a> {
 	pub token_program: &'a AccountView,
 }
 
+/// Seed prefix for escrow PDAs. The full seeds, `["escrow", <maker_address>,
+/// <seed>]`, are declared on `EscrowState`, which generates
+/// `EscrowState::seeds` and `EscrowState::seeds_with_bump`.
 const SEED_PREFIX: &[u8] = b"escrow";
 const SPL_PROGRAM_IDS: [Address; 2] = [token::ID, token_2022::ID];
 
-#[macro_export]
-macro_rules! seeds_escrow {
-	($maker:expr, $seed:expr) => {
-		&[SEED_PREFIX, $maker, $seed]
-	};
-
-	($maker:expr, $seed:expr, $bump:expr) => {
-		&[SEED_PREFIX, $maker, $seed, &[$bump]]
-	};
-	(true, $maker:expr, $seed:expr, $bump:expr) => {
-		[
-			Seed::from(SEED_PREFIX),
-			Seed::from($maker),
-			Seed::from($seed),
-			Seed::from($bump),
-		]
-	};
-}
-
 impl<'a> ProcessAccountInfos<'a> for MakeAccounts<'a> {
 	fn process(self, data: &[u8]) -> ProgramResult {
 		// Parse instruction and prepare PDA seeds
 		let args = MakeInstruction::try_from_bytes(data)?;
 		let maker_address = *self.maker.address();
-		let escrow_seeds = seeds_escrow!(maker_address.as_ref(), &args.seed.0);
-		let escrow_seeds_with_bump = seeds_escrow!(maker_address.as_ref(), &args.seed.0, args.bump);
+		let escrow_seeds = EscrowState::seeds(maker_address.as_ref(), &args.seed.0);
+		let bump = [args.bump];
+		let escrow_seeds_with_bump =
+			EscrowState::seeds_with_bump(maker_address.as_ref(), &args.seed.0, &bump);
 
 		// Validate accounts
 		self.token_program.assert_addresses(&SPL_PROGRAM_IDS)?;
 		self.system_program.assert_address(&system::ID)?;
 		self.maker.assert_signer()?;
-		self.mint_a.assert_owners(&SPL_PROGRAM_IDS)?;
-		self.mint_b.assert_owners(&SPL_PROGRAM_IDS)?;
+		self.mint_a.assert_token_owned()?;
+		self.mint_b.assert_token_owned()?;
 		self.maker_ata_a.assert_associated_token_address(
 			self.maker.address(),
 			self.mint_a.address(),
@@ -148,7 +134,7 @@ impl<'a> ProcessAccountInfos<'a> for MakeAccounts<'a> {
 		self.escrow
 			.assert_empty()?
 			.assert_writable()?
-			.assert_seeds_with_bump(escrow_seeds_with_bump, &ID)?;
+			.assert_seeds_with_bump(&escrow_seeds_with_bump, &ID)?;
 		self.vault
 			.assert_empty()?
 			.assert_writable()?
@@ -163,7 +149,7 @@ impl<'a> ProcessAccountInfos<'a> for MakeAccounts<'a> {
 			self.escrow,
 			self.maker,
 			&ID,
-			escrow_seeds,
+			&escrow_seeds,
 			args.bump,
 		)?;
 
@@ -192,17 +178,14 @@ impl<'a> ProcessAccountInfos<'a> for MakeAccounts<'a> {
 		.invoke()?;
 
 		// Transfer tokens to vault
-		let decimals = self.mint_a.as_token_mint()?.decimals();
-		token_2022::instructions::TransferChecked {
-			from: self.maker_ata_a,
-			to: self.vault,
-			authority: self.maker,
-			amount: args.amount_a.into(),
-			mint: self.mint_a,
-			decimals,
-			token_program: self.token_program.address(),
-		}
-		.invoke()?;
+		transfer_checked_tokens(
+			self.maker_ata_a,
+			self.vault,
+			self.mint_a,
+			self.maker,
+			args.amount_a.into(),
+			self.token_program,
+		)?;
 
 		Ok(())
 	}
@@ -235,7 +218,7 @@ impl<'a> ProcessAccountInfos<'a> for TakeAccounts<'a> {
 		// Validate taker accounts
 		self.taker.assert_signer()?.assert_writable()?;
 		self.taker_ata_a
-			.assert_owners(&SPL_PROGRAM_IDS)?
+			.assert_token_owned()?
 			.assert_data_len(token::state::TokenAccount::LEN)?
 			.assert_associated_token_address(
 				self.taker.address(),
@@ -244,7 +227,7 @@ impl<'a> ProcessAccountInfos<'a> for TakeAccounts<'a> {
 			)?;
 		self.taker_ata_b
 			.assert_writable()?
-			.assert_owners(&SPL_PROGRAM_IDS)?
+			.assert_token_owned()?
 			.assert_associated_token_address(
 				self.taker.address(),
 				self.mint_b.address(),
@@ -269,87 +252,65 @@ impl<'a> ProcessAccountInfos<'a> for TakeAccounts<'a> {
 			)
 		};
 
-		let escrow_seeds_with_bump = seeds_escrow!(self.maker.address().as_ref(), &seed.0, bump);
+		let bump = [bump];
+		let escrow_seeds_with_bump = EscrowState::seeds_with_bump(maker.as_ref(), &seed.0, &bump);
 		self.escrow
-			.assert_seeds_with_bump(escrow_seeds_with_bump, &ID)?;
+			.assert_seeds_with_bump(&escrow_seeds_with_bump, &ID)?;
 
 		// Validate maker and mint accounts
 		self.maker.assert_address(&maker)?;
-		self.mint_a
-			.assert_owners(&SPL_PROGRAM_IDS)?
-			.assert_address(&mint_a)?;
-		self.mint_b
-			.assert_owners(&SPL_PROGRAM_IDS)?
-			.assert_address(&mint_b)?;
+		self.mint_a.assert_token_owned()?.assert_address(&mint_a)?;
+		self.mint_b.assert_token_owned()?.assert_address(&mint_b)?;
 
 		// Validate vault and maker ATA
-		self.vault
-			.assert_not_empty()?
-			.assert_writable()?
-			.assert_owners(&SPL_PROGRAM_IDS)?
-			.assert_associated_token_address(
-				self.escrow.address(),
-				self.mint_a.address(),
-				self.token_program.address(),
-			)?;
-		self.maker_ata_b
-			.assert_writable()?
-			.assert_associated_token_address(
-				self.maker.address(),
-				self.mint_b.address(),
-				self.token_program.address(),
-			)?;
+		self.vault.assert_writable()?;
+		assert_initialized_ata(
+			self.vault,
+			self.escrow.address(),
+			self.mint_a.address(),
+			self.token_program.address(),
+		)?;
+		self.maker_ata_b.assert_writable()?;
 
 		// Create maker's token B account if needed
-		associated_token_account::instructions::CreateIdempotent {
-			funding_account: self.taker,
-			account: self.maker_ata_b,
-			wallet: self.maker,
-			mint: self.mint_b,
-			system_program: self.system_program,
-			token_program: self.token_program,
-		}
-		.invoke()?;
+		create_associated_token_account_if_needed(
+			self.maker_ata_b,
+			self.taker,
+			self.maker,
+			self.mint_b,
+			self.system_program,
+			self.token_program,
+		)?;
 
 		// Transfer token B from taker to maker
-		token_2022::instructions::TransferChecked {
-			from: self.taker_ata_b,
-			mint: self.mint_b,
-			to: self.maker_ata_b,
-			authority: self.taker,
-			amount: u64::from(amount_b),
-			decimals: self.mint_b.as_token_2022_mint()?.decimals(),
-			token_program: self.token_program.address(),
-		}
-		.invoke()?;
+		transfer_checked_tokens(
+			self.taker_ata_b,
+			self.maker_ata_b,
+			self.mint_b,
+			self.taker,
+			u64::from(amount_b),
+			self.token_program,
+		)?;
 
 		// Prepare escrow signer for vault operations
-		let bump_as_seeds = [bump];
-		let escrow_seeds =
-			seeds_escrow!(true, self.maker.address().as_ref(), &seed.0, &bump_as_seeds);
+		let escrow_seeds = escrow_seeds_with_bump.map(Seed::from);
 		let escrow_signer = Signer::from(&escrow_seeds);
 		let signers = [escrow_signer];
 
 		// Transfer token A from vault to taker
-		token_2022::instructions::TransferChecked {
-			from: self.vault,
-			mint: self.mint_a,
-			to: self.taker_ata_a,
-			authority: self.escrow,
-			amount: self.vault.as_token_2022_account()?.amount(),
-			decimals: self.mint_a.as_token_2022_mint()?.decimals(),
-			token_program: self.token_program.address(),
-		}
-		.invoke_signed(&signers)?;
+		transfer_checked_tokens_signed(
+			self.vault,
+			self.taker_ata_a,
+			self.mint_a,
+			self.escrow,
+			self.vault.as_token_2022_account()?.amount(),
+			self.token_program,
+			&signers,
+		)?;
 
 		// Close vault account
-		token_2022::instructions::CloseAccount {
-			account: self.vault,
-			destination: self.maker,
-			authority: self.escrow,
-			token_program: self.token_program.address(),
-		}
-		.invoke_signed(&signers)?;
+		TokenCpi::new(self.token_program, self.escrow, self.mint_a)
+			.close_signed(self.vault, self.maker, &signers)?;
 
 		// Zero out escrow state and close
 		self.escrow.as_account_mut::<EscrowState>(&ID)?.zeroed();
@@ -374,23 +335,23 @@ mod tests {
 	}
 
 	#[test]
-	fn seeds_macro_builds_expected_seed_arrays() {
+	fn escrow_seeds_build_expected_seed_arrays() {
 		let maker = [3u8; 32];
 		let seed = PodU64::from_primitive(42);
-		let bump = 7u8;
+		let bump = [7u8];
 
-		let seeds = seeds_escrow!(&maker, &seed.0);
+		let seeds = EscrowState::seeds(&maker, &seed.0);
 		assert_eq!(seeds.len(), 3);
 		assert_eq!(seeds[0], SEED_PREFIX);
 		assert_eq!(seeds[1], &maker);
 		assert_eq!(seeds[2], &seed.0);
 
-		let seeds_with_bump = seeds_escrow!(&maker, &seed.0, bump);
+		let seeds_with_bump = EscrowState::seeds_with_bump(&maker, &seed.0, &bump);
 		assert_eq!(seeds_with_bump.len(), 4);
 		assert_eq!(seeds_with_bump[0], SEED_PREFIX);
 		assert_eq!(seeds_with_bump[1], &maker);
 		assert_eq!(seeds_with_bump[2], &seed.0);
-		assert_eq!(seeds_with_bump[3], &[bump]);
+		assert_eq!(seeds_with_bump[3], &bump);
 	}
 
 	#[test]
@@ -28,8 +28,6 @@
 ))]
 extern crate std;
 
-use core::mem::size_of;
-
 use pina::*;
 
 pub mod cpi;
@@ -100,7 +98,7 @@ pub struct RotateAuthorityAccounts<'a> {
 }
 
 fn oracle_size() -> usize {
-	size_of::<OracleState>()
+	OracleState::LEN
 }
 
 fn assert_update_authority(authority: &AccountView) -> ProgramResult {
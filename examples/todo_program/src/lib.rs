@@ -32,7 +32,7 @@ pub enum TodoAccount {
 	TodoState = 1,
 }
 
-#[account(discriminator = TodoAccount)]
+#[account(discriminator = TodoAccount, seeds = [TODO_SEED, owner])]
 pub struct TodoState {
 	pub owner: Address,
 	pub bump: u8,
@@ -54,18 +54,11 @@ pub struct UpdateDigestInstruction {
 	pub digest: [u8; 32],
 }
 
+/// Seed prefix for todo PDAs. The full seeds, `["todo", <owner_address>]`,
+/// are declared on `TodoState`, which generates `TodoState::seeds` and
+/// `TodoState::seeds_with_bump`.
 const TODO_SEED: &[u8] = b"todo";
 
-#[macro_export]
-macro_rules! todo_seeds {
-	($owner:expr) => {
-		&[TODO_SEED, $owner]
-	};
-	($owner:expr, $bump:expr) => {
-		&[TODO_SEED, $owner, &[$bump]]
-	};
-}
-
 #[derive(Accounts, Debug)]
 pub struct InitializeAccounts<'a> {
 	pub owner: &'a AccountView,
@@ -84,20 +77,21 @@ impl<'a> ProcessAccountInfos<'a> for InitializeAccounts<'a> {
 		// Parse instruction and prepare PDA seeds
 		let args = InitializeInstruction::try_from_bytes(data)?;
 		let owner = self.owner.address();
-		let seeds = todo_seeds!(owner.as_ref());
-		let seeds_with_bump = todo_seeds!(owner.as_ref(), args.bump);
+		let seeds = TodoState::seeds(owner.as_ref());
+		let bump = [args.bump];
+		let seeds_with_bump = TodoState::seeds_with_bump(owner.as_ref(), &bump);
 
 		// Validate accounts
 		self.owner.assert_signer()?;
 		self.todo
 			.assert_empty()?
 			.assert_writable()?
-			.assert_seeds_with_bump(seeds_with_bump, &ID)?;
+			.assert_seeds_with_bump(&seeds_with_bump, &ID)?;
 		self.system_program.assert_address(&system::ID)?;
 
 		// Create the PDA account
 		create_program_account_with_bump::<TodoState>(
-			self.todo, self.owner, &ID, seeds, args.bump,
+			self.todo, self.owner, &ID, &seeds, args.bump,
 		)?;
 
 		// Initialize account data
@@ -137,8 +131,9 @@ impl<'a> ProcessAccountInfos<'a> for UpdateAccounts<'a> {
 			todo.bump
 		};
 
-		let seeds_with_bump = todo_seeds!(owner.as_ref(), bump);
-		self.todo.assert_seeds_with_bump(seeds_with_bump, &ID)?;
+		let bump = [bump];
+		let seeds_with_bump = TodoState::seeds_with_bump(owner.as_ref(), &bump);
+		self.todo.assert_seeds_with_bump(&seeds_with_bump, &ID)?;
 
 		// Execute instruction
 		match instruction {
@@ -1,13 +1,16 @@
 //! SECURE: Signer authorization check present.
 //!
 //! This program verifies that the authority account signed the transaction
-//! before allowing any state mutation.
+//! before allowing any state mutation. The authority is a `SignerAccount`, so
+//! the check runs while the accounts are parsed and cannot be forgotten in
+//! the processor.
 
 #![no_std]
 
 #[cfg(all(not(any(target_os = "solana", target_arch = "bpf")), not(test)))]
 extern crate std;
 
+use pina::accounts::SignerAccount;
 use pina::*;
 
 declare_id!("Fiowp2vKZUHi9yLjtmmshG8rPeV4P4hpG9NRpshhJsW4");
@@ -35,7 +38,7 @@ pub struct WithdrawInstruction {
 
 #[derive(Accounts, Debug)]
 pub struct WithdrawAccounts<'a> {
-	pub authority: &'a AccountView,
+	pub authority: SignerAccount<'a>,
 	pub vault: &'a mut AccountView,
 }
 
@@ -49,8 +52,8 @@ impl<'a> ProcessAccountInfos<'a> for WithdrawAccounts<'a> {
 	fn process(self, data: &[u8]) -> ProgramResult {
 		let args = WithdrawInstruction::try_from_bytes(data)?;
 
-		// SECURE: Verify the authority signed this transaction.
-		self.authority.assert_signer()?;
+		// SECURE: `SignerAccount` verified that the authority signed this
+		// transaction before `process` runs.
 
 		self.vault
 			.assert_writable()?
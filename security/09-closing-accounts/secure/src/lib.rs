@@ -1,6 +1,7 @@
 //! SECURE: Proper account closing with data zeroing.
 //!
-//! This program zeros account data before closing, preventing revival attacks.
+//! This program zeros account data and marks it as closed, preventing revival
+//! attacks.
 
 #![no_std]
 
@@ -45,6 +46,7 @@ impl<'a> ProcessAccountInfos<'a> for ClaimAndCloseAccounts<'a> {
 		self.reward
 			.assert_not_empty()?
 			.assert_writable()?
+			.assert_not_closed()?
 			.assert_type::<RewardState>(&ID)?;
 
 		let reward_authority = {
@@ -54,12 +56,8 @@ impl<'a> ProcessAccountInfos<'a> for ClaimAndCloseAccounts<'a> {
 
 		self.authority.assert_address(&reward_authority)?;
 
-		// SECURE: Zero the account data first, then close properly.
-		// zeroed() clears all bytes, preventing stale data reuse.
-		{
-			self.reward.as_account_mut::<RewardState>(&ID)?.zeroed();
-		}
-
-		self.reward.close_with_recipient(self.recipient)
+		// SECURE: Zero the account data and mark it as closed. A revival later
+		// in the transaction fails `assert_not_closed()` above.
+		self.reward.close_account_secure(self.recipient)
 	}
 }